@@ -19,6 +19,11 @@ fn main_wrapper() -> Result<()> {
 	let group_num = rosy_mpi_context.get_group_num(&mut  &mut 1.0f64)
 		.context("Failed to get group number")? + 1.0f64;
 	// <MPI_END>
+	// <THREADS_START>
+	let mut rosy_mpi_context_inner = RosyThreadContext::new()
+		.context("Failed to initialize Rosy thread context")?;
+	let rosy_mpi_context: &mut RosyThreadContext = &mut rosy_mpi_context_inner;
+	// <THREADS_END>
 
 	// Initialize Taylor series system (lightweight default — DAINI/OV overrides this)
 	taylor::init_taylor(3, 6)
@@ -35,6 +40,7 @@ fn main_wrapper() -> Result<()> {
     Ok(())
 }
 fn main() -> Result<()> {
+	rosy_lib::core::trace::install_panic_hook();
 	if let Err(err) = main_wrapper() {
 		let mut err_str = format!("{}", err.root_cause());
 
@@ -51,6 +57,12 @@ fn main() -> Result<()> {
 			}
         }
 
+		// Where in the Rosy source it failed
+		let report = rosy_lib::core::trace::Trace::of_error(&err).report();
+		if !report.is_empty() {
+			err_str += &format!("\n{report}");
+		}
+
 		// Check if the user has backtraces enabled
 		if std::env::var("RUST_BACKTRACE").unwrap_or_default() == "1" {
 			err_str += "\n\nFull backtrace...";
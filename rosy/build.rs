@@ -97,7 +97,7 @@ fn main() {
 
     // ─── Tree-sitter: Generate grammar.js and highlights.scm from Pest ────
     let intrinsics = extract_intrinsic_functions(&pest_source);
-    let types = vec!["RE", "ST", "LO", "CM", "VE", "DA", "CD"];
+    let types = vec!["RE", "ST", "LO", "CM", "VE", "DA", "CD", "GR"];
     generate_tree_sitter_grammar(&out_dir, &keywords, &intrinsics, &types);
     generate_tree_sitter_highlights(&out_dir, &keywords, &intrinsics, &types);
 }
@@ -346,7 +346,7 @@ fn dir_name_to_keyword(dir_name: &str) -> Option<String> {
         "da" => "DA",
         "core" | "io" | "math" | "trig" | "exponential" | "rounding" | "special" | "vector"
         | "complex" | "memory" | "query" | "conversion" | "sys" | "collection" | "comparison"
-        | "arithmetic" | "unary" | "types" | "operators" | "functions" => return None,
+        | "arithmetic" | "unary" | "types" | "operators" | "functions" | "graphics" => return None,
         other => return Some(other.to_uppercase()),
     };
     Some(keyword.to_string())
@@ -472,6 +472,7 @@ fn generate_hover_file(
             "Complex Taylor series",
             "Complex Differential Algebra",
         ),
+        ("GR", "GR", "Graphics object"),
     ] {
         writeln!(f, "    (\"{name}\", \"**{name}** \\u{{2014}} {desc} (`{rust_type}`)\\n\\n[Documentation]({base}/rosy_lib/enum.RosyBaseType.html#variant.{name})\", \"{desc}\"),").unwrap();
     }
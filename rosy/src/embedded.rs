@@ -8,9 +8,43 @@
 //! The `rosy_lib` source files are embedded into the transpiler binary at
 //! compile time via `include_str!()` (see `build.rs`). At transpilation time,
 //! they are extracted and path-rewritten (`crate::rosy_lib::` → `crate::`).
+//!
+//! ## Build Cache
+//!
+//! The output directory (`.rosy_output` unless `-d` says otherwise) is a
+//! cache that every script built in it shares:
+//!
+//! ```text
+//! .rosy_output/
+//! ├── rosy_lib/<hash>/   the vendored runtime, per runtime source and MAX_VARS
+//! ├── projects/<hash>/   one generated project per distinct program
+//! ├── sources/<hash>     the project each source file last built
+//! ├── packages/          MODULE GITHUB downloads
+//! └── target/            the cargo target directory of every project
+//! ```
+//!
+//! A project's hash covers its `main.rs`, its `Cargo.toml` (runtime,
+//! features and profile settings), the build profile and the rosy version.
+//! Running an unchanged program finds its binary already built, and a new
+//! program only compiles its own `main.rs`: the runtime it links against
+//! was compiled into the shared target directory by the first program with
+//! the same runtime and features. Nothing is ever written over, so builds
+//! in the same cache can run side by side.
+//!
+//! `sources/` records, per source file and build settings (profile,
+//! features, parallel backend), the project that file last built. When an
+//! edited program builds a new project, its previous one is deleted along
+//! with its binary and everything cargo kept for it, unless another source
+//! still builds to it. The cache therefore holds one project per program
+//! and settings, however often each is edited. The shared runtimes, in
+//! `rosy_lib/` and the target directory, are kept; delete the whole
+//! directory to clear them after a rosy upgrade.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+
+use crate::parallel_config::ParallelBackend;
 
 // Include the auto-generated embedded rosy_lib files
 include!(concat!(env!("OUT_DIR"), "/embedded_rosy_lib.rs"));
@@ -18,10 +52,39 @@ include!(concat!(env!("OUT_DIR"), "/embedded_rosy_lib.rs"));
 /// Embedded main.rs template for generated projects
 const MAIN_RS_TEMPLATE: &str = include_str!("../assets/output_template/main.rs");
 
-/// Writes the vendored rosy_lib to the output directory
-fn write_vendored_lib(output_dir: &Path) -> Result<()> {
-    let lib_dir = output_dir.join("vendored/rosy_lib");
+/// The `MAX_VARS` declaration in `taylor/mod.rs`, rewritten per project.
+const MAX_VARS_DECL: &str = "pub const MAX_VARS: usize = 16;";
 
+/// Narrowest monomial width we vendor. Six covers the template's default
+/// `init_taylor(3, 6)` and keeps the common 6D phase-space case compact.
+const MIN_MONOMIAL_WIDTH: usize = 6;
+
+/// Width used when a `DAINI` variable count is only known at runtime.
+const DEFAULT_MONOMIAL_WIDTH: usize = 16;
+
+/// Picks the `MAX_VARS` width for a generated project from its `DAINI` calls.
+///
+/// Every `DAINI` transpiles to `taylor::init_taylor(<order> as u32, <nv> as usize)`.
+/// When all `nv` arguments are numeric literals the widest one wins (never
+/// below [`MIN_MONOMIAL_WIDTH`]); otherwise we fall back to the library default.
+pub fn monomial_width(transpiled_code: &str) -> usize {
+    let mut width = MIN_MONOMIAL_WIDTH;
+    for call in transpiled_code.split("taylor::init_taylor(").skip(1) {
+        let literal_nv = call
+            .split_once(" as u32, ")
+            .and_then(|(_, rest)| rest.split_once(" as usize)"))
+            .and_then(|(nv, _)| nv.strip_suffix("f64"))
+            .and_then(|nv| nv.parse::<usize>().ok());
+        match literal_nv {
+            Some(nv) => width = width.max(nv),
+            None => return width.max(DEFAULT_MONOMIAL_WIDTH),
+        }
+    }
+    width
+}
+
+/// Writes the vendored rosy_lib to `lib_dir`
+fn write_vendored_lib(lib_dir: &Path, monomial_width: usize) -> Result<()> {
     for embedded_file in ROSY_LIB_FILES {
         // Determine the target path - rename mod.rs to lib.rs, put everything else in src/
         let target_path = if embedded_file.path == "mod.rs" {
@@ -40,6 +103,18 @@ fn write_vendored_lib(output_dir: &Path) -> Result<()> {
         // since when vendored, this IS the rosy_lib crate
         let mut transformed_content = embedded_file.content.replace("crate::rosy_lib::", "crate::");
 
+        // Size the monomial exponent array for this program's DAINI calls
+        if embedded_file.path == "taylor/mod.rs" {
+            anyhow::ensure!(
+                transformed_content.contains(MAX_VARS_DECL),
+                "Expected `{MAX_VARS_DECL}` in taylor/mod.rs!"
+            );
+            transformed_content = transformed_content.replace(
+                MAX_VARS_DECL,
+                &format!("pub const MAX_VARS: usize = {monomial_width};"),
+            );
+        }
+
         // Add warning suppressions and feature gates to the lib.rs file
         if embedded_file.path == "mod.rs" {
             transformed_content = format!("#![cfg_attr(feature = \"nightly-simd\", feature(portable_simd))]\n#![allow(unused_imports)]\n#![allow(dead_code)]\n\n{}", transformed_content);
@@ -68,6 +143,7 @@ serial_test = "3.2"
 num-complex = "0.4"
 rustc-hash = "2"
 rand = "0.9"
+rand_chacha = "0.9"
 libm = "0.2"
 memory-stats = "1"
 "#;
@@ -78,16 +154,17 @@ memory-stats = "1"
     Ok(())
 }
 
-/// Generates a Cargo.toml for the output project
-fn generate_cargo_toml(uses_mpi: bool, optimized: bool) -> String {
+/// Generates a Cargo.toml for an output project named `name`, using the
+/// runtime vendored at `lib_path`
+fn generate_cargo_toml(name: &str, lib_path: &str, uses_mpi: bool, optimized: bool) -> String {
     let mut features = Vec::new();
     if uses_mpi { features.push("\"mpi\""); }
     if optimized { features.push("\"nightly-simd\""); }
 
     let mpi_dep = if features.is_empty() {
-        "rosy_lib = { path = \"./vendored/rosy_lib\" }".to_string()
+        format!("rosy_lib = {{ path = \"{lib_path}\" }}")
     } else {
-        format!("rosy_lib = {{ path = \"./vendored/rosy_lib\", features = [{}] }}", features.join(", "))
+        format!("rosy_lib = {{ path = \"{lib_path}\", features = [{}] }}", features.join(", "))
     };
 
     let profile_section = if optimized {
@@ -99,53 +176,288 @@ fn generate_cargo_toml(uses_mpi: bool, optimized: bool) -> String {
     };
 
     format!(
-        "[package]\nname = \"rosy_output\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\nanyhow = \"1.0\"\n{mpi_dep}\nnum-complex = \"0.4\"\n{profile_section}"
+        "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\nanyhow = \"1.0\"\n{mpi_dep}\nnum-complex = \"0.4\"\n{profile_section}"
     )
 }
 
-/// Creates the output project structure in the specified directory
-pub fn create_output_project(output_dir: &Path, uses_mpi: bool, optimized: bool) -> Result<()> {
-    // Create the directory structure
-    std::fs::create_dir_all(output_dir.join("src"))
-        .context("Failed to create output directory structure")?;
+/// The first 16 hex digits of the SHA-256 of `parts`, each length-prefixed
+/// so that moving text from one part to the next changes the hash.
+fn content_hash(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    }
+    format!("{:x}", hasher.finalize())[..16].to_string()
+}
+
+/// Fill `dir` with `write` unless it already exists. The files are written
+/// to a scratch directory that is then renamed into place, so a concurrent
+/// build never sees a half-written `dir`; if another build got there first,
+/// its copy (with the same content) is kept.
+fn write_once(dir: &Path, write: impl FnOnce(&Path) -> Result<()>) -> Result<()> {
+    if dir.exists() {
+        return Ok(());
+    }
+    let parent = dir.parent().context("Cache entries always have a parent")?;
+    let name = dir.file_name().context("Cache entries always have a name")?;
+    std::fs::create_dir_all(parent)
+        .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
 
-    // Write vendored rosy_lib
-    write_vendored_lib(output_dir)
-        .context("Failed to write vendored rosy_lib")?;
+    let scratch = parent.join(format!(".{}-{}", name.to_string_lossy(), std::process::id()));
+    let _ = std::fs::remove_dir_all(&scratch);
+    let written = write(&scratch).and_then(|()| {
+        std::fs::rename(&scratch, dir)
+            .or_else(|e| if dir.exists() { Ok(()) } else { Err(e) })
+            .with_context(|| format!("Failed to move {} into place", dir.display()))
+    });
+    let _ = std::fs::remove_dir_all(&scratch);
+    written
+}
 
-    // Write Cargo.toml
-    std::fs::write(output_dir.join("Cargo.toml"), generate_cargo_toml(uses_mpi, optimized))
+/// A generated project in the build cache.
+pub struct OutputProject {
+    /// The project's directory, `projects/<hash>`
+    pub dir: PathBuf,
+    /// The package and binary name, `rosy_<hash>`
+    pub name: String,
+    /// The target directory all projects in the cache share
+    pub target_dir: PathBuf,
+}
+
+impl OutputProject {
+    /// Where cargo puts the project's binary.
+    pub fn binary(&self, release: bool) -> PathBuf {
+        let profile = if release { "release" } else { "debug" };
+        let file_name = if cfg!(windows) {
+            format!("{}.exe", self.name)
+        } else {
+            self.name.clone()
+        };
+        self.target_dir.join(profile).join(file_name)
+    }
+
+    /// Whether the binary was built before, so cargo needn't run at all.
+    /// Cargo only moves a binary into place once it linked.
+    pub fn is_built(&self, release: bool) -> bool {
+        self.binary(release).is_file()
+    }
+}
+
+/// Finds or creates the project for `main_rs`, generated from `source`, in
+/// the build cache at `cache_dir`, along with the runtime it uses. The
+/// project `source` last built with the same settings is dropped (see the
+/// module docs).
+pub fn create_output_project(
+    cache_dir: &Path,
+    source: &Path,
+    main_rs: &str,
+    parallel: Option<ParallelBackend>,
+    optimized: bool,
+    release: bool,
+    monomial_width: usize,
+) -> Result<OutputProject> {
+    let uses_mpi = parallel == Some(ParallelBackend::Mpi);
+    std::fs::create_dir_all(cache_dir)
+        .with_context(|| format!("Failed to create build cache: {}", cache_dir.display()))?;
+    let cache_dir = std::fs::canonicalize(cache_dir)
+        .with_context(|| format!("Failed to resolve build cache: {}", cache_dir.display()))?;
+
+    // The runtime, shared by every project with the same MAX_VARS
+    let width = monomial_width.to_string();
+    let mut lib_parts = vec![env!("CARGO_PKG_VERSION").as_bytes(), width.as_bytes()];
+    for embedded_file in ROSY_LIB_FILES {
+        lib_parts.push(embedded_file.path.as_bytes());
+        lib_parts.push(embedded_file.content.as_bytes());
+    }
+    let lib_hash = content_hash(&lib_parts);
+    write_once(&cache_dir.join("rosy_lib").join(&lib_hash), |lib_dir| {
+        write_vendored_lib(lib_dir, monomial_width)
+    })
+    .context("Failed to write vendored rosy_lib")?;
+
+    // The project, named after everything that goes into its binary
+    let lib_path = format!("../../rosy_lib/{lib_hash}");
+    let profile = if release { "release" } else { "debug" };
+    let settings = generate_cargo_toml("", &lib_path, uses_mpi, optimized);
+    let hash = content_hash(&[
+        env!("CARGO_PKG_VERSION").as_bytes(),
+        profile.as_bytes(),
+        settings.as_bytes(),
+        main_rs.as_bytes(),
+    ]);
+    let name = format!("rosy_{hash}");
+    let dir = cache_dir.join("projects").join(&hash);
+    write_once(&dir, |project_dir| {
+        std::fs::create_dir_all(project_dir.join("src"))
+            .context("Failed to create output directory structure")?;
+        std::fs::write(
+            project_dir.join("Cargo.toml"),
+            generate_cargo_toml(&name, &lib_path, uses_mpi, optimized),
+        )
         .context("Failed to write Cargo.toml template")?;
+        std::fs::write(project_dir.join("src/main.rs"), main_rs)
+            .context("Failed to write Rust output file!")
+    })?;
+
+    // The program's last build with these settings, if it was of other code
+    let source = std::fs::canonicalize(source).unwrap_or_else(|_| source.to_path_buf());
+    let parallel = format!("{parallel:?}");
+    let slot = content_hash(&[
+        source.as_os_str().as_encoded_bytes(),
+        profile.as_bytes(),
+        settings.as_bytes(),
+        parallel.as_bytes(),
+    ]);
+    let sources_dir = cache_dir.join("sources");
+    std::fs::create_dir_all(&sources_dir)
+        .with_context(|| format!("Failed to create directory: {}", sources_dir.display()))?;
+    let slot_path = sources_dir.join(slot);
+    let previous = std::fs::read_to_string(&slot_path).ok();
+    std::fs::write(&slot_path, &hash)
+        .with_context(|| format!("Failed to write {}", slot_path.display()))?;
+    if let Some(previous) = previous
+        && previous != hash
+        && !is_recorded(&sources_dir, &previous)
+    {
+        evict_project(&cache_dir, &previous);
+    }
+
+    Ok(OutputProject {
+        dir,
+        name,
+        target_dir: cache_dir.join("target"),
+    })
+}
+
+/// Whether some program's last build in `sources_dir` was project `hash`.
+fn is_recorded(sources_dir: &Path, hash: &str) -> bool {
+    let Ok(entries) = std::fs::read_dir(sources_dir) else {
+        return false;
+    };
+    entries
+        .flatten()
+        .any(|entry| std::fs::read_to_string(entry.path()).is_ok_and(|recorded| recorded == hash))
+}
+
+/// Deletes project `hash` and everything cargo built for it. Only disk
+/// space is at stake, so whatever can't be deleted is left.
+fn evict_project(cache_dir: &Path, hash: &str) {
+    let _ = std::fs::remove_dir_all(cache_dir.join("projects").join(hash));
+    let name = format!("rosy_{hash}");
+    for profile in ["debug", "release"] {
+        let profile_dir = cache_dir.join("target").join(profile);
+        for dir in ["", "deps", ".fingerprint", "incremental", "build"] {
+            let Ok(entries) = std::fs::read_dir(profile_dir.join(dir)) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                // The binary and its `.d`, `.pdb`, ... files, and the
+                // `rosy_<hash>-<metadata>` cargo keeps per crate
+                let file_name = entry.file_name();
+                let is_project = file_name
+                    .to_string_lossy()
+                    .strip_prefix(&name)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '-']));
+                if is_project {
+                    let path = entry.path();
+                    let _ = if path.is_dir() {
+                        std::fs::remove_dir_all(&path)
+                    } else {
+                        std::fs::remove_file(&path)
+                    };
+                }
+            }
+        }
+    }
+}
 
-    // Write main.rs template
-    std::fs::write(output_dir.join("src/main.rs"), MAIN_RS_TEMPLATE)
-        .context("Failed to write main.rs template")?;
+/// First line of the Cargo.toml of every library crate, so that
+/// [`create_library_crate`] only ever writes over its own output.
+const LIBRARY_MARKER: &str = "# Generated by `rosy build --lib`";
+
+/// Writes a library crate (see [`crate::library`]) named `name` to `dir`:
+/// its Cargo.toml, `src/lib.rs` and the runtime vendored in `rosy_lib/`, so
+/// that it works as a path dependency without anything else. With `cdylib`
+/// it builds as a shared library too.
+pub fn create_library_crate(
+    dir: &Path,
+    name: &str,
+    lib_rs: &str,
+    optimized: bool,
+    monomial_width: usize,
+    cdylib: bool,
+) -> Result<()> {
+    let cargo_toml_path = dir.join("Cargo.toml");
+    if let Ok(existing) = std::fs::read_to_string(&cargo_toml_path) {
+        anyhow::ensure!(
+            existing.starts_with(LIBRARY_MARKER),
+            "{} already exists and wasn't written by `rosy build --lib`; refusing to overwrite it",
+            dir.display()
+        );
+    }
+
+    std::fs::create_dir_all(dir.join("src"))
+        .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+    write_vendored_lib(&dir.join("rosy_lib"), monomial_width)
+        .context("Failed to write vendored rosy_lib")?;
+
+    let rosy_lib_dep = if optimized {
+        "rosy_lib = { path = \"rosy_lib\", features = [\"nightly-simd\"] }"
+    } else {
+        "rosy_lib = { path = \"rosy_lib\" }"
+    };
+    // A shared library for C, still usable as a path dependency
+    let lib_section = if cdylib {
+        "\n[lib]\ncrate-type = [\"cdylib\", \"rlib\"]\n"
+    } else {
+        ""
+    };
+    std::fs::write(
+        &cargo_toml_path,
+        format!(
+            "{LIBRARY_MARKER}\n[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n{lib_section}\n[dependencies]\nanyhow = \"1.0\"\n{rosy_lib_dep}\nnum-complex = \"0.4\"\n"
+        ),
+    )
+    .context("Failed to write library Cargo.toml")?;
+    std::fs::write(dir.join("src/lib.rs"), lib_rs).context("Failed to write library lib.rs")?;
 
     Ok(())
 }
 
+/// Removes the `// <{marker}_START>` ... `// <{marker}_END>` block from the template.
+fn strip_block(template: &str, marker: &str) -> Result<String> {
+    let start = format!("// <{marker}_START>");
+    let end = format!("// <{marker}_END>");
+    let parts: Vec<&str> = template.split(start.as_str()).collect();
+    anyhow::ensure!(
+        parts.len() == 2,
+        "Expected exactly one '{start}' in main.rs template!"
+    );
+    let before = parts[0];
+    let after_parts: Vec<&str> = parts[1].split(end.as_str()).collect();
+    anyhow::ensure!(
+        after_parts.len() == 2,
+        "Expected exactly one '{end}' in main.rs template!"
+    );
+    Ok(format!("{}{}", before, after_parts[1]))
+}
+
 /// Injects the transpiled code into the main.rs template.
 ///
-/// When `uses_mpi` is false, the MPI initialization block (between
-/// `// <MPI_START>` and `// <MPI_END>`) is stripped from the output.
-pub fn inject_code(transpiled_code: &str, uses_mpi: bool) -> Result<String> {
+/// `parallel` is the backend the program's `PLOOP`s use, if it has any.
+/// Only that backend's context initialization (between `// <MPI_START>`
+/// and `// <MPI_END>`, or `// <THREADS_START>` and `// <THREADS_END>`) is
+/// kept in the output.
+pub fn inject_code(transpiled_code: &str, parallel: Option<ParallelBackend>) -> Result<String> {
     let mut template = MAIN_RS_TEMPLATE.to_string();
 
-    // Strip MPI initialization block when not needed
-    if !uses_mpi {
-        let mpi_parts: Vec<&str> = template.split("// <MPI_START>").collect();
-        anyhow::ensure!(
-            mpi_parts.len() == 2,
-            "Expected exactly one '// <MPI_START>' in main.rs template!"
-        );
-        let before_mpi = mpi_parts[0];
-        let after_mpi_parts: Vec<&str> = mpi_parts[1].split("// <MPI_END>").collect();
-        anyhow::ensure!(
-            after_mpi_parts.len() == 2,
-            "Expected exactly one '// <MPI_END>' in main.rs template!"
-        );
-        let after_mpi = after_mpi_parts[1];
-        template = format!("{}{}", before_mpi, after_mpi);
+    // Strip the context initialization of every backend not in use
+    if parallel != Some(ParallelBackend::Mpi) {
+        template = strip_block(&template, "MPI")?;
+    }
+    if parallel != Some(ParallelBackend::Threads) {
+        template = strip_block(&template, "THREADS")?;
     }
 
     // Split by injection markers
@@ -178,3 +490,86 @@ pub fn inject_code(transpiled_code: &str, uses_mpi: bool) -> Result<String> {
         after_inject
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN_RS: &str = "fn main() {}\n";
+
+    const SOURCE: &str = "program.rosy";
+
+    #[test]
+    fn projects_are_keyed_by_content_and_share_the_runtime() {
+        let cache = tempfile::tempdir().unwrap();
+        let project = create_output_project(cache.path(), Path::new(SOURCE), MAIN_RS, None, false, false, 6).unwrap();
+        assert!(project.dir.join("src/main.rs").is_file());
+        assert!(!project.is_built(false));
+
+        // The same program maps to the same project, without rewriting it
+        let marker = project.dir.join("marker");
+        std::fs::write(&marker, "").unwrap();
+        let again = create_output_project(cache.path(), Path::new(SOURCE), MAIN_RS, None, false, false, 6).unwrap();
+        assert_eq!(again.dir, project.dir);
+        assert!(marker.exists());
+
+        // A different program, profile or feature set gets its own project
+        // and binary, but all of them use the one vendored runtime
+        let other = create_output_project(cache.path(), Path::new("other.rosy"), "fn main() { }\n", None, false, false, 6).unwrap();
+        let release = create_output_project(cache.path(), Path::new(SOURCE), MAIN_RS, None, false, true, 6).unwrap();
+        let mpi = create_output_project(cache.path(), Path::new(SOURCE), MAIN_RS, Some(ParallelBackend::Mpi), false, false, 6).unwrap();
+        let names = [&project.name, &other.name, &release.name, &mpi.name];
+        for (i, name) in names.iter().enumerate() {
+            assert!(!names[i + 1..].contains(name), "{name} is used twice");
+        }
+        assert_eq!(std::fs::read_dir(cache.path().join("rosy_lib")).unwrap().count(), 1);
+        assert_eq!(project.target_dir, other.target_dir);
+
+        // A different MAX_VARS is a different runtime
+        create_output_project(cache.path(), Path::new(SOURCE), MAIN_RS, None, false, false, 8).unwrap();
+        assert_eq!(std::fs::read_dir(cache.path().join("rosy_lib")).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn rebuilding_a_program_drops_its_previous_project() {
+        let cache = tempfile::tempdir().unwrap();
+        let build = |source: &str, main_rs: &str| {
+            let project =
+                create_output_project(cache.path(), Path::new(source), main_rs, None, false, false, 6)
+                    .unwrap();
+            // Stand in for what cargo would have built
+            let deps = project.target_dir.join("debug/deps");
+            std::fs::create_dir_all(&deps).unwrap();
+            std::fs::write(project.binary(false), "").unwrap();
+            std::fs::write(deps.join(format!("{}-0123abcd", project.name)), "").unwrap();
+            project
+        };
+        let first = build("a.rosy", MAIN_RS);
+        let copy = build("copy.rosy", "fn main() { }\n");
+        let threaded = create_output_project(
+            cache.path(),
+            Path::new("a.rosy"),
+            "fn main() {  }\n",
+            Some(ParallelBackend::Threads),
+            false,
+            false,
+            6,
+        )
+        .unwrap();
+
+        // An edit of a.rosy replaces its project and binary, but not its
+        // build for another backend
+        let edited = build("a.rosy", "fn main() { }\n");
+        assert!(!first.dir.exists());
+        assert!(!first.binary(false).exists());
+        let deps = first.target_dir.join("debug/deps");
+        assert!(!deps.join(format!("{}-0123abcd", first.name)).exists());
+        assert!(threaded.dir.exists());
+
+        // copy.rosy builds to the same project, so it stays when a.rosy moves on
+        assert_eq!(edited.dir, copy.dir);
+        build("a.rosy", MAIN_RS);
+        assert!(copy.dir.exists());
+        assert!(copy.binary(false).exists());
+    }
+}
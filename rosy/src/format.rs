@@ -0,0 +1,392 @@
+//! # Source Formatter
+//!
+//! Backs `rosy fmt` and the LSP's `textDocument/formatting`. The source is
+//! parsed with the pest grammar, and only layout and keyword case change:
+//!
+//! - keywords are written in upper case,
+//! - every statement starts on its own line,
+//! - the bodies of `BEGIN`, `LOOP`, `PLOOP`, `WHILE`, `IF`, `PROCEDURE`,
+//!   `FUNCTION`, `FIT` and `PFIT` are indented by four spaces per level,
+//!   and a statement continued over several lines is indented one more level,
+//! - runs of blank lines are collapsed to one, and trailing whitespace is removed.
+//!
+//! `{ }` comments are kept where they are: a comment that follows a
+//! statement on the same line stays there, any other comment is indented
+//! like the statement after it. Strings and the text of comments are never
+//! touched, and neither is the spacing inside a statement.
+
+use anyhow::{Context, Result};
+use pest::{Parser, iterators::Pair};
+
+use crate::ast::{CosyParser, Rule};
+
+const INDENT: &str = "    ";
+
+/// Format a Rosy program. Fails if it does not parse.
+pub fn format_source(source: &str) -> Result<String> {
+    let program = CosyParser::parse(Rule::program, source)
+        .context("Couldn't parse!")?
+        .next()
+        .context("Expected a program")?;
+
+    // Keywords are upper-cased in place; ASCII case changes keep every
+    // byte offset valid.
+    let mut text = source.to_string();
+    let mut strings = Vec::new();
+    for pair in program.clone().into_inner().flatten() {
+        let span = pair.as_span();
+        if pair.as_rule() == Rule::string {
+            strings.push((span.start(), span.end()));
+        } else if starts_with_keyword(pair.as_rule()) {
+            let word = span
+                .as_str()
+                .bytes()
+                .take_while(|b| b.is_ascii_alphanumeric() || *b == b'_')
+                .count();
+            text[span.start()..span.start() + word].make_ascii_uppercase();
+        }
+    }
+
+    let mut units = Vec::new();
+    for pair in program.into_inner() {
+        match pair.as_rule() {
+            Rule::begin => units.push(Unit::opening(&pair, 0)),
+            Rule::end => units.push(Unit::plain(&pair, 0)),
+            Rule::EOI => {}
+            _ => collect_statement(pair, 1, &mut units),
+        }
+    }
+
+    let mut writer = Writer {
+        text: &text,
+        strings: &strings,
+        lines: Vec::new(),
+        depth: 0,
+    };
+    let mut position = 0;
+    for unit in &units {
+        writer.gap(position, unit.start);
+        writer.unit(unit);
+        writer.depth = unit.depth + usize::from(unit.opens);
+        position = unit.end;
+    }
+    writer.gap(position, text.len());
+
+    let mut formatted = writer.lines.join("\n");
+    formatted.push('\n');
+    Ok(formatted)
+}
+
+/// Whether pairs of `rule` begin with a keyword (as opposed to a name,
+/// number, string or bracket).
+fn starts_with_keyword(rule: Rule) -> bool {
+    !matches!(
+        rule,
+        Rule::program
+            | Rule::assignment
+            | Rule::procedure_call
+            | Rule::function_call
+            | Rule::expr
+            | Rule::memory_size
+            | Rule::variable_identifier
+            | Rule::variable_name
+            | Rule::procedure_name
+            | Rule::procedure_argument_name_and_type
+            | Rule::procedure_argument_name
+            | Rule::function_name
+            | Rule::function_argument_name
+            | Rule::fit_variable
+            | Rule::fit_lower
+            | Rule::fit_upper
+            | Rule::inner_type
+            | Rule::number
+            | Rule::unit
+            | Rule::constant
+            | Rule::daini_nm_zero
+            | Rule::string
+            | Rule::new_string
+            | Rule::old_string
+    )
+}
+
+/// A piece of the source that starts a line of its own: a simple statement,
+/// or the header, `ELSEIF`/`ELSE` clause or footer of a block.
+struct Unit {
+    start: usize,
+    end: usize,
+    depth: usize,
+    /// Whether what follows is indented one level deeper
+    opens: bool,
+}
+
+impl Unit {
+    fn plain(pair: &Pair<Rule>, depth: usize) -> Self {
+        Unit {
+            start: pair.as_span().start(),
+            end: pair.as_span().end(),
+            depth,
+            opens: false,
+        }
+    }
+
+    fn opening(pair: &Pair<Rule>, depth: usize) -> Self {
+        Unit {
+            opens: true,
+            ..Unit::plain(pair, depth)
+        }
+    }
+}
+
+fn collect_statement(pair: Pair<Rule>, depth: usize, units: &mut Vec<Unit>) {
+    match pair.as_rule() {
+        Rule::r#loop
+        | Rule::ploop
+        | Rule::while_loop
+        | Rule::procedure
+        | Rule::function
+        | Rule::fit_statement => {
+            let children: Vec<_> = pair.into_inner().collect();
+            let last = children.len() - 1;
+            for (i, child) in children.into_iter().enumerate() {
+                if i == 0 {
+                    units.push(Unit::opening(&child, depth));
+                } else if i == last {
+                    units.push(Unit::plain(&child, depth));
+                } else {
+                    collect_statement(child, depth + 1, units);
+                }
+            }
+        }
+        Rule::if_statement => {
+            for clause in pair.into_inner() {
+                if clause.as_rule() == Rule::endif {
+                    units.push(Unit::plain(&clause, depth));
+                    continue;
+                }
+                // The clause header runs up to its semicolon; the
+                // statements of the clause follow.
+                let start = clause.as_span().start();
+                let mut header = true;
+                for child in clause.into_inner() {
+                    if header {
+                        if child.as_rule() == Rule::semicolon {
+                            let end = child.as_span().end();
+                            units.push(Unit {
+                                start,
+                                end,
+                                depth,
+                                opens: true,
+                            });
+                            header = false;
+                        }
+                    } else {
+                        collect_statement(child, depth + 1, units);
+                    }
+                }
+            }
+        }
+        _ => units.push(Unit::plain(&pair, depth)),
+    }
+}
+
+/// Accumulates the formatted lines.
+struct Writer<'a> {
+    text: &'a str,
+    strings: &'a [(usize, usize)],
+    lines: Vec<String>,
+    /// Indentation level of comments between units: the depth of the
+    /// statements that follow the last unit
+    depth: usize,
+}
+
+impl Writer<'_> {
+    /// Lay out the whitespace and comments in `text[start..end]`, which
+    /// precede the next unit (or the end of the file).
+    fn gap(&mut self, start: usize, end: usize) {
+        let gap = &self.text[start..end];
+        let mut newlines = 0;
+        let mut spaces = String::new();
+        let mut chars = gap.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if c != '{' {
+                if c == '\n' {
+                    newlines += 1;
+                    spaces.clear();
+                } else if c != '\r' {
+                    spaces.push(c);
+                }
+                continue;
+            }
+
+            let length = comment_length(&gap[i..]);
+            let comment = &gap[i..i + length];
+            while chars.peek().is_some_and(|(j, _)| *j < i + length) {
+                chars.next();
+            }
+
+            let mut comment_lines = comment.split('\n').map(|line| line.trim_end());
+            let first = comment_lines.next().unwrap_or_default();
+            match self.lines.last_mut() {
+                Some(last) if newlines == 0 => {
+                    last.push_str(&spaces);
+                    last.push_str(first);
+                }
+                _ => {
+                    self.blank_line(newlines);
+                    self.lines
+                        .push(format!("{}{}", INDENT.repeat(self.depth), first));
+                }
+            }
+            self.lines.extend(comment_lines.map(str::to_string));
+            newlines = 0;
+            spaces.clear();
+        }
+        if end < self.text.len() {
+            self.blank_line(newlines);
+        }
+    }
+
+    /// Keep one blank line where the source had at least one.
+    fn blank_line(&mut self, newlines: usize) {
+        if newlines >= 2 && self.lines.last().is_some_and(|line| !line.is_empty()) {
+            self.lines.push(String::new());
+        }
+    }
+
+    fn unit(&mut self, unit: &Unit) {
+        let indent = INDENT.repeat(unit.depth);
+        let continuation = INDENT.repeat(unit.depth + 1);
+        let mut line = indent.clone();
+        let mut comment_depth = 0usize;
+        let mut skip_whitespace = false;
+        let mut position = unit.start;
+        while position < unit.end {
+            if let Ok(i) = self.strings.binary_search_by_key(&position, |(s, _)| *s) {
+                let string_end = self.strings[i].1;
+                line.push_str(&self.text[position..string_end]);
+                skip_whitespace = false;
+                position = string_end;
+                continue;
+            }
+            let c = self.text[position..].chars().next().unwrap_or_default();
+            position += c.len_utf8();
+            match c {
+                '\n' => {
+                    let trimmed = line.trim_end().len();
+                    line.truncate(trimmed);
+                    if comment_depth > 0 || !line.trim().is_empty() {
+                        self.lines.push(std::mem::take(&mut line));
+                    }
+                    if comment_depth == 0 {
+                        line = continuation.clone();
+                        skip_whitespace = true;
+                    }
+                }
+                ' ' | '\t' | '\r' if skip_whitespace => {}
+                _ => {
+                    match c {
+                        '{' => comment_depth += 1,
+                        '}' => comment_depth = comment_depth.saturating_sub(1),
+                        _ => {}
+                    }
+                    skip_whitespace = false;
+                    line.push(c);
+                }
+            }
+        }
+        let trimmed = line.trim_end().len();
+        line.truncate(trimmed);
+        self.lines.push(line);
+    }
+}
+
+/// Length of the (possibly nested) `{ }` comment at the start of `text`.
+fn comment_length(text: &str) -> usize {
+    let mut depth = 0usize;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indents_blocks_and_uppercases_keywords() {
+        let source = "begin;\nvariable (RE) x; variable (RE) Total 2;\n  loop i 1 3; x := sin(i);\n if x > 0; Total(1) := x; elseif x<0 or false;\nwrite 6 'neg'; else; endif;\nendloop;\nend;\n";
+        let expected = "\
+BEGIN;
+    VARIABLE (RE) x;
+    VARIABLE (RE) Total 2;
+    LOOP i 1 3;
+        x := SIN(i);
+        IF x > 0;
+            Total(1) := x;
+        ELSEIF x<0 OR FALSE;
+            WRITE 6 'neg';
+        ELSE;
+        ENDIF;
+    ENDLOOP;
+END;
+";
+        assert_eq!(format_source(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn keeps_comments_and_strings() {
+        let source = "{ header\n  kept as is }\nBEGIN;\n\n\n  {before} procedure RUN;   {trailing}\n\nwrite 6 'loop { not a comment }'\n   \"and\" {inside} 1;\n   {last in body}\n endprocedure; RUN;\nEND; {done}\n";
+        let expected = "\
+{ header
+  kept as is }
+BEGIN;
+
+    {before}
+    PROCEDURE RUN;   {trailing}
+
+        WRITE 6 'loop { not a comment }'
+            \"and\" {inside} 1;
+        {last in body}
+    ENDPROCEDURE;
+    RUN;
+END; {done}
+";
+        let formatted = format_source(source).unwrap();
+        assert_eq!(formatted, expected);
+        assert_eq!(format_source(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn examples_are_stable() {
+        let examples = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../examples");
+        for entry in std::fs::read_dir(examples).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|ext| ext != "rosy") {
+                continue;
+            }
+            let source = std::fs::read_to_string(&path).unwrap();
+            let Ok(once) = format_source(&source) else {
+                continue;
+            };
+            let twice = format_source(&once).unwrap_or_else(|e| {
+                panic!("{} no longer parses once formatted: {e:?}", path.display())
+            });
+            assert_eq!(
+                once,
+                twice,
+                "formatting {} is not idempotent",
+                path.display()
+            );
+        }
+    }
+}
@@ -0,0 +1,579 @@
+//! # Interpreter
+//!
+//! Runs a program directly against the in-process [`rosy_lib`](crate::rosy_lib),
+//! without generating and compiling a Rust project. Backs `rosy run
+//! --interpret` and `rosy repl`.
+//!
+//! Every statement implements [`Interpret`] and every expression [`Evaluate`],
+//! next to its `Transpile` impl, calling the same runtime functions the
+//! generated code would. A program is always transpiled before it is
+//! interpreted, so type errors are reported exactly as `rosy run` reports
+//! them and the interpreter can take well-typed input for granted.
+//!
+//! ## Scopes
+//!
+//! Variables live in [`Slot`]s, owned by a chain of [`Scope`]s. `LOOP`,
+//! `WHILE` and `IF` bodies run in a child scope; a `PROCEDURE` or `FUNCTION`
+//! body runs in a child of the scope it was defined in. An argument that is
+//! a bare variable shares the caller's slot, which is how Rosy's
+//! pass-by-reference is kept; any other argument gets a slot of its own.
+//!
+//! ## Runtime Errors
+//!
+//! Statements mark themselves in [`trace`](crate::rosy_lib::core::trace) as
+//! generated code does, so errors and panics are reported at the same Rosy
+//! source locations as in a compiled program (see [`report`]).
+
+pub mod value;
+
+pub use value::{Element, FromValue, Value};
+pub(crate) use value::{binary, unary};
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::{Rc, Weak};
+use std::time::Instant;
+
+use anyhow::{Context, Result, anyhow};
+
+use crate::program::expressions::Expr;
+use crate::program::statements::Statement;
+use crate::program::statements::core::function::FunctionStatement;
+use crate::program::statements::core::procedure::ProcedureStatement;
+use crate::program::statements::core::var_decl::VariableDeclarationData;
+use crate::rosy_lib::RosyType;
+use crate::rosy_lib::core::checkpoint::Checkpoint;
+use crate::rosy_lib::core::trace::{self, Location};
+use crate::rosy_lib::taylor;
+use crate::transpile::TranspileableExpr;
+
+/// A statement the interpreter can run.
+pub trait Interpret {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow>;
+}
+
+/// An expression the interpreter can evaluate.
+pub trait Evaluate {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value>;
+    /// Write `value` back to what this expression reads, for statements that
+    /// take their outputs as expressions (`DADER X 1 Y;`). Only variables
+    /// store anything; the result of any other expression is a temporary.
+    fn store(&self, _interpreter: &mut Interpreter<'_>, _value: Value) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// What to do after a statement.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Flow {
+    /// Go on with the next statement
+    Next,
+    /// Leave the innermost `LOOP` or `WHILE`
+    Break,
+    /// End the program with this exit code
+    Quit(i32),
+}
+
+/// A variable: its declared type and current value.
+#[derive(Debug)]
+pub struct Variable {
+    pub r#type: RosyType,
+    pub value: Value,
+}
+
+/// Where a variable is stored, shared by every scope and argument that
+/// refers to it.
+pub type Slot = Rc<RefCell<Variable>>;
+
+/// The checkpointed `LOOP` while it runs: what its `CHECKPOINT` saves.
+pub struct CheckpointTarget {
+    /// The iteration running, from 0
+    pub iteration: usize,
+    /// The variables declared before the loop, in name order
+    pub variables: Vec<(String, Slot)>,
+}
+
+/// A procedure or function, with the scope it was defined in.
+pub struct Callable<'a, T> {
+    pub definition: &'a T,
+    pub scope: Rc<RefCell<Scope<'a>>>,
+}
+
+/// The variables, procedures and functions declared in one block.
+#[derive(Default)]
+pub struct Scope<'a> {
+    variables: HashMap<String, Slot>,
+    procedures: HashMap<String, (&'a ProcedureStatement, Weak<RefCell<Scope<'a>>>)>,
+    functions: HashMap<String, (&'a FunctionStatement, Weak<RefCell<Scope<'a>>>)>,
+    parent: Option<Rc<RefCell<Scope<'a>>>>,
+}
+
+impl<'a> Scope<'a> {
+    /// Look `name` up in this scope and then its parents.
+    fn find<T>(
+        scope: &Rc<RefCell<Scope<'a>>>,
+        lookup: impl Fn(&Scope<'a>) -> Option<T>,
+    ) -> Option<T> {
+        let mut current = scope.clone();
+        loop {
+            if let Some(found) = lookup(&current.borrow()) {
+                return Some(found);
+            }
+            let parent = current.borrow().parent.clone()?;
+            current = parent;
+        }
+    }
+}
+
+pub struct Interpreter<'a> {
+    scope: Rc<RefCell<Scope<'a>>>,
+    /// When the program started, for `CPUSEC` and `PWTIME`
+    pub start: Instant,
+    /// How errors name the program's own file
+    pub source_file: String,
+    /// What `ARGGET` reads: the program, then its arguments
+    pub args: Vec<String>,
+    /// The checkpoint to resume the program from
+    pub resume: Option<Checkpoint>,
+    /// Set while the checkpointed `LOOP` runs
+    pub checkpoint: Option<CheckpointTarget>,
+    /// For `rosy repl`: the line each input starts on in the program it was
+    /// checked as, in order, so that locations are given within their input
+    pub input_lines: Vec<usize>,
+    /// The trace location of each statement run so far, by address
+    locations: HashMap<usize, &'static Location>,
+    /// Names the runtime keeps for good, such as the trace frame
+    /// `PROCEDURE RUN`
+    names: HashMap<String, &'static str>,
+}
+
+impl<'a> Interpreter<'a> {
+    /// A fresh interpreter, with the default Taylor system a generated
+    /// program starts with.
+    pub fn new(source_file: &str) -> Result<Self> {
+        taylor::cleanup_taylor();
+        taylor::init_taylor(3, 6).context("Failed to initialize Taylor system")?;
+        Ok(Interpreter {
+            scope: Rc::default(),
+            start: Instant::now(),
+            source_file: source_file.to_string(),
+            args: vec![source_file.to_string()],
+            resume: None,
+            checkpoint: None,
+            input_lines: Vec::new(),
+            locations: HashMap::new(),
+            names: HashMap::new(),
+        })
+    }
+
+    /// Run `statements` in the current scope until one of them does not
+    /// continue with the next.
+    pub fn run(&mut self, statements: &'a [Statement]) -> Result<Flow> {
+        for statement in statements {
+            let flow = statement.interpret(self)?;
+            if flow != Flow::Next {
+                return Ok(flow);
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    /// Run `statements` in a new child scope, as a block body.
+    pub fn run_block(&mut self, statements: &'a [Statement]) -> Result<Flow> {
+        self.block(|interpreter| interpreter.run(statements))
+    }
+
+    /// Run `f` in a new child scope of the current one.
+    pub fn block<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let child = self.child_scope(self.scope.clone());
+        self.in_scope(child, f)
+    }
+
+    /// A new, empty scope below `parent`.
+    pub fn child_scope(&self, parent: Rc<RefCell<Scope<'a>>>) -> Rc<RefCell<Scope<'a>>> {
+        Rc::new(RefCell::new(Scope {
+            parent: Some(parent),
+            ..Default::default()
+        }))
+    }
+
+    /// Run `f` with `scope` as the current scope.
+    pub fn in_scope<T>(
+        &mut self,
+        scope: Rc<RefCell<Scope<'a>>>,
+        f: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        let outer = std::mem::replace(&mut self.scope, scope);
+        let result = f(self);
+        self.scope = outer;
+        result
+    }
+
+    /// Go back to the outermost scope, after a panic left blocks unfinished.
+    pub fn leave_blocks(&mut self) {
+        loop {
+            let parent = self.scope.borrow().parent.clone();
+            match parent {
+                Some(parent) => self.scope = parent,
+                None => break,
+            }
+        }
+    }
+
+    /// Declare a variable in the current scope, replacing any of the same
+    /// name there.
+    pub fn declare(&mut self, name: &str, r#type: RosyType, value: Value) -> Result<()> {
+        let value = value.conform(&r#type)?;
+        self.bind(name, Rc::new(RefCell::new(Variable { r#type, value })));
+        Ok(())
+    }
+
+    /// Make `slot` visible as `name` in the current scope.
+    pub fn bind(&mut self, name: &str, slot: Slot) {
+        self.scope
+            .borrow_mut()
+            .variables
+            .insert(name.to_string(), slot);
+    }
+
+    /// The slot of the variable `name`.
+    pub fn slot(&self, name: &str) -> Result<Slot> {
+        Scope::find(&self.scope, |scope| scope.variables.get(name).cloned())
+            .ok_or_else(|| anyhow!("Variable '{name}' is not defined in this scope!"))
+    }
+
+    /// The variables declared in the current scope itself, in name order.
+    pub fn local_variables(&self) -> Vec<(String, Slot)> {
+        let mut variables: Vec<(String, Slot)> = self
+            .scope
+            .borrow()
+            .variables
+            .iter()
+            .map(|(name, slot)| (name.clone(), slot.clone()))
+            .collect();
+        variables.sort_by(|a, b| a.0.cmp(&b.0));
+        variables
+    }
+
+    /// Every variable visible from the current scope, each name once.
+    pub fn visible_variables(&self) -> Vec<(String, Slot)> {
+        let mut visible: HashMap<String, Slot> = HashMap::new();
+        let mut current = Some(self.scope.clone());
+        while let Some(scope) = current {
+            for (name, slot) in &scope.borrow().variables {
+                visible.entry(name.clone()).or_insert_with(|| slot.clone());
+            }
+            current = scope.borrow().parent.clone();
+        }
+        visible.into_iter().collect()
+    }
+
+    /// The declared type of the variable `name`, if there is one.
+    pub fn variable_type(&self, name: &str) -> Option<RosyType> {
+        Scope::find(&self.scope, |scope| {
+            scope.variables.get(name).map(|slot| slot.borrow().r#type)
+        })
+    }
+
+    pub fn define_procedure(&mut self, procedure: &'a ProcedureStatement) {
+        let defined_in = Rc::downgrade(&self.scope);
+        self.scope
+            .borrow_mut()
+            .procedures
+            .insert(procedure.name.clone(), (procedure, defined_in));
+    }
+
+    pub fn procedure(&self, name: &str) -> Result<Callable<'a, ProcedureStatement>> {
+        Scope::find(&self.scope, |scope| scope.procedures.get(name).cloned())
+            .and_then(|(definition, scope)| {
+                Some(Callable {
+                    definition,
+                    scope: scope.upgrade()?,
+                })
+            })
+            .ok_or_else(|| anyhow!("procedure '{name}' is not defined in this scope!"))
+    }
+
+    pub fn define_function(&mut self, function: &'a FunctionStatement) {
+        let defined_in = Rc::downgrade(&self.scope);
+        self.scope
+            .borrow_mut()
+            .functions
+            .insert(function.name.clone(), (function, defined_in));
+    }
+
+    pub fn function(&self, name: &str) -> Result<Callable<'a, FunctionStatement>> {
+        Scope::find(&self.scope, |scope| scope.functions.get(name).cloned())
+            .and_then(|(definition, scope)| {
+                Some(Callable {
+                    definition,
+                    scope: scope.upgrade()?,
+                })
+            })
+            .ok_or_else(|| anyhow!("Function '{name}' is not defined in this scope!"))
+    }
+
+    /// The number of arguments of the function `name`, if there is one.
+    pub fn function_arity(&self, name: &str) -> Option<usize> {
+        Scope::find(&self.scope, |scope| {
+            scope
+                .functions
+                .get(name)
+                .map(|(function, _)| function.args.len())
+        })
+    }
+
+    /// Call a procedure or function defined in `callee`: bind `arguments` to
+    /// `parameters`, run `body` in a new scope below `callee`, then `finish`
+    /// there (a function reads its return variable).
+    ///
+    /// A bare variable argument shares the caller's slot. Repeating one in
+    /// the same call passes a copy that is written back afterwards, as the
+    /// generated code does.
+    pub fn call<T>(
+        &mut self,
+        frame: String,
+        callee: Rc<RefCell<Scope<'a>>>,
+        parameters: &[VariableDeclarationData],
+        arguments: &[Expr],
+        body: &'a [Statement],
+        finish: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        let mut bound = Vec::new();
+        let mut passed = HashSet::new();
+        let mut writebacks = Vec::new();
+        for (parameter, argument) in parameters.iter().zip(arguments) {
+            let r#type = parameter.require_type()?;
+            let slot = match argument.as_bare_variable_name() {
+                Some(name) if passed.insert(name) => self.slot(name)?,
+                Some(name) => {
+                    let original = self.slot(name)?;
+                    let copy = Rc::new(RefCell::new(Variable {
+                        r#type,
+                        value: original.borrow().value.clone(),
+                    }));
+                    writebacks.push((original, copy.clone()));
+                    copy
+                }
+                None => {
+                    let value = argument.evaluate(self)?.conform(&r#type)?;
+                    Rc::new(RefCell::new(Variable { r#type, value }))
+                }
+            };
+            bound.push((parameter.name.as_str(), slot));
+        }
+
+        let scope = self.child_scope(callee);
+        let result = self.in_scope(scope, |interpreter| {
+            for (name, slot) in bound {
+                interpreter.bind(name, slot);
+            }
+            interpreter.frame(frame, |interpreter| {
+                if let Flow::Quit(code) = interpreter.run(body)? {
+                    // There is no statement left to hand the exit to
+                    std::process::exit(code);
+                }
+                finish(interpreter)
+            })
+        })?;
+
+        for (original, copy) in writebacks {
+            original.borrow_mut().value = copy.borrow().value.clone();
+        }
+        Ok(result)
+    }
+
+    /// Evaluate `expr` as the Rust type a runtime function takes.
+    pub fn evaluate_as<T: FromValue>(&mut self, expr: &Expr) -> Result<T> {
+        expr.evaluate(self)?.to()
+    }
+
+    /// Read `expr` as `T`, let `f` change it and store it back, for runtime
+    /// functions that take a `&mut` argument.
+    pub fn update<T: FromValue>(
+        &mut self,
+        expr: &Expr,
+        f: impl FnOnce(&mut T) -> Result<()>,
+    ) -> Result<()>
+    where
+        Value: From<T>,
+    {
+        let mut value: T = self.evaluate_as(expr)?;
+        f(&mut value)?;
+        expr.store(self, Value::from(value))
+    }
+
+    /// Record `statement` as running in the trace.
+    pub fn mark(&mut self, statement: &Statement) {
+        let source_file = &self.source_file;
+        let input_lines = &self.input_lines;
+        let location = *self
+            .locations
+            .entry(statement as *const Statement as usize)
+            .or_insert_with(|| {
+                let location = &statement.source_location;
+                let (file, line) = match &location.file {
+                    Some(path) => (path.display().to_string(), location.line),
+                    None => (source_file.clone(), input_line(input_lines, location.line)),
+                };
+                Box::leak(Box::new(Location {
+                    file: Box::leak(file.into_boxed_str()),
+                    line: line as u32,
+                    col: location.col as u32,
+                    snippet: Box::leak(location.snippet.clone().into_boxed_str()),
+                }))
+            });
+        trace::at(location);
+    }
+
+    /// `line` of the checked program as a line of the input it is in, if
+    /// inputs are being counted.
+    pub fn input_line(&self, line: usize) -> usize {
+        input_line(&self.input_lines, line)
+    }
+
+    /// `name` as a `&'static str`, leaked once however often it is asked
+    /// for.
+    pub fn intern(&mut self, name: &str) -> &'static str {
+        if let Some(interned) = self.names.get(name) {
+            return interned;
+        }
+        let interned: &'static str = Box::leak(name.to_string().into_boxed_str());
+        self.names.insert(name.to_string(), interned);
+        interned
+    }
+
+    /// Run `f` as the body of a procedure or function called by the current
+    /// statement, such as `PROCEDURE RUN`. On an error the frame stays, so
+    /// the report names the call.
+    pub fn frame<T>(&mut self, name: String, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        trace::enter(self.intern(&name));
+        let result = f(self)?;
+        trace::leave();
+        Ok(result)
+    }
+}
+
+/// `line` counted from the start of the last of `input_lines` at or before it.
+fn input_line(input_lines: &[usize], line: usize) -> usize {
+    match input_lines.partition_point(|&start| start <= line) {
+        0 => line,
+        input => line - input_lines[input - 1] + 1,
+    }
+}
+
+/// An error as a generated program reports it: the cause, its context and
+/// where in the Rosy source it happened.
+pub fn report(error: &anyhow::Error) -> String {
+    let mut report = error.root_cause().to_string();
+    for (index, context) in error.chain().rev().enumerate().skip(1) {
+        if let Some(source) = context.source() {
+            report += &format!("\n {index}: {source}");
+        }
+    }
+    let location = trace::Trace::of_error(error).report();
+    if !location.is_empty() {
+        report += &format!("\n{location}");
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{CosyParser, Rule};
+    use crate::program::{IncludeTracker, Program};
+    use crate::resolve::TypeResolver;
+    use crate::transpile::{TranspilationInputContext, Transpile};
+    use pest::Parser;
+    use serial_test::serial;
+
+    /// Check `source` as `rosy run` would and run it, returning the value
+    /// each of `names` ends up with.
+    fn run(source: &str, names: &[&str]) -> Vec<Value> {
+        let pair = CosyParser::parse(Rule::program, source)
+            .unwrap()
+            .next()
+            .unwrap();
+        let mut program =
+            Program::from_rule_with_includes(pair, None, &mut IncludeTracker::default())
+                .unwrap()
+                .unwrap();
+        TypeResolver::resolve(&mut program).unwrap();
+        program
+            .transpile(&mut TranspilationInputContext::default())
+            .map_err(|errors| errors[0].to_string())
+            .unwrap();
+
+        let mut interpreter = Interpreter::new("test.rosy").unwrap();
+        interpreter.run(&program.statements).unwrap();
+        names
+            .iter()
+            .map(|name| interpreter.slot(name).unwrap().borrow().value.clone())
+            .collect()
+    }
+
+    #[test]
+    #[serial]
+    fn loops_and_procedures_share_the_callers_variables() {
+        let values = run(
+            "BEGIN;
+                VARIABLE (RE) TOTAL;
+                PROCEDURE ADD X (RE) N (RE);
+                    X := X + N;
+                ENDPROCEDURE;
+                TOTAL := 0;
+                LOOP I 1 4;
+                    ADD TOTAL I;
+                ENDLOOP;
+            END;",
+            &["TOTAL"],
+        );
+        assert_eq!(values[0].to::<f64>().unwrap(), 10.0);
+    }
+
+    #[test]
+    #[serial]
+    fn functions_return_through_their_name() {
+        let values = run(
+            "BEGIN;
+                VARIABLE (RE) Y;
+                VARIABLE (VE) V;
+                FUNCTION (RE) SQ A;
+                    SQ := A * A;
+                ENDFUNCTION;
+                Y := SQ(3) + 1;
+                V := Y & SQ(Y);
+            END;",
+            &["Y", "V"],
+        );
+        assert_eq!(values[0].to::<f64>().unwrap(), 10.0);
+        assert_eq!(values[1].to::<Vec<f64>>().unwrap(), vec![10.0, 100.0]);
+    }
+
+    #[test]
+    #[serial]
+    fn da_arithmetic_runs_on_the_runtime() {
+        let values = run(
+            "BEGIN;
+                VARIABLE (DA) D;
+                VARIABLE (RE) C;
+                DAINI 2 1 0 0;
+                D := (1 + DA(1)) * (1 + DA(1));
+                C := CONS(D);
+            END;",
+            &["C"],
+        );
+        assert_eq!(values[0].to::<f64>().unwrap(), 1.0);
+    }
+
+    #[test]
+    fn lines_are_counted_within_their_input() {
+        // The prologue, a one-line input, then a three-line one
+        let input_lines = [2, 3];
+        assert_eq!(input_line(&[], 7), 7);
+        assert_eq!(input_line(&input_lines, 2), 1);
+        assert_eq!(input_line(&input_lines, 3), 1);
+        assert_eq!(input_line(&input_lines, 5), 3);
+    }
+}
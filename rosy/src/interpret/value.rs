@@ -0,0 +1,435 @@
+//! # Runtime Values
+//!
+//! [`Value`] holds any Rosy value while the interpreter runs. Each variant
+//! wraps the same `rosy_lib` type the transpiler would have generated, so
+//! operators and intrinsics are evaluated by the very trait impls a compiled
+//! program calls.
+//!
+//! A one-dimensional RE array and a VE are both `Vec<f64>` in Rust. Here the
+//! VE is [`Value::VE`] and the array is a [`Value::Array`] of [`Value::RE`];
+//! [`FromValue`] accepts either where the runtime wants a `Vec<f64>`, and
+//! [`Value::conform`] turns results back into the shape of the variable they
+//! are stored in.
+
+use std::borrow::Cow;
+
+use anyhow::{Result, anyhow, bail, ensure};
+
+use crate::rosy_lib::operators::widen::RosyWiden;
+use crate::rosy_lib::{CD, CM, DA, GR, LO, RE, RosyBaseType, RosyST, RosyType, ST, VE};
+
+/// A Rosy value.
+#[derive(Clone)]
+pub enum Value {
+    RE(RE),
+    ST(ST),
+    LO(LO),
+    CM(CM),
+    VE(VE),
+    DA(DA),
+    CD(CD),
+    GR(GR),
+    /// The elements of an array, all of the same type
+    Array(Vec<Value>),
+}
+
+/// Formats as the wrapped value does, so `MEMDPV` dumps what a compiled
+/// program would.
+impl std::fmt::Debug for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::RE(x) => x.fmt(f),
+            Value::ST(x) => x.fmt(f),
+            Value::LO(x) => x.fmt(f),
+            Value::CM(x) => x.fmt(f),
+            Value::VE(x) => x.fmt(f),
+            Value::DA(x) => x.fmt(f),
+            Value::CD(x) => x.fmt(f),
+            Value::GR(x) => x.fmt(f),
+            Value::Array(elements) => elements.fmt(f),
+        }
+    }
+}
+
+impl Value {
+    /// What a new variable of type `r#type` holds: zero, empty, or `FALSE`.
+    /// Arrays start out empty.
+    pub fn zero(r#type: &RosyType) -> Value {
+        if r#type.dimensions > 0 {
+            return Value::Array(Vec::new());
+        }
+        match r#type.base_type {
+            RosyBaseType::RE => Value::RE(0.0),
+            RosyBaseType::ST => Value::ST(String::new()),
+            RosyBaseType::LO => Value::LO(false),
+            RosyBaseType::CM => Value::CM(CM::new(0.0, 0.0)),
+            RosyBaseType::VE => Value::VE(Vec::new()),
+            RosyBaseType::DA => Value::DA(DA::zero()),
+            RosyBaseType::CD => Value::CD(CD::zero()),
+            RosyBaseType::GR => Value::GR(GR::default()),
+        }
+    }
+
+    /// A `VARIABLE` of type `r#type` declared with the given array lengths,
+    /// outermost first.
+    pub fn declared(r#type: &RosyType, lengths: &[usize]) -> Value {
+        if lengths.is_empty() {
+            return Value::zero(r#type);
+        }
+        let mut value = Value::zero(&RosyType::new(r#type.base_type, 0));
+        for &length in lengths.iter().rev() {
+            value = Value::Array(vec![value; length]);
+        }
+        value
+    }
+
+    /// The base type of this value, or of the elements of an array. An
+    /// empty array has none.
+    pub fn base_type(&self) -> Option<RosyBaseType> {
+        Some(match self {
+            Value::RE(_) => RosyBaseType::RE,
+            Value::ST(_) => RosyBaseType::ST,
+            Value::LO(_) => RosyBaseType::LO,
+            Value::CM(_) => RosyBaseType::CM,
+            Value::VE(_) => RosyBaseType::VE,
+            Value::DA(_) => RosyBaseType::DA,
+            Value::CD(_) => RosyBaseType::CD,
+            Value::GR(_) => RosyBaseType::GR,
+            Value::Array(elements) => return elements.first().and_then(Value::base_type),
+        })
+    }
+
+    /// The type as Rosy prints it, for error messages.
+    pub fn type_name(&self) -> String {
+        match self {
+            Value::Array(elements) => match elements.first() {
+                Some(element) => format!("an array of {}", element.type_name()),
+                None => "an empty array".to_string(),
+            },
+            value => match value.base_type() {
+                Some(base_type) => format!("({base_type})"),
+                None => unreachable!("only arrays can lack a base type"),
+            },
+        }
+    }
+
+    /// Convert to the Rust type a `rosy_lib` function takes.
+    pub fn to<T: FromValue>(&self) -> Result<T> {
+        T::from_value(self)
+            .map(Cow::into_owned)
+            .ok_or_else(|| anyhow!("Expected {}, found {}", T::type_name(), self.type_name()))
+    }
+
+    /// This value as `WRITE` and `ST()` print it.
+    pub fn to_rosy_string(&self) -> Result<String> {
+        Ok(match self {
+            Value::RE(value) => RosyST::rosy_to_string(value),
+            Value::ST(value) => RosyST::rosy_to_string(value),
+            Value::LO(value) => RosyST::rosy_to_string(value),
+            Value::CM(value) => RosyST::rosy_to_string(value),
+            Value::VE(value) => RosyST::rosy_to_string(value),
+            Value::DA(value) => RosyST::rosy_to_string(value),
+            Value::CD(value) => RosyST::rosy_to_string(value),
+            Value::GR(value) => RosyST::rosy_to_string(value),
+            Value::Array(_) => bail!("Cannot convert {} to 'ST'!", self.type_name()),
+        })
+    }
+
+    /// Reshape this value to be stored in a variable of type `r#type`,
+    /// widening it if it is narrower (see [`widen`](crate::rosy_lib::operators::widen)).
+    pub fn conform(self, r#type: &RosyType) -> Result<Value> {
+        if r#type.dimensions == 0 {
+            return match (self, r#type.base_type) {
+                (Value::Array(elements), RosyBaseType::VE) => elements
+                    .iter()
+                    .map(Value::to::<RE>)
+                    .collect::<Result<_>>()
+                    .map(Value::VE),
+                (value, base_type) => value.widen(base_type),
+            };
+        }
+        let element_type = RosyType::new(r#type.base_type, r#type.dimensions - 1);
+        let elements = match self {
+            Value::Array(elements) => elements,
+            Value::VE(elements) if r#type.dimensions == 1 => {
+                elements.into_iter().map(Value::RE).collect()
+            }
+            value => bail!(
+                "Expected an array of type {}, found {}",
+                r#type,
+                value.type_name()
+            ),
+        };
+        elements
+            .into_iter()
+            .map(|element| element.conform(&element_type))
+            .collect::<Result<_>>()
+            .map(Value::Array)
+    }
+
+    /// Widen a single value to `base_type`, like [`RosyWiden`].
+    fn widen(self, base_type: RosyBaseType) -> Result<Value> {
+        Ok(match (self, base_type) {
+            (Value::RE(value), RosyBaseType::CM) => Value::CM(RosyWiden::<CM>::rosy_widen(&value)?),
+            (Value::RE(value), RosyBaseType::DA) => Value::DA(RosyWiden::<DA>::rosy_widen(&value)?),
+            (Value::RE(value), RosyBaseType::CD) => Value::CD(RosyWiden::<CD>::rosy_widen(&value)?),
+            (Value::CM(value), RosyBaseType::CD) => Value::CD(RosyWiden::<CD>::rosy_widen(&value)?),
+            (Value::DA(value), RosyBaseType::CD) => Value::CD(RosyWiden::<CD>::rosy_widen(&value)?),
+            (value, base_type) => {
+                ensure!(
+                    value.base_type() == Some(base_type),
+                    "Cannot store {} in a variable of type ({base_type})",
+                    value.type_name()
+                );
+                value
+            }
+        })
+    }
+
+    /// Read the element at the given 1-based indices, like `rosy_get`.
+    /// A VE takes one more index than its array dimensions, for a component.
+    pub fn get(&self, indices: &[f64], name: &str) -> Result<Value> {
+        let mut value = self;
+        for (position, &index) in indices.iter().enumerate() {
+            match value {
+                Value::Array(elements) => value = element(elements, index, name)?,
+                Value::VE(components) if position + 1 == indices.len() => {
+                    return Ok(Value::RE(*element(components, index, name)?));
+                }
+                other => bail!("Cannot index into '{name}' of type {}", other.type_name()),
+            }
+        }
+        Ok(value.clone())
+    }
+
+    /// Store `new` at the given 1-based indices, like `rosy_get_mut`: arrays
+    /// grow to fit the index, padded with the zero of their element type.
+    /// `r#type` is the type of this value.
+    pub fn set(
+        &mut self,
+        indices: &[f64],
+        new: Value,
+        r#type: &RosyType,
+        name: &str,
+    ) -> Result<()> {
+        let Some((&index, rest)) = indices.split_first() else {
+            *self = new.conform(r#type)?;
+            return Ok(());
+        };
+        let position = slot(index, name)?;
+        match self {
+            Value::Array(elements) => {
+                ensure!(
+                    r#type.dimensions > 0,
+                    "Cannot index into '{name}' of type {}",
+                    r#type
+                );
+                let element_type = RosyType::new(r#type.base_type, r#type.dimensions - 1);
+                if position >= elements.len() {
+                    elements.resize(position + 1, Value::zero(&element_type));
+                }
+                elements[position].set(rest, new, &element_type, name)
+            }
+            Value::VE(components) if rest.is_empty() => {
+                if position >= components.len() {
+                    components.resize(position + 1, 0.0);
+                }
+                components[position] = new.to::<RE>()?;
+                Ok(())
+            }
+            other => bail!("Cannot index into '{name}' of type {}", other.type_name()),
+        }
+    }
+}
+
+/// The element at a 1-based index, with `rosy_get`'s message when it is
+/// out of bounds.
+fn element<'v, T>(elements: &'v [T], index: f64, name: &str) -> Result<&'v T> {
+    let index = index.round() as usize;
+    elements.get(index.wrapping_sub(1)).ok_or_else(|| {
+        anyhow!(
+            "Index {} into '{}' is out of bounds (1-{})",
+            index,
+            name,
+            elements.len()
+        )
+    })
+}
+
+/// The 0-based position a 1-based index writes to.
+fn slot(index: f64, name: &str) -> Result<usize> {
+    let index = index.round() as usize;
+    ensure!(
+        index != 0,
+        "Index 0 into '{name}' is out of bounds — Rosy uses 1-based indexing"
+    );
+    Ok(index - 1)
+}
+
+/// A Rust type from `rosy_lib` that a [`Value`] can be read as.
+pub trait FromValue: Clone {
+    /// The value as this type, borrowed where the representation allows.
+    fn from_value(value: &Value) -> Option<Cow<'_, Self>>;
+    /// How the type reads in errors.
+    fn type_name() -> String;
+}
+
+/// A Rust type whose `Vec` is a Rosy array. Everything but `f64`, whose
+/// `Vec` is read as a VE as well.
+pub trait Element: FromValue {}
+
+macro_rules! scalar {
+    ($($variant:ident),*) => {$(
+        impl FromValue for $variant {
+            fn from_value(value: &Value) -> Option<Cow<'_, Self>> {
+                match value {
+                    Value::$variant(inner) => Some(Cow::Borrowed(inner)),
+                    _ => None,
+                }
+            }
+            fn type_name() -> String {
+                format!("({})", RosyBaseType::$variant)
+            }
+        }
+        impl From<$variant> for Value {
+            fn from(inner: $variant) -> Value {
+                Value::$variant(inner)
+            }
+        }
+    )*};
+}
+scalar!(RE, ST, LO, CM, DA, CD, GR);
+
+impl Element for ST {}
+impl Element for LO {}
+impl Element for CM {}
+impl Element for DA {}
+impl Element for CD {}
+impl Element for GR {}
+impl Element for VE {}
+impl<T: Element> Element for Vec<T> where Value: From<T> {}
+
+impl FromValue for VE {
+    fn from_value(value: &Value) -> Option<Cow<'_, Self>> {
+        match value {
+            Value::VE(components) => Some(Cow::Borrowed(components)),
+            Value::Array(elements) => elements
+                .iter()
+                .map(|element| RE::from_value(element).map(Cow::into_owned))
+                .collect::<Option<_>>()
+                .map(Cow::Owned),
+            _ => None,
+        }
+    }
+    fn type_name() -> String {
+        "(VE)".to_string()
+    }
+}
+impl From<VE> for Value {
+    fn from(components: VE) -> Value {
+        Value::VE(components)
+    }
+}
+
+impl<T: Element> FromValue for Vec<T> {
+    fn from_value(value: &Value) -> Option<Cow<'_, Self>> {
+        match value {
+            Value::Array(elements) => elements
+                .iter()
+                .map(|element| T::from_value(element).map(Cow::into_owned))
+                .collect::<Option<_>>()
+                .map(Cow::Owned),
+            _ => None,
+        }
+    }
+    fn type_name() -> String {
+        format!("an array of {}", T::type_name())
+    }
+}
+impl<T: Element> From<Vec<T>> for Value
+where
+    Value: From<T>,
+{
+    fn from(elements: Vec<T>) -> Value {
+        Value::Array(elements.into_iter().map(Value::from).collect())
+    }
+}
+
+/// Evaluate a one-argument intrinsic for the first of `types` the value is.
+/// `$value` binds as a reference to that type in `$body`; the enclosing
+/// function returns the result.
+///
+/// ```ignore
+/// unary!("SIN", value, x => RosySIN::rosy_sin(x)?, [RE, CM, VE, DA, CD])
+/// ```
+macro_rules! unary {
+    ($name:literal, $value:expr, $bound:ident => $body:expr, [$($type:ty),* $(,)?]) => {{
+        let value: &$crate::interpret::Value = $value;
+        $(
+            if let Some($bound) = <$type as $crate::interpret::FromValue>::from_value(value) {
+                let $bound: &$type = &$bound;
+                return Ok($crate::interpret::Value::from($body));
+            }
+        )*
+        anyhow::bail!("{} is not supported for {}", $name, value.type_name())
+    }};
+}
+pub(crate) use unary;
+
+/// Evaluate a two-operand operator for the first of the `(left, right)`
+/// type pairs the operands are, like [`unary!`].
+macro_rules! binary {
+    ($name:literal, $left:expr, $right:expr, ($l:ident, $r:ident) => $body:expr, [$(($left_type:ty, $right_type:ty)),* $(,)?]) => {{
+        let (left, right): (&$crate::interpret::Value, &$crate::interpret::Value) = ($left, $right);
+        $(
+            if let (Some($l), Some($r)) = (
+                <$left_type as $crate::interpret::FromValue>::from_value(left),
+                <$right_type as $crate::interpret::FromValue>::from_value(right),
+            ) {
+                let ($l, $r): (&$left_type, &$right_type) = (&$l, &$r);
+                return Ok($crate::interpret::Value::from($body));
+            }
+        )*
+        anyhow::bail!(
+            "'{}' is not supported for {} and {}",
+            $name,
+            left.type_name(),
+            right.type_name()
+        )
+    }};
+}
+pub(crate) use binary;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrays_and_vectors_keep_their_shape() {
+        let array = RosyType::new(RosyBaseType::RE, 1);
+        let mut x = Value::declared(&array, &[2]);
+        x.set(&[3.0], Value::RE(7.0), &array, "X").unwrap();
+        assert_eq!(x.to::<Vec<f64>>().unwrap(), vec![0.0, 0.0, 7.0]);
+        assert!(matches!(x.get(&[3.0], "X").unwrap(), Value::RE(7.0)));
+        assert_eq!(
+            x.get(&[4.0], "X").unwrap_err().to_string(),
+            "Index 4 into 'X' is out of bounds (1-3)"
+        );
+
+        // A VE result stored in an RE array becomes an array, and the other
+        // way around
+        let stored = Value::VE(vec![1.0, 2.0]).conform(&array).unwrap();
+        assert!(matches!(&stored, Value::Array(elements) if elements.len() == 2));
+        assert!(matches!(
+            stored.conform(&RosyType::VE()).unwrap(),
+            Value::VE(_)
+        ));
+
+        // Narrower values widen to the variable's type
+        assert!(matches!(
+            Value::RE(1.0).conform(&RosyType::CM()).unwrap(),
+            Value::CM(_)
+        ));
+        assert!(Value::ST("A".into()).conform(&RosyType::RE()).is_err());
+    }
+}
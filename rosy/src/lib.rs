@@ -17,11 +17,15 @@
 pub mod ast;
 pub mod embedded;
 pub mod errors;
+pub mod format;
+pub mod interpret;
+pub mod library;
 pub mod lsp;
 pub mod manifest;
 pub mod program;
 pub mod resolve;
 #[allow(unused_imports, dead_code)]
 pub mod rosy_lib;
+pub mod parallel_config;
 pub mod syntax_config;
 pub mod transpile;
@@ -0,0 +1,523 @@
+//! # C ABI
+//!
+//! Backs `rosy build --cdylib`: the library crate also gets a `c_abi`
+//! module of `extern "C"` wrappers around [`State`](super) and its methods,
+//! and a C header declaring them, so C, C++ and Python (`ctypes`) can load
+//! the program as a shared library.
+//!
+//! Every symbol starts with the crate name. Taking `lattice` as the crate:
+//!
+//! | Rosy | C parameter | Notes |
+//! |------|-------------|-------|
+//! | `RE` | `double *` | Written back |
+//! | `LO` | `bool *` | Written back |
+//! | `VE`, `(RE 1)` | `double *, size_t` | Written back; the length must stay the same |
+//! | `ST` | `const char *` | UTF-8; changes are not written back |
+//! | `DA` | `lattice_da *` | An opaque handle, changed in place |
+//!
+//! Each `PROCEDURE` and `FUNCTION` becomes `int lattice_NAME(lattice_state
+//! *state, <arguments>[, <out>])`, returning 0, or -1 with the message in
+//! `lattice_last_error()`. A `FUNCTION`'s result goes to the trailing
+//! `out` pointer. Definitions using other types are left out of the C ABI.
+
+use crate::rosy_lib::{RosyBaseType, RosyType};
+
+use super::{Export, Library};
+
+/// How a Rosy type crosses the C ABI.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CType {
+    Real,
+    Logical,
+    Vector,
+    String,
+    Da,
+}
+
+impl CType {
+    fn of(r#type: &RosyType) -> Option<CType> {
+        match (r#type.base_type, r#type.dimensions) {
+            (RosyBaseType::RE, 0) => Some(CType::Real),
+            (RosyBaseType::LO, 0) => Some(CType::Logical),
+            (RosyBaseType::VE, 0) | (RosyBaseType::RE, 1) => Some(CType::Vector),
+            (RosyBaseType::ST, 0) => Some(CType::String),
+            (RosyBaseType::DA, 0) => Some(CType::Da),
+            _ => None,
+        }
+    }
+
+    /// The C declarations of the parameters for an argument `name`.
+    fn c_parameters(self, name: &str, prefix: &str) -> Vec<String> {
+        match self {
+            CType::Real => vec![format!("double *{name}")],
+            CType::Logical => vec![format!("bool *{name}")],
+            CType::Vector => vec![format!("double *{name}"), format!("size_t {name}_len")],
+            CType::String => vec![format!("const char *{name}")],
+            CType::Da => vec![format!("{prefix}_da *{name}")],
+        }
+    }
+
+    /// The same parameters in Rust.
+    fn rust_parameters(self, name: &str) -> Vec<String> {
+        match self {
+            CType::Real => vec![format!("{name}: *mut f64")],
+            CType::Logical => vec![format!("{name}: *mut bool")],
+            CType::Vector => vec![format!("{name}: *mut f64"), format!("{name}_len: usize")],
+            CType::String => vec![format!("{name}: *const c_char")],
+            CType::Da => vec![format!("{name}: *mut DA")],
+        }
+    }
+
+    /// The C declaration of a `FUNCTION`'s `out` parameter(s).
+    fn c_out(self, prefix: &str) -> Vec<String> {
+        match self {
+            CType::Vector => vec!["double *out".to_string(), "size_t out_len".to_string()],
+            CType::String => vec!["char **out".to_string()],
+            CType::Da => vec![format!("{prefix}_da **out")],
+            other => other.c_parameters("out", prefix),
+        }
+    }
+
+    /// The same parameters in Rust.
+    fn rust_out(self) -> Vec<String> {
+        match self {
+            CType::String => vec!["out: *mut *mut c_char".to_string()],
+            CType::Da => vec!["out: *mut *mut DA".to_string()],
+            other => other.rust_parameters("out"),
+        }
+    }
+
+    /// Statements storing `result` through `out`.
+    fn store(self) -> String {
+        match self {
+            CType::Real | CType::Logical => {
+                "*unsafe { value(out, \"out\") }? = result;".to_string()
+            }
+            CType::Vector => "unsafe { vector_out(&result, out, out_len, \"out\") }?;".to_string(),
+            CType::String => {
+                "*unsafe { value(out, \"out\") }? = CString::new(result)?.into_raw();".to_string()
+            }
+            CType::Da => {
+                "*unsafe { value(out, \"out\") }? = Box::into_raw(Box::new(result));".to_string()
+            }
+        }
+    }
+}
+
+/// The C types of an export's arguments and result, or the first Rosy type
+/// that has none.
+fn c_types(export: &Export) -> Result<(Vec<CType>, Option<CType>), RosyType> {
+    let args = export
+        .args
+        .iter()
+        .map(|arg| CType::of(&arg.r#type).ok_or(arg.r#type))
+        .collect::<Result<Vec<_>, _>>()?;
+    let returns = match &export.return_type {
+        Some(return_type) => Some(CType::of(return_type).ok_or(*return_type)?),
+        None => None,
+    };
+    Ok((args, returns))
+}
+
+/// The wrappers every C ABI has, before those of the exports. `{prefix}`
+/// is replaced with the crate name.
+const RUNTIME: &str = r#"
+	thread_local! {
+		static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+	}
+
+	/// Run `call`, returning 0, or -1 with the error kept for `{prefix}_last_error`.
+	fn status(call: impl FnOnce() -> Result<()>) -> c_int {
+		let message = match catch_unwind(AssertUnwindSafe(call)) {
+			Ok(Ok(())) => return 0,
+			Ok(Err(e)) => format!("{e:#}"),
+			Err(panic) => panic
+				.downcast_ref::<String>()
+				.cloned()
+				.or_else(|| panic.downcast_ref::<&str>().map(|s| s.to_string()))
+				.unwrap_or_else(|| "panicked".to_string()),
+		};
+		let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+		LAST_ERROR.with(|last| *last.borrow_mut() = message);
+		-1
+	}
+
+	/// Like `status`, for a call making a handle; NULL on error.
+	fn handle<T>(make: impl FnOnce() -> Result<T>) -> *mut T {
+		let mut made = std::ptr::null_mut();
+		status(|| {
+			made = Box::into_raw(Box::new(make()?));
+			Ok(())
+		});
+		made
+	}
+
+	unsafe fn value<'a, T>(pointer: *mut T, name: &str) -> Result<&'a mut T> {
+		unsafe { pointer.as_mut() }.with_context(|| format!("{name} is NULL"))
+	}
+
+	unsafe fn vector_in(pointer: *const f64, len: usize, name: &str) -> Result<Vec<f64>> {
+		if len == 0 {
+			return Ok(Vec::new());
+		}
+		ensure!(!pointer.is_null(), "{name} is NULL");
+		Ok(unsafe { std::slice::from_raw_parts(pointer, len) }.to_vec())
+	}
+
+	unsafe fn vector_out(vector: &[f64], pointer: *mut f64, len: usize, name: &str) -> Result<()> {
+		ensure!(
+			vector.len() == len,
+			"{name} ended up with {} elements, but its buffer holds {len}",
+			vector.len()
+		);
+		if len > 0 {
+			ensure!(!pointer.is_null(), "{name} is NULL");
+			unsafe { std::slice::from_raw_parts_mut(pointer, len) }.copy_from_slice(vector);
+		}
+		Ok(())
+	}
+
+	unsafe fn string_in(pointer: *const c_char, name: &str) -> Result<String> {
+		ensure!(!pointer.is_null(), "{name} is NULL");
+		Ok(unsafe { CStr::from_ptr(pointer) }
+			.to_str()
+			.with_context(|| format!("{name} isn't UTF-8"))?
+			.to_string())
+	}
+
+	unsafe fn monomial(exponents: *const u8, count: usize) -> Result<taylor::Monomial> {
+		ensure!(
+			count <= taylor::MAX_VARS,
+			"A monomial has at most {} exponents",
+			taylor::MAX_VARS
+		);
+		let mut all = [0u8; taylor::MAX_VARS];
+		if count > 0 {
+			ensure!(!exponents.is_null(), "exponents is NULL");
+			all[..count].copy_from_slice(unsafe { std::slice::from_raw_parts(exponents, count) });
+		}
+		let monomial = taylor::Monomial::from_powers(all, &taylor::get_weights());
+		ensure!(
+			taylor::get_runtime()?.monomial_index.contains_key(&monomial),
+			"The monomial is beyond the Taylor system's order or number of variables"
+		);
+		Ok(monomial)
+	}
+
+	#[unsafe(no_mangle)]
+	pub extern "C" fn {prefix}_last_error() -> *const c_char {
+		LAST_ERROR.with(|last| last.borrow().as_ptr())
+	}
+
+	#[unsafe(no_mangle)]
+	pub extern "C" fn {prefix}_new() -> *mut State {
+		handle(State::new)
+	}
+
+	#[unsafe(no_mangle)]
+	pub unsafe extern "C" fn {prefix}_free(state: *mut State) {
+		if !state.is_null() {
+			drop(unsafe { Box::from_raw(state) });
+		}
+	}
+
+	#[unsafe(no_mangle)]
+	pub unsafe extern "C" fn {prefix}_string_free(string: *mut c_char) {
+		if !string.is_null() {
+			drop(unsafe { CString::from_raw(string) });
+		}
+	}
+
+	#[unsafe(no_mangle)]
+	pub extern "C" fn {prefix}_da_constant(value: f64) -> *mut DA {
+		handle(|| {
+			taylor::get_runtime()?;
+			Ok(DA::constant(value))
+		})
+	}
+
+	#[unsafe(no_mangle)]
+	pub extern "C" fn {prefix}_da_variable(index: usize) -> *mut DA {
+		handle(|| DA::variable(index))
+	}
+
+	#[unsafe(no_mangle)]
+	pub unsafe extern "C" fn {prefix}_da_clone(da: *const DA) -> *mut DA {
+		handle(|| Ok(unsafe { value(da.cast_mut(), "da") }?.clone()))
+	}
+
+	#[unsafe(no_mangle)]
+	pub unsafe extern "C" fn {prefix}_da_free(da: *mut DA) {
+		if !da.is_null() {
+			drop(unsafe { Box::from_raw(da) });
+		}
+	}
+
+	#[unsafe(no_mangle)]
+	pub unsafe extern "C" fn {prefix}_da_get(
+		da: *const DA,
+		exponents: *const u8,
+		count: usize,
+		out: *mut f64,
+	) -> c_int {
+		status(|| {
+			let monomial = unsafe { monomial(exponents, count) }?;
+			let coefficient = unsafe { value(da.cast_mut(), "da") }?.get_coeff(&monomial);
+			*unsafe { value(out, "out") }? = coefficient;
+			Ok(())
+		})
+	}
+
+	#[unsafe(no_mangle)]
+	pub unsafe extern "C" fn {prefix}_da_set(
+		da: *mut DA,
+		exponents: *const u8,
+		count: usize,
+		coefficient: f64,
+	) -> c_int {
+		status(|| {
+			let monomial = unsafe { monomial(exponents, count) }?;
+			unsafe { value(da, "da") }?.set_coeff(monomial, coefficient);
+			Ok(())
+		})
+	}
+"#;
+
+/// The header's declarations for [`RUNTIME`], after the typedefs.
+const HEADER_RUNTIME: &str = r#"/* The message of the last call on this thread that failed. */
+const char *{prefix}_last_error(void);
+
+/* Run the program's top-level statements. NULL on error. The Taylor
+ * system is set up here, for the whole process, so make a state before
+ * any DA. States and DA handles work on any thread, but a call that
+ * changes the Taylor system (DAINI, DANOT, ...) must not overlap DA
+ * calls on another thread. */
+{prefix}_state *{prefix}_new(void);
+void {prefix}_free({prefix}_state *state);
+
+/* Free a string a FUNCTION returned. */
+void {prefix}_string_free(char *string);
+
+/* DA handles, each freed with {prefix}_da_free. NULL on error. */
+{prefix}_da *{prefix}_da_constant(double value);
+{prefix}_da *{prefix}_da_variable(size_t index);
+{prefix}_da *{prefix}_da_clone(const {prefix}_da *da);
+void {prefix}_da_free({prefix}_da *da);
+
+/* The coefficient of the monomial with `count` exponents, one per
+ * variable; the rest are 0. */
+int {prefix}_da_get(const {prefix}_da *da, const uint8_t *exponents, size_t count, double *out);
+int {prefix}_da_set({prefix}_da *da, const uint8_t *exponents, size_t count, double coefficient);
+"#;
+
+impl Library {
+    /// The exports the C ABI leaves out, each with why.
+    pub fn c_abi_omissions(&self) -> Vec<String> {
+        self.exports
+            .iter()
+            .filter_map(|export| match c_types(export) {
+                Ok(_) => None,
+                Err(r#type) => Some(format!(
+                    "`{}` isn't in the C ABI: {} can't cross it",
+                    export.signature(),
+                    r#type
+                )),
+            })
+            .collect()
+    }
+
+    /// The `c_abi` module, to append to [`Library::render`]'s `lib.rs`.
+    /// `prefix` starts every symbol.
+    pub fn render_c_abi(&self, prefix: &str) -> String {
+        let mut exports = String::new();
+        for export in &self.exports {
+            let Ok((args, returns)) = c_types(export) else {
+                continue;
+            };
+
+            let mut parameters = vec!["state: *mut State".to_string()];
+            let mut prologue = String::new();
+            let mut arguments = Vec::new();
+            let mut epilogue = String::new();
+            for (arg, c_type) in export.args.iter().zip(&args) {
+                let name = &arg.name;
+                parameters.extend(c_type.rust_parameters(name));
+                match c_type {
+                    CType::Real | CType::Logical | CType::Da => {
+                        prologue.push_str(&format!(
+                            "\t\t\tlet {name} = unsafe {{ value({name}, \"{name}\") }}?;\n"
+                        ));
+                        arguments.push(name.clone());
+                    }
+                    CType::Vector => {
+                        prologue.push_str(&format!(
+                            "\t\t\tlet mut arg_{name} = unsafe {{ vector_in({name}, {name}_len, \"{name}\") }}?;\n"
+                        ));
+                        arguments.push(format!("&mut arg_{name}"));
+                        epilogue.push_str(&format!(
+                            "\t\t\tunsafe {{ vector_out(&arg_{name}, {name}, {name}_len, \"{name}\") }}?;\n"
+                        ));
+                    }
+                    CType::String => {
+                        prologue.push_str(&format!(
+                            "\t\t\tlet mut arg_{name} = unsafe {{ string_in({name}, \"{name}\") }}?;\n"
+                        ));
+                        arguments.push(format!("&mut arg_{name}"));
+                    }
+                }
+            }
+            let call = format!("state.{}({})?;", export.name, arguments.join(", "));
+            let (call, store) = match returns {
+                Some(c_type) => {
+                    parameters.extend(c_type.rust_out());
+                    (
+                        format!("let result = {call}"),
+                        format!("\t\t\t{}\n", c_type.store()),
+                    )
+                }
+                None => (call, String::new()),
+            };
+
+            exports.push_str(&format!(
+                "\n\t/// `{signature}`\n\t#[unsafe(no_mangle)]\n\tpub unsafe extern \"C\" fn {prefix}_{name}({parameters}) -> c_int {{\n\t\tstatus(|| {{\n\t\t\tlet state = unsafe {{ value(state, \"state\") }}?;\n{prologue}\t\t\t{call}\n{epilogue}{store}\t\t\tOk(())\n\t\t}})\n\t}}\n",
+                signature = export.signature(),
+                name = export.name,
+                parameters = parameters.join(", "),
+            ));
+        }
+
+        format!(
+            r#"
+/// The C ABI, declared in `{prefix}.h`.
+pub mod c_abi {{
+	use super::*;
+	use std::cell::RefCell;
+	use std::ffi::{{CStr, CString, c_char, c_int}};
+	use std::panic::{{AssertUnwindSafe, catch_unwind}};
+{runtime}{exports}}}
+"#,
+            runtime = RUNTIME.replace("{prefix}", prefix),
+        )
+    }
+
+    /// The C header declaring [`Library::render_c_abi`]'s symbols.
+    /// `source_name` is the program's file name, for the docs.
+    pub fn render_c_header(&self, prefix: &str, source_name: &str) -> String {
+        let mut declarations = String::new();
+        for export in &self.exports {
+            let Ok((args, returns)) = c_types(export) else {
+                continue;
+            };
+            let mut parameters = vec![format!("{prefix}_state *state")];
+            for (arg, c_type) in export.args.iter().zip(&args) {
+                parameters.extend(c_type.c_parameters(&arg.name, prefix));
+            }
+            if let Some(c_type) = returns {
+                parameters.extend(c_type.c_out(prefix));
+            }
+            declarations.push_str(&format!(
+                "\n/* {} */\nint {prefix}_{}({});\n",
+                export.signature(),
+                export.name,
+                parameters.join(", ")
+            ));
+        }
+
+        let guard = format!("{}_H", prefix.to_uppercase());
+        format!(
+            r#"/* Generated by rosy v{version} from `{source_name}`. Rebuild it with
+ * `rosy build --cdylib` rather than editing it.
+ *
+ * Each top-level PROCEDURE and FUNCTION is a function taking the state,
+ * then its arguments by pointer, written back when the call returns; a
+ * VE also takes its length, which must not change. A FUNCTION stores its
+ * result through the trailing `out`. Every such call returns 0, or -1
+ * with the reason in {prefix}_last_error(). */
+
+#ifndef {guard}
+#define {guard}
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {{
+#endif
+
+typedef struct {prefix}_state {prefix}_state;
+typedef struct {prefix}_da {prefix}_da;
+
+{runtime}{declarations}
+#ifdef __cplusplus
+}}
+#endif
+
+#endif /* {guard} */
+"#,
+            version = env!("CARGO_PKG_VERSION"),
+            runtime = HEADER_RUNTIME.replace("{prefix}", prefix),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rosy_lib::RosyType;
+    use crate::transpile::VariableData;
+
+    fn export(return_type: Option<RosyType>, args: &[(&str, RosyType)]) -> Export {
+        Export {
+            name: "F".to_string(),
+            rust_name: "__fn_F".to_string(),
+            return_type,
+            args: args
+                .iter()
+                .map(|(name, r#type)| VariableData {
+                    name: name.to_string(),
+                    r#type: *r#type,
+                })
+                .collect(),
+            globals: Vec::new(),
+        }
+    }
+
+    fn library(exports: Vec<Export>) -> Library {
+        Library {
+            globals: Vec::new(),
+            exports,
+            items: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn arguments_map_to_c_types() {
+        let library = library(vec![export(
+            Some(RosyType::RE()),
+            &[
+                ("V", RosyType::VE()),
+                ("S", RosyType::ST()),
+                ("D", RosyType::DA()),
+            ],
+        )]);
+
+        let header = library.render_c_header("lat", "lat.rosy");
+        assert!(header.contains(
+            "int lat_F(lat_state *state, double *V, size_t V_len, const char *S, lat_da *D, double *out);"
+        ));
+        let rust = library.render_c_abi("lat");
+        assert!(rust.contains("pub unsafe extern \"C\" fn lat_F(state: *mut State, V: *mut f64, V_len: usize, S: *const c_char, D: *mut DA, out: *mut f64) -> c_int"));
+        assert!(rust.contains("let result = state.F(&mut arg_V, &mut arg_S, D)?;"));
+        assert!(library.c_abi_omissions().is_empty());
+    }
+
+    #[test]
+    fn other_types_are_left_out() {
+        let library = library(vec![export(None, &[("Z", RosyType::CM())])]);
+
+        assert!(!library.render_c_header("lat", "lat.rosy").contains("lat_F"));
+        assert_eq!(library.c_abi_omissions().len(), 1);
+    }
+}
@@ -0,0 +1,366 @@
+//! # Library Crates
+//!
+//! Backs `rosy build --lib`: instead of a binary, a program becomes a Rust
+//! library crate whose top-level `PROCEDURE`s and `FUNCTION`s other Rust code
+//! can call.
+//!
+//! A top-level procedure or function already transpiles to a self-contained
+//! Rust function, which takes every program variable it uses as a `&mut`
+//! parameter ahead of its own arguments. Those functions move to the crate
+//! root unchanged. The rest of the top-level statements become
+//! `State::new`, and the variables they declare become the fields of
+//! `State`. Each procedure and function is then a method of `State` that
+//! passes it those fields:
+//!
+//! ```text
+//! VARIABLE (RE) SCALE;                pub struct State { pub SCALE: f64 }
+//! PROCEDURE KICK X (VE);
+//!     X := X * SCALE;                 impl State {
+//! ENDPROCEDURE;                           pub fn new() -> Result<Self>
+//! FUNCTION (RE) TWICE X (RE);             pub fn KICK(&mut self, X: &mut Vec<f64>) -> Result<()>
+//!     TWICE := 2 * X;                     pub fn TWICE(&mut self, X: &mut f64) -> Result<f64>
+//! ENDFUNCTION;                        }
+//! SCALE := 1.5;
+//! ```
+//!
+//! Arguments stay `&mut`, since Rosy passes them by reference.
+//!
+//! `rosy build --cdylib` adds C wrappers around these methods; see
+//! [`c_abi`].
+
+pub mod c_abi;
+
+use std::collections::HashSet;
+
+use anyhow::{Error, anyhow};
+
+use crate::{program::Program, rosy_lib::RosyType, transpile::*};
+
+/// A top-level `PROCEDURE` or `FUNCTION`, as the library exports it.
+#[derive(Debug, Clone)]
+pub struct Export {
+    /// The Rosy name, which the method keeps
+    pub name: String,
+    /// The Rust function the definition transpiled to
+    pub rust_name: String,
+    /// What a `FUNCTION` returns; `None` for a `PROCEDURE`
+    pub return_type: Option<RosyType>,
+    pub args: Vec<VariableData>,
+    /// The program variables it uses, passed ahead of `args`
+    pub globals: Vec<String>,
+}
+
+impl Export {
+    /// How the definition starts in Rosy, such as `FUNCTION (RE) TWICE X (RE);`
+    pub fn signature(&self) -> String {
+        let keyword = match &self.return_type {
+            Some(return_type) => format!("FUNCTION {return_type}"),
+            None => "PROCEDURE".to_string(),
+        };
+        let args: String = self
+            .args
+            .iter()
+            .map(|arg| format!(" {} {}", arg.name, arg.r#type))
+            .collect();
+        format!("{keyword} {}{args};", self.name)
+    }
+}
+
+/// A program split into the parts of a library crate.
+#[derive(Debug)]
+pub struct Library {
+    /// The top-level variables, in declaration order
+    pub globals: Vec<VariableData>,
+    /// The top-level procedures and functions, in definition order
+    pub exports: Vec<Export>,
+    /// The transpiled definitions, for the crate root
+    pub items: Vec<String>,
+    /// The other top-level statements, for `State::new`
+    pub body: Vec<String>,
+}
+
+impl Library {
+    /// Transpile `program` statement by statement, sorting definitions
+    /// from the statements `State::new` runs.
+    pub fn transpile(
+        program: &Program,
+        context: &mut TranspilationInputContext,
+    ) -> Result<Library, Vec<Error>> {
+        let mut library = Library {
+            globals: Vec::new(),
+            exports: Vec::new(),
+            items: Vec::new(),
+            body: Vec::new(),
+        };
+        let mut errors = Vec::new();
+
+        for statement in &program.statements {
+            let procedures: HashSet<String> = context.procedures.keys().cloned().collect();
+            let functions: HashSet<String> = context.functions.keys().cloned().collect();
+            let variables: HashSet<String> = context.variables.keys().cloned().collect();
+
+            let output = match statement.transpile(context) {
+                Ok(output) => output,
+                Err(stmt_errors) => {
+                    for e in stmt_errors {
+                        errors.push(e.context("...while transpiling a top-level statement"));
+                    }
+                    continue;
+                }
+            };
+
+            // A definition is whatever adds a procedure or function
+            let new_procedure = context
+                .procedures
+                .iter()
+                .find(|(name, _)| !procedures.contains(*name));
+            let new_function = context
+                .functions
+                .iter()
+                .find(|(name, _)| !functions.contains(*name));
+            let export = match (new_procedure, new_function) {
+                (Some((name, procedure)), _) => Some(Export {
+                    name: name.clone(),
+                    rust_name: name.clone(),
+                    return_type: None,
+                    args: procedure.args.clone(),
+                    globals: procedure.requested_variables.iter().cloned().collect(),
+                }),
+                (_, Some((name, function))) => Some(Export {
+                    name: name.clone(),
+                    rust_name: format!("__fn_{name}"),
+                    return_type: Some(function.return_type),
+                    args: function.args.clone(),
+                    globals: function.requested_variables.iter().cloned().collect(),
+                }),
+                (None, None) => None,
+            };
+
+            match export {
+                Some(export) => {
+                    // Drop the runtime location marker ahead of the
+                    // definition, which can't stand at the crate root
+                    let item = output
+                        .serialization
+                        .split_once('\n')
+                        .map_or(output.serialization.as_str(), |(_, item)| item);
+                    library.exports.push(export);
+                    library.items.push(item.to_string());
+                }
+                None => {
+                    let mut declared: Vec<&ScopedVariableData> = context
+                        .variables
+                        .iter()
+                        .filter(|(name, _)| !variables.contains(*name))
+                        .map(|(_, variable)| variable)
+                        .collect();
+                    declared.sort_by(|a, b| a.data.name.cmp(&b.data.name));
+                    library
+                        .globals
+                        .extend(declared.into_iter().map(|variable| variable.data.clone()));
+                    library.body.push(output.serialization);
+                }
+            }
+        }
+
+        // PLOOP and PNPRO need the parallel context only a binary sets up
+        let parallel = library.exports.iter().any(|export| {
+            export
+                .globals
+                .iter()
+                .any(|global| global == "rosy_mpi_context")
+        }) || library
+            .body
+            .iter()
+            .any(|body| body.contains("rosy_mpi_context"));
+        if parallel {
+            errors.push(anyhow!(
+                "PLOOP and PNPRO can't be used in a library; they need the MPI or thread context of a program's binary"
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(library)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// All the generated code, for [`embedded::monomial_width`](crate::embedded::monomial_width).
+    pub fn serialization(&self) -> String {
+        [self.items.join("\n"), self.body.join("\n")].join("\n")
+    }
+
+    /// The crate's `src/lib.rs`. `source_name` is the program's file name,
+    /// for the docs.
+    pub fn render(&self, source_name: &str) -> String {
+        let fields: String = self
+            .globals
+            .iter()
+            .map(|global| format!("\tpub {}: {},\n", global.name, global.r#type.as_rust_type()))
+            .collect();
+        let field_names: Vec<&str> = self
+            .globals
+            .iter()
+            .map(|global| global.name.as_str())
+            .collect();
+        let methods: String = self
+            .exports
+            .iter()
+            .map(|export| {
+                let parameters: String = export
+                    .args
+                    .iter()
+                    .map(|arg| format!(", {}: &mut {}", arg.name, arg.r#type.as_rust_type()))
+                    .collect();
+                let arguments: Vec<String> = export
+                    .globals
+                    .iter()
+                    .map(|global| format!("&mut self.{global}"))
+                    .chain(export.args.iter().map(|arg| arg.name.clone()))
+                    .collect();
+                let returns = match &export.return_type {
+                    Some(return_type) => return_type.as_rust_type(),
+                    None => "()".to_string(),
+                };
+                format!(
+                    "\n\t/// `{}`\n\tpub fn {}(&mut self{}) -> Result<{}> {{\n\t\t{}({})\n\t}}\n",
+                    export.signature(),
+                    export.name,
+                    parameters,
+                    returns,
+                    export.rust_name,
+                    arguments.join(", ")
+                )
+            })
+            .collect();
+        let body = indent(indent(self.body.join("\n")));
+
+        format!(
+            r#"//! Generated by rosy v{version} from `{source_name}`. Rebuild it with
+//! `rosy build --lib` rather than editing it.
+//!
+//! [`State`] holds the program's top-level variables, and each top-level
+//! PROCEDURE and FUNCTION is one of its methods. The Rosy runtime is
+//! re-exported as [`rosy_lib`].
+//!
+//! The Taylor system and the random numbers are process-wide, so every
+//! `State` shares them, and a `State` or `DA` made on one thread can be used
+//! on any other. A call that changes the Taylor system (DAINI, DANOT, DAEPS,
+//! ...), including `State::new()`, changes it for every thread, so it must
+//! not overlap DA arithmetic on another thread.
+
+#![allow(unused_variables)]
+#![allow(unused_assignments)]
+#![allow(unused_imports)]
+#![allow(unused_mut)]
+#![allow(unused_parens)]
+#![allow(dead_code)]
+#![allow(non_snake_case)]
+
+pub use rosy_lib;
+pub use rosy_lib::{{CD, DA, GR}};
+
+use rosy_lib::*;
+use anyhow::{{Result, Context, ensure, bail}};
+use num_complex::Complex64;
+
+/// The top-level variables of `{source_name}`.
+#[derive(Debug, Clone)]
+pub struct State {{
+{fields}}}
+
+impl State {{
+	/// Run the program's top-level statements, outside its PROCEDUREs and
+	/// FUNCTIONs, and keep the variables they leave.
+	pub fn new() -> Result<Self> {{
+		let start = std::time::Instant::now();
+		if !taylor::config::is_initialized() {{
+			taylor::init_taylor(3, 6)
+				.context("Failed to initialize Taylor system")?;
+		}}
+
+{body}
+
+		Ok(State {{ {field_names} }})
+	}}
+{methods}}}
+
+{items}
+"#,
+            version = env!("CARGO_PKG_VERSION"),
+            field_names = field_names.join(", "),
+            items = self.items.join("\n\n"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{CosyParser, Rule};
+    use crate::program::IncludeTracker;
+    use crate::resolve::TypeResolver;
+    use pest::Parser;
+
+    fn library(source: &str) -> Result<Library, Vec<Error>> {
+        let pair = CosyParser::parse(Rule::program, source)
+            .unwrap()
+            .next()
+            .unwrap();
+        let mut program =
+            Program::from_rule_with_includes(pair, None, &mut IncludeTracker::default())
+                .unwrap()
+                .unwrap();
+        TypeResolver::resolve(&mut program).unwrap();
+        Library::transpile(&program, &mut TranspilationInputContext::default())
+    }
+
+    #[test]
+    fn definitions_are_exported_and_variables_kept_in_state() {
+        let library = library(
+            "BEGIN;
+                VARIABLE (RE) SCALE;
+                PROCEDURE KICK X (VE);
+                    X := X * SCALE;
+                ENDPROCEDURE;
+                FUNCTION (RE) TWICE X (RE);
+                    TWICE := 2 * X;
+                ENDFUNCTION;
+                SCALE := 1.5;
+            END;",
+        )
+        .unwrap();
+
+        let globals: Vec<&str> = library.globals.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(globals, ["SCALE"]);
+        let signatures: Vec<String> = library.exports.iter().map(Export::signature).collect();
+        assert_eq!(
+            signatures,
+            ["PROCEDURE KICK X (VE);", "FUNCTION (RE) TWICE X (RE);"]
+        );
+        assert_eq!(library.exports[0].globals, ["SCALE"]);
+
+        let lib_rs = library.render("test.rosy");
+        assert!(lib_rs.contains("pub SCALE: f64,"));
+        assert!(lib_rs.contains(
+            "pub fn KICK(&mut self, X: &mut Vec<f64>) -> Result<()> {\n\t\tKICK(&mut self.SCALE, X)"
+        ));
+        assert!(lib_rs.contains("pub fn TWICE(&mut self, X: &mut f64) -> Result<f64>"));
+    }
+
+    #[test]
+    fn parallel_loops_are_rejected() {
+        let errors = library(
+            "BEGIN;
+                VARIABLE (RE) I;
+                VARIABLE (RE 4) X;
+                PLOOP I 1 4;
+                    X(I) := I;
+                ENDPLOOP X;
+            END;",
+        )
+        .unwrap_err();
+        assert!(errors[0].to_string().contains("PLOOP"));
+    }
+}
@@ -3,6 +3,7 @@
 //! Runs the real rosy pipeline (parse → AST → type resolution → transpilation)
 //! on a document and extracts diagnostics, resolved types, and symbol locations.
 
+use super::symbols::SymbolIndex;
 use crate::{
     ast::{CosyParser, Rule},
     errors::RosyError,
@@ -23,6 +24,8 @@ pub struct AnalysisResult {
     pub variable_types: Vec<InlayHintData>,
     /// Semantic tokens for syntax highlighting via the LSP.
     pub semantic_tokens: Vec<SemanticTokenData>,
+    /// User-declared names and where they are used, for navigation.
+    pub symbols: SymbolIndex,
 }
 
 /// Data for a single semantic token.
@@ -160,6 +163,10 @@ pub fn analyze(source: &str, source_path: Option<&std::path::Path>) -> AnalysisR
         }
     };
 
+    // Names and their uses come from the parse tree, so navigation keeps
+    // working when a later stage fails.
+    result.symbols = SymbolIndex::build(source, source_path);
+
     // Step 2: Build AST (resolves INCLUDEs at the AST level)
     let mut ast = match Program::from_rule_with_includes(
         program_pair,
@@ -233,6 +240,14 @@ pub fn analyze(source: &str, source_path: Option<&std::path::Path>) -> AnalysisR
         for (_slot, node) in &resolver.nodes {
             extract_inlay_hint(node, &mut result.variable_types);
         }
+        for symbol in &mut result.symbols.symbols {
+            symbol.r#type = symbol
+                .slot
+                .as_ref()
+                .and_then(|slot| resolver.nodes.get(slot))
+                .and_then(|node| node.resolved.as_ref())
+                .map(|t| t.to_string());
+        }
     }
 
     // Step 5: Transpilation — catches type mismatches, invalid operations,
@@ -464,7 +479,7 @@ fn tokenize_source(source: &str) -> Vec<SemanticTokenData> {
                 // Check if it's a type annotation
                 let token_type = if matches!(
                     upper.as_str(),
-                    "RE" | "ST" | "LO" | "CM" | "VE" | "DA" | "CD"
+                    "RE" | "ST" | "LO" | "CM" | "VE" | "DA" | "CD" | "GR"
                 ) {
                     // If followed by `(`, it's a function call; otherwise it's a type
                     let rest = &line[i..].trim_start();
@@ -513,7 +528,9 @@ fn pest_error_to_diagnostic(error: &pest::error::Error<Rule>) -> Diagnostic {
             Position::new(line.saturating_sub(1) as u32, col as u32),
         ),
         severity: Some(DiagnosticSeverity::ERROR),
-        message: format!("{error}"),
+        // The range already points at the error; pest's own rendering
+        // would repeat the location and the source line.
+        message: error.variant.message().into_owned(),
         source: Some("rosy".to_string()),
         ..Default::default()
     }
@@ -523,66 +540,16 @@ fn pest_error_to_diagnostic(error: &pest::error::Error<Rule>) -> Diagnostic {
 include!(concat!(env!("OUT_DIR"), "/keywords_generated.rs"));
 include!(concat!(env!("OUT_DIR"), "/hover_generated.rs"));
 
-/// Intrinsic functions — these get `FUNC($0)` snippet insertion.
-/// Everything else in the keyword list gets plain keyword completion.
-const INTRINSIC_FUNCTIONS: &[&str] = &[
+/// Intrinsic functions — these get `FUNC($0)` snippet insertion and are
+/// highlighted as functions. Everything else in the keyword list gets plain
+/// keyword completion.
+pub const INTRINSIC_FUNCTIONS: &[&str] = &[
     "ABS", "ACOS", "ASIN", "ATAN", "CD", "CM", "CMPLX", "CONJ", "CONS", "COS", "COSH", "DA", "ERF",
     "EXP", "IMAG", "INT", "ISRT", "ISRT3", "LCD", "LCM", "LDA", "LENGTH", "LLO", "LO", "LOG",
     "LRE", "LST", "LTRIM", "LVE", "NINT", "NORM", "RE", "REAL", "SIN", "SINH", "SQR", "SQRT", "ST",
     "TAN", "TANH", "TRIM", "TYPE", "VARMEM", "VARPOI", "VE", "VMAX", "VMIN", "WERF",
 ];
 
-/// Build completion items from the auto-generated keyword list.
-/// Keywords are extracted from `keyword_raw` in rosy.pest at compile time,
-/// so adding a new construct to the grammar automatically updates completions.
-pub fn rosy_keywords() -> Vec<CompletionItem> {
-    let base_url = "https://rosy-team.github.com/rosy/rosy";
-
-    let mut items: Vec<CompletionItem> = ROSY_KEYWORD_LIST
-        .iter()
-        .map(|(label, detail)| {
-            let is_function = INTRINSIC_FUNCTIONS.contains(label);
-
-            CompletionItem {
-                label: label.to_string(),
-                kind: Some(if is_function {
-                    CompletionItemKind::FUNCTION
-                } else {
-                    CompletionItemKind::KEYWORD
-                }),
-                detail: Some(detail.to_string()),
-                insert_text: if is_function {
-                    Some(format!("{label}($0)"))
-                } else {
-                    None
-                },
-                insert_text_format: if is_function {
-                    Some(InsertTextFormat::SNIPPET)
-                } else {
-                    None
-                },
-                documentation: Some(Documentation::MarkupContent(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: format!("{detail}\n\n[Documentation]({base_url}/)"),
-                })),
-                ..Default::default()
-            }
-        })
-        .collect();
-
-    // Boolean constants (not in keyword_raw since they're expression-level)
-    for (label, detail) in [("TRUE", "Boolean true"), ("FALSE", "Boolean false")] {
-        items.push(CompletionItem {
-            label: label.to_string(),
-            kind: Some(CompletionItemKind::CONSTANT),
-            detail: Some(detail.to_string()),
-            ..Default::default()
-        });
-    }
-
-    items
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -614,4 +581,13 @@ mod tests {
             labels
         );
     }
+
+    #[test]
+    fn parse_errors_point_at_the_failure() {
+        let result = analyze("BEGIN;\n  VARIABLE X\nEND;\n", None);
+        assert_eq!(result.diagnostics.len(), 1);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.range.start, Position::new(2, 0));
+        assert_eq!(diagnostic.message, "expected expr or semicolon");
+    }
 }
@@ -0,0 +1,608 @@
+//! Context-aware completion and signature help.
+//!
+//! A document rarely parses while it is being typed, so the context of the
+//! cursor comes from scanning the statement it is in, up to the cursor:
+//!
+//! - where a statement starts: statement keywords, procedures, and the
+//!   names that can be assigned to,
+//! - inside an expression: variables, user functions, intrinsic functions
+//!   and operators,
+//! - inside the parentheses of a declaration: type names,
+//! - on a name being declared, in a comment or in a string: nothing.
+//!
+//! Names in scope come from the [`SymbolIndex`] of the last version of the
+//! document that parsed.
+
+use tower_lsp::lsp_types::*;
+
+use super::analysis::{INTRINSIC_FUNCTIONS, ROSY_HOVER_DOCS, ROSY_KEYWORD_LIST, ROSY_TYPE_HOVER};
+use super::symbols::{SymbolIndex, SymbolKind};
+
+const DOCS_URL: &str = "https://rosy-team.github.com/rosy/rosy";
+
+/// Completion items for the cursor at `position` in `text`.
+pub fn completions(text: &str, position: Position, symbols: &SymbolIndex) -> Vec<CompletionItem> {
+    let prefix = Prefix::scan(text, position);
+    let context = prefix.context();
+    let visible = symbols.visible_at(position);
+
+    let mut items = Vec::new();
+    match context {
+        Context::Nothing => {}
+        Context::Type => {
+            for (name, markdown, description) in ROSY_TYPE_HOVER {
+                items.push(CompletionItem {
+                    label: name.to_string(),
+                    kind: Some(CompletionItemKind::TYPE_PARAMETER),
+                    detail: Some(description.to_string()),
+                    documentation: Some(markdown_docs(markdown.to_string())),
+                    ..Default::default()
+                });
+            }
+        }
+        Context::Statement => {
+            items.extend(visible.values.iter().map(|&id| value_item(symbols, id)));
+            items.extend(visible.procedures.iter().map(|&id| call_item(symbols, id)));
+            items.extend(keyword_items(false));
+        }
+        Context::Expression => {
+            items.extend(visible.values.iter().map(|&id| value_item(symbols, id)));
+            items.extend(visible.functions.iter().map(|&id| call_item(symbols, id)));
+            items.extend(keyword_items(true));
+            for (label, detail) in [("TRUE", "Boolean true"), ("FALSE", "Boolean false")] {
+                items.push(CompletionItem {
+                    label: label.to_string(),
+                    kind: Some(CompletionItemKind::CONSTANT),
+                    detail: Some(detail.to_string()),
+                    ..Default::default()
+                });
+            }
+        }
+    }
+    items
+}
+
+/// The signature of the user procedure or function whose arguments are
+/// being typed at `position`, if any.
+pub fn signature_help(
+    text: &str,
+    position: Position,
+    symbols: &SymbolIndex,
+) -> Option<SignatureHelp> {
+    let prefix = Prefix::scan(text, position);
+    if prefix.in_literal {
+        return None;
+    }
+    let visible = symbols.visible_at(position);
+    let named = |ids: &[usize], name: &str| {
+        ids.iter()
+            .copied()
+            .find(|&id| symbols.symbols[id].name == name)
+    };
+
+    let mut tokens = prefix.tokens;
+    if !prefix.word.is_empty() {
+        tokens.push(Token::Word(prefix.word));
+    }
+
+    // Innermost call first: `F(A, G(B` is in G's arguments if G is a
+    // function, and in F's otherwise.
+    let mut open = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Open => open.push(i),
+            Token::Close => {
+                open.pop();
+            }
+            _ => {}
+        }
+    }
+    for &paren in open.iter().rev() {
+        let Some(Token::Word(name)) = paren.checked_sub(1).map(|i| &tokens[i]) else {
+            continue;
+        };
+        if let Some(id) = named(&visible.functions, name) {
+            let mut depth = 0;
+            let mut active = 0;
+            for token in &tokens[paren + 1..] {
+                match token {
+                    Token::Open => depth += 1,
+                    Token::Close => depth -= 1,
+                    Token::Comma if depth == 0 => active += 1,
+                    _ => {}
+                }
+            }
+            return Some(signature(symbols, id, active));
+        }
+    }
+
+    // A procedure call is the procedure's name followed by its arguments.
+    let Some(Token::Word(name)) = tokens.first() else {
+        return None;
+    };
+    if tokens.len() == 1 && !prefix.trailing_space {
+        return None;
+    }
+    let id = named(&visible.procedures, name)?;
+    Some(signature(
+        symbols,
+        id,
+        argument_index(&tokens[1..], prefix.trailing_space),
+    ))
+}
+
+/// Where the cursor is, as far as completion is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Context {
+    /// Where a statement starts
+    Statement,
+    Expression,
+    /// The type of a declaration, as in `VARIABLE (RE) X;`
+    Type,
+    /// A name being declared, a comment or a string
+    Nothing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// A name or keyword
+    Word(String),
+    /// A number or string
+    Literal,
+    /// `(` or `[`
+    Open,
+    /// `)` or `]`
+    Close,
+    Comma,
+    /// Any other symbol, and `AND`/`OR`
+    Operator,
+}
+
+/// The statement the cursor is in, up to the cursor.
+#[derive(Debug, Default)]
+struct Prefix {
+    /// Tokens before the word being typed
+    tokens: Vec<Token>,
+    /// The part of a name or keyword before the cursor
+    word: String,
+    /// Whether the last token is separated from the cursor by whitespace
+    /// or a comment
+    trailing_space: bool,
+    /// Whether the cursor is inside a comment or string
+    in_literal: bool,
+}
+
+impl Prefix {
+    fn scan(text: &str, position: Position) -> Self {
+        let text = &text[..offset_at(text, position)];
+        let mut prefix = Prefix::default();
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            let in_number = prefix.word.starts_with(|d: char| d.is_ascii_digit());
+            if c.is_ascii_alphanumeric() || c == '_' || (c == '.' && in_number) {
+                prefix.word.push(c);
+                prefix.trailing_space = false;
+                continue;
+            }
+            prefix.end_word();
+            match c {
+                '{' => {
+                    // Comments nest.
+                    let mut depth = 1;
+                    for c in chars.by_ref() {
+                        match c {
+                            '{' => depth += 1,
+                            '}' => depth -= 1,
+                            _ => {}
+                        }
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    prefix.in_literal = depth > 0;
+                    prefix.trailing_space = true;
+                }
+                '\'' | '"' => {
+                    prefix.in_literal = !chars.by_ref().any(|q| q == c);
+                    prefix.tokens.push(Token::Literal);
+                    prefix.trailing_space = false;
+                }
+                ';' => prefix = Prefix::default(),
+                c if c.is_whitespace() => prefix.trailing_space = true,
+                _ => {
+                    prefix.tokens.push(match c {
+                        '(' | '[' => Token::Open,
+                        ')' | ']' => Token::Close,
+                        ',' => Token::Comma,
+                        _ => Token::Operator,
+                    });
+                    prefix.trailing_space = false;
+                }
+            }
+        }
+        prefix
+    }
+
+    /// Move a completed word into the tokens.
+    fn end_word(&mut self) {
+        if self.word.is_empty() {
+            return;
+        }
+        let word = std::mem::take(&mut self.word);
+        let token = if word.starts_with(|c: char| c.is_ascii_digit()) {
+            Token::Literal
+        } else if word.eq_ignore_ascii_case("AND") || word.eq_ignore_ascii_case("OR") {
+            Token::Operator
+        } else {
+            Token::Word(word)
+        };
+        self.tokens.push(token);
+    }
+
+    fn context(&self) -> Context {
+        if self.in_literal {
+            return Context::Nothing;
+        }
+        let tokens = &self.tokens;
+        let Some(Token::Word(first)) = tokens.first() else {
+            return if tokens.is_empty() {
+                Context::Statement
+            } else {
+                Context::Expression
+            };
+        };
+
+        let mut open = Vec::new();
+        let mut first_close = None;
+        for (i, token) in tokens.iter().enumerate() {
+            match token {
+                Token::Open => open.push(i),
+                Token::Close => {
+                    let opened = open.pop();
+                    if opened == Some(1) {
+                        first_close.get_or_insert(i);
+                    }
+                }
+                _ => {}
+            }
+        }
+        let innermost = open.last().copied();
+        let last = tokens.len() - 1;
+
+        match first.to_ascii_uppercase().as_str() {
+            // VARIABLE (type) name dimensions...
+            "VARIABLE" => match innermost {
+                Some(1) if last == 1 => Context::Type,
+                Some(_) => Context::Expression,
+                None => {
+                    let name = match (tokens.get(1), first_close) {
+                        (Some(Token::Open), Some(close)) => close + 1,
+                        _ => 1,
+                    };
+                    if last < name {
+                        Context::Nothing
+                    } else {
+                        Context::Expression
+                    }
+                }
+            },
+            // Only names and types in the header
+            "PROCEDURE" | "FUNCTION" => match innermost {
+                Some(paren) if paren == last => Context::Type,
+                Some(_) => Context::Expression,
+                None => Context::Nothing,
+            },
+            // The iterator is being declared
+            "LOOP" | "PLOOP" if last == 0 => Context::Nothing,
+            _ => Context::Expression,
+        }
+    }
+}
+
+/// The byte offset of an LSP position (UTF-16 column) in `text`.
+fn offset_at(text: &str, position: Position) -> usize {
+    let mut start = 0;
+    for _ in 0..position.line {
+        match text[start..].find('\n') {
+            Some(i) => start += i + 1,
+            None => return text.len(),
+        }
+    }
+    let mut column = 0;
+    for (i, c) in text[start..].char_indices() {
+        if c == '\n' || column >= position.character as usize {
+            return start + i;
+        }
+        column += c.len_utf16();
+    }
+    text.len()
+}
+
+/// Which of a procedure's space-separated arguments the cursor is in,
+/// given the tokens after the procedure's name.
+fn argument_index(tokens: &[Token], trailing_space: bool) -> u32 {
+    let mut count = 0u32;
+    let mut depth = 0;
+    let mut after_operand = false;
+    for token in tokens {
+        if depth > 0 {
+            match token {
+                Token::Open => depth += 1,
+                Token::Close => depth -= 1,
+                _ => {}
+            }
+            after_operand = true;
+            continue;
+        }
+        match token {
+            Token::Word(_) | Token::Literal => {
+                // Two operands in a row start a new argument.
+                if count == 0 || after_operand {
+                    count += 1;
+                }
+                after_operand = true;
+            }
+            Token::Open => {
+                // `X(1)` indexes X; `(A + B)` starts an argument.
+                if count == 0 {
+                    count += 1;
+                }
+                depth += 1;
+            }
+            Token::Operator => {
+                if count == 0 {
+                    count += 1;
+                }
+                after_operand = false;
+            }
+            Token::Close | Token::Comma => {}
+        }
+    }
+    if depth == 0 && after_operand && trailing_space {
+        count
+    } else {
+        count.saturating_sub(1)
+    }
+}
+
+fn signature(symbols: &SymbolIndex, id: usize, active: u32) -> SignatureHelp {
+    let symbol = &symbols.symbols[id];
+    let is_function = symbol.kind == SymbolKind::Function;
+    let mut label = symbol.name.clone();
+    let mut parameters = Vec::new();
+    for (i, &argument) in symbol.arguments.iter().enumerate() {
+        label.push_str(match (is_function, i) {
+            (true, 0) => "(",
+            (true, _) => ", ",
+            (false, _) => " ",
+        });
+        let start = label.len() as u32;
+        label.push_str(&typed_name(symbols, argument));
+        parameters.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, label.len() as u32]),
+            documentation: None,
+        });
+    }
+    if is_function {
+        label.push(')');
+    }
+
+    let active = (active < parameters.len() as u32).then_some(active);
+    SignatureHelp {
+        signatures: vec![SignatureInformation {
+            label,
+            documentation: Some(markdown_docs(symbols.describe(id))),
+            parameters: Some(parameters),
+            active_parameter: active,
+        }],
+        active_signature: Some(0),
+        active_parameter: active,
+    }
+}
+
+fn typed_name(symbols: &SymbolIndex, id: usize) -> String {
+    let symbol = &symbols.symbols[id];
+    match &symbol.r#type {
+        Some(t) => format!("{} {}", symbol.name, t),
+        None => symbol.name.clone(),
+    }
+}
+
+/// A variable, argument, loop iterator, or a function's return value.
+fn value_item(symbols: &SymbolIndex, id: usize) -> CompletionItem {
+    let symbol = &symbols.symbols[id];
+    CompletionItem {
+        label: symbol.name.clone(),
+        kind: Some(CompletionItemKind::VARIABLE),
+        detail: symbol.r#type.clone(),
+        documentation: Some(markdown_docs(symbols.describe(id))),
+        // User names before keywords
+        sort_text: Some(format!("0{}", symbol.name)),
+        ..Default::default()
+    }
+}
+
+/// A procedure or function, with a snippet for its arguments.
+fn call_item(symbols: &SymbolIndex, id: usize) -> CompletionItem {
+    let symbol = &symbols.symbols[id];
+    let placeholders: Vec<String> = symbol
+        .arguments
+        .iter()
+        .enumerate()
+        .map(|(i, &argument)| format!("${{{}:{}}}", i + 1, symbols.symbols[argument].name))
+        .collect();
+    let snippet = if symbol.kind == SymbolKind::Function {
+        format!("{}({})", symbol.name, placeholders.join(", "))
+    } else {
+        std::iter::once(symbol.name.clone())
+            .chain(placeholders)
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    CompletionItem {
+        label: symbol.name.clone(),
+        kind: Some(CompletionItemKind::FUNCTION),
+        detail: Some(signature(symbols, id, 0).signatures.remove(0).label),
+        documentation: Some(markdown_docs(symbols.describe(id))),
+        sort_text: Some(format!("0{}", symbol.name)),
+        insert_text: Some(snippet),
+        insert_text_format: Some(InsertTextFormat::SNIPPET),
+        ..Default::default()
+    }
+}
+
+/// Keywords from the grammar: those that start statements, or those used
+/// in expressions.
+fn keyword_items(expression: bool) -> impl Iterator<Item = CompletionItem> {
+    ROSY_KEYWORD_LIST
+        .iter()
+        .filter(move |(label, _)| {
+            !matches!(*label, "TRUE" | "FALSE") && is_expression_keyword(label) == expression
+        })
+        .map(|(label, detail)| {
+            let is_function = INTRINSIC_FUNCTIONS.contains(label);
+            CompletionItem {
+                label: label.to_string(),
+                kind: Some(if is_function {
+                    CompletionItemKind::FUNCTION
+                } else {
+                    CompletionItemKind::KEYWORD
+                }),
+                detail: Some(detail.to_string()),
+                insert_text: is_function.then(|| format!("{label}($0)")),
+                insert_text_format: is_function.then_some(InsertTextFormat::SNIPPET),
+                documentation: Some(markdown_docs(format!(
+                    "{detail}\n\n[Documentation]({DOCS_URL}/)"
+                ))),
+                ..Default::default()
+            }
+        })
+}
+
+/// Whether `keyword` belongs in an expression rather than at the start of
+/// a statement. Keywords without module docs (`ENDIF`, `ELSE`, ...) are
+/// statement keywords unless they are operators.
+fn is_expression_keyword(keyword: &str) -> bool {
+    INTRINSIC_FUNCTIONS.contains(&keyword)
+        || matches!(keyword, "AND" | "OR")
+        || ROSY_HOVER_DOCS
+            .iter()
+            .any(|(kw, _, _, _, is_stmt)| *kw == keyword && !is_stmt)
+}
+
+fn markdown_docs(value: String) -> Documentation {
+    Documentation::MarkupContent(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lsp::analysis::analyze;
+
+    const SOURCE: &str = "\
+BEGIN;
+    VARIABLE (RE) X;
+    PROCEDURE RUN A (RE) B (RE);
+        VARIABLE (ST) Y;
+        Y := ST(A);
+        X := B;
+    ENDPROCEDURE;
+    FUNCTION (RE) TWICE C (RE);
+        TWICE := 2 * C;
+    ENDFUNCTION;
+    X := TWICE(X);
+    RUN X X;
+END;
+";
+
+    fn labels(items: &[CompletionItem]) -> Vec<&str> {
+        items.iter().map(|item| item.label.as_str()).collect()
+    }
+
+    #[test]
+    fn items_depend_on_position_and_scope() {
+        let symbols = analyze(SOURCE, None).symbols;
+
+        let statement = completions(SOURCE, Position::new(10, 4), &symbols);
+        let found = labels(&statement);
+        for label in ["X", "RUN", "WRITE", "LOOP"] {
+            assert!(found.contains(&label), "{label} missing at statement start");
+        }
+        for label in ["Y", "TWICE", "SIN"] {
+            assert!(
+                !found.contains(&label),
+                "{label} offered at statement start"
+            );
+        }
+
+        let expression = completions(SOURCE, Position::new(10, 9), &symbols);
+        let found = labels(&expression);
+        for label in ["X", "TWICE", "SIN", "AND", "TRUE"] {
+            assert!(found.contains(&label), "{label} missing in expression");
+        }
+        for label in ["Y", "RUN", "WRITE"] {
+            assert!(!found.contains(&label), "{label} offered in expression");
+        }
+        let twice = expression.iter().find(|i| i.label == "TWICE").unwrap();
+        assert_eq!(twice.insert_text.as_deref(), Some("TWICE(${1:C})"));
+        let x = expression.iter().find(|i| i.label == "X").unwrap();
+        assert_eq!(x.detail.as_deref(), Some("(RE)"));
+
+        let in_procedure = completions(SOURCE, Position::new(4, 8), &symbols);
+        let found = labels(&in_procedure);
+        for label in ["X", "Y", "A", "B", "RUN"] {
+            assert!(found.contains(&label), "{label} missing in RUN");
+        }
+        let run = in_procedure.iter().find(|i| i.label == "RUN").unwrap();
+        assert_eq!(run.insert_text.as_deref(), Some("RUN ${1:A} ${2:B}"));
+    }
+
+    #[test]
+    fn declarations_comments_and_strings() {
+        let symbols = SymbolIndex::default();
+        let at_end = |text: &str| {
+            let line = text.lines().count() as u32 - 1;
+            let column = text.lines().last().unwrap().len() as u32;
+            completions(text, Position::new(line, column), &symbols)
+        };
+
+        assert!(labels(&at_end("BEGIN;\n    VARIABLE (")).contains(&"VE"));
+        assert!(labels(&at_end("BEGIN;\n    PROCEDURE P A (R")).contains(&"RE"));
+        assert!(at_end("BEGIN;\n    VARIABLE (RE) ").is_empty());
+        assert!(at_end("BEGIN;\n    FUNCTION (RE) F ").is_empty());
+        assert!(at_end("BEGIN;\n    LOOP ").is_empty());
+        assert!(labels(&at_end("BEGIN;\n    LOOP I ")).contains(&"SIN"));
+        assert!(at_end("BEGIN;\n    { a comment ").is_empty());
+        assert!(at_end("BEGIN;\n    WRITE 6 'a string ").is_empty());
+        assert!(labels(&at_end("BEGIN;\n    WRITE 6 'a string' ")).contains(&"SIN"));
+    }
+
+    #[test]
+    fn signature_help_tracks_the_argument() {
+        let symbols = analyze(SOURCE, None).symbols;
+        let help = |line: &str| {
+            let text = format!("BEGIN;\n{line}");
+            let position = Position::new(1, line.len() as u32);
+            signature_help(&text, position, &symbols).map(|h| {
+                let signature = &h.signatures[0];
+                (signature.label.clone(), h.active_parameter)
+            })
+        };
+
+        let run = "RUN A (RE) B (RE)".to_string();
+        assert_eq!(help("    RUN "), Some((run.clone(), Some(0))));
+        assert_eq!(help("    RUN X"), Some((run.clone(), Some(0))));
+        assert_eq!(help("    RUN X "), Some((run.clone(), Some(1))));
+        assert_eq!(help("    RUN X + "), Some((run.clone(), Some(0))));
+        assert_eq!(help("    RUN (X) 2.5"), Some((run.clone(), Some(1))));
+        assert_eq!(help("    RUN"), None);
+        assert_eq!(
+            help("    X := TWICE(X + "),
+            Some(("TWICE(C (RE))".to_string(), Some(0)))
+        );
+        assert_eq!(help("    X := SIN("), None);
+    }
+}
@@ -1,13 +1,16 @@
 //! # Language Server
 //!
-//! LSP server for Rosy, providing diagnostics, completion, hover,
-//! inlay type hints, and semantic token highlighting.
+//! LSP server for Rosy, providing diagnostics, completion, signature help,
+//! hover, formatting, navigation, inlay type hints, and semantic token
+//! highlighting.
 //!
 //! Launched via `rosy lsp` — communicates over stdin/stdout using
 //! the Language Server Protocol.
 
 pub mod analysis;
+pub mod completion;
 pub mod server;
+pub mod symbols;
 
 /// Run the LSP server on stdin/stdout. This function blocks until the client disconnects.
 pub async fn run() {
@@ -1,7 +1,8 @@
 //! LSP server implementation for Rosy.
 //!
-//! Provides diagnostics, completion, hover, and inlay hints by running
-//! the real rosy parser and type resolver on each document change.
+//! Provides diagnostics, completion, signature help, hover, navigation, inlay
+//! hints and formatting by running the real rosy parser and type resolver on
+//! each document change.
 
 use std::collections::HashMap;
 use std::sync::Mutex;
@@ -10,7 +11,8 @@ use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer};
 
-use super::analysis;
+use super::symbols::{self, SymbolIndex};
+use super::{analysis, completion};
 
 /// Per-document state cached between requests.
 struct DocumentState {
@@ -18,6 +20,16 @@ struct DocumentState {
     text: String,
     /// The latest analysis result.
     analysis: analysis::AnalysisResult,
+    /// The symbols of the last version that parsed, while the latest one
+    /// does not (as is usual in the middle of typing a statement).
+    last_parsed: Option<SymbolIndex>,
+}
+
+impl DocumentState {
+    /// The names to offer for completion.
+    fn completion_symbols(&self) -> &SymbolIndex {
+        self.last_parsed.as_ref().unwrap_or(&self.analysis.symbols)
+    }
 }
 
 pub struct RosyLanguageServer {
@@ -46,13 +58,29 @@ impl RosyLanguageServer {
 
         let diagnostics = result.diagnostics.clone();
 
-        self.documents.lock().unwrap().insert(
-            uri.clone(),
-            DocumentState {
-                text,
-                analysis: result,
-            },
-        );
+        {
+            let mut documents = self.documents.lock().unwrap();
+            // An index without scopes is one of a document that did not parse.
+            let last_parsed = if result.symbols.scopes.is_empty() {
+                documents.remove(&uri).and_then(|old| {
+                    if old.analysis.symbols.scopes.is_empty() {
+                        old.last_parsed
+                    } else {
+                        Some(old.analysis.symbols)
+                    }
+                })
+            } else {
+                None
+            };
+            documents.insert(
+                uri.clone(),
+                DocumentState {
+                    text,
+                    analysis: result,
+                    last_parsed,
+                },
+            );
+        }
 
         self.client
             .publish_diagnostics(uri, diagnostics, None)
@@ -69,15 +97,37 @@ impl LanguageServer for RosyLanguageServer {
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
                     TextDocumentSyncKind::FULL,
                 )),
-                // Completion for keywords and intrinsic functions
+                // Completion of keywords, intrinsics, types and user names
+                // in scope, depending on where the cursor is
                 completion_provider: Some(CompletionOptions {
-                    trigger_characters: Some(vec![" ".to_string()]),
+                    trigger_characters: Some(vec![" ".to_string(), "(".to_string()]),
                     ..Default::default()
                 }),
+                // Signatures of user procedures and functions being called
+                signature_help_provider: Some(SignatureHelpOptions {
+                    trigger_characters: Some(vec![
+                        " ".to_string(),
+                        "(".to_string(),
+                        ",".to_string(),
+                    ]),
+                    retrigger_characters: None,
+                    work_done_progress_options: Default::default(),
+                }),
                 // Hover for type info and documentation links
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
                 // Inlay hints for variable types
                 inlay_hint_provider: Some(OneOf::Left(true)),
+                // Whole-document formatting, same as `rosy fmt`
+                document_formatting_provider: Some(OneOf::Left(true)),
+                // Navigation on user variables, procedures and functions,
+                // across INCLUDEd and MODULE-loaded files
+                definition_provider: Some(OneOf::Left(true)),
+                references_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Right(RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                })),
+                document_symbol_provider: Some(OneOf::Left(true)),
                 // Semantic tokens for syntax highlighting via the real parser
                 semantic_tokens_provider: Some(
                     SemanticTokensServerCapabilities::SemanticTokensOptions(
@@ -147,8 +197,31 @@ impl LanguageServer for RosyLanguageServer {
 
     // ─── Completion ────────────────────────────────────────────────────
 
-    async fn completion(&self, _params: CompletionParams) -> Result<Option<CompletionResponse>> {
-        Ok(Some(CompletionResponse::Array(analysis::rosy_keywords())))
+    async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        let uri = &params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+
+        let docs = self.documents.lock().unwrap();
+        let Some(doc) = docs.get(uri) else {
+            return Ok(None);
+        };
+        let items = completion::completions(&doc.text, position, doc.completion_symbols());
+        Ok(Some(CompletionResponse::Array(items)))
+    }
+
+    async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
+        let uri = &params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        let docs = self.documents.lock().unwrap();
+        let Some(doc) = docs.get(uri) else {
+            return Ok(None);
+        };
+        Ok(completion::signature_help(
+            &doc.text,
+            position,
+            doc.completion_symbols(),
+        ))
     }
 
     // ─── Hover ─────────────────────────────────────────────────────────
@@ -162,6 +235,26 @@ impl LanguageServer for RosyLanguageServer {
             return Ok(None);
         };
 
+        // User names: resolved types and signatures
+        let symbols = &doc.analysis.symbols;
+        let user_hover = symbols
+            .occurrence_at(position)
+            .map(|occurrence| (symbols.describe(occurrence.symbol), occurrence.range))
+            .or_else(|| {
+                symbols
+                    .include_at(position)
+                    .map(|link| (format!("`{}`", link.target.display()), link.range))
+            });
+        if let Some((text, range)) = user_hover {
+            return Ok(Some(Hover {
+                contents: HoverContents::Markup(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value: text,
+                }),
+                range: Some(range),
+            }));
+        }
+
         let Some(word) = word_at_position(&doc.text, position) else {
             return Ok(None);
         };
@@ -287,6 +380,195 @@ impl LanguageServer for RosyLanguageServer {
             data: lsp_tokens,
         })))
     }
+
+    // ─── Navigation ────────────────────────────────────────────────────
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let uri = &params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+        let docs = self.documents.lock().unwrap();
+        let Some(doc) = docs.get(uri) else {
+            return Ok(None);
+        };
+        let symbols = &doc.analysis.symbols;
+
+        // On an INCLUDE or MODULE path, go to the file it loads.
+        if let Some(link) = symbols.include_at(position) {
+            return Ok(Url::from_file_path(&link.target).ok().map(|target| {
+                GotoDefinitionResponse::Scalar(Location::new(target, Range::default()))
+            }));
+        }
+
+        let Some(occurrence) = symbols.occurrence_at(position) else {
+            return Ok(None);
+        };
+        let symbol = &symbols.symbols[occurrence.symbol];
+        Ok(location(uri, symbol.file.as_deref(), symbol.range).map(GotoDefinitionResponse::Scalar))
+    }
+
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        let uri = &params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+        let docs = self.documents.lock().unwrap();
+        let Some(doc) = docs.get(uri) else {
+            return Ok(None);
+        };
+        let symbols = &doc.analysis.symbols;
+        let Some(occurrence) = symbols.occurrence_at(position) else {
+            return Ok(None);
+        };
+
+        let locations = symbols
+            .occurrences_of(occurrence.symbol)
+            .filter(|o| params.context.include_declaration || !o.is_declaration)
+            .filter_map(|o| location(uri, o.file.as_deref(), o.range))
+            .collect();
+        Ok(Some(locations))
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        let docs = self.documents.lock().unwrap();
+        let Some(doc) = docs.get(&params.text_document.uri) else {
+            return Ok(None);
+        };
+        Ok(doc
+            .analysis
+            .symbols
+            .occurrence_at(params.position)
+            .map(|occurrence| PrepareRenameResponse::Range(occurrence.range)))
+    }
+
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let uri = &params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+        let new_name = params.new_name;
+
+        let is_identifier = !new_name.is_empty() && new_name.bytes().all(is_ident_char);
+        let upper = new_name.to_uppercase();
+        let is_keyword = analysis::ROSY_KEYWORD_LIST
+            .iter()
+            .any(|(keyword, _)| *keyword == upper)
+            || matches!(upper.as_str(), "TRUE" | "FALSE");
+        if !is_identifier || is_keyword {
+            return Err(tower_lsp::jsonrpc::Error::invalid_params(format!(
+                "'{new_name}' is not a valid Rosy name"
+            )));
+        }
+
+        let docs = self.documents.lock().unwrap();
+        let Some(doc) = docs.get(uri) else {
+            return Ok(None);
+        };
+        let symbols = &doc.analysis.symbols;
+        let Some(occurrence) = symbols.occurrence_at(position) else {
+            return Ok(None);
+        };
+
+        let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+        for o in symbols.occurrences_of(occurrence.symbol) {
+            if let Some(location) = location(uri, o.file.as_deref(), o.range) {
+                changes.entry(location.uri).or_default().push(TextEdit {
+                    range: location.range,
+                    new_text: new_name.clone(),
+                });
+            }
+        }
+        Ok(Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }))
+    }
+
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        let docs = self.documents.lock().unwrap();
+        let Some(doc) = docs.get(&params.text_document.uri) else {
+            return Ok(None);
+        };
+        Ok(Some(DocumentSymbolResponse::Nested(document_symbols(
+            &doc.analysis.symbols,
+            None,
+        ))))
+    }
+
+    // ─── Formatting ────────────────────────────────────────────────────
+
+    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        let docs = self.documents.lock().unwrap();
+        let Some(doc) = docs.get(&params.text_document.uri) else {
+            return Ok(None);
+        };
+
+        // A document that doesn't parse is left alone; its diagnostics
+        // already say why.
+        let Ok(formatted) = crate::format::format_source(&doc.text) else {
+            return Ok(None);
+        };
+        if formatted == doc.text {
+            return Ok(Some(vec![]));
+        }
+
+        // Replace the whole document.
+        let last_line = doc.text.rsplit('\n').next().unwrap_or_default();
+        let end = Position::new(
+            doc.text.matches('\n').count() as u32,
+            last_line.encode_utf16().count() as u32,
+        );
+        Ok(Some(vec![TextEdit {
+            range: Range::new(Position::new(0, 0), end),
+            new_text: formatted,
+        }]))
+    }
+}
+
+/// The location of `range` in `file`, where `None` is the document `uri`.
+fn location(uri: &Url, file: Option<&std::path::Path>, range: Range) -> Option<Location> {
+    match file {
+        None => Some(Location::new(uri.clone(), range)),
+        Some(path) => Url::from_file_path(path)
+            .ok()
+            .map(|target| Location::new(target, range)),
+    }
+}
+
+/// The outline of the analyzed document: its procedures, functions and
+/// variables declared in `parent`, nested by scope.
+fn document_symbols(index: &SymbolIndex, parent: Option<usize>) -> Vec<DocumentSymbol> {
+    index
+        .symbols
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.file.is_none() && s.parent == parent)
+        .filter_map(|(id, s)| {
+            let kind = match s.kind {
+                symbols::SymbolKind::Variable => SymbolKind::VARIABLE,
+                symbols::SymbolKind::Procedure | symbols::SymbolKind::Function => {
+                    SymbolKind::FUNCTION
+                }
+                symbols::SymbolKind::Argument | symbols::SymbolKind::LoopIterator => return None,
+            };
+            let children = document_symbols(index, Some(id));
+            #[allow(deprecated)]
+            Some(DocumentSymbol {
+                name: s.name.clone(),
+                detail: s.r#type.clone(),
+                kind,
+                tags: None,
+                deprecated: None,
+                range: s.full_range,
+                selection_range: s.range,
+                children: (!children.is_empty()).then_some(children),
+            })
+        })
+        .collect()
 }
 
 /// Extract the word at a given position in the source text.
@@ -0,0 +1,791 @@
+//! Symbol index behind go-to-definition, find-references, rename, document
+//! symbols and hover on user names.
+//!
+//! Built from the parse tree rather than the AST, since the AST keeps no
+//! position for the names inside an expression. `INCLUDE`d and
+//! `MODULE`-loaded files are indexed too: their top-level statements join the
+//! including program's top-level scope, as they do when it is compiled.
+//!
+//! Scopes follow the resolver's: the body of a `PROCEDURE` or `FUNCTION` sees
+//! everything declared around it, a `FUNCTION`'s name inside its body is its
+//! return value, and a `LOOP` or `PLOOP` iterator is only visible in the
+//! loop body. Each symbol carries the [`TypeSlot`] the resolver gives it, so
+//! its resolved type can be looked up after resolution.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use pest::{Parser, iterators::Pair};
+use tower_lsp::lsp_types::{Position, Range};
+
+use crate::{
+    ast::{CosyParser, Rule},
+    program::Program,
+    resolve::TypeSlot,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Variable,
+    Argument,
+    LoopIterator,
+    Procedure,
+    Function,
+}
+
+/// A declared name.
+#[derive(Debug)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// File of the declaration; `None` for the analyzed document itself
+    pub file: Option<PathBuf>,
+    /// The declared name
+    pub range: Range,
+    /// The whole declaration: the `VARIABLE` statement, or the
+    /// `PROCEDURE ... ENDPROCEDURE` block
+    pub full_range: Range,
+    /// The `PROCEDURE` or `FUNCTION` this symbol is declared in
+    pub parent: Option<usize>,
+    /// The resolver's slot for this symbol (the return type of a function;
+    /// none for a procedure)
+    pub slot: Option<TypeSlot>,
+    /// Arguments of a procedure or function
+    pub arguments: Vec<usize>,
+    /// Resolved type such as `(RE)`, filled in after type resolution
+    pub r#type: Option<String>,
+}
+
+/// A place in the source where a symbol's name appears.
+#[derive(Debug)]
+pub struct Occurrence {
+    pub symbol: usize,
+    pub file: Option<PathBuf>,
+    pub range: Range,
+    pub is_declaration: bool,
+}
+
+/// The file an `INCLUDE` or `MODULE` statement loads.
+#[derive(Debug)]
+pub struct IncludeLink {
+    pub file: Option<PathBuf>,
+    /// The statement's path string
+    pub range: Range,
+    pub target: PathBuf,
+}
+
+/// The names declared directly in one scope of the analyzed document.
+#[derive(Debug)]
+pub struct Scope {
+    /// The `PROCEDURE`, `FUNCTION` or loop; `None` for the top level
+    pub range: Option<Range>,
+    pub values: Vec<usize>,
+    pub procedures: Vec<usize>,
+    pub functions: Vec<usize>,
+}
+
+/// The names in scope at a position, by namespace.
+#[derive(Debug, Default)]
+pub struct Visible {
+    /// Variables, arguments, loop iterators, and the return value of the
+    /// enclosing functions
+    pub values: Vec<usize>,
+    pub procedures: Vec<usize>,
+    pub functions: Vec<usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct SymbolIndex {
+    pub symbols: Vec<Symbol>,
+    pub occurrences: Vec<Occurrence>,
+    pub includes: Vec<IncludeLink>,
+    /// Innermost first; the top-level scope is last, and only missing when
+    /// the document did not parse
+    pub scopes: Vec<Scope>,
+}
+
+impl SymbolIndex {
+    /// Index `source`, the text of the document at `source_path`, and every
+    /// file it loads. Returns an empty index if the document does not parse.
+    pub fn build(source: &str, source_path: Option<&Path>) -> Self {
+        if CosyParser::parse(Rule::program, source).is_err() {
+            return SymbolIndex::default();
+        }
+        let mut files = vec![SourceFile::new(None, source.to_string())];
+        let mut builder = Builder {
+            index: SymbolIndex::default(),
+            frames: vec![Frame::default()],
+            files: HashMap::new(),
+            completed: HashSet::new(),
+            declarations: HashMap::new(),
+        };
+        // A library that INCLUDEs the program back is the program itself.
+        if let Some(path) = source_path.and_then(|p| std::fs::canonicalize(p).ok()) {
+            builder.files.insert(path, 0);
+        }
+        load_includes(&mut files, 0, source_path, &mut builder.files);
+
+        let mut top_level = Vec::new();
+        builder.top_level(&files, 0, source_path, &mut top_level);
+        builder.declare_all(&top_level);
+        for (file, pair) in top_level {
+            builder.statement(file, pair);
+        }
+        builder.close_scope(&files[0], None);
+        builder.index
+    }
+
+    /// The occurrence at `position` in the analyzed document, if any.
+    pub fn occurrence_at(&self, position: Position) -> Option<&Occurrence> {
+        self.occurrences
+            .iter()
+            .find(|o| o.file.is_none() && contains(&o.range, position))
+    }
+
+    /// The `INCLUDE`/`MODULE` path string at `position` in the analyzed
+    /// document, if any.
+    pub fn include_at(&self, position: Position) -> Option<&IncludeLink> {
+        self.includes
+            .iter()
+            .find(|l| l.file.is_none() && contains(&l.range, position))
+    }
+
+    /// The symbols that can be named at `position` in the analyzed
+    /// document, inner declarations hiding outer ones of the same name.
+    pub fn visible_at(&self, position: Position) -> Visible {
+        let scopes: Vec<&Scope> = self
+            .scopes
+            .iter()
+            .filter(|scope| scope.range.is_none_or(|range| contains(&range, position)))
+            .collect();
+        let namespace = |names: fn(&Scope) -> &Vec<usize>| {
+            let mut seen = HashSet::new();
+            let mut ids: Vec<usize> = scopes
+                .iter()
+                .flat_map(|scope| names(scope))
+                .copied()
+                .filter(|&id| seen.insert(self.symbols[id].name.as_str()))
+                .collect();
+            ids.sort_by_key(|&id| &self.symbols[id].name);
+            ids
+        };
+        Visible {
+            values: namespace(|s| &s.values),
+            procedures: namespace(|s| &s.procedures),
+            functions: namespace(|s| &s.functions),
+        }
+    }
+
+    /// Every occurrence of `symbol`, declaration first.
+    pub fn occurrences_of(&self, symbol: usize) -> impl Iterator<Item = &Occurrence> {
+        self.occurrences.iter().filter(move |o| o.symbol == symbol)
+    }
+
+    /// A Markdown description of `symbol` for hover.
+    pub fn describe(&self, symbol: usize) -> String {
+        let s = &self.symbols[symbol];
+        let typed = |sym: &Symbol| match &sym.r#type {
+            Some(t) => format!("{} {}", sym.name, t),
+            None => sym.name.clone(),
+        };
+        let arguments = s
+            .arguments
+            .iter()
+            .map(|&a| format!(" {}", typed(&self.symbols[a])))
+            .collect::<String>();
+        let signature = match s.kind {
+            SymbolKind::Variable | SymbolKind::LoopIterator => match &s.r#type {
+                Some(t) => format!("VARIABLE {} {}", t, s.name),
+                None => format!("VARIABLE {}", s.name),
+            },
+            SymbolKind::Argument => typed(s),
+            SymbolKind::Procedure => format!("PROCEDURE {}{}", s.name, arguments),
+            SymbolKind::Function => match &s.r#type {
+                Some(t) => format!("FUNCTION {} {}{}", t, s.name, arguments),
+                None => format!("FUNCTION {}{}", s.name, arguments),
+            },
+        };
+
+        let role = match (s.kind, s.parent) {
+            (SymbolKind::Argument, Some(parent)) => {
+                format!("Argument of `{}`", self.symbols[parent].name)
+            }
+            (SymbolKind::LoopIterator, _) => "Loop iterator".to_string(),
+            (_, Some(parent)) => format!("Declared in `{}`", self.symbols[parent].name),
+            (_, None) => "Declared at the top level".to_string(),
+        };
+        let place = match &s.file {
+            Some(path) => format!(
+                "{}, line {}",
+                path.file_name().unwrap_or_default().to_string_lossy(),
+                s.range.start.line + 1
+            ),
+            None => format!("line {}", s.range.start.line + 1),
+        };
+        format!("```rosy\n{signature}\n```\n{role} ({place})")
+    }
+}
+
+fn contains(range: &Range, position: Position) -> bool {
+    range.start <= position && position <= range.end
+}
+
+/// A source file and where its lines start, for converting byte offsets
+/// into LSP positions (UTF-16 columns).
+struct SourceFile {
+    path: Option<PathBuf>,
+    text: String,
+    line_starts: Vec<usize>,
+}
+
+impl SourceFile {
+    fn new(path: Option<PathBuf>, text: String) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        SourceFile {
+            path,
+            text,
+            line_starts,
+        }
+    }
+
+    fn position(&self, offset: usize) -> Position {
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let column = self.text[self.line_starts[line]..offset]
+            .encode_utf16()
+            .count();
+        Position::new(line as u32, column as u32)
+    }
+
+    fn range(&self, pair: &Pair<Rule>) -> Range {
+        let span = pair.as_span();
+        Range::new(self.position(span.start()), self.position(span.end()))
+    }
+}
+
+/// Read every file loaded by `files[index]`, recursively. `loaded` maps
+/// canonical paths to their index in `files`.
+fn load_includes(
+    files: &mut Vec<SourceFile>,
+    index: usize,
+    path: Option<&Path>,
+    loaded: &mut HashMap<PathBuf, usize>,
+) {
+    let Ok(mut pairs) = CosyParser::parse(Rule::program, &files[index].text) else {
+        return;
+    };
+    let Some(program) = pairs.next() else { return };
+    let mut targets = Vec::new();
+    for stmt in program.into_inner() {
+        if let Some(target) = include_target(&stmt, path) {
+            targets.push(target);
+        }
+    }
+    for target in targets {
+        if loaded.contains_key(&target) {
+            continue;
+        }
+        let Ok(text) = std::fs::read_to_string(&target) else {
+            continue;
+        };
+        files.push(SourceFile::new(Some(target.clone()), text));
+        let new_index = files.len() - 1;
+        loaded.insert(target.clone(), new_index);
+        load_includes(files, new_index, Some(&target), loaded);
+    }
+}
+
+/// The file an `INCLUDE` or `MODULE` statement loads, if it resolves.
+fn include_target(stmt: &Pair<Rule>, path: Option<&Path>) -> Option<PathBuf> {
+    match stmt.as_rule() {
+        Rule::include_stmt => Program::resolve_include(stmt, path).ok(),
+        Rule::module_stmt => Program::resolve_module(stmt, path).ok(),
+        _ => None,
+    }
+}
+
+/// Names declared in one lexical scope.
+#[derive(Default)]
+struct Frame {
+    /// Scope path in the resolver's convention: the enclosing PROCEDURE and
+    /// FUNCTION names
+    path: Vec<String>,
+    /// The PROCEDURE or FUNCTION whose body this is
+    owner: Option<usize>,
+    values: HashMap<String, usize>,
+    procedures: HashMap<String, usize>,
+    functions: HashMap<String, usize>,
+}
+
+struct Builder {
+    index: SymbolIndex,
+    frames: Vec<Frame>,
+    /// Loaded files by canonical path
+    files: HashMap<PathBuf, usize>,
+    /// Files whose statements were already spliced in
+    completed: HashSet<usize>,
+    /// Symbols by the file and byte offset of their declared name
+    declarations: HashMap<(Option<PathBuf>, usize), usize>,
+}
+
+/// A parse-tree node and the file it is in.
+type Located<'a> = (&'a SourceFile, Pair<'a, Rule>);
+
+impl Builder {
+    /// Collect the top-level statements of `files[index]`, with those of the
+    /// files it loads spliced in at the loading statement.
+    fn top_level<'a>(
+        &mut self,
+        files: &'a [SourceFile],
+        index: usize,
+        path: Option<&Path>,
+        out: &mut Vec<Located<'a>>,
+    ) {
+        if !self.completed.insert(index) {
+            return;
+        }
+        let file = &files[index];
+        let Ok(mut pairs) = CosyParser::parse(Rule::program, &file.text) else {
+            return;
+        };
+        let Some(program) = pairs.next() else { return };
+        for stmt in program.into_inner() {
+            match stmt.as_rule() {
+                Rule::begin | Rule::end | Rule::EOI => {}
+                Rule::include_stmt | Rule::module_stmt => {
+                    let Some(target) = include_target(&stmt, path) else {
+                        continue;
+                    };
+                    if let Some(string) = stmt
+                        .clone()
+                        .into_inner()
+                        .find(|p| p.as_rule() == Rule::string)
+                    {
+                        self.index.includes.push(IncludeLink {
+                            file: file.path.clone(),
+                            range: file.range(&string),
+                            target: target.clone(),
+                        });
+                    }
+                    if let Some(&included) = self.files.get(&target) {
+                        self.top_level(files, included, Some(&target), out);
+                    }
+                }
+                _ => out.push((file, stmt)),
+            }
+        }
+    }
+
+    /// Pop the current frame, recording its names as a scope if it is in
+    /// the analyzed document.
+    fn close_scope(&mut self, file: &SourceFile, range: Option<Range>) {
+        let Some(frame) = self.frames.pop() else {
+            return;
+        };
+        if file.path.is_none() {
+            let ids = |names: HashMap<String, usize>| names.into_values().collect();
+            self.index.scopes.push(Scope {
+                range,
+                values: ids(frame.values),
+                procedures: ids(frame.procedures),
+                functions: ids(frame.functions),
+            });
+        }
+    }
+
+    fn frame(&mut self) -> &mut Frame {
+        self.frames
+            .last_mut()
+            .expect("there is always a top-level frame")
+    }
+
+    fn add_symbol(
+        &mut self,
+        file: &SourceFile,
+        name_pair: &Pair<Rule>,
+        full: &Pair<Rule>,
+        kind: SymbolKind,
+        slot: Option<TypeSlot>,
+    ) -> usize {
+        let id = self.index.symbols.len();
+        self.index.symbols.push(Symbol {
+            name: name_pair.as_str().to_string(),
+            kind,
+            file: file.path.clone(),
+            range: file.range(name_pair),
+            full_range: file.range(full),
+            parent: self.frames.last().and_then(|f| f.owner),
+            slot,
+            arguments: Vec::new(),
+            r#type: None,
+        });
+        self.index.occurrences.push(Occurrence {
+            symbol: id,
+            file: file.path.clone(),
+            range: file.range(name_pair),
+            is_declaration: true,
+        });
+        self.declarations
+            .insert((file.path.clone(), name_pair.as_span().start()), id);
+        id
+    }
+
+    /// The symbol declared by the name `name_pair`, if it was declared already.
+    fn declared(&self, file: &SourceFile, name_pair: &Pair<Rule>) -> Option<usize> {
+        self.declarations
+            .get(&(file.path.clone(), name_pair.as_span().start()))
+            .copied()
+    }
+
+    fn add_reference(&mut self, file: &SourceFile, name_pair: &Pair<Rule>, symbol: Option<usize>) {
+        if let Some(symbol) = symbol {
+            self.index.occurrences.push(Occurrence {
+                symbol,
+                file: file.path.clone(),
+                range: file.range(name_pair),
+                is_declaration: false,
+            });
+        }
+    }
+
+    fn lookup(
+        &self,
+        name: &str,
+        namespace: fn(&Frame) -> &HashMap<String, usize>,
+    ) -> Option<usize> {
+        self.frames
+            .iter()
+            .rev()
+            .find_map(|frame| namespace(frame).get(name).copied())
+    }
+
+    /// Declare the variables, procedures and functions of a statement list
+    /// up front, so that they can be used before their declaration, as the
+    /// resolver allows.
+    fn declare_all(&mut self, statements: &[Located]) {
+        for (file, stmt) in statements {
+            let file: &SourceFile = file;
+            match stmt.as_rule() {
+                Rule::var_decl => {
+                    let Some(name) = stmt
+                        .clone()
+                        .into_inner()
+                        .find(|p| p.as_rule() == Rule::variable_name)
+                    else {
+                        continue;
+                    };
+                    let slot =
+                        TypeSlot::Variable(self.frame().path.clone(), name.as_str().to_string());
+                    let id = self.add_symbol(file, &name, stmt, SymbolKind::Variable, Some(slot));
+                    self.frame().values.insert(name.as_str().to_string(), id);
+                }
+                Rule::procedure | Rule::function => {
+                    let is_function = stmt.as_rule() == Rule::function;
+                    let Some(header) = stmt.clone().into_inner().next() else {
+                        continue;
+                    };
+                    let name_rule = if is_function {
+                        Rule::function_name
+                    } else {
+                        Rule::procedure_name
+                    };
+                    let Some(name) = header.into_inner().find(|p| p.as_rule() == name_rule) else {
+                        continue;
+                    };
+                    let path = self.frame().path.clone();
+                    let (kind, slot) = if is_function {
+                        (
+                            SymbolKind::Function,
+                            Some(TypeSlot::FunctionReturn(path, name.as_str().to_string())),
+                        )
+                    } else {
+                        (SymbolKind::Procedure, None)
+                    };
+                    let id = self.add_symbol(file, &name, stmt, kind, slot);
+                    let frame = self.frame();
+                    let namespace = if is_function {
+                        &mut frame.functions
+                    } else {
+                        &mut frame.procedures
+                    };
+                    namespace.insert(name.as_str().to_string(), id);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn statement(&mut self, file: &SourceFile, stmt: Pair<Rule>) {
+        match stmt.as_rule() {
+            Rule::var_decl => {
+                // Declared by `declare_all`, or here when nested in a
+                // block that is not a scope of its own (IF, WHILE, FIT).
+                let declared = stmt
+                    .clone()
+                    .into_inner()
+                    .find(|p| p.as_rule() == Rule::variable_name)
+                    .is_some_and(|name| self.declared(file, &name).is_some());
+                if !declared {
+                    self.declare_all(&[(file, stmt.clone())]);
+                }
+                for child in stmt.into_inner() {
+                    if child.as_rule() != Rule::variable_name {
+                        self.references(file, child);
+                    }
+                }
+            }
+            Rule::procedure | Rule::function => self.callable(file, stmt),
+            Rule::r#loop | Rule::ploop => {
+                let range = file.range(&stmt);
+                let mut children = stmt.into_inner();
+                let Some(header) = children.next() else {
+                    return;
+                };
+                let mut header = header.into_inner();
+                let Some(iterator) = header.next() else {
+                    return;
+                };
+                for bound in header {
+                    self.references(file, bound);
+                }
+
+                let path = self.frame().path.clone();
+                let owner = self.frame().owner;
+                self.frames.push(Frame {
+                    path: path.clone(),
+                    owner,
+                    ..Frame::default()
+                });
+                let slot = TypeSlot::Variable(path, iterator.as_str().to_string());
+                let id = self.add_symbol(
+                    file,
+                    &iterator,
+                    &iterator,
+                    SymbolKind::LoopIterator,
+                    Some(slot),
+                );
+                self.frame()
+                    .values
+                    .insert(iterator.as_str().to_string(), id);
+
+                let children: Vec<_> = children.collect();
+                let (footer, body) = children
+                    .split_last()
+                    .map_or((None, &children[..]), |(f, b)| (Some(f.clone()), b));
+                self.block(file, body.to_vec());
+                self.close_scope(file, Some(range));
+                if let Some(footer) = footer {
+                    // ENDPLOOP's result variable lives outside the loop.
+                    self.references(file, footer);
+                }
+            }
+            Rule::if_statement | Rule::while_loop | Rule::fit_statement => {
+                for child in stmt.into_inner() {
+                    self.statement(file, child);
+                }
+            }
+            Rule::if_clause | Rule::elseif_clause | Rule::else_clause => {
+                for child in stmt.into_inner() {
+                    self.statement(file, child);
+                }
+            }
+            _ => self.references(file, stmt),
+        }
+    }
+
+    /// A PROCEDURE or FUNCTION, already declared in the enclosing scope.
+    fn callable(&mut self, file: &SourceFile, stmt: Pair<Rule>) {
+        let is_function = stmt.as_rule() == Rule::function;
+        let range = file.range(&stmt);
+        let mut children = stmt.into_inner();
+        let Some(header) = children.next() else {
+            return;
+        };
+        let name_rule = if is_function {
+            Rule::function_name
+        } else {
+            Rule::procedure_name
+        };
+        let Some(name) = header
+            .clone()
+            .into_inner()
+            .find(|p| p.as_rule() == name_rule)
+        else {
+            return;
+        };
+        let Some(id) = self.declared(file, &name) else {
+            return;
+        };
+
+        let outer = self.frame().path.clone();
+        let mut path = outer.clone();
+        path.push(name.as_str().to_string());
+        self.frames.push(Frame {
+            path,
+            owner: Some(id),
+            ..Frame::default()
+        });
+        if is_function {
+            // The function's name is its return value inside the body.
+            self.frame().values.insert(name.as_str().to_string(), id);
+        }
+
+        for part in header.into_inner() {
+            let argument = match part.as_rule() {
+                Rule::function_argument_name => Some(part.clone()),
+                Rule::procedure_argument_name_and_type => part
+                    .clone()
+                    .into_inner()
+                    .find(|p| p.as_rule() == Rule::procedure_argument_name),
+                Rule::r#type => {
+                    self.references(file, part.clone());
+                    None
+                }
+                _ => None,
+            };
+            let Some(argument) = argument else { continue };
+            let slot = TypeSlot::Argument(
+                outer.clone(),
+                name.as_str().to_string(),
+                argument.as_str().to_string(),
+            );
+            let arg = self.add_symbol(file, &argument, &part, SymbolKind::Argument, Some(slot));
+            self.frame()
+                .values
+                .insert(argument.as_str().to_string(), arg);
+            self.index.symbols[id].arguments.push(arg);
+            if part.as_rule() == Rule::procedure_argument_name_and_type {
+                for child in part.into_inner().skip(1) {
+                    self.references(file, child);
+                }
+            }
+        }
+
+        let body: Vec<_> = children
+            .filter(|p| !matches!(p.as_rule(), Rule::end_procedure | Rule::end_function))
+            .collect();
+        self.block(file, body);
+        self.close_scope(file, Some(range));
+    }
+
+    /// A statement list in the current frame.
+    fn block<'a>(&mut self, file: &'a SourceFile, body: Vec<Pair<'a, Rule>>) {
+        let located: Vec<Located> = body.into_iter().map(|p| (file, p)).collect();
+        self.declare_all(&located);
+        for (file, stmt) in located {
+            self.statement(file, stmt);
+        }
+    }
+
+    /// Record the names used anywhere inside `pair`.
+    fn references(&mut self, file: &SourceFile, pair: Pair<Rule>) {
+        // Calls parse as a variable name followed by a paren group, so a
+        // name with arguments prefers a function and a bare name a value.
+        let mut called = HashSet::new();
+        for inner in std::iter::once(pair.clone()).chain(pair.into_inner().flatten()) {
+            let symbol = match inner.as_rule() {
+                Rule::variable_identifier => {
+                    if inner
+                        .clone()
+                        .into_inner()
+                        .any(|part| part.as_rule() == Rule::paren_group)
+                    {
+                        called.insert(inner.as_span().start());
+                    }
+                    continue;
+                }
+                Rule::variable_name if called.contains(&inner.as_span().start()) => self
+                    .lookup(inner.as_str(), |f| &f.functions)
+                    .or_else(|| self.lookup(inner.as_str(), |f| &f.values)),
+                Rule::variable_name => self
+                    .lookup(inner.as_str(), |f| &f.values)
+                    .or_else(|| self.lookup(inner.as_str(), |f| &f.functions)),
+                Rule::procedure_name => self.lookup(inner.as_str(), |f| &f.procedures),
+                Rule::function_name => self.lookup(inner.as_str(), |f| &f.functions),
+                _ => continue,
+            };
+            self.add_reference(file, &inner, symbol);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lsp::analysis::analyze;
+
+    /// The declaration the name at `line`:`col` refers to, as (line, col).
+    fn definition(index: &SymbolIndex, line: u32, col: u32) -> (u32, u32) {
+        let occurrence = index
+            .occurrence_at(Position::new(line, col))
+            .unwrap_or_else(|| panic!("no name at {line}:{col}"));
+        let range = index.symbols[occurrence.symbol].range;
+        (range.start.line, range.start.character)
+    }
+
+    #[test]
+    fn names_resolve_to_the_innermost_declaration() {
+        let source = "\
+BEGIN;
+    VARIABLE (RE) X;
+    PROCEDURE RUN X;
+        VARIABLE (RE) Y;
+        Y := X + TWICE(Y);
+    ENDPROCEDURE;
+    FUNCTION TWICE A;
+        TWICE := 2 * A;
+    ENDFUNCTION;
+    LOOP X 1 3;
+        RUN X;
+    ENDLOOP;
+    X := TWICE(X);
+END;
+";
+        let index = SymbolIndex::build(source, None);
+        // X in RUN's body is its argument; TWICE is called before it is declared.
+        assert_eq!(definition(&index, 4, 13), (2, 18));
+        assert_eq!(definition(&index, 4, 17), (6, 13));
+        // A function's name inside its body is the function.
+        assert_eq!(definition(&index, 7, 8), (6, 13));
+        // The loop iterator shadows X inside the loop only.
+        assert_eq!(definition(&index, 10, 12), (9, 9));
+        assert_eq!(definition(&index, 12, 4), (1, 18));
+        assert_eq!(definition(&index, 12, 15), (1, 18));
+
+        let x = index.occurrence_at(Position::new(1, 18)).unwrap().symbol;
+        assert_eq!(index.occurrences_of(x).count(), 3);
+    }
+
+    #[test]
+    fn included_files_share_the_top_level_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("lib.rosy"),
+            "BEGIN;\n    VARIABLE (VE) SHARED;\n    PROCEDURE FILL N;\n        SHARED := N & N;\n    ENDPROCEDURE;\nEND;\n",
+        )
+        .unwrap();
+        let main = dir.path().join("main.rosy");
+        let source = "BEGIN;\n    INCLUDE 'lib.rosy';\n    FILL 2;\n    WRITE 6 SHARED;\nEND;\n";
+        std::fs::write(&main, source).unwrap();
+
+        let result = analyze(source, Some(&main));
+        let index = &result.symbols;
+        let lib = std::fs::canonicalize(dir.path().join("lib.rosy")).unwrap();
+
+        let fill = index.occurrence_at(Position::new(2, 5)).unwrap().symbol;
+        assert_eq!(index.symbols[fill].file.as_deref(), Some(lib.as_path()));
+        assert_eq!(index.symbols[fill].range.start, Position::new(2, 14));
+
+        let shared = index.occurrence_at(Position::new(3, 12)).unwrap().symbol;
+        let files: Vec<_> = index
+            .occurrences_of(shared)
+            .map(|o| o.file.is_some())
+            .collect();
+        assert_eq!(files, vec![true, true, false]);
+        assert_eq!(index.include_at(Position::new(1, 14)).unwrap().target, lib);
+
+        assert!(index.describe(shared).contains("VARIABLE (VE) SHARED"));
+        assert!(index.describe(fill).contains("PROCEDURE FILL N (RE)"));
+    }
+}
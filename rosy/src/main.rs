@@ -1,10 +1,27 @@
+mod repl;
 mod update_check;
 
 use anyhow::{Context, Result, anyhow, ensure};
-use clap::{Parser as ClapParser, Subcommand};
+use clap::{Parser as ClapParser, Subcommand, ValueEnum};
 use pest::Parser;
-use rosy::{ast, embedded, program::Program, resolve, syntax_config, transpile::*};
-use std::{fs, fs::write, path::PathBuf, process::Command, time::Instant};
+use rosy::{
+    ast, embedded,
+    interpret::{self, Flow, Interpret, Interpreter},
+    library::Library,
+    parallel_config::{self, ParallelBackend},
+    program::Program,
+    resolve,
+    rosy_lib::core::{checkpoint::Checkpoint, trace},
+    syntax_config,
+    transpile::*,
+};
+use std::{
+    fs,
+    fs::write,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Instant,
+};
 use tracing::info;
 use tracing_subscriber;
 
@@ -45,7 +62,7 @@ enum Commands {
         /// Path to the Rosy source file
         source: PathBuf,
 
-        /// Output directory for build artifacts (default: .rosy_output)
+        /// Build cache directory, shared by every script built with it (default: .rosy_output)
         #[arg(short = 'd', long)]
         output_dir: Option<PathBuf>,
 
@@ -60,6 +77,18 @@ enum Commands {
         /// Enforce COSY INFINITY syntax: memory sizes are required in VARIABLE declarations
         #[arg(long)]
         cosy_syntax: bool,
+
+        /// Run PLOOP on a thread pool instead of MPI (no MPI install or mpirun needed; thread count from ROSY_THREADS, default all cores)
+        #[arg(long)]
+        threads: bool,
+
+        /// Resume from a file written by CHECKPOINT instead of starting over
+        #[arg(long, value_name = "CHECKPOINT")]
+        resume: Option<PathBuf>,
+
+        /// Interpret the program in-process instead of generating and compiling Rust (no cargo build; slower to run)
+        #[arg(short, long)]
+        interpret: bool,
     },
 
     /// Run language feature tests (transpile, compile, execute each construct)
@@ -72,9 +101,17 @@ enum Commands {
         #[arg(short, long)]
         release: bool,
 
-        /// Number of parallel test workers (each gets its own build directory)
+        /// Number of parallel test workers
         #[arg(short, long, default_value = "1")]
         parallel: usize,
+
+        /// Build constructs that use PLOOP/PNPRO with the thread backend instead of MPI
+        #[arg(long)]
+        threads: bool,
+
+        /// Run each construct with the interpreter instead of compiling it
+        #[arg(short, long)]
+        interpret: bool,
     },
 
     /// Build a Rosy script and place the binary in PWD
@@ -82,11 +119,11 @@ enum Commands {
         /// Path to the Rosy source file
         source: PathBuf,
 
-        /// Output binary name (default: source filename without extension)
+        /// Output binary name, or crate directory with --lib (default: source filename without extension)
         #[arg(short, long)]
         output: Option<String>,
 
-        /// Output directory for build artifacts (default: .rosy_output)
+        /// Build cache directory, shared by every script built with it (default: .rosy_output)
         #[arg(short = 'd', long)]
         output_dir: Option<PathBuf>,
 
@@ -101,6 +138,51 @@ enum Commands {
         /// Enforce COSY INFINITY syntax: memory sizes are required in VARIABLE declarations
         #[arg(long)]
         cosy_syntax: bool,
+
+        /// Run PLOOP on a thread pool instead of MPI (no MPI install or mpirun needed; thread count from ROSY_THREADS, default all cores)
+        #[arg(long)]
+        threads: bool,
+
+        /// Generate a library crate instead of a binary: top-level PROCEDUREs and FUNCTIONs become Rust functions, for use as a path dependency
+        #[arg(long, conflicts_with = "threads")]
+        lib: bool,
+
+        /// Like --lib, but also a shared library with C wrappers and a C header, for C, C++ and Python (ctypes)
+        #[arg(long, conflicts_with_all = ["threads", "lib"])]
+        cdylib: bool,
+    },
+
+    /// Check Rosy scripts for errors without compiling them (parse, type resolution, transpilation)
+    Check {
+        /// Paths to the Rosy source files
+        #[arg(required = true)]
+        sources: Vec<PathBuf>,
+
+        /// Output format for diagnostics
+        #[arg(long, value_enum, default_value_t = CheckFormat::Text)]
+        format: CheckFormat,
+
+        /// Enforce COSY INFINITY syntax: memory sizes are required in VARIABLE declarations
+        #[arg(long)]
+        cosy_syntax: bool,
+    },
+
+    /// Start an interactive session that interprets statements as they are entered
+    Repl {
+        /// Enforce COSY INFINITY syntax: memory sizes are required in VARIABLE declarations
+        #[arg(long)]
+        cosy_syntax: bool,
+    },
+
+    /// Format Rosy scripts in place: keyword case, indentation, one statement per line
+    Fmt {
+        /// Paths to the Rosy source files
+        #[arg(required = true)]
+        sources: Vec<PathBuf>,
+
+        /// Don't write anything; list the files that would change and fail if there are any
+        #[arg(long)]
+        check: bool,
     },
 
     /// Start the Language Server Protocol (LSP) server on stdin/stdout
@@ -118,6 +200,14 @@ enum Commands {
     },
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum CheckFormat {
+    /// One `file:line:col: severity: message` line per diagnostic
+    Text,
+    /// A JSON array of diagnostics on stdout
+    Json,
+}
+
 #[derive(Subcommand)]
 enum EditorTarget {
     /// Install the VS Code extension (syntax highlighting + LSP)
@@ -126,29 +216,17 @@ enum EditorTarget {
     Zed,
 }
 
-fn rosy(
+/// Read, parse, resolve and transpile a Rosy script, reporting steps 1 to 5
+/// of `total`. The transpilation is what type-checks the program, so even a
+/// program that is only interpreted goes through it. Returns the AST and the
+/// generated Rust code.
+fn front_end(
     script_path: &PathBuf,
-    output_dir: Option<PathBuf>,
-    release: bool,
-    optimized: bool,
-) -> Result<PathBuf> {
-    let total_start = Instant::now();
-    let filename = script_path
-        .file_name()
-        .map(|s| s.to_string_lossy().to_string())
-        .unwrap_or_else(|| "unknown".into());
-    let profile_label = if optimized {
-        "optimized"
-    } else if release {
-        "release"
-    } else {
-        "debug"
-    };
-    eprintln!("{BOLD}        Rosy{RESET} v{}", env!("CARGO_PKG_VERSION"));
-    eprintln!("{BOLD}  Transpiling{RESET} {filename} ({profile_label})");
-
+    total: usize,
+    transpile_label: &str,
+) -> Result<(Program, String)> {
     // --- Step 0: Read source ---
-    step(1, 6, "Reading");
+    step(1, total, "Reading");
     let t = Instant::now();
     let raw_script = std::fs::read_to_string(script_path).with_context(|| {
         format!(
@@ -159,7 +237,7 @@ fn rosy(
     step_done(t);
 
     // --- Step 1: Parse ---
-    step(2, 6, "Parsing");
+    step(2, total, "Parsing");
     let t = Instant::now();
     let program = ast::CosyParser::parse(ast::Rule::program, &raw_script)
         .context("Couldn't parse!")?
@@ -168,7 +246,7 @@ fn rosy(
     step_done(t);
 
     // --- Step 2: AST Generation (resolves INCLUDEs at the AST level) ---
-    step(3, 6, "Building AST");
+    step(3, total, "Building AST");
     let t = Instant::now();
     let mut ast = Program::from_rule_with_includes(
         program,
@@ -180,7 +258,7 @@ fn rosy(
     step_done(t);
 
     // --- Step 3: Type Resolution ---
-    step(4, 6, "Resolving types");
+    step(4, total, "Resolving types");
     let t = Instant::now();
     let (_resolver, warnings) =
         resolve::TypeResolver::resolve(&mut ast).context("Failed to resolve types!")?;
@@ -190,93 +268,443 @@ fn rosy(
     }
 
     // --- Step 4: Transpilation ---
-    step(5, 6, "Generating Rust code");
+    step(5, total, transpile_label);
     let t = Instant::now();
-    let TranspilationOutput { serialization, .. } = ast
-        .transpile(&mut TranspilationInputContext::default())
-        .map_err(|vec_errs| {
+    let mut context = TranspilationInputContext {
+        source_file: Some(script_path.display().to_string()),
+        ..Default::default()
+    };
+    let TranspilationOutput { serialization, .. } =
+        ast.transpile(&mut context).map_err(|vec_errs| {
             step_fail();
-            let mut combined = String::new();
-            for (outer_ind, err) in vec_errs.iter().enumerate() {
-                let mut body = String::new();
-                for (ind, ctx) in err.chain().enumerate() {
-                    body.push_str(&format!("  {}. {}\n", ind + 1, ctx));
-                }
-                combined.push_str(&format!(
-                    "\n#{}: {}\nContext:\n{}",
-                    outer_ind + 1,
-                    err.root_cause(),
-                    body
-                ));
-            }
-            anyhow!(
-                "Failed to transpile with the following errors:\n{}",
-                combined
-            )
+            transpile_error(vec_errs)
         })?;
+    step_done(t);
+
+    Ok((ast, serialization))
+}
 
-    // Detect whether the program uses MPI (only PLOOP generates rosy_mpi_context references)
-    let uses_mpi = serialization.contains("rosy_mpi_context");
-    if uses_mpi {
-        info!("Program uses PLOOP — MPI support enabled in output");
+/// Combine the errors from transpiling a program into one, numbered, with
+/// the context of each.
+fn transpile_error(vec_errs: Vec<anyhow::Error>) -> anyhow::Error {
+    let mut combined = String::new();
+    for (outer_ind, err) in vec_errs.iter().enumerate() {
+        let mut body = String::new();
+        for (ind, ctx) in err.chain().enumerate() {
+            body.push_str(&format!("  {}. {}\n", ind + 1, ctx));
+        }
+        combined.push_str(&format!(
+            "\n#{}: {}\nContext:\n{}",
+            outer_ind + 1,
+            err.root_cause(),
+            body
+        ));
     }
+    anyhow!(
+        "Failed to transpile with the following errors:\n{}",
+        combined
+    )
+}
 
-    // Determine output directory
-    let rosy_output_path = output_dir.unwrap_or_else(|| PathBuf::from(".rosy_output"));
+fn rosy(
+    script_path: &PathBuf,
+    output_dir: Option<PathBuf>,
+    release: bool,
+    optimized: bool,
+) -> Result<PathBuf> {
+    let total_start = Instant::now();
+    let filename = script_path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unknown".into());
+    let profile_label = if optimized {
+        "optimized"
+    } else if release {
+        "release"
+    } else {
+        "debug"
+    };
+    eprintln!("{BOLD}        Rosy{RESET} v{}", env!("CARGO_PKG_VERSION"));
+    eprintln!("{BOLD}  Transpiling{RESET} {filename} ({profile_label})");
 
-    // Create the output project structure from embedded templates
-    embedded::create_output_project(&rosy_output_path, uses_mpi, optimized)
-        .context("Failed to create output project structure")?;
+    let (_ast, serialization) = front_end(script_path, 6, "Generating Rust code")?;
+
+    // Detect whether the program runs in parallel (only PLOOP and PNPRO
+    // generate rosy_mpi_context references), and on which backend
+    let parallel = serialization
+        .contains("rosy_mpi_context")
+        .then(parallel_config::parallel_backend);
+    match parallel {
+        Some(ParallelBackend::Mpi) => info!("Program uses PLOOP — MPI support enabled in output"),
+        Some(ParallelBackend::Threads) => info!("Program uses PLOOP — running it on threads"),
+        None => {}
+    }
+
+    // Size DA monomials for the widest DAINI in the program
+    let monomial_width = embedded::monomial_width(&serialization);
+    info!("Vendoring rosy_lib with MAX_VARS = {monomial_width}");
 
     // Inject the transpiled code into main.rs
-    let new_contents = embedded::inject_code(&serialization, uses_mpi)
+    let new_contents = embedded::inject_code(&serialization, parallel)
         .context("Failed to inject transpiled code into template")?;
 
-    write(rosy_output_path.join("src/main.rs"), &new_contents)
-        .context("Failed to write Rust output file!")?;
-    step_done(t);
+    // Find or create the project for it in the build cache
+    let cache_dir = output_dir.unwrap_or_else(|| PathBuf::from(".rosy_output"));
+    let project = embedded::create_output_project(
+        &cache_dir,
+        script_path,
+        &new_contents,
+        parallel,
+        optimized,
+        release,
+        monomial_width,
+    )
+    .context("Failed to create output project structure")?;
+    info!("Generated project at {}", project.dir.display());
+    let binary_path = project.binary(release);
 
     // --- Step 5: Compilation (piped to user's terminal) ---
-    eprintln!("{BOLD}{CYAN}[6/6]{RESET} Compiling generated Rust code...");
-    let mut cargo_args = vec!["build", "--bin", "rosy_output", "--color", "always"];
-    if release {
-        cargo_args.push("--release");
-    }
-
-    let status = Command::new("cargo")
-        .args(&cargo_args)
-        .current_dir(&rosy_output_path)
-        .stdin(std::process::Stdio::null())
-        .status()
-        .context("Failed to spawn cargo build process")?;
-    if !status.success() {
-        eprintln!();
-        eprintln!("{BOLD}{RED}  error:{RESET} The generated Rust code failed to compile.");
-        eprintln!();
-        eprintln!("  This is a bug in the Rosy transpiler, not in your code.");
-        eprintln!("  Please report it at: {BOLD}https://github.com/rosy-team/rosy/issues{RESET}");
-        eprintln!("  Include your {BOLD}.rosy{RESET} file and the error output above.");
-        anyhow::bail!(
-            "Internal transpiler error: generated code failed to compile (exit code {:?})",
-            status.code()
-        );
+    if project.is_built(release) {
+        eprintln!("{BOLD}{CYAN}[6/6]{RESET} Compiling generated Rust code... {GREEN}cached{RESET}");
+    } else {
+        eprintln!("{BOLD}{CYAN}[6/6]{RESET} Compiling generated Rust code...");
+        let mut cargo_args = vec!["build", "--bin", &project.name, "--color", "always"];
+        if release {
+            cargo_args.push("--release");
+        }
+
+        let status = Command::new("cargo")
+            .args(&cargo_args)
+            .arg("--target-dir")
+            .arg(&project.target_dir)
+            .current_dir(&project.dir)
+            .stdin(std::process::Stdio::null())
+            .status()
+            .context("Failed to spawn cargo build process")?;
+        if !status.success() {
+            eprintln!();
+            eprintln!("{BOLD}{RED}  error:{RESET} The generated Rust code failed to compile.");
+            eprintln!();
+            eprintln!("  This is a bug in the Rosy transpiler, not in your code.");
+            eprintln!("  Please report it at: {BOLD}https://github.com/rosy-team/rosy/issues{RESET}");
+            eprintln!("  Include your {BOLD}.rosy{RESET} file and the error output above.");
+            anyhow::bail!(
+                "Internal transpiler error: generated code failed to compile (exit code {:?})",
+                status.code()
+            );
+        }
     }
 
-    let build_profile = if release { "release" } else { "debug" };
-    let binary_name = if cfg!(windows) {
-        "rosy_output.exe"
-    } else {
-        "rosy_output"
+    let total_ms = total_start.elapsed().as_millis();
+    eprintln!(
+        "{BOLD}{GREEN}    Finished{RESET} in {DIM}{:.2}s{RESET}",
+        total_ms as f64 / 1000.0
+    );
+
+    Ok(binary_path)
+}
+
+// ─── Interpreting Without Compiling (`rosy run --interpret`) ────────────────
+
+/// Check a Rosy script as [`rosy`] does, then run it in-process with the
+/// interpreter instead of generating and compiling a Rust project.
+fn interpret(script_path: &PathBuf, resume: Option<&Path>) -> Result<()> {
+    let total_start = Instant::now();
+    let filename = script_path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unknown".into());
+    eprintln!("{BOLD}        Rosy{RESET} v{}", env!("CARGO_PKG_VERSION"));
+    eprintln!("{BOLD} Interpreting{RESET} {filename}");
+
+    let (ast, _) = front_end(script_path, 5, "Checking")?;
+    let mut interpreter = Interpreter::new(&script_path.display().to_string())?;
+    if let Some(checkpoint) = resume {
+        interpreter.resume = Some(Checkpoint::load(checkpoint)?);
+    }
+
+    let total_ms = total_start.elapsed().as_millis();
+    eprintln!(
+        "{BOLD}{GREEN}    Finished{RESET} in {DIM}{:.2}s{RESET}",
+        total_ms as f64 / 1000.0
+    );
+    eprintln!("{BOLD}{CYAN}     Running{RESET} {}\n", script_path.display());
+
+    trace::install_panic_hook();
+    match ast.interpret(&mut interpreter) {
+        Ok(Flow::Quit(code)) => std::process::exit(code),
+        Ok(_) => Ok(()),
+        Err(err) => Err(anyhow!(interpret::report(&err))),
+    }
+}
+
+// ─── Library Crates (`rosy build --lib` and `--cdylib`) ─────────────────────
+
+/// Generate a library crate from a Rosy script (see [`rosy::library`]) and
+/// compile it once in the build cache, to check it and to have the runtime
+/// built for the crates that use it. With `cdylib`, the crate also gets a C
+/// ABI, and its shared library is copied next to the C header.
+fn build_library(
+    script_path: &PathBuf,
+    output: Option<&str>,
+    output_dir: Option<PathBuf>,
+    release: bool,
+    optimized: bool,
+    cdylib: bool,
+) -> Result<()> {
+    let total_start = Instant::now();
+    let filename = script_path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unknown".into());
+    eprintln!("{BOLD}        Rosy{RESET} v{}", env!("CARGO_PKG_VERSION"));
+    eprintln!("{BOLD}  Transpiling{RESET} {filename} (library)");
+
+    let (ast, _) = front_end(script_path, 6, "Generating Rust code")?;
+    let mut context = TranspilationInputContext {
+        source_file: Some(script_path.display().to_string()),
+        ..Default::default()
     };
-    let binary_path = rosy_output_path.join(format!("target/{}/{}", build_profile, binary_name));
+    let library = Library::transpile(&ast, &mut context).map_err(transpile_error)?;
+
+    // The crate goes where -o says, named after its directory
+    let crate_dir = PathBuf::from(output.map(str::to_string).unwrap_or_else(|| {
+        script_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("rosy_output")
+            .to_string()
+    }));
+    let crate_name: String = crate_dir
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    ensure!(
+        crate_name.starts_with(|c: char| c.is_ascii_alphabetic()),
+        "'{crate_name}' can't name a crate; pick another with -o"
+    );
+    let mut lib_rs = library.render(&filename);
+    if cdylib {
+        for omission in library.c_abi_omissions() {
+            eprintln!("{BOLD}{YELLOW}    warning{RESET}: {omission}");
+        }
+        lib_rs.push_str(&library.render_c_abi(&crate_name));
+    }
+    let monomial_width = embedded::monomial_width(&library.serialization());
+    embedded::create_library_crate(
+        &crate_dir,
+        &crate_name,
+        &lib_rs,
+        optimized,
+        monomial_width,
+        cdylib,
+    )
+    .context("Failed to create library crate")?;
+    let header_path = crate_dir.join(format!("{crate_name}.h"));
+    if cdylib {
+        fs::write(&header_path, library.render_c_header(&crate_name, &filename))
+            .context("Failed to write C header")?;
+    }
+
+    eprintln!("{BOLD}{CYAN}[6/6]{RESET} Compiling library crate...");
+    let cache_dir = output_dir.unwrap_or_else(|| PathBuf::from(".rosy_output"));
+    fs::create_dir_all(&cache_dir).context("Failed to create build cache")?;
+    let target_dir = fs::canonicalize(&cache_dir)
+        .context("Failed to resolve build cache")?
+        .join("target");
+    let mut cmd = Command::new("cargo");
+    cmd.args(["build", "--lib", "--color", "always", "--target-dir"])
+        .arg(&target_dir)
+        .current_dir(&crate_dir)
+        .stdin(std::process::Stdio::null());
+    if release {
+        cmd.arg("--release");
+    }
+    let status = cmd.status().context("Failed to spawn cargo build process")?;
+    ensure!(
+        status.success(),
+        "Internal transpiler error: the generated library failed to compile (exit code {:?})",
+        status.code()
+    );
+
+    // Cargo names the shared library after the platform's conventions
+    let shared_library = format!(
+        "{}{crate_name}{}",
+        std::env::consts::DLL_PREFIX,
+        std::env::consts::DLL_SUFFIX
+    );
+    if cdylib {
+        let profile = if release { "release" } else { "debug" };
+        fs::copy(
+            target_dir.join(profile).join(&shared_library),
+            crate_dir.join(&shared_library),
+        )
+        .context("Failed to copy the shared library out of the build cache")?;
+    }
 
     let total_ms = total_start.elapsed().as_millis();
     eprintln!(
         "{BOLD}{GREEN}    Finished{RESET} in {DIM}{:.2}s{RESET}",
         total_ms as f64 / 1000.0
     );
+    eprintln!(
+        "  Library crate written to {BOLD}{}{RESET} ({} exported)",
+        crate_dir.display(),
+        library.exports.len()
+    );
+    if cdylib {
+        eprintln!(
+            "  Shared library {BOLD}{}{RESET}, declared in {BOLD}{}{RESET}",
+            crate_dir.join(&shared_library).display(),
+            header_path.display()
+        );
+    }
+    Ok(())
+}
 
-    Ok(binary_path)
+// ─── Diagnostics Without Compiling (`rosy check`) ───────────────────────────
+
+/// A diagnostic reported by `rosy check`, with 1-based line and column.
+#[derive(serde::Serialize)]
+struct CheckDiagnostic {
+    file: String,
+    line: u32,
+    col: u32,
+    severity: &'static str,
+    message: String,
+}
+
+/// Run the front half of the pipeline (parse, AST, type resolution,
+/// transpilation) on each source, the same analysis the LSP runs, and print
+/// every error and warning. Returns whether all sources are free of errors.
+fn check_sources(sources: &[PathBuf], format: CheckFormat) -> bool {
+    use tower_lsp::lsp_types::DiagnosticSeverity;
+
+    let start = Instant::now();
+    let mut diagnostics = Vec::new();
+    for source in sources {
+        let file = source.display().to_string();
+        let text = match std::fs::read_to_string(source) {
+            Ok(text) => text,
+            Err(e) => {
+                diagnostics.push(CheckDiagnostic {
+                    file,
+                    line: 1,
+                    col: 1,
+                    severity: "error",
+                    message: format!("Failed to read file: {e}"),
+                });
+                continue;
+            }
+        };
+        for diagnostic in rosy::lsp::analysis::analyze(&text, Some(source)).diagnostics {
+            diagnostics.push(CheckDiagnostic {
+                file: file.clone(),
+                line: diagnostic.range.start.line + 1,
+                col: diagnostic.range.start.character + 1,
+                severity: if diagnostic.severity == Some(DiagnosticSeverity::WARNING) {
+                    "warning"
+                } else {
+                    "error"
+                },
+                message: diagnostic.message,
+            });
+        }
+    }
+
+    let errors = diagnostics.iter().filter(|d| d.severity == "error").count();
+    let warnings = diagnostics.len() - errors;
+    match format {
+        CheckFormat::Text => {
+            for d in &diagnostics {
+                println!(
+                    "{}:{}:{}: {}: {}",
+                    d.file, d.line, d.col, d.severity, d.message
+                );
+            }
+            let color = if errors > 0 {
+                RED
+            } else if warnings > 0 {
+                YELLOW
+            } else {
+                GREEN
+            };
+            eprintln!(
+                "{BOLD}{color}     Checked{RESET} {} file(s): {} error(s), {} warning(s) {DIM}({}ms){RESET}",
+                sources.len(),
+                errors,
+                warnings,
+                start.elapsed().as_millis()
+            );
+        }
+        CheckFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&diagnostics)
+                    .expect("Failed to serialize diagnostics")
+            );
+        }
+    }
+
+    errors == 0
+}
+
+// ─── Source Formatter (`rosy fmt`) ───────────────────────────────────────────
+
+/// Format each source in place, or with `check` only report the ones that
+/// are not formatted. Returns whether every source was formatted (or, with
+/// `check`, already was) without errors.
+fn format_sources(sources: &[PathBuf], check: bool) -> bool {
+    let mut changed = 0;
+    let mut failed = 0;
+    for source in sources {
+        let result = std::fs::read_to_string(source)
+            .context("Failed to read file")
+            .and_then(|text| Ok((rosy::format::format_source(&text)?, text)));
+        let (formatted, original) = match result {
+            Ok(texts) => texts,
+            Err(e) => {
+                eprintln!("{BOLD}{RED}       error{RESET} {}: {e:#}", source.display());
+                failed += 1;
+                continue;
+            }
+        };
+        if formatted == original {
+            continue;
+        }
+        changed += 1;
+        if check {
+            println!("{}", source.display());
+        } else if let Err(e) = write(source, formatted) {
+            eprintln!("{BOLD}{RED}       error{RESET} {}: {e}", source.display());
+            failed += 1;
+        }
+    }
+
+    let verb = if check {
+        "would reformat"
+    } else {
+        "reformatted"
+    };
+    let color = if failed > 0 || (check && changed > 0) {
+        RED
+    } else {
+        GREEN
+    };
+    eprintln!(
+        "{BOLD}{color}   Formatted{RESET} {} file(s): {changed} {verb}, {failed} failed",
+        sources.len()
+    );
+    failed == 0 && !(check && changed > 0)
 }
 
 // ─── Construct Test Runner (`rosy test`) ────────────────────────────────────
@@ -333,15 +761,54 @@ struct TestResult {
     failure_msg: Option<String>,
 }
 
-/// Run a single construct test using the given build directory.
+/// Write a test's output next to it, leaving the file alone if the output
+/// didn't change: `build.rs` watches the source tree, so every write makes
+/// the next `cargo run` of the transpiler rebuild it.
+fn write_if_changed(path: &std::path::Path, contents: &str) {
+    if fs::read_to_string(path).ok().as_deref() != Some(contents) {
+        fs::write(path, contents).ok();
+    }
+}
+
+/// Compares an interpreted run's output with the committed `rosy_output.txt`,
+/// describing the first line that differs.
+fn snapshot_mismatch(path: &std::path::Path, actual: &str) -> Option<String> {
+    let Ok(expected) = fs::read_to_string(path) else {
+        return Some(format!("no {} to compare against", path.display()));
+    };
+    if expected == actual {
+        return None;
+    }
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    for line in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => continue,
+            (None, None) => break,
+            (e, a) => {
+                return Some(format!(
+                    "output differs from rosy_output.txt at line {line}\n  expected: {}\n    actual: {}",
+                    e.unwrap_or("<end of output>"),
+                    a.unwrap_or("<end of output>")
+                ));
+            }
+        }
+    }
+    Some("output differs from rosy_output.txt in its line endings".to_string())
+}
+
+/// Run a single construct test, building it in the given build cache.
+#[allow(clippy::too_many_arguments)]
 fn run_single_test(
     category: &str,
     name: &str,
     construct_path: &std::path::Path,
-    build_dir: &std::path::Path,
+    cache_dir: &std::path::Path,
     workspace_root: &std::path::Path,
     cosy_bin: Option<&std::path::Path>,
     release: bool,
+    threads: bool,
+    interpret: bool,
 ) -> TestResult {
     let test_label = format!("{category}/{name}");
     let t = Instant::now();
@@ -364,8 +831,14 @@ fn run_single_test(
         .arg("run")
         .arg(&rosy_script)
         .arg("-d")
-        .arg(build_dir)
-        .current_dir(build_dir);
+        .arg(cache_dir)
+        .current_dir(cache_dir);
+    if threads {
+        cmd.arg("--threads");
+    }
+    if interpret {
+        cmd.arg("--interpret");
+    }
 
     let rosy_result = cmd.output();
 
@@ -380,7 +853,20 @@ fn run_single_test(
                     failure_msg: Some("empty output".to_string()),
                 };
             }
-            fs::write(&rosy_output_path, &stdout).ok();
+            // The compiled run owns the snapshot; an interpreted run must
+            // reproduce it rather than overwrite it
+            if interpret {
+                if let Some(msg) = snapshot_mismatch(&rosy_output_path, &stdout) {
+                    return TestResult {
+                        label: test_label,
+                        ok: false,
+                        elapsed_secs: t.elapsed().as_secs_f64(),
+                        failure_msg: Some(msg),
+                    };
+                }
+            } else {
+                write_if_changed(&rosy_output_path, &stdout);
+            }
 
             // Run COSY if available
             if let Some(cosy) = cosy_bin {
@@ -404,7 +890,7 @@ fn run_single_test(
                                 String::from_utf8_lossy(&cosy_result.stdout).to_string();
                             let cosy_output = extract_cosy_output(&cosy_stdout);
                             if !cosy_output.trim().is_empty() {
-                                fs::write(&cosy_output_path, &cosy_output).ok();
+                                write_if_changed(&cosy_output_path, &cosy_output);
                             }
                         }
                     }
@@ -437,7 +923,13 @@ fn run_single_test(
 }
 
 /// Run all construct tests, printing results as they complete.
-fn run_construct_tests(filter: Option<&str>, release: bool, parallel: usize) -> Result<()> {
+fn run_construct_tests(
+    filter: Option<&str>,
+    release: bool,
+    parallel: usize,
+    threads: bool,
+    interpret: bool,
+) -> Result<()> {
     let parallel = parallel.max(1);
     let crate_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     let workspace_root = crate_root
@@ -479,6 +971,9 @@ fn run_construct_tests(filter: Option<&str>, release: bool, parallel: usize) ->
     if release {
         eprintln!("        Mode: release");
     }
+    if interpret {
+        eprintln!("        Mode: interpreted");
+    }
     if parallel > 1 {
         eprintln!("    Parallel: {parallel} workers");
     }
@@ -490,17 +985,12 @@ fn run_construct_tests(filter: Option<&str>, release: bool, parallel: usize) ->
     }
     eprintln!();
 
-    // Create build directories in tmp upfront
-    let tmp_base = std::env::temp_dir().join(format!("rosy_test_{}", std::process::id()));
-    let build_dirs: Vec<PathBuf> = (0..parallel)
-        .map(|i| {
-            let dir = tmp_base.join(format!("worker_{i}"));
-            fs::create_dir_all(&dir).expect("Failed to create build directory");
-            dir
-        })
-        .collect();
+    // All workers share one build cache, kept between runs, so constructs
+    // that haven't changed since the last run don't compile again
+    let cache_dir = workspace_root.join(".rosy_test_cache");
+    fs::create_dir_all(&cache_dir).context("Failed to create build cache")?;
 
-    eprintln!("  Build dirs: {}\n", tmp_base.display());
+    eprintln!("  Build cache: {}\n", cache_dir.display());
 
     let total_start = Instant::now();
 
@@ -515,11 +1005,11 @@ fn run_construct_tests(filter: Option<&str>, release: bool, parallel: usize) ->
 
     // Spawn worker threads
     let mut handles = Vec::new();
-    for worker_id in 0..parallel {
+    for _ in 0..parallel {
         let work_index = Arc::clone(&work_index);
         let all_tests = Arc::clone(&all_tests);
         let results = Arc::clone(&results);
-        let build_dir = build_dirs[worker_id].clone();
+        let cache_dir = cache_dir.clone();
         let workspace_root = workspace_root.clone();
         let cosy_bin = if has_cosy {
             Some(cosy_bin.clone())
@@ -539,10 +1029,12 @@ fn run_construct_tests(filter: Option<&str>, release: bool, parallel: usize) ->
                     category,
                     name,
                     construct_path,
-                    &build_dir,
+                    &cache_dir,
                     &workspace_root,
                     cosy_bin.as_deref(),
                     release,
+                    threads,
+                    interpret,
                 );
 
                 results.lock().unwrap().push(result);
@@ -582,9 +1074,6 @@ fn run_construct_tests(filter: Option<&str>, release: bool, parallel: usize) ->
     }
     print_handle.join().expect("Print thread panicked");
 
-    // Cleanup build directories
-    let _ = fs::remove_dir_all(&tmp_base);
-
     // Summarize
     let all_results = Arc::try_unwrap(results).unwrap().into_inner().unwrap();
     let passed = all_results.iter().filter(|r| r.ok).count();
@@ -854,6 +1343,30 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Handle Check command — diagnostics only, nothing is compiled
+    if let Commands::Check {
+        sources,
+        format,
+        cosy_syntax,
+    } = &cli.command
+    {
+        update_handle.finish();
+        syntax_config::set_cosy_syntax(*cosy_syntax);
+        if !check_sources(sources, *format) {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Handle Fmt command — rewrite sources, nothing is compiled
+    if let Commands::Fmt { sources, check } = &cli.command {
+        update_handle.finish();
+        if !format_sources(sources, *check) {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Handle Setup command — install editor extensions
     if let Commands::Setup { editor } = &cli.command {
         update_handle.finish();
@@ -865,59 +1378,131 @@ fn main() -> Result<()> {
         filter,
         release,
         parallel,
+        threads,
+        interpret,
     } = &cli.command
     {
         update_handle.finish();
-        return run_construct_tests(filter.as_deref(), *release, *parallel);
+        return run_construct_tests(
+            filter.as_deref(),
+            *release,
+            *parallel,
+            *threads,
+            *interpret,
+        );
     }
 
-    // Extract common fields and transpile
-    let (source, output_dir, release, optimized, cosy_syntax, output_name) = match &cli.command {
-        Commands::Run {
+    // Handle Repl command — interactive, nothing is compiled
+    if let Commands::Repl { cosy_syntax } = &cli.command {
+        update_handle.finish();
+        syntax_config::set_cosy_syntax(*cosy_syntax);
+        return repl::run();
+    }
+
+    // Handle Build --lib and --cdylib — a library crate instead of a binary
+    if let Commands::Build {
+        source,
+        output,
+        output_dir,
+        release,
+        optimized,
+        cosy_syntax,
+        lib,
+        cdylib,
+        ..
+    } = &cli.command
+        && (*lib || *cdylib)
+    {
+        update_handle.finish();
+        syntax_config::set_cosy_syntax(*cosy_syntax);
+        return build_library(
             source,
-            output_dir,
-            release,
-            optimized,
-            cosy_syntax,
-        } => (
-            source.clone(),
+            output.as_deref(),
             output_dir.clone(),
             *release || *optimized,
             *optimized,
-            *cosy_syntax,
-            None,
-        ),
-        Commands::Build {
-            source,
-            output,
-            output_dir,
-            release,
-            optimized,
-            cosy_syntax,
-        } => {
-            let mut name = output.clone().unwrap_or_else(|| {
-                source
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("rosy_output")
-                    .to_string()
-            });
-            if cfg!(windows) && !name.ends_with(".exe") {
-                name.push_str(".exe");
-            }
-            (
+            *cdylib,
+        );
+    }
+
+    // Handle Run --interpret — the front half of the pipeline, then the
+    // interpreter instead of cargo
+    if let Commands::Run {
+        source,
+        cosy_syntax,
+        resume,
+        interpret: true,
+        ..
+    } = &cli.command
+    {
+        update_handle.finish();
+        syntax_config::set_cosy_syntax(*cosy_syntax);
+        return interpret(source, resume.as_deref());
+    }
+
+    // Extract common fields and transpile
+    let (source, output_dir, release, optimized, cosy_syntax, threads, output_name) =
+        match &cli.command {
+            Commands::Run {
+                source,
+                output_dir,
+                release,
+                optimized,
+                cosy_syntax,
+                threads,
+                ..
+            } => (
                 source.clone(),
                 output_dir.clone(),
                 *release || *optimized,
                 *optimized,
                 *cosy_syntax,
-                Some(name),
-            )
-        }
-        Commands::Test { .. } | Commands::Lsp { .. } | Commands::Setup { .. } => unreachable!(),
-    };
+                *threads,
+                None,
+            ),
+            Commands::Build {
+                source,
+                output,
+                output_dir,
+                release,
+                optimized,
+                cosy_syntax,
+                threads,
+                lib: _,
+                cdylib: _,
+            } => {
+                let mut name = output.clone().unwrap_or_else(|| {
+                    source
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or("rosy_output")
+                        .to_string()
+                });
+                if cfg!(windows) && !name.ends_with(".exe") {
+                    name.push_str(".exe");
+                }
+                (
+                    source.clone(),
+                    output_dir.clone(),
+                    *release || *optimized,
+                    *optimized,
+                    *cosy_syntax,
+                    *threads,
+                    Some(name),
+                )
+            }
+            Commands::Test { .. }
+            | Commands::Repl { .. }
+            | Commands::Check { .. }
+            | Commands::Fmt { .. }
+            | Commands::Lsp { .. }
+            | Commands::Setup { .. } => unreachable!(),
+        };
 
     syntax_config::set_cosy_syntax(cosy_syntax);
+    if threads {
+        parallel_config::set_parallel_backend(ParallelBackend::Threads);
+    }
     let binary_path = rosy(&source, output_dir, release, optimized)?;
 
     // Show update notice after transpilation (network has had time)
@@ -925,10 +1510,14 @@ fn main() -> Result<()> {
 
     // Run or copy the binary
     match cli.command {
-        Commands::Run { .. } => {
+        Commands::Run { resume, .. } => {
             eprintln!("{BOLD}{CYAN}     Running{RESET} {}\n", source.display());
 
-            let status = Command::new(&binary_path)
+            let mut cmd = Command::new(&binary_path);
+            if let Some(checkpoint) = resume {
+                cmd.arg("--resume").arg(checkpoint);
+            }
+            let status = cmd
                 .status()
                 .with_context(|| format!("Failed to run binary at `{}`!", binary_path.display()))?;
             ensure!(
@@ -943,7 +1532,12 @@ fn main() -> Result<()> {
                 .context("Failed to copy binary to current directory")?;
             eprintln!("  Binary written to {BOLD}{}{RESET}", destination.display());
         }
-        Commands::Test { .. } | Commands::Lsp { .. } | Commands::Setup { .. } => unreachable!(),
+        Commands::Test { .. }
+        | Commands::Repl { .. }
+        | Commands::Check { .. }
+        | Commands::Fmt { .. }
+        | Commands::Lsp { .. }
+        | Commands::Setup { .. } => unreachable!(),
     }
 
     Ok(())
@@ -0,0 +1,41 @@
+//! # Parallel Backend Configuration
+//!
+//! Global choice of how `PLOOP` (and `PNPRO`) run in the generated program.
+//!
+//! By default they run on MPI: the generated project enables `rosy_lib`'s
+//! `mpi` feature and is launched with `mpirun`. With `--threads` they run
+//! on a pool of threads in a single process instead (see
+//! `rosy_lib::threads`), which needs neither an MPI install nor `mpirun`.
+
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParallelBackend {
+    Mpi,
+    Threads,
+}
+
+impl ParallelBackend {
+    /// The Rust type of the `rosy_mpi_context` the generated code threads
+    /// through `PLOOP`s and procedures.
+    pub fn context_type(self) -> &'static str {
+        match self {
+            ParallelBackend::Mpi => "RosyMPIContext",
+            ParallelBackend::Threads => "RosyThreadContext",
+        }
+    }
+}
+
+static PARALLEL_BACKEND: OnceLock<ParallelBackend> = OnceLock::new();
+
+/// Set the global parallel backend. Call this once from `main()` before transpiling.
+pub fn set_parallel_backend(backend: ParallelBackend) {
+    PARALLEL_BACKEND
+        .set(backend)
+        .expect("parallel backend was already set");
+}
+
+/// The active parallel backend (MPI unless `--threads` was passed).
+pub fn parallel_backend() -> ParallelBackend {
+    *PARALLEL_BACKEND.get().unwrap_or(&ParallelBackend::Mpi)
+}
@@ -55,6 +55,7 @@ use std::collections::BTreeSet;
 use std::collections::HashSet;
 
 use crate::resolve::{ExprRecipe, ScopeContext, TypeResolver, TypeSlot};
+use crate::interpret::{Evaluate, Interpreter, Value};
 
 /// Find a case-insensitive match among known names and return a hint string.
 fn find_case_similar<'a>(name: &str, candidates: impl Iterator<Item = &'a String>) -> String {
@@ -94,9 +95,28 @@ impl VarExpr {
     /// - No paren groups → variable
     /// - Any invalid combo → error
     pub fn classify(&self, context: &TranspilationInputContext) -> Result<VarExprKind, Vec<Error>> {
+        let name = &self.identifier.name;
+        self.classify_by(
+            context.variables.get(name).map(|v| &v.data.r#type),
+            context.functions.get(name).map(|f| f.args.len()),
+        )
+        .map_err(|e| vec![e])
+    }
+
+    /// The decision tree behind [`VarExpr::classify`], given the type of the
+    /// variable and the number of arguments of the function this name refers
+    /// to, if either is in scope. The interpreter classifies with its own
+    /// scopes through this.
+    pub fn classify_by(
+        &self,
+        variable_type: Option<&RosyType>,
+        function_arity: Option<usize>,
+    ) -> Result<VarExprKind, Error> {
         let ident = &self.identifier;
         let num_groups = ident.paren_groups.len();
         let has_brackets = !ident.bracket_indices.is_empty();
+        let is_var = variable_type.is_some();
+        let is_func = function_arity.is_some();
 
         match num_groups {
             0 => {
@@ -109,18 +129,13 @@ impl VarExpr {
                     // Multiple args in one paren group — could be either a
                     // function call `FUNC(a, b)` or COSY-style multi-dim
                     // indexing `X(I, J)`. Disambiguate via the symbol table.
-                    let is_var = context.variables.contains_key(&ident.name);
-                    let is_func = context.functions.contains_key(&ident.name);
-
-                    let func_accepts = is_func
-                        && context.functions.get(&ident.name).unwrap().args.len() == num_args;
-                    let var_accepts = is_var && {
-                        let v = context.variables.get(&ident.name).unwrap();
+                    let func_accepts = function_arity == Some(num_args);
+                    let var_accepts = variable_type.is_some_and(|t| {
                         // VE only accepts a single index, so it can never satisfy
                         // a multi-arg group; fall through to function-call routing.
-                        v.data.r#type.base_type != crate::rosy_lib::RosyBaseType::VE
-                            && num_args <= v.data.r#type.dimensions
-                    };
+                        t.base_type != crate::rosy_lib::RosyBaseType::VE
+                            && num_args <= t.dimensions
+                    });
 
                     let route_as_call = match (func_accepts, var_accepts) {
                         (true, _) => true,           // function arity matches — prefer call
@@ -130,10 +145,10 @@ impl VarExpr {
 
                     if route_as_call {
                         if has_brackets {
-                            return Err(vec![anyhow::anyhow!(
+                            return Err(anyhow::anyhow!(
                                 "'{}': function call with bracket indexing is not valid",
                                 ident.name
-                            )]);
+                            ));
                         }
                         Ok(VarExprKind::FunctionCall)
                     } else {
@@ -141,44 +156,40 @@ impl VarExpr {
                     }
                 } else {
                     // Single paren group, single arg → check context
-                    let is_var = context.variables.contains_key(&ident.name);
-                    let is_func = context.functions.contains_key(&ident.name);
-
-                    match (is_var, is_func) {
-                        (true, false) => Ok(VarExprKind::Variable),
-                        (false, true) => {
+                    match (variable_type, is_func) {
+                        (Some(_), false) => Ok(VarExprKind::Variable),
+                        (None, true) => {
                             if has_brackets {
-                                return Err(vec![anyhow::anyhow!(
+                                return Err(anyhow::anyhow!(
                                     "'{}': function call with bracket indexing is not valid",
                                     ident.name
-                                )]);
+                                ));
                             }
                             Ok(VarExprKind::FunctionCall)
                         }
-                        (true, true) => {
+                        (Some(var_type), true) => {
                             // Both exist — disambiguate by checking variable dimensions.
                             // A scalar variable (0 dimensions) cannot be indexed, so
                             // parentheses must be a function call (e.g. recursion where
                             // the function name doubles as the return variable).
-                            let var_data = context.variables.get(&ident.name).unwrap();
-                            if var_data.data.r#type.dimensions > 0 {
+                            if var_type.dimensions > 0 {
                                 // Variable is an array — prefer indexing
                                 Ok(VarExprKind::Variable)
                             } else {
                                 // Variable is a scalar — can't index, must be a function call
                                 if has_brackets {
-                                    return Err(vec![anyhow::anyhow!(
+                                    return Err(anyhow::anyhow!(
                                         "'{}': function call with bracket indexing is not valid",
                                         ident.name
-                                    )]);
+                                    ));
                                 }
                                 Ok(VarExprKind::FunctionCall)
                             }
                         }
-                        (false, false) => Err(vec![anyhow::anyhow!(
+                        (None, false) => Err(anyhow::anyhow!(
                             "'{}' is neither a defined variable nor a defined function in this scope!",
                             ident.name
-                        )]),
+                        )),
                     }
                 }
             }
@@ -312,8 +323,22 @@ impl TranspileableExpr for VarExpr {
         // Apply the same disambiguation logic as classify():
         // - 0 paren groups → variable
         // - 1 group, multiple args → function call
-        // - 1 group, 1 arg → prefer variable if it exists, else function
+        // - 1 group, 1 arg → prefer variable if it exists, else function;
+        //   if both exist, decided once the variable's dimensions are known
         // - ≥2 groups → variable (multi-dim indexing)
+        if num_groups == 1
+            && ident.paren_groups[0].len() == 1
+            && ident.bracket_indices.is_empty()
+            && let (Some(var_slot), Some((ret_slot, _))) =
+                (ctx.variables.get(&ident.name), ctx.functions.get(&ident.name))
+        {
+            deps.insert(var_slot.clone());
+            deps.insert(ret_slot.clone());
+            return ExprRecipe::IndexOrCall {
+                variable: var_slot.clone(),
+                function: ret_slot.clone(),
+            };
+        }
         let is_function_call = match num_groups {
             0 => false,
             1 => {
@@ -635,3 +660,54 @@ pub fn function_call_transpile_helper(
         Err(errors)
     }
 }
+impl Evaluate for VarExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        match self.classify_in(interpreter)? {
+            VarExprKind::FunctionCall => function_call_evaluate_helper(
+                &self.identifier.name,
+                &self.identifier.paren_groups[0],
+                interpreter,
+            ),
+            VarExprKind::Variable => self.identifier.read(interpreter),
+        }
+    }
+    fn store(&self, interpreter: &mut Interpreter<'_>, value: Value) -> Result<()> {
+        match self.classify_in(interpreter)? {
+            VarExprKind::FunctionCall => Ok(()),
+            VarExprKind::Variable => self.identifier.write(interpreter, value),
+        }
+    }
+}
+/// Calls the function `name` with `args`, returning its result. Shared with
+/// the function call statement, which discards it.
+pub fn function_call_evaluate_helper(
+    name: &str,
+    args: &[Expr],
+    interpreter: &mut Interpreter<'_>,
+) -> Result<Value> {
+    let callable = interpreter.function(name)?;
+    let function = callable.definition;
+    let return_type = function
+        .return_type
+        .ok_or_else(|| anyhow!("Could not infer a return type for function '{name}'"))?;
+    interpreter.call(
+        format!("FUNCTION {name}"),
+        callable.scope,
+        &function.args,
+        args,
+        &function.body,
+        |interpreter| {
+            let value = interpreter.slot(name)?.borrow().value.clone();
+            value.conform(&return_type)
+        },
+    )
+}
+impl VarExpr {
+    fn classify_in(&self, interpreter: &Interpreter<'_>) -> Result<VarExprKind> {
+        let name = &self.identifier.name;
+        self.classify_by(
+            interpreter.variable_type(name).as_ref(),
+            interpreter.function_arity(name),
+        )
+    }
+}
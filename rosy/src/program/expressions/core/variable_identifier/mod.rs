@@ -42,6 +42,7 @@ use crate::rosy_lib::RosyType;
 use crate::{ast::FromRule, transpile::*};
 use anyhow::{Context, Error, Result, ensure};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value};
 
 /// A parsed identifier with optional parenthesized arguments and bracket indices.
 #[derive(Debug)]
@@ -301,3 +302,47 @@ impl Transpile for VariableIdentifier {
         }
     }
 }
+impl VariableIdentifier {
+    /// Evaluate the index expressions, in order.
+    fn evaluate_indices(&self, interpreter: &mut Interpreter<'_>) -> Result<Vec<f64>> {
+        self.flat_indices()
+            .into_iter()
+            .map(|index| interpreter.evaluate_as(index))
+            .collect()
+    }
+
+    /// The value of the variable, or of the element its indices name.
+    pub fn read(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let indices = self.evaluate_indices(interpreter)?;
+        let slot = interpreter.slot(&self.name)?;
+        let variable = slot.borrow();
+        if indices.is_empty() {
+            Ok(variable.value.clone())
+        } else {
+            variable.value.get(&indices, &self.name)
+        }
+    }
+
+    /// Store `value` in the variable, or in the element its indices name,
+    /// widening it to the declared type as an assignment does.
+    pub fn write(&self, interpreter: &mut Interpreter<'_>, value: Value) -> Result<()> {
+        let indices = self.evaluate_indices(interpreter)?;
+        let slot = interpreter.slot(&self.name)?;
+        let mut variable = slot.borrow_mut();
+        let r#type = variable.r#type;
+        if indices.is_empty() {
+            variable.value = value.conform(&r#type)?;
+            Ok(())
+        } else {
+            variable.value.set(&indices, value, &r#type, &self.name)
+        }
+    }
+}
+impl Evaluate for VariableIdentifier {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        self.read(interpreter)
+    }
+    fn store(&self, interpreter: &mut Interpreter<'_>, value: Value) -> Result<()> {
+        self.write(interpreter, value)
+    }
+}
@@ -34,6 +34,8 @@ use crate::transpile::{ExprFunctionCallResult, TranspileableExpr};
 use crate::transpile::{TranspilationInputContext, TranspilationOutput, Transpile, ValueKind};
 use anyhow::{Context, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, RE, RosyCM, VE};
 
 /// AST node for the `CM(expr)` type conversion function.
 #[derive(Debug)]
@@ -117,3 +119,9 @@ impl Transpile for ComplexConvertExpr {
         })
     }
 }
+impl Evaluate for ComplexConvertExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("CM", &value, x => RosyCM::rosy_cm(x)?, [RE, CM, VE, CD])
+    }
+}
@@ -33,6 +33,8 @@ use crate::transpile::{ExprFunctionCallResult, TranspileableExpr};
 use crate::transpile::{TranspilationInputContext, TranspilationOutput, Transpile, ValueKind};
 use anyhow::{Context, Error, Result, anyhow};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{LO, RE, RosyLO};
 
 /// AST node for the `LO(expr)` type conversion function.
 #[derive(Debug)]
@@ -111,3 +113,9 @@ impl Transpile for LogicalConvertExpr {
         })
     }
 }
+impl Evaluate for LogicalConvertExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("LO", &value, x => RosyLO::rosy_to_logical(x), [LO, RE])
+    }
+}
@@ -43,6 +43,8 @@ use crate::transpile::{ExprFunctionCallResult, TranspileableExpr};
 use crate::transpile::{TranspilationInputContext, TranspilationOutput, Transpile, ValueKind};
 use anyhow::{Context, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CM, DA, RE, RosyREConvert, ST, VE};
 
 /// AST node for the `RE(expr)` type conversion function.
 #[derive(Debug)]
@@ -126,3 +128,9 @@ impl Transpile for ReConvertExpr {
         })
     }
 }
+impl Evaluate for ReConvertExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("RE", &value, x => RosyREConvert::rosy_re_convert(x)?, [RE, CM, VE, ST, DA])
+    }
+}
@@ -37,6 +37,7 @@ use crate::transpile::{
     Transpile, TranspileableExpr, ValueKind,
 };
 use anyhow::{Context, Error, Result, anyhow};
+use crate::interpret::{Evaluate, Interpreter, Value};
 
 /// AST node for the `ST(expr)` type conversion function.
 #[derive(Debug)]
@@ -122,3 +123,8 @@ pub fn string_convert_transpile_helper(
         value_kind: ValueKind::Owned,
     })
 }
+impl Evaluate for StringConvertExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        Ok(Value::ST(self.expr.evaluate(interpreter)?.to_rosy_string()?))
+    }
+}
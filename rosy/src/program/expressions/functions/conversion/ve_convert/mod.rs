@@ -41,6 +41,8 @@ use crate::transpile::{ExprFunctionCallResult, TranspileableExpr};
 use crate::transpile::{TranspilationInputContext, TranspilationOutput, Transpile, ValueKind};
 use anyhow::{Context, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CM, RE, RosyVEConvert, VE};
 
 /// AST node for the `VE(expr)` type conversion function.
 #[derive(Debug)]
@@ -124,3 +126,9 @@ impl Transpile for VeConvertExpr {
         })
     }
 }
+impl Evaluate for VeConvertExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("VE", &value, x => RosyVEConvert::rosy_ve_convert(x)?, [RE, CM, VE])
+    }
+}
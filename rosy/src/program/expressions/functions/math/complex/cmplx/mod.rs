@@ -44,6 +44,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyCMPLX};
 
 /// AST node for the `CMPLX(expr)` intrinsic function.
 #[derive(Debug)]
@@ -118,3 +120,9 @@ impl TranspileableExpr for CmplxExpr {
         ExprRecipe::Unknown(None)
     }
 }
+impl Evaluate for CmplxExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("CMPLX", &value, x => RosyCMPLX::rosy_cmplx(x)?, [RE, CM, DA, CD])
+    }
+}
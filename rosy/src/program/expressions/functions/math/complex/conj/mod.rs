@@ -43,6 +43,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, RE, RosyCONJ};
 
 /// AST node for the `CONJ(expr)` intrinsic function.
 #[derive(Debug)]
@@ -115,3 +117,9 @@ impl TranspileableExpr for ConjExpr {
         ExprRecipe::TypePreserving(Box::new(resolver.build_expr_recipe(&self.expr, ctx, deps)))
     }
 }
+impl Evaluate for ConjExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("CONJ", &value, x => RosyCONJ::rosy_conj(x)?, [RE, CM, CD])
+    }
+}
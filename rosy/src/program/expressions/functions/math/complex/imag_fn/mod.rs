@@ -44,6 +44,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyIMAG};
 
 /// AST node for the `IMAG(expr)` intrinsic function.
 #[derive(Debug)]
@@ -120,3 +122,9 @@ impl TranspileableExpr for ImagFnExpr {
         ExprRecipe::ImagFn(Box::new(inner))
     }
 }
+impl Evaluate for ImagFnExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("IMAG", &value, x => RosyIMAG::rosy_imag(x)?, [RE, CM, DA, CD])
+    }
+}
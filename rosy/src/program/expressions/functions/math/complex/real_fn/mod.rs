@@ -44,6 +44,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyREAL};
 
 /// AST node for the `REAL(expr)` intrinsic function.
 #[derive(Debug)]
@@ -120,3 +122,9 @@ impl TranspileableExpr for RealFnExpr {
         ExprRecipe::RealFn(Box::new(inner))
     }
 }
+impl Evaluate for RealFnExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("REAL", &value, x => RosyREAL::rosy_real(x)?, [RE, CM, DA, CD])
+    }
+}
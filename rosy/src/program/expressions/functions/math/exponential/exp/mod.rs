@@ -44,6 +44,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyEXP, VE};
 
 /// AST node for the `EXP(expr)` intrinsic function (exponential e^x).
 #[derive(Debug)]
@@ -124,3 +126,9 @@ impl TranspileableExpr for ExpExpr {
         ExprRecipe::TypePreserving(Box::new(resolver.build_expr_recipe(&self.expr, ctx, deps)))
     }
 }
+impl Evaluate for ExpExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("EXP", &value, x => RosyEXP::rosy_exp(x)?, [RE, CM, VE, DA, CD])
+    }
+}
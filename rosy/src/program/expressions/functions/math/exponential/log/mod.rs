@@ -16,6 +16,7 @@
 //! | CM | CM |
 //! | VE | VE |
 //! | DA | DA |
+//! | CD | CD |
 //!
 //! ## Rosy Example
 //! ```text
@@ -44,6 +45,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyLOG, VE};
 
 /// AST node for the `LOG(expr)` intrinsic function (natural logarithm ln(x)).
 #[derive(Debug)]
@@ -124,3 +127,9 @@ impl TranspileableExpr for LogExpr {
         ExprRecipe::TypePreserving(Box::new(resolver.build_expr_recipe(&self.expr, ctx, deps)))
     }
 }
+impl Evaluate for LogExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("LOG", &value, x => RosyLOG::rosy_log(x)?, [RE, CM, VE, DA, CD])
+    }
+}
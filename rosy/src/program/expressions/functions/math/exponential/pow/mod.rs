@@ -13,7 +13,14 @@
 //! | Left | Right | Result | Comment |
 //! |------|-------|--------|---------|
 //! | RE | RE | RE | |
+//! | CM | RE | CM | |
+//! | CM | CM | CM | |
 //! | VE | RE | VE | Raise to Real power componentwise |
+//! | DA | RE | DA | Constant part must be positive for non-integer powers |
+//! | CD | RE | CD | Constant part must be nonzero for non-integer powers |
+//!
+//! Integer powers of DA/CD use repeated squaring at the current truncation
+//! order, so `X^3` matches `X*X*X` exactly.
 //!
 //! ## Rosy Example
 //! ```text
@@ -41,6 +48,8 @@ use crate::rosy_lib::RosyType;
 use crate::transpile::{ExprFunctionCallResult, TranspileableExpr};
 use crate::transpile::{TranspilationInputContext, TranspilationOutput, Transpile, ValueKind};
 use anyhow::{Error, Result, anyhow};
+use crate::interpret::{Evaluate, Interpreter, Value, binary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyPow, VE};
 
 /// AST node for the power/exponentiation operator (`^`).
 #[derive(Debug)]
@@ -155,3 +164,12 @@ impl Transpile for PowExpr {
         }
     }
 }
+impl Evaluate for PowExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let left = self.left.evaluate(interpreter)?;
+        let right = self.right.evaluate(interpreter)?;
+        binary!("^", &left, &right, (l, r) => RosyPow::rosy_pow(l, r)?, [
+            (RE, RE), (CM, RE), (VE, RE), (DA, RE), (CD, RE), (CM, CM),
+        ])
+    }
+}
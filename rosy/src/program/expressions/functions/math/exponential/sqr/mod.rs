@@ -44,6 +44,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosySQR, VE};
 
 /// AST node for the `SQR(expr)` intrinsic function (square root).
 #[derive(Debug)]
@@ -126,3 +128,9 @@ impl TranspileableExpr for SqrExpr {
         ExprRecipe::TypePreserving(Box::new(resolver.build_expr_recipe(&self.expr, ctx, deps)))
     }
 }
+impl Evaluate for SqrExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("SQR", &value, x => RosySQR::rosy_sqr(x)?, [RE, CM, VE, DA, CD])
+    }
+}
@@ -16,6 +16,7 @@
 //! | CM | CM |
 //! | VE | VE |
 //! | DA | DA |
+//! | CD | CD |
 //!
 //! ## Rosy Example
 //! ```text
@@ -44,6 +45,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosySQRT, VE};
 
 /// AST node for the `SQRT(expr)` intrinsic function (square root).
 #[derive(Debug)]
@@ -124,3 +127,9 @@ impl TranspileableExpr for SqrtExpr {
         ExprRecipe::TypePreserving(Box::new(resolver.build_expr_recipe(&self.expr, ctx, deps)))
     }
 }
+impl Evaluate for SqrtExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("SQRT", &value, x => RosySQRT::rosy_sqrt(x)?, [RE, CM, VE, DA, CD])
+    }
+}
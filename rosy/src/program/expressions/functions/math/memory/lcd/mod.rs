@@ -43,6 +43,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{RosyLCD, VE};
 
 /// LCD(ve) — DA memory size estimator (COSY compatibility).
 /// Takes a VE with (order & num_vars) and returns estimated DA memory size.
@@ -109,3 +111,9 @@ impl TranspileableExpr for LcdExpr {
         ExprRecipe::Literal(RosyType::RE())
     }
 }
+impl Evaluate for LcdExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("LCD", &value, x => RosyLCD::rosy_lcd(x), [VE])
+    }
+}
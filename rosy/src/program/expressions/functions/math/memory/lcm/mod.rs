@@ -42,6 +42,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{RE, RosyLCM};
 
 /// LCM(n) — Complex memory size estimator (COSY compatibility).
 /// Returns `2*n` as RE. Rosy doesn't need memory management.
@@ -107,3 +109,9 @@ impl TranspileableExpr for LcmExpr {
         ExprRecipe::Literal(RosyType::RE())
     }
 }
+impl Evaluate for LcmExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("LCM", &value, x => RosyLCM::rosy_lcm(x), [RE])
+    }
+}
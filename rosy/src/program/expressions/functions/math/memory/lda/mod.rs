@@ -43,6 +43,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{RosyLDA, VE};
 
 /// LDA(ve) — DA memory size estimator (COSY compatibility).
 /// Takes a VE with (order & num_vars) and returns estimated DA memory size.
@@ -109,3 +111,9 @@ impl TranspileableExpr for LdaExpr {
         ExprRecipe::Literal(RosyType::RE())
     }
 }
+impl Evaluate for LdaExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("LDA", &value, x => RosyLDA::rosy_lda(x), [VE])
+    }
+}
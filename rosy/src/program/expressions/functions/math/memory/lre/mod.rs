@@ -45,6 +45,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{RE, RosyLRE};
 
 /// LRE(n) — Real memory size estimator (COSY compatibility).
 /// Returns `1` as RE. A real always takes 1 unit of allocation.
@@ -111,3 +113,9 @@ impl TranspileableExpr for LreExpr {
         ExprRecipe::Literal(RosyType::RE())
     }
 }
+impl Evaluate for LreExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("LRE", &value, x => RosyLRE::rosy_lre(x), [RE])
+    }
+}
@@ -45,6 +45,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{RE, RosyLST};
 
 /// LST(n) — String memory size estimator (COSY compatibility).
 /// Returns `n` as RE. Rosy doesn't need memory management, but returns
@@ -111,3 +113,9 @@ impl TranspileableExpr for LstExpr {
         ExprRecipe::Literal(RosyType::RE())
     }
 }
+impl Evaluate for LstExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("LST", &value, x => RosyLST::rosy_lst(x), [RE])
+    }
+}
@@ -46,6 +46,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{RE, RosyLVE};
 
 /// LVE(n) — Vector memory size estimator (COSY compatibility).
 /// Returns `n` as RE. A vector of n components takes n units of allocation.
@@ -112,3 +114,9 @@ impl TranspileableExpr for LveExpr {
         ExprRecipe::Literal(RosyType::RE())
     }
 }
+impl Evaluate for LveExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("LVE", &value, x => RosyLVE::rosy_lve(x), [RE])
+    }
+}
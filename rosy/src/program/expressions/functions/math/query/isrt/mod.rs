@@ -13,8 +13,10 @@
 //! | Input | Result |
 //! |-------|--------|
 //! | RE | RE |
+//! | CM | CM |
 //! | VE | VE |
 //! | DA | DA |
+//! | CD | CD |
 //!
 //! ## Rosy Example
 //! ```text
@@ -43,6 +45,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyISRT, VE};
 
 /// AST node for the `ISRT(expr)` intrinsic function.
 #[derive(Debug)]
@@ -115,3 +119,9 @@ impl TranspileableExpr for IsrtExpr {
         ExprRecipe::Unknown(None)
     }
 }
+impl Evaluate for IsrtExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("ISRT", &value, x => RosyISRT::rosy_isrt(x)?, [RE, CM, VE, DA, CD])
+    }
+}
@@ -43,6 +43,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{DA, RE, RosyISRT3, VE};
 
 /// AST node for the `ISRT3(expr)` intrinsic function.
 #[derive(Debug)]
@@ -115,3 +117,9 @@ impl TranspileableExpr for Isrt3Expr {
         ExprRecipe::Unknown(None)
     }
 }
+impl Evaluate for Isrt3Expr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("ISRT3", &value, x => RosyISRT3::rosy_isrt3(x)?, [RE, VE, DA])
+    }
+}
@@ -48,6 +48,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, GR, LO, RE, RosyTYPE, ST, VE};
 
 /// AST node for the `TYPE(expr)` intrinsic function.
 #[derive(Debug)]
@@ -116,3 +118,9 @@ impl TranspileableExpr for TypeFnExpr {
         ExprRecipe::Literal(RosyType::RE())
     }
 }
+impl Evaluate for TypeFnExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("TYPE", &value, x => RosyTYPE::rosy_type(x)?, [RE, ST, LO, CM, VE, DA, CD, GR])
+    }
+}
@@ -45,6 +45,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyABS, VE};
 
 /// AST node for the `ABS(expr)` intrinsic function (absolute value).
 #[derive(Debug)]
@@ -123,3 +125,9 @@ impl TranspileableExpr for AbsExpr {
         ExprRecipe::Literal(RosyType::RE())
     }
 }
+impl Evaluate for AbsExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("ABS", &value, x => RosyABS::rosy_abs(x)?, [RE, CM, VE, DA, CD])
+    }
+}
@@ -45,6 +45,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyCONS, VE};
 
 /// AST node for the `CONS(expr)` intrinsic function.
 #[derive(Debug)]
@@ -129,3 +131,9 @@ impl TranspileableExpr for ConsExpr {
         ExprRecipe::Unknown(None)
     }
 }
+impl Evaluate for ConsExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("CONS", &value, x => RosyCONS::rosy_cons(x)?, [RE, CM, VE, DA, CD])
+    }
+}
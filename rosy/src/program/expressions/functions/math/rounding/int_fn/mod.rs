@@ -42,6 +42,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{RE, RosyINT, VE};
 
 /// AST node for the `INT(expr)` intrinsic function (truncate toward zero).
 #[derive(Debug)]
@@ -120,3 +122,9 @@ impl TranspileableExpr for IntExpr {
         ExprRecipe::Unknown(None)
     }
 }
+impl Evaluate for IntExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("INT", &value, x => RosyINT::rosy_int(x)?, [RE, VE])
+    }
+}
@@ -42,6 +42,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{RE, RosyNINT, VE};
 
 /// AST node for the `NINT(expr)` intrinsic function (round to nearest integer).
 #[derive(Debug)]
@@ -120,3 +122,9 @@ impl TranspileableExpr for NintExpr {
         ExprRecipe::Unknown(None)
     }
 }
+impl Evaluate for NintExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("NINT", &value, x => RosyNINT::rosy_nint(x)?, [RE, VE])
+    }
+}
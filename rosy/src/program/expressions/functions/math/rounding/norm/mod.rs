@@ -43,6 +43,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, DA, RosyNORM, VE};
 
 /// AST node for the `NORM(expr)` intrinsic function.
 #[derive(Debug)]
@@ -127,3 +129,13 @@ impl TranspileableExpr for NormExpr {
         ExprRecipe::Unknown(None)
     }
 }
+impl Evaluate for NormExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        // rosy_lib has no NORM for RE; the transpiler uses `abs` too
+        if let Value::RE(x) = value {
+            return Ok(Value::RE(x.abs()));
+        }
+        unary!("NORM", &value, x => RosyNORM::rosy_norm(x)?, [VE, DA, CD])
+    }
+}
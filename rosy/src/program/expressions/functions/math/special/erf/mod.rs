@@ -42,6 +42,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{DA, RE, RosyERF};
 
 /// AST node for the `ERF(expr)` intrinsic function (real error function).
 #[derive(Debug)]
@@ -116,3 +118,9 @@ impl TranspileableExpr for ErfExpr {
         ExprRecipe::TypePreserving(Box::new(resolver.build_expr_recipe(&self.expr, ctx, deps)))
     }
 }
+impl Evaluate for ErfExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("ERF", &value, x => RosyERF::rosy_erf(x)?, [RE, DA])
+    }
+}
@@ -42,6 +42,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, RosyWERF};
 
 /// AST node for the `WERF(expr)` intrinsic function (Faddeeva function).
 #[derive(Debug)]
@@ -116,3 +118,9 @@ impl TranspileableExpr for WerfExpr {
         ExprRecipe::TypePreserving(Box::new(resolver.build_expr_recipe(&self.expr, ctx, deps)))
     }
 }
+impl Evaluate for WerfExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("WERF", &value, x => RosyWERF::rosy_werf(x)?, [CM, CD])
+    }
+}
@@ -13,8 +13,10 @@
 //! | Input | Result |
 //! |-------|--------|
 //! | RE | RE |
+//! | CM | CM |
 //! | VE | VE |
 //! | DA | DA |
+//! | CD | CD |
 //!
 //! ## Rosy Example
 //! ```text
@@ -43,6 +45,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyASIN, VE};
 
 /// AST node for the `ASIN(expr)` intrinsic function.
 #[derive(Debug)]
@@ -121,3 +125,9 @@ impl TranspileableExpr for AsinExpr {
         ExprRecipe::TypePreserving(Box::new(resolver.build_expr_recipe(&self.expr, ctx, deps)))
     }
 }
+impl Evaluate for AsinExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("ASIN", &value, x => RosyASIN::rosy_asin(x)?, [RE, CM, VE, DA, CD])
+    }
+}
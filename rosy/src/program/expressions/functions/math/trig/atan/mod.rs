@@ -13,8 +13,10 @@
 //! | Input | Result |
 //! |-------|--------|
 //! | RE | RE |
+//! | CM | CM |
 //! | VE | VE |
 //! | DA | DA |
+//! | CD | CD |
 //!
 //! ## Rosy Example
 //! ```text
@@ -43,6 +45,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyATAN, VE};
 
 /// AST node for the `ATAN(expr)` intrinsic function.
 #[derive(Debug)]
@@ -121,3 +125,9 @@ impl TranspileableExpr for AtanExpr {
         ExprRecipe::TypePreserving(Box::new(resolver.build_expr_recipe(&self.expr, ctx, deps)))
     }
 }
+impl Evaluate for AtanExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("ATAN", &value, x => RosyATAN::rosy_atan(x)?, [RE, CM, VE, DA, CD])
+    }
+}
@@ -44,6 +44,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyCOS, VE};
 
 /// AST node for the `COS(expr)` intrinsic function.
 #[derive(Debug)]
@@ -126,3 +128,9 @@ impl TranspileableExpr for CosExpr {
         ExprRecipe::TypePreserving(Box::new(resolver.build_expr_recipe(&self.expr, ctx, deps)))
     }
 }
+impl Evaluate for CosExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("COS", &value, x => RosyCOS::rosy_cos(x)?, [RE, CM, VE, DA, CD])
+    }
+}
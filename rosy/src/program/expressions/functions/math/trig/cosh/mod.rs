@@ -16,6 +16,7 @@
 //! | CM | CM |
 //! | VE | VE |
 //! | DA | DA |
+//! | CD | CD |
 //!
 //! ## Rosy Example
 //! ```text
@@ -44,6 +45,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyCOSH, VE};
 
 /// AST node for the `COSH(expr)` intrinsic function.
 #[derive(Debug)]
@@ -122,3 +125,9 @@ impl TranspileableExpr for CoshExpr {
         ExprRecipe::TypePreserving(Box::new(resolver.build_expr_recipe(&self.expr, ctx, deps)))
     }
 }
+impl Evaluate for CoshExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("COSH", &value, x => RosyCOSH::rosy_cosh(x)?, [RE, CM, VE, DA, CD])
+    }
+}
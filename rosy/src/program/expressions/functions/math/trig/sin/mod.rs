@@ -44,6 +44,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosySIN, VE};
 
 /// AST node for the `SIN(expr)` intrinsic function.
 #[derive(Debug)]
@@ -127,3 +129,9 @@ impl TranspileableExpr for SinExpr {
         ExprRecipe::TypePreserving(Box::new(inner))
     }
 }
+impl Evaluate for SinExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("SIN", &value, x => RosySIN::rosy_sin(x)?, [RE, CM, VE, DA, CD])
+    }
+}
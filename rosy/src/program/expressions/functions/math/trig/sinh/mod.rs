@@ -16,6 +16,7 @@
 //! | CM | CM |
 //! | VE | VE |
 //! | DA | DA |
+//! | CD | CD |
 //!
 //! ## Rosy Example
 //! ```text
@@ -44,6 +45,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosySINH, VE};
 
 /// AST node for the `SINH(expr)` intrinsic function.
 #[derive(Debug)]
@@ -122,3 +125,9 @@ impl TranspileableExpr for SinhExpr {
         ExprRecipe::TypePreserving(Box::new(resolver.build_expr_recipe(&self.expr, ctx, deps)))
     }
 }
+impl Evaluate for SinhExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("SINH", &value, x => RosySINH::rosy_sinh(x)?, [RE, CM, VE, DA, CD])
+    }
+}
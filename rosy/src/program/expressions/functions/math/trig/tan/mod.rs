@@ -13,8 +13,10 @@
 //! | Input | Result |
 //! |-------|--------|
 //! | RE | RE |
+//! | CM | CM |
 //! | VE | VE |
 //! | DA | DA |
+//! | CD | CD |
 //!
 //! ## Rosy Example
 //! ```text
@@ -43,6 +45,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyTAN, VE};
 
 /// AST node for the `TAN(expr)` intrinsic function.
 #[derive(Debug)]
@@ -123,3 +127,9 @@ impl TranspileableExpr for TanExpr {
         ExprRecipe::TypePreserving(Box::new(resolver.build_expr_recipe(&self.expr, ctx, deps)))
     }
 }
+impl Evaluate for TanExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("TAN", &value, x => RosyTAN::rosy_tan(x)?, [RE, CM, VE, DA, CD])
+    }
+}
@@ -13,8 +13,10 @@
 //! | Input | Result |
 //! |-------|--------|
 //! | RE | RE |
+//! | CM | CM |
 //! | VE | VE |
 //! | DA | DA |
+//! | CD | CD |
 //!
 //! Note: CM is NOT supported for TANH in COSY.
 //!
@@ -45,6 +47,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyTANH, VE};
 
 /// AST node for the `TANH(expr)` intrinsic function.
 #[derive(Debug)]
@@ -123,3 +127,9 @@ impl TranspileableExpr for TanhExpr {
         ExprRecipe::TypePreserving(Box::new(resolver.build_expr_recipe(&self.expr, ctx, deps)))
     }
 }
+impl Evaluate for TanhExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("TANH", &value, x => RosyTANH::rosy_tanh(x)?, [RE, CM, VE, DA, CD])
+    }
+}
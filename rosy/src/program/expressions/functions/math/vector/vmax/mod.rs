@@ -41,6 +41,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{RosyVMAX, VE};
 
 /// AST node for the `VMAX(expr)` function (vector maximum).
 #[derive(Debug)]
@@ -116,3 +118,9 @@ impl TranspileableExpr for VmaxExpr {
         ExprRecipe::Literal(RosyType::RE())
     }
 }
+impl Evaluate for VmaxExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("VMAX", &value, x => RosyVMAX::rosy_vmax(x)?, [VE])
+    }
+}
@@ -41,6 +41,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{RosyVMIN, VE};
 
 /// AST node for the `VMIN(expr)` function (vector minimum).
 #[derive(Debug)]
@@ -116,3 +118,9 @@ impl TranspileableExpr for VminExpr {
         ExprRecipe::Literal(RosyType::RE())
     }
 }
+impl Evaluate for VminExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("VMIN", &value, x => RosyVMIN::rosy_vmin(x)?, [VE])
+    }
+}
@@ -20,6 +20,7 @@
 //! | VE | RE |
 //! | DA | RE |
 //! | CD | RE |
+//! | GR | RE |
 //!
 //! ## Rosy Example
 //! ```text
@@ -48,6 +49,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, GR, LO, RE, RosyLENGTH, ST, VE};
 
 /// AST node for the `LENGTH(expr)` system function.
 #[derive(Debug)]
@@ -124,3 +127,9 @@ impl TranspileableExpr for LengthExpr {
         ExprRecipe::Literal(RosyType::RE())
     }
 }
+impl Evaluate for LengthExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("LENGTH", &value, x => RosyLENGTH::rosy_length(x), [RE, ST, LO, CM, VE, DA, CD, GR])
+    }
+}
@@ -41,6 +41,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{RosyLTRIM, ST};
 
 /// AST node for the `LTRIM(expr)` function.
 #[derive(Debug)]
@@ -113,3 +115,9 @@ impl TranspileableExpr for LtrimExpr {
         ExprRecipe::Literal(RosyType::ST())
     }
 }
+impl Evaluate for LtrimExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("LTRIM", &value, x => RosyLTRIM::rosy_ltrim(x)?, [ST])
+    }
+}
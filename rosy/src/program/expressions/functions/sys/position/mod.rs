@@ -25,6 +25,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value};
+use crate::rosy_lib::{RosyPOSITION, ST};
 
 /// AST node for the `POSITION(haystack, needle)` system function.
 #[derive(Debug)]
@@ -126,3 +128,10 @@ impl TranspileableExpr for PositionExpr {
         ExprRecipe::Literal(RosyType::RE())
     }
 }
+impl Evaluate for PositionExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let haystack: ST = interpreter.evaluate_as(&self.haystack)?;
+        let needle: ST = interpreter.evaluate_as(&self.needle)?;
+        Ok(Value::RE(haystack.rosy_position(&needle)))
+    }
+}
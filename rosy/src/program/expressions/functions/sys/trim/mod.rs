@@ -41,6 +41,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{RosyTRIM, ST};
 
 /// AST node for the `TRIM(expr)` function.
 #[derive(Debug)]
@@ -113,3 +115,9 @@ impl TranspileableExpr for TrimExpr {
         ExprRecipe::Literal(RosyType::ST())
     }
 }
+impl Evaluate for TrimExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("TRIM", &value, x => RosyTRIM::rosy_trim(x)?, [ST])
+    }
+}
@@ -49,6 +49,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, CM, DA, LO, RE, RosyVARMEM, ST, VE};
 
 /// AST node for the `VARMEM(expr)` system function.
 #[derive(Debug)]
@@ -125,3 +127,9 @@ impl TranspileableExpr for VarmemExpr {
         ExprRecipe::Literal(RosyType::RE())
     }
 }
+impl Evaluate for VarmemExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let value = self.expr.evaluate(interpreter)?;
+        unary!("VARMEM", &value, x => RosyVARMEM::rosy_varmem(x), [RE, ST, LO, CM, VE, DA, CD])
+    }
+}
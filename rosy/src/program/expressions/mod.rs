@@ -42,6 +42,7 @@ pub mod types;
 use std::collections::HashSet;
 use crate::{ast::{FromRule, PRATT_PARSER, Rule}, resolve::{ExprRecipe, ScopeContext, TypeResolver, TypeSlot}, rosy_lib::RosyType, transpile::{TranspileableExpr, ExprFunctionCallResult, add_context_to_all}};
 use crate::transpile::{Transpile, TranspilationInputContext, TranspilationOutput};
+use crate::interpret::{Evaluate, Interpreter, Value};
 
 use crate::program::expressions::core::var_expr::VarExpr;
 
@@ -782,6 +783,14 @@ impl TranspileableExpr for Expr {
         self.inner.try_inplace_append(target_var, context)
     }
 }
+impl Evaluate for Expr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        self.inner.evaluate(interpreter)
+    }
+    fn store(&self, interpreter: &mut Interpreter<'_>, value: Value) -> Result<()> {
+        self.inner.store(interpreter, value)
+    }
+}
 impl Transpile for Expr {
     fn transpile (
         &self, context: &mut TranspilationInputContext
@@ -60,6 +60,8 @@ use crate::rosy_lib::RosyType;
 use crate::transpile::{ExprFunctionCallResult, TranspileableExpr};
 use crate::transpile::{TranspilationInputContext, TranspilationOutput, Transpile, ValueKind};
 use anyhow::{Error, Result, anyhow};
+use crate::interpret::{Evaluate, Interpreter, Value, binary};
+use crate::rosy_lib::{CD, CM, DA, LO, RE, RosyAdd, VE};
 
 /// AST node for the binary addition operator (`+`).
 ///
@@ -194,3 +196,14 @@ impl Transpile for AddExpr {
         }
     }
 }
+impl Evaluate for AddExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let left = self.left.evaluate(interpreter)?;
+        let right = self.right.evaluate(interpreter)?;
+        binary!("+", &left, &right, (l, r) => RosyAdd::rosy_add(l, r)?, [
+            (RE, RE), (CM, RE), (DA, RE), (CD, RE), (VE, RE), (RE, CM), (CM, CM), (DA, CM),
+            (CD, CM), (RE, DA), (CM, DA), (DA, DA), (CD, DA), (RE, CD), (CM, CD), (DA, CD),
+            (CD, CD), (RE, VE), (VE, VE), (LO, LO),
+        ])
+    }
+}
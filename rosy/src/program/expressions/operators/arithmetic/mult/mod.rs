@@ -59,6 +59,8 @@ use crate::transpile::{ExprFunctionCallResult, TranspileableExpr};
 use crate::transpile::{TranspilationInputContext, TranspilationOutput, Transpile, ValueKind};
 use anyhow::{Error, Result, anyhow};
 use std::collections::{BTreeSet, HashSet};
+use crate::interpret::{Evaluate, Interpreter, Value, binary};
+use crate::rosy_lib::{CD, CM, DA, LO, RE, RosyMult, VE};
 
 /// AST node for the binary multiplication operator (`*`).
 #[derive(Debug)]
@@ -194,3 +196,14 @@ impl Transpile for MultExpr {
         }
     }
 }
+impl Evaluate for MultExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let left = self.left.evaluate(interpreter)?;
+        let right = self.right.evaluate(interpreter)?;
+        binary!("*", &left, &right, (l, r) => RosyMult::rosy_mult(l, r)?, [
+            (RE, RE), (CM, RE), (DA, RE), (CD, RE), (VE, RE), (RE, CM), (CM, CM), (DA, CM),
+            (CD, CM), (RE, DA), (CM, DA), (DA, DA), (CD, DA), (RE, CD), (CM, CD), (DA, CD),
+            (CD, CD), (RE, VE), (VE, VE), (LO, LO),
+        ])
+    }
+}
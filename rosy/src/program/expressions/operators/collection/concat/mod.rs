@@ -1,6 +1,7 @@
 //! # Concatenation Operator (`&`)
 //!
-//! Concatenates scalars and vectors into larger vectors, or strings together.
+//! Concatenates scalars and vectors into larger vectors, strings together,
+//! or graphics objects into one picture.
 //!
 //! ## Syntax
 //!
@@ -17,6 +18,7 @@
 //! | ST | ST | ST | Concatenate two Strings |
 //! | VE | RE | VE | Append a Real to the right of a Vector |
 //! | VE | VE | VE | Concatenate two Vectors |
+//! | GR | GR | GR | Append the primitives of the right GR to the left |
 //!
 //! ## Rosy Example
 //! ```text
@@ -46,6 +48,8 @@ use crate::transpile::{
 use anyhow::{Context, Error, Result};
 use std::collections::BTreeSet;
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value, binary};
+use crate::rosy_lib::{CD, DA, GR, RE, RosyConcat, ST, VE};
 
 /// AST node for the concatenation operator (`&`).
 #[derive(Debug)]
@@ -216,3 +220,14 @@ impl Transpile for ConcatExpr {
         })
     }
 }
+impl Evaluate for ConcatExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let left = self.left.evaluate(interpreter)?;
+        let right = self.right.evaluate(interpreter)?;
+        binary!("&", &left, &right, (l, r) => RosyConcat::rosy_concat(l, r)?, [
+            (RE, RE), (RE, VE), (ST, ST), (VE, RE), (VE, VE), (DA, DA), (DA, Vec<DA>),
+            (Vec<DA>, DA), (Vec<DA>, Vec<DA>), (CD, CD), (CD, Vec<CD>), (Vec<CD>, CD),
+            (Vec<CD>, Vec<CD>), (GR, GR),
+        ])
+    }
+}
@@ -42,6 +42,8 @@ use crate::transpile::{
 };
 use anyhow::{Context as AnyhowContext, Error, Result};
 use std::collections::{BTreeSet, HashSet};
+use crate::interpret::{Evaluate, Interpreter, Value, unary};
+use crate::rosy_lib::{CD, DA, RE, RosyDerive};
 
 /// DA%n = partial derivative w.r.t. variable n (positive n)
 /// DA%(-n) = anti-derivative (integral) w.r.t. variable n (negative n)
@@ -121,3 +123,10 @@ impl TranspileableExpr for DeriveExpr {
         }
     }
 }
+impl Evaluate for DeriveExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let object = self.object.evaluate(interpreter)?;
+        let index: RE = interpreter.evaluate_as(&self.index)?;
+        unary!("%", &object, x => RosyDerive::rosy_derive(x, index as i64)?, [DA, CD])
+    }
+}
@@ -47,7 +47,9 @@ use crate::resolve::{BinaryOpKind, ExprRecipe, ScopeContext, TypeResolver, TypeS
 use crate::rosy_lib::RosyType;
 use crate::transpile::{ExprFunctionCallResult, TranspileableExpr};
 use crate::transpile::{TranspilationInputContext, TranspilationOutput, Transpile, ValueKind};
-use anyhow::{Error, Result};
+use anyhow::{Context, Error, Result};
+use crate::interpret::{Evaluate, Interpreter, Value, binary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosyExtract, ST, VE};
 
 /// AST node for the extraction operator (`|`).
 #[derive(Debug)]
@@ -169,3 +171,16 @@ impl Transpile for ExtractExpr {
         }
     }
 }
+impl Evaluate for ExtractExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let object = self.object.evaluate(interpreter)?;
+        let index = self.index.evaluate(interpreter)?;
+        let extract = || -> Result<Value> {
+            binary!("|", &object, &index, (o, i) => RosyExtract::rosy_extract(o, i)?, [
+                (ST, RE), (CM, RE), (VE, RE), (DA, RE), (CD, RE),
+                (ST, VE), (VE, VE), (DA, VE), (CD, VE),
+            ])
+        };
+        extract().context("...while trying to extract an element")
+    }
+}
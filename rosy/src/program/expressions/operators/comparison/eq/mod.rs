@@ -13,6 +13,20 @@
 //! | Left | Right | Result | Comment |
 //! |------|-------|--------|---------|
 //! | RE | RE | LO | Equality with epsilon tolerance |
+//! | RE | DA | LO | Compares the constant part |
+//! | DA | RE | LO | Compares the constant part |
+//! | DA | DA | LO | Compares the constant parts |
+//! | CD | RE | LO | Compares the constant part |
+//! | RE | CD | LO | Compares the constant part |
+//! | CD | CD | LO | Compares the constant parts |
+//! | CD | CM | LO | Compares the constant part |
+//! | CM | CD | LO | Compares the constant part |
+//! | CM | RE | LO | Complex equality with epsilon tolerance |
+//! | RE | CM | LO | Complex equality with epsilon tolerance |
+//! | CM | CM | LO | Complex equality with epsilon tolerance |
+//! | RE | VE | LO | Every component |
+//! | VE | RE | LO | Every component |
+//! | VE | VE | LO | Same length and every component |
 //! | ST | ST | LO | String equality |
 //! | LO | LO | LO | Logical equality |
 //!
@@ -38,11 +52,13 @@ use std::collections::HashSet;
 
 use crate::ast::{FromRule, Rule};
 use crate::program::expressions::Expr;
-use crate::resolve::{ExprRecipe, ScopeContext, TypeResolver, TypeSlot};
+use crate::resolve::{BinaryOpKind, ExprRecipe, ScopeContext, TypeResolver, TypeSlot};
 use crate::rosy_lib::RosyType;
 use crate::transpile::{ExprFunctionCallResult, TranspileableExpr};
 use crate::transpile::{TranspilationInputContext, TranspilationOutput, Transpile, ValueKind};
 use anyhow::{Error, Result, anyhow};
+use crate::interpret::{Evaluate, Interpreter, Value, binary};
+use crate::rosy_lib::{CD, CM, DA, LO, RE, RosyEq, ST, VE};
 
 /// AST node for the equality operator (`=`).
 #[derive(Debug)]
@@ -83,11 +99,17 @@ impl TranspileableExpr for EqExpr {
     }
     fn build_expr_recipe(
         &self,
-        _resolver: &TypeResolver,
-        _ctx: &ScopeContext,
-        _deps: &mut HashSet<TypeSlot>,
+        resolver: &TypeResolver,
+        ctx: &ScopeContext,
+        deps: &mut HashSet<TypeSlot>,
     ) -> ExprRecipe {
-        ExprRecipe::Literal(RosyType::LO())
+        let left = resolver.build_expr_recipe(&self.left, ctx, deps);
+        let right = resolver.build_expr_recipe(&self.right, ctx, deps);
+        ExprRecipe::BinaryOp {
+            op: BinaryOpKind::Eq,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
     }
 }
 impl Transpile for EqExpr {
@@ -161,3 +183,14 @@ impl Transpile for EqExpr {
         }
     }
 }
+impl Evaluate for EqExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let left = self.left.evaluate(interpreter)?;
+        let right = self.right.evaluate(interpreter)?;
+        binary!("=", &left, &right, (l, r) => RosyEq::rosy_eq(l, r)?, [
+            (RE, RE), (ST, ST), (LO, LO), (DA, RE), (RE, DA), (DA, DA), (CD, RE), (RE, CD),
+            (CD, CD), (CD, CM), (CM, CD), (CM, RE), (RE, CM), (CM, CM), (VE, RE), (RE, VE),
+            (VE, VE),
+        ])
+    }
+}
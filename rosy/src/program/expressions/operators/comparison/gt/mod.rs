@@ -13,6 +13,12 @@
 //! | Left | Right | Result | Comment |
 //! |------|-------|--------|---------|
 //! | RE | RE | LO | Numeric greater-than |
+//! | RE | DA | LO | Compares the constant part |
+//! | DA | RE | LO | Compares the constant part |
+//! | DA | DA | LO | Compares the constant parts |
+//! | RE | VE | LO | Holds for every component |
+//! | VE | RE | LO | Holds for every component |
+//! | VE | VE | LO | Componentwise; lengths must match |
 //! | ST | ST | LO | Lexicographic ordering |
 //!
 //! ## Rosy Example
@@ -37,11 +43,13 @@ use std::collections::HashSet;
 
 use crate::ast::{FromRule, Rule};
 use crate::program::expressions::Expr;
-use crate::resolve::{ExprRecipe, ScopeContext, TypeResolver, TypeSlot};
+use crate::resolve::{BinaryOpKind, ExprRecipe, ScopeContext, TypeResolver, TypeSlot};
 use crate::rosy_lib::RosyType;
 use crate::transpile::{ExprFunctionCallResult, TranspileableExpr};
 use crate::transpile::{TranspilationInputContext, TranspilationOutput, Transpile, ValueKind};
 use anyhow::{Error, Result, anyhow};
+use crate::interpret::{Evaluate, Interpreter, Value, binary};
+use crate::rosy_lib::{DA, RE, RosyGt, ST, VE};
 
 /// AST node for the greater-than operator (`>`).
 #[derive(Debug)]
@@ -81,11 +89,17 @@ impl TranspileableExpr for GtExpr {
     }
     fn build_expr_recipe(
         &self,
-        _resolver: &TypeResolver,
-        _ctx: &ScopeContext,
-        _deps: &mut HashSet<TypeSlot>,
+        resolver: &TypeResolver,
+        ctx: &ScopeContext,
+        deps: &mut HashSet<TypeSlot>,
     ) -> ExprRecipe {
-        ExprRecipe::Literal(RosyType::LO())
+        let left = resolver.build_expr_recipe(&self.left, ctx, deps);
+        let right = resolver.build_expr_recipe(&self.right, ctx, deps);
+        ExprRecipe::BinaryOp {
+            op: BinaryOpKind::Gt,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
     }
 }
 impl Transpile for GtExpr {
@@ -154,3 +168,12 @@ impl Transpile for GtExpr {
         }
     }
 }
+impl Evaluate for GtExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let left = self.left.evaluate(interpreter)?;
+        let right = self.right.evaluate(interpreter)?;
+        binary!(">", &left, &right, (l, r) => RosyGt::rosy_gt(l, r)?, [
+            (RE, RE), (DA, RE), (VE, RE), (RE, DA), (DA, DA), (ST, ST), (RE, VE), (VE, VE),
+        ])
+    }
+}
@@ -13,6 +13,12 @@
 //! | Left | Right | Result | Comment |
 //! |------|-------|--------|---------|
 //! | RE | RE | LO | Numeric greater-than-or-equal |
+//! | RE | DA | LO | Compares the constant part |
+//! | DA | RE | LO | Compares the constant part |
+//! | DA | DA | LO | Compares the constant parts |
+//! | RE | VE | LO | Holds for every component |
+//! | VE | RE | LO | Holds for every component |
+//! | VE | VE | LO | Componentwise; lengths must match |
 //! | ST | ST | LO | Lexicographic ordering |
 //!
 //! ## Rosy Example
@@ -27,7 +33,7 @@
 use std::collections::BTreeSet;
 use std::collections::HashSet;
 
-use crate::resolve::{ExprRecipe, ScopeContext, TypeResolver, TypeSlot};
+use crate::resolve::{BinaryOpKind, ExprRecipe, ScopeContext, TypeResolver, TypeSlot};
 
 use crate::ast::{FromRule, Rule};
 use crate::program::expressions::Expr;
@@ -35,6 +41,8 @@ use crate::rosy_lib::RosyType;
 use crate::transpile::{ExprFunctionCallResult, TranspileableExpr};
 use crate::transpile::{TranspilationInputContext, TranspilationOutput, Transpile, ValueKind};
 use anyhow::{Error, Result, anyhow};
+use crate::interpret::{Evaluate, Interpreter, Value, binary};
+use crate::rosy_lib::{DA, RE, RosyGte, ST, VE};
 
 /// AST node for the greater-than-or-equal operator (`>=`).
 #[derive(Debug)]
@@ -74,11 +82,17 @@ impl TranspileableExpr for GteExpr {
     }
     fn build_expr_recipe(
         &self,
-        _resolver: &TypeResolver,
-        _ctx: &ScopeContext,
-        _deps: &mut HashSet<TypeSlot>,
+        resolver: &TypeResolver,
+        ctx: &ScopeContext,
+        deps: &mut HashSet<TypeSlot>,
     ) -> ExprRecipe {
-        ExprRecipe::Literal(RosyType::LO())
+        let left = resolver.build_expr_recipe(&self.left, ctx, deps);
+        let right = resolver.build_expr_recipe(&self.right, ctx, deps);
+        ExprRecipe::BinaryOp {
+            op: BinaryOpKind::Gte,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
     }
 }
 impl Transpile for GteExpr {
@@ -155,3 +169,12 @@ impl Transpile for GteExpr {
         }
     }
 }
+impl Evaluate for GteExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let left = self.left.evaluate(interpreter)?;
+        let right = self.right.evaluate(interpreter)?;
+        binary!(">=", &left, &right, (l, r) => RosyGte::rosy_gte(l, r)?, [
+            (RE, RE), (DA, RE), (VE, RE), (RE, DA), (DA, DA), (ST, ST), (RE, VE), (VE, VE),
+        ])
+    }
+}
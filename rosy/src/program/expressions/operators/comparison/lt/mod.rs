@@ -13,6 +13,12 @@
 //! | Left | Right | Result | Comment |
 //! |------|-------|--------|---------|
 //! | RE | RE | LO | Numeric less-than |
+//! | RE | DA | LO | Compares the constant part |
+//! | DA | RE | LO | Compares the constant part |
+//! | DA | DA | LO | Compares the constant parts |
+//! | RE | VE | LO | Holds for every component |
+//! | VE | RE | LO | Holds for every component |
+//! | VE | VE | LO | Componentwise; lengths must match |
 //! | ST | ST | LO | Lexicographic ordering |
 //!
 //! ## Rosy Example
@@ -35,7 +41,7 @@
 use std::collections::BTreeSet;
 use std::collections::HashSet;
 
-use crate::resolve::{ExprRecipe, ScopeContext, TypeResolver, TypeSlot};
+use crate::resolve::{BinaryOpKind, ExprRecipe, ScopeContext, TypeResolver, TypeSlot};
 
 use crate::ast::{FromRule, Rule};
 use crate::program::expressions::Expr;
@@ -43,6 +49,8 @@ use crate::rosy_lib::RosyType;
 use crate::transpile::{ExprFunctionCallResult, TranspileableExpr};
 use crate::transpile::{TranspilationInputContext, TranspilationOutput, Transpile, ValueKind};
 use anyhow::{Error, Result, anyhow};
+use crate::interpret::{Evaluate, Interpreter, Value, binary};
+use crate::rosy_lib::{DA, RE, RosyLt, ST, VE};
 
 /// AST node for the less-than operator (`<`).
 #[derive(Debug)]
@@ -82,11 +90,17 @@ impl TranspileableExpr for LtExpr {
     }
     fn build_expr_recipe(
         &self,
-        _resolver: &TypeResolver,
-        _ctx: &ScopeContext,
-        _deps: &mut HashSet<TypeSlot>,
+        resolver: &TypeResolver,
+        ctx: &ScopeContext,
+        deps: &mut HashSet<TypeSlot>,
     ) -> ExprRecipe {
-        ExprRecipe::Literal(RosyType::LO())
+        let left = resolver.build_expr_recipe(&self.left, ctx, deps);
+        let right = resolver.build_expr_recipe(&self.right, ctx, deps);
+        ExprRecipe::BinaryOp {
+            op: BinaryOpKind::Lt,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
     }
 }
 impl Transpile for LtExpr {
@@ -154,3 +168,12 @@ impl Transpile for LtExpr {
         }
     }
 }
+impl Evaluate for LtExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let left = self.left.evaluate(interpreter)?;
+        let right = self.right.evaluate(interpreter)?;
+        binary!("<", &left, &right, (l, r) => RosyLt::rosy_lt(l, r)?, [
+            (RE, RE), (DA, RE), (VE, RE), (RE, DA), (DA, DA), (ST, ST), (RE, VE), (VE, VE),
+        ])
+    }
+}
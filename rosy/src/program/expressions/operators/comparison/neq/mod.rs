@@ -13,6 +13,20 @@
 //! | Left | Right | Result | Comment |
 //! |------|-------|--------|---------|
 //! | RE | RE | LO | Not-equals with epsilon tolerance |
+//! | RE | DA | LO | Compares the constant part |
+//! | DA | RE | LO | Compares the constant part |
+//! | DA | DA | LO | Compares the constant parts |
+//! | CD | RE | LO | Compares the constant part |
+//! | RE | CD | LO | Compares the constant part |
+//! | CD | CD | LO | Compares the constant parts |
+//! | CD | CM | LO | Compares the constant part |
+//! | CM | CD | LO | Compares the constant part |
+//! | CM | RE | LO | Complex not-equals with epsilon tolerance |
+//! | RE | CM | LO | Complex not-equals with epsilon tolerance |
+//! | CM | CM | LO | Complex not-equals with epsilon tolerance |
+//! | RE | VE | LO | Any component differs |
+//! | VE | RE | LO | Any component differs |
+//! | VE | VE | LO | Lengths or any component differ |
 //! | ST | ST | LO | String not-equals |
 //! | LO | LO | LO | Logical not-equals |
 //!
@@ -36,7 +50,7 @@
 use std::collections::BTreeSet;
 use std::collections::HashSet;
 
-use crate::resolve::{ExprRecipe, ScopeContext, TypeResolver, TypeSlot};
+use crate::resolve::{BinaryOpKind, ExprRecipe, ScopeContext, TypeResolver, TypeSlot};
 
 use crate::ast::{FromRule, Rule};
 use crate::program::expressions::Expr;
@@ -44,6 +58,8 @@ use crate::rosy_lib::RosyType;
 use crate::transpile::{ExprFunctionCallResult, TranspileableExpr};
 use crate::transpile::{TranspilationInputContext, TranspilationOutput, Transpile, ValueKind};
 use anyhow::{Error, Result, anyhow};
+use crate::interpret::{Evaluate, Interpreter, Value, binary};
+use crate::rosy_lib::{CD, CM, DA, LO, RE, RosyNeq, ST, VE};
 
 /// AST node for the not-equal operator (`<>`).
 #[derive(Debug)]
@@ -84,11 +100,17 @@ impl TranspileableExpr for NeqExpr {
     }
     fn build_expr_recipe(
         &self,
-        _resolver: &TypeResolver,
-        _ctx: &ScopeContext,
-        _deps: &mut HashSet<TypeSlot>,
+        resolver: &TypeResolver,
+        ctx: &ScopeContext,
+        deps: &mut HashSet<TypeSlot>,
     ) -> ExprRecipe {
-        ExprRecipe::Literal(RosyType::LO())
+        let left = resolver.build_expr_recipe(&self.left, ctx, deps);
+        let right = resolver.build_expr_recipe(&self.right, ctx, deps);
+        ExprRecipe::BinaryOp {
+            op: BinaryOpKind::Neq,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
     }
 }
 impl Transpile for NeqExpr {
@@ -162,3 +184,14 @@ impl Transpile for NeqExpr {
         }
     }
 }
+impl Evaluate for NeqExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let left = self.left.evaluate(interpreter)?;
+        let right = self.right.evaluate(interpreter)?;
+        binary!("#", &left, &right, (l, r) => RosyNeq::rosy_neq(l, r)?, [
+            (RE, RE), (ST, ST), (LO, LO), (DA, RE), (RE, DA), (DA, DA), (CD, RE), (RE, CD),
+            (CD, CD), (CD, CM), (CM, CD), (CM, RE), (RE, CM), (CM, CM), (VE, RE), (RE, VE),
+            (VE, VE),
+        ])
+    }
+}
@@ -24,6 +24,8 @@ use crate::rosy_lib::RosyType;
 use crate::transpile::{ExprFunctionCallResult, TranspileableExpr};
 use crate::transpile::{TranspilationInputContext, TranspilationOutput, Transpile, ValueKind};
 use anyhow::{Error, Result, anyhow};
+use crate::interpret::{Evaluate, Interpreter, Value};
+use crate::rosy_lib::LO;
 
 /// AST node for the logical AND operator.
 #[derive(Debug)]
@@ -128,3 +130,9 @@ impl Transpile for AndExpr {
         }
     }
 }
+impl Evaluate for AndExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let left: LO = interpreter.evaluate_as(&self.left)?;
+        Ok(Value::LO(left && interpreter.evaluate_as::<LO>(&self.right)?))
+    }
+}
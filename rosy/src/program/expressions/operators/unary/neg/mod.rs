@@ -41,6 +41,8 @@ use crate::transpile::{
     Transpile, TranspileableExpr, ValueKind,
 };
 use anyhow::{Error, Result, anyhow};
+use crate::interpret::{Evaluate, Interpreter, Value, binary};
+use crate::rosy_lib::{CD, CM, DA, RE, RosySub, VE};
 
 /// Unary negation expression: `-expr`
 /// Transpiled as `0 - expr` using the existing subtraction operator.
@@ -129,3 +131,14 @@ impl Transpile for NegExpr {
         }
     }
 }
+impl Evaluate for NegExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let operand = self.operand.evaluate(interpreter)?;
+        if let Value::RE(x) = operand {
+            return Ok(Value::RE(-x));
+        }
+        binary!("-", &Value::RE(0.0), &operand, (l, r) => RosySub::rosy_sub(l, r)?, [
+            (RE, CM), (RE, DA), (RE, CD), (RE, VE),
+        ])
+    }
+}
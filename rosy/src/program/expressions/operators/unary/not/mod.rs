@@ -41,6 +41,8 @@ use crate::rosy_lib::RosyType;
 use crate::transpile::{ExprFunctionCallResult, TranspileableExpr};
 use crate::transpile::{TranspilationInputContext, TranspilationOutput, Transpile, ValueKind};
 use anyhow::{Error, Result, anyhow};
+use crate::interpret::{Evaluate, Interpreter, Value};
+use crate::rosy_lib::{LO, RosyNot};
 
 /// Logical NOT expression (unary operator).
 /// Supports both `!x` and `NOT x` syntax.
@@ -121,3 +123,9 @@ impl Transpile for NotExpr {
         }
     }
 }
+impl Evaluate for NotExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        let operand: LO = interpreter.evaluate_as(&self.operand)?;
+        Ok(Value::LO(RosyNot::rosy_not(&operand)?))
+    }
+}
@@ -38,6 +38,7 @@ use crate::{
         TranspilationOutput, Transpile, TranspileableExpr, ValueKind,
     },
 };
+use crate::interpret::{Evaluate, Interpreter, Value};
 
 impl FromRule for bool {
     fn from_rule(pair: pest::iterators::Pair<Rule>) -> Result<Option<Self>> {
@@ -86,3 +87,8 @@ impl Transpile for bool {
         })
     }
 }
+impl Evaluate for bool {
+    fn evaluate(&self, _interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        Ok(Value::LO(*self))
+    }
+}
@@ -27,7 +27,7 @@
 //! ```
 
 use crate::resolve::{ExprRecipe, ScopeContext, TypeResolver, TypeSlot};
-use crate::rosy_lib::RosyType;
+use crate::rosy_lib::{CD, RE, RosyType};
 use crate::{
     ast::{FromRule, Rule},
     program::expressions::Expr,
@@ -38,6 +38,7 @@ use crate::{
 };
 use anyhow::{Context, Error};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value};
 
 /// AST node for the `CD(n)` constructor expression.
 #[derive(Debug)]
@@ -106,3 +107,9 @@ impl Transpile for CDExpr {
         })
     }
 }
+impl Evaluate for CDExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> anyhow::Result<Value> {
+        let index: RE = interpreter.evaluate_as(&self.index)?;
+        Ok(Value::CD(CD::variable(index as usize)?))
+    }
+}
@@ -9,6 +9,9 @@
 //! DA(n)          { creates DA identity for variable n }
 //! ```
 //!
+//! Inside the body of a FIT with DA fit variables, the DA variables those
+//! are seeded on cannot be created this way.
+//!
 //! ## Rosy Example
 //! ```text
 #![doc = include_str!("test.rosy")]
@@ -27,7 +30,7 @@
 //! ```
 
 use crate::resolve::{ExprRecipe, ScopeContext, TypeResolver, TypeSlot};
-use crate::rosy_lib::RosyType;
+use crate::rosy_lib::{RE, RosyType, optimizer};
 use crate::{
     ast::{FromRule, Rule},
     program::expressions::Expr,
@@ -38,6 +41,7 @@ use crate::{
 };
 use anyhow::{Context, Error};
 use std::collections::HashSet;
+use crate::interpret::{Evaluate, Interpreter, Value};
 
 /// AST node for the `DA(n)` constructor expression.
 #[derive(Debug)]
@@ -96,8 +100,11 @@ impl Transpile for DAExpr {
                 .collect::<Vec<_>>()
         })?;
 
-        // Use DA::variable(usize) to create a DA differential variable
-        let serialization = format!("DA::variable({} as usize)?", index_output.as_value());
+        // Create the DA differential variable, unless a DA FIT has it
+        let serialization = format!(
+            "rosy_lib::optimizer::da_variable({} as usize)?",
+            index_output.as_value()
+        );
 
         Ok(TranspilationOutput {
             serialization,
@@ -106,3 +113,9 @@ impl Transpile for DAExpr {
         })
     }
 }
+impl Evaluate for DAExpr {
+    fn evaluate(&self, interpreter: &mut Interpreter<'_>) -> anyhow::Result<Value> {
+        let index: RE = interpreter.evaluate_as(&self.index)?;
+        Ok(Value::DA(optimizer::da_variable(index as usize)?))
+    }
+}
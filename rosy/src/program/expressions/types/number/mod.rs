@@ -43,6 +43,7 @@ use crate::{
         TranspilationOutput, Transpile, TranspileableExpr, ValueKind,
     },
 };
+use crate::interpret::{Evaluate, Interpreter, Value};
 
 impl FromRule for f64 {
     fn from_rule(pair: pest::iterators::Pair<Rule>) -> Result<Option<Self>> {
@@ -97,6 +98,12 @@ impl Transpile for f64 {
     }
 }
 
+impl Evaluate for f64 {
+    fn evaluate(&self, _interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        Ok(Value::RE(*self))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ast::{CosyParser, Rule};
@@ -41,6 +41,7 @@ use crate::{
         TranspilationOutput, Transpile, TranspileableExpr, ValueKind,
     },
 };
+use crate::interpret::{Evaluate, Interpreter, Value};
 
 impl FromRule for String {
     fn from_rule(pair: pest::iterators::Pair<Rule>) -> Result<Option<Self>> {
@@ -92,3 +93,8 @@ impl Transpile for String {
         })
     }
 }
+impl Evaluate for String {
+    fn evaluate(&self, _interpreter: &mut Interpreter<'_>) -> Result<Value> {
+        Ok(Value::ST(self.clone()))
+    }
+}
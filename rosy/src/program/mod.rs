@@ -17,8 +17,9 @@ use std::path::{Path, PathBuf};
 
 use crate::{
     ast::{CosyParser, FromRule, Rule},
+    interpret::{self, Flow, Interpret, Interpreter},
     manifest::RosyToml,
-    program::statements::{SourceLocation, Statement},
+    program::statements::{SourceLocation, Statement, core::checkpoint},
     resolve::*,
     transpile::*,
 };
@@ -129,62 +130,7 @@ impl Program {
 
         for stmt in pair.into_inner() {
             if stmt.as_rule() == Rule::include_stmt {
-                // Extract the path from the string literal inside `include_stmt`
-                let include_path = Self::extract_include_path(&stmt)?;
-
-                // Resolve relative to the including file's directory
-                let base_dir = source_path.and_then(|p| p.parent());
-                let resolved = if Path::new(&include_path).is_absolute() {
-                    PathBuf::from(&include_path)
-                } else {
-                    let base = base_dir.ok_or_else(|| {
-                        anyhow::anyhow!(
-                            "Cannot resolve relative INCLUDE '{}' — source file path is unknown \
-                             (hint: save the file to disk first)",
-                            include_path,
-                        )
-                    })?;
-                    base.join(&include_path)
-                };
-
-                // Resolve to a concrete `mod.rosy` file:
-                //   (1) `resolved` is a regular file        → use it (current behavior)
-                //   (2) `resolved` is a directory           → look for `<dir>/mod.rosy`
-                //   (3) `resolved` doesn't exist            → still try `<resolved>/mod.rosy`
-                //                                             (so `INCLUDE 'libcosy';` works
-                //                                              before any `libcosy.rosy` exists)
-                let canonical = match std::fs::canonicalize(&resolved) {
-                    Ok(p) if p.is_file() => p,
-                    Ok(p) if p.is_dir() => {
-                        let mod_path = p.join("mod.rosy");
-                        std::fs::canonicalize(&mod_path).with_context(|| {
-                            format!(
-                                "INCLUDE '{}' resolved to directory '{}' but no 'mod.rosy' was found inside.\n\
-                                 Hint: create '{}/mod.rosy' or include a specific .rosy file.",
-                                include_path,
-                                p.display(),
-                                p.display(),
-                            )
-                        })?
-                    }
-                    Ok(p) => bail!(
-                        "INCLUDE '{}' resolved to '{}' which is neither a regular file nor a directory",
-                        include_path,
-                        p.display(),
-                    ),
-                    Err(_) => {
-                        let mod_path = resolved.join("mod.rosy");
-                        std::fs::canonicalize(&mod_path).with_context(|| {
-                            format!(
-                                "Failed to resolve INCLUDE path '{}' — tried '{}' (file) and '{}/mod.rosy' (directory module)",
-                                include_path,
-                                resolved.display(),
-                                resolved.display(),
-                            )
-                        })?
-                    }
-                };
-
+                let canonical = Self::resolve_include(&stmt, source_path)?;
                 Self::splice_resolved_file(canonical, &mut statements, tracker)?;
             } else if stmt.as_rule() == Rule::module_stmt {
                 Self::process_module_stmt(&stmt, source_path, &mut statements, tracker)?;
@@ -201,6 +147,70 @@ impl Program {
         Ok(Some(Program { statements }))
     }
 
+    /// Resolve an `INCLUDE` statement to the canonical path of the file it
+    /// loads, relative to the including file `source_path`.
+    pub fn resolve_include(
+        stmt: &pest::iterators::Pair<Rule>,
+        source_path: Option<&Path>,
+    ) -> Result<PathBuf> {
+        // Extract the path from the string literal inside `include_stmt`
+        let include_path = Self::extract_include_path(stmt)?;
+
+        // Resolve relative to the including file's directory
+        let base_dir = source_path.and_then(|p| p.parent());
+        let resolved = if Path::new(&include_path).is_absolute() {
+            PathBuf::from(&include_path)
+        } else {
+            let base = base_dir.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Cannot resolve relative INCLUDE '{}' — source file path is unknown \
+                     (hint: save the file to disk first)",
+                    include_path,
+                )
+            })?;
+            base.join(&include_path)
+        };
+
+        // Resolve to a concrete `mod.rosy` file:
+        //   (1) `resolved` is a regular file        → use it (current behavior)
+        //   (2) `resolved` is a directory           → look for `<dir>/mod.rosy`
+        //   (3) `resolved` doesn't exist            → still try `<resolved>/mod.rosy`
+        //                                             (so `INCLUDE 'libcosy';` works
+        //                                              before any `libcosy.rosy` exists)
+        let canonical = match std::fs::canonicalize(&resolved) {
+            Ok(p) if p.is_file() => p,
+            Ok(p) if p.is_dir() => {
+                let mod_path = p.join("mod.rosy");
+                std::fs::canonicalize(&mod_path).with_context(|| {
+                    format!(
+                        "INCLUDE '{}' resolved to directory '{}' but no 'mod.rosy' was found inside.\n\
+                         Hint: create '{}/mod.rosy' or include a specific .rosy file.",
+                        include_path,
+                        p.display(),
+                        p.display(),
+                    )
+                })?
+            }
+            Ok(p) => bail!(
+                "INCLUDE '{}' resolved to '{}' which is neither a regular file nor a directory",
+                include_path,
+                p.display(),
+            ),
+            Err(_) => {
+                let mod_path = resolved.join("mod.rosy");
+                std::fs::canonicalize(&mod_path).with_context(|| {
+                    format!(
+                        "Failed to resolve INCLUDE path '{}' — tried '{}' (file) and '{}/mod.rosy' (directory module)",
+                        include_path,
+                        resolved.display(),
+                        resolved.display(),
+                    )
+                })?
+            }
+        };
+        Ok(canonical)
+    }
+
     /// Read, parse, and splice a resolved canonical file into `statements`,
     /// updating `tracker`. Shared by INCLUDE and MODULE since both ultimately
     /// reduce to "treat the file's `BEGIN; ... END;` body as inlined here".
@@ -268,6 +278,16 @@ impl Program {
         statements: &mut Vec<Statement>,
         tracker: &mut IncludeTracker,
     ) -> Result<()> {
+        let canonical = Self::resolve_module(stmt, source_path)?;
+        Self::splice_resolved_file(canonical, statements, tracker)
+    }
+
+    /// Resolve a `MODULE` statement to the canonical path of its package's
+    /// `mod.rosy`, fetching and validating the package on the way.
+    pub fn resolve_module(
+        stmt: &pest::iterators::Pair<Rule>,
+        source_path: Option<&Path>,
+    ) -> Result<PathBuf> {
         let info = Self::extract_module_info(stmt)?;
 
         // Step 1: locate the package directory (resolution rules differ per source type).
@@ -365,16 +385,15 @@ impl Program {
             );
         }
 
-        // Step 6: behave like INCLUDE on the package's mod.rosy entry point.
+        // Step 6: the package's mod.rosy entry point is INCLUDEd.
         let mod_path = package_dir.join("mod.rosy");
-        let canonical = std::fs::canonicalize(&mod_path).with_context(|| {
+        std::fs::canonicalize(&mod_path).with_context(|| {
             format!(
                 "Package '{}' is missing 'mod.rosy' at '{}'",
                 manifest.package.name,
                 mod_path.display(),
             )
-        })?;
-        Self::splice_resolved_file(canonical, statements, tracker)
+        })
     }
 
     /// Download and extract a GitHub Release source tarball into `dest`.
@@ -690,6 +709,36 @@ mod include_resolution_tests {
     }
 }
 
+impl Interpret for Program {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        // A resumed program skips everything in front of its checkpointed
+        // LOOP except declarations, as in `transpile`
+        let checkpointed_loop = self
+            .statements
+            .iter()
+            .position(|statement| statement.inner.ends_with_checkpoint());
+        for (index, statement) in self.statements.iter().enumerate() {
+            if interpreter.resume.is_some()
+                && checkpointed_loop.is_some_and(|position| index < position)
+                && !statement.inner.is_declaration()
+            {
+                continue;
+            }
+            if checkpointed_loop == Some(index) {
+                interpreter.checkpoint = Some(interpret::CheckpointTarget {
+                    iteration: 0,
+                    variables: interpreter.local_variables(),
+                });
+            }
+            let flow = statement.interpret(interpreter)?;
+            if flow != Flow::Next {
+                return Ok(flow);
+            }
+        }
+        Ok(Flow::Next)
+    }
+}
+
 impl Transpile for Program {
     fn transpile(
         &self,
@@ -697,8 +746,53 @@ impl Transpile for Program {
     ) -> Result<TranspilationOutput, Vec<Error>> {
         let mut serialization = Vec::new();
         let mut errors = Vec::new();
-        for statement in &self.statements {
-            match statement.transpile(context) {
+
+        // The LOOP ending with CHECKPOINT; a resumed program skips everything
+        // in front of it except declarations
+        let mut checkpointed = self
+            .statements
+            .iter()
+            .enumerate()
+            .filter(|(_, statement)| statement.inner.ends_with_checkpoint());
+        let checkpointed_loop = checkpointed.next().map(|(index, _)| index);
+        if let Some((_, second)) = checkpointed.next() {
+            errors.push(anyhow::anyhow!(
+                "Only one LOOP of a program can end with CHECKPOINT, found another at {}",
+                second.source_location
+            ));
+        }
+        if checkpointed_loop.is_some() {
+            serialization.push(format!(
+                "let {} = rosy_lib::core::checkpoint::Checkpoint::resume_from_args()?;",
+                checkpoint::RESUME_BINDING
+            ));
+        }
+
+        for (index, statement) in self.statements.iter().enumerate() {
+            let is_checkpointed_loop = checkpointed_loop == Some(index);
+            if is_checkpointed_loop {
+                let mut variables: Vec<VariableData> = context
+                    .variables
+                    .values()
+                    .map(|variable| variable.data.clone())
+                    .collect();
+                variables.sort_by(|a, b| a.name.cmp(&b.name));
+                context.checkpoint = Some(CheckpointTarget { variables });
+            }
+            let output = statement.transpile(context);
+            if is_checkpointed_loop {
+                context.checkpoint = None;
+            }
+            match output {
+                Ok(output) if checkpointed_loop.is_some_and(|position| index < position)
+                    && !statement.inner.is_declaration() =>
+                {
+                    serialization.push(format!(
+                        "if {}.is_none() {{\n{}\n}}",
+                        checkpoint::RESUME_BINDING,
+                        indent(output.serialization)
+                    ));
+                }
                 Ok(output) => {
                     serialization.push(output.serialization);
                 }
@@ -11,7 +11,9 @@
 //! - `n`          — RE expression (the zero-based index of the argument)
 //! - `result_var` — ST variable that receives the argument string
 //!
-//! If the n-th argument does not exist, an empty string is returned.
+//! If the n-th argument does not exist, an empty string is returned. A
+//! `--resume <file>` flag (see [`checkpoint`](super::checkpoint)) is not
+//! counted.
 //!
 //! ## Rosy Example
 //! ```text
@@ -40,6 +42,7 @@ use crate::{
     resolve::*,
     transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
 
 #[derive(Debug)]
 pub struct ArggetStatement {
@@ -145,7 +148,7 @@ impl Transpile for ArggetStatement {
         };
 
         let serialization = format!(
-            "{deref}{dest} = std::env::args().nth({src} as usize).unwrap_or_default();",
+            "{deref}{dest} = rosy_lib::core::checkpoint::program_args().nth({src} as usize).unwrap_or_default();",
             deref = dereference,
             dest = output_id_output.serialization,
             src = index_output.as_value(),
@@ -158,3 +161,11 @@ impl Transpile for ArggetStatement {
         })
     }
 }
+impl Interpret for ArggetStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let index: f64 = interpreter.evaluate_as(&self.index_expr)?;
+        let arg = interpreter.args.get(index as usize).cloned().unwrap_or_default();
+        self.output_var.write(interpreter, Value::ST(arg))?;
+        Ok(Flow::Next)
+    }
+}
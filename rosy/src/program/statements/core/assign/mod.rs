@@ -14,6 +14,11 @@
 //!
 //! The right-hand side expression must be type-compatible with the target
 //! variable. Indexed assignments check that the resulting element type matches.
+//! An untyped variable takes the type of its first assignment, and every later
+//! assignment must match it. The exception is a variable whose type depends on
+//! itself (`X := 1; LOOP I 1 3; X := X*Y; ENDLOOP;` with Y a DA): it is inferred
+//! as the widest type around the cycle, and narrower values stored in it are
+//! widened (RE into CM or DA, and any of those into CD).
 //!
 //! ## Rosy Example
 //! ```text
@@ -36,7 +41,7 @@ use std::collections::{BTreeSet, HashSet};
 
 use super::super::super::{TranspilationInputContext, TranspilationOutput, Transpile};
 use crate::errors::RosyError;
-use crate::rosy_lib::{RosyBaseType, RosyType};
+use crate::rosy_lib::{RosyBaseType, RosyType, operators::widen};
 use crate::{
     ast::*,
     program::{
@@ -47,6 +52,7 @@ use crate::{
     transpile::*,
 };
 use anyhow::{Context, Error, Result, anyhow, ensure};
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter, Value};
 
 /// AST node for the assignment statement `name := expr;`.
 #[derive(Debug)]
@@ -54,6 +60,9 @@ pub struct AssignStatement {
     pub identifier: VariableIdentifier,
     /// `None` when RHS is `.` (clear/reset), `Some` for normal expressions.
     pub value: Option<Expr>,
+    /// Whether the variable is on a type dependency cycle, so a narrower
+    /// value may be widened into it. Set during type hydration.
+    pub widens: bool,
 }
 
 impl FromRule for AssignStatement {
@@ -89,7 +98,11 @@ impl FromRule for AssignStatement {
             )
         };
 
-        Ok(Some(AssignStatement { identifier, value }))
+        Ok(Some(AssignStatement {
+            identifier,
+            value,
+            widens: false,
+        }))
     }
 }
 impl TranspileableStatement for AssignStatement {
@@ -339,8 +352,16 @@ impl TranspileableStatement for AssignStatement {
                     }
                 }
 
+                // Only an assignment that closes a cycle back to the variable
+                // may widen it; any other must keep the first one's type.
+                let closes_cycle = resolver.reaches(&deps, &var_slot);
                 if let (Ok(old_type), Ok(new_type)) = (old_type_result, new_type_result) {
-                    if old_type != new_type {
+                    let conflicts = if closes_cycle {
+                        widen::join(&old_type, &new_type).is_none()
+                    } else {
+                        old_type != new_type
+                    };
+                    if conflicts {
                         let scope_str = if ctx.scope_path.is_empty() {
                             "global scope".to_string()
                         } else {
@@ -416,12 +437,24 @@ impl TranspileableStatement for AssignStatement {
                 }
             }
 
-            // Keep the first (non-self-referential) assignment's recipe.
-            // Subsequent assignments are mutations — the variable's type
-            // is established by its first assignment. This prevents false
-            // cycles when a variable is re-assigned from a value that
-            // transitively depends on itself (e.g. X1 := f(X3) after
-            // X3 := g(X1)).
+            // Keep the first assignment's recipe as the rule. A later
+            // assignment that depends on the variable itself (`X := X*Y`,
+            // or X1 := f(X3) after X3 := g(X1)) makes a cycle, which the
+            // resolver's fixpoint pass settles from the first assignment,
+            // joining in the later ones; off a cycle they are ignored.
+            let num_indices = self.identifier.num_index_dimensions();
+            let recipe = if num_indices > 0 {
+                ExprRecipe::WithDimensions(Box::new(recipe), num_indices)
+            } else {
+                recipe
+            };
+            resolver.add_widening(
+                &var_slot,
+                recipe,
+                deps,
+                "inferred from assignment".to_string(),
+                Some(source_location),
+            );
             return InferenceEdgeResult::HasEdges { result: Ok(()) };
         }
 
@@ -449,10 +482,11 @@ impl TranspileableStatement for AssignStatement {
     }
     fn hydrate_resolved_types(
         &mut self,
-        _resolver: &TypeResolver,
-        _current_scope: &[String],
+        resolver: &TypeResolver,
+        current_scope: &[String],
     ) -> TypeHydrationResult {
-        TypeHydrationResult::NothingToHydrate
+        self.widens = resolver.is_cyclic_variable(current_scope, &self.identifier.name);
+        TypeHydrationResult::Hydrated { result: Ok(()) }
     }
 }
 impl Transpile for AssignStatement {
@@ -535,7 +569,11 @@ impl Transpile for AssignStatement {
         let value_type = value.type_of(context).map_err(|e| {
             vec![e.context("...while determining type of value expression for assignment")]
         })?;
-        if variable_type != value_type {
+        // A narrower value stored in a variable on a cycle that was inferred
+        // to a wider type (e.g. the `X := 1` in `X := 1; X := X*Y;` with Y a
+        // DA) is widened.
+        let widens = self.widens && widen::is_widening(&value_type, &variable_type);
+        if variable_type != value_type && !widens {
             return Err(vec![anyhow!(
                 "Cannot assign value of type '{}' to variable '{}' of type '{}'!",
                 value_type,
@@ -545,10 +583,11 @@ impl Transpile for AssignStatement {
         }
 
         // Optimization: detect `X := X & expr` and generate in-place append
-        if self.identifier.num_index_dimensions() == 0 {
-            if let Some(result) = value.inner.try_inplace_append(&self.identifier.name, context) {
-                return result;
-            }
+        if self.identifier.num_index_dimensions() == 0
+            && !widens
+            && let Some(result) = value.inner.try_inplace_append(&self.identifier.name, context)
+        {
+            return result;
         }
 
         let mut requested_variables = BTreeSet::new();
@@ -585,7 +624,15 @@ impl Transpile for AssignStatement {
         };
         requested_variables.extend(value_output.requested_variables.iter().cloned());
 
-        let serialized_value = value_output.as_owned(&variable_type);
+        let serialized_value = if widens {
+            format!(
+                "RosyWiden::<{}>::rosy_widen({})?",
+                variable_type.as_rust_type(),
+                value_output.as_ref()
+            )
+        } else {
+            value_output.as_owned(&variable_type)
+        };
 
         // Serialize the entire assignment
         let var_scope = context
@@ -693,3 +740,31 @@ impl Transpile for AssignStatement {
         }
     }
 }
+impl Interpret for AssignStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let value = match &self.value {
+            Some(value) => value.evaluate(interpreter)?,
+            None => {
+                // `:= .` empties a VE, or the outermost level of an array
+                let name = &self.identifier.name;
+                let r#type = interpreter
+                    .variable_type(name)
+                    .ok_or_else(|| anyhow!("Variable '{name}' is not defined in this scope!"))?;
+                let dimensions = r#type
+                    .dimensions
+                    .saturating_sub(self.identifier.num_index_dimensions());
+                let mut cleared = if dimensions == 0 {
+                    Value::VE(Vec::new())
+                } else {
+                    Value::Array(Vec::new())
+                };
+                for _ in 1..dimensions {
+                    cleared = Value::Array(vec![cleared]);
+                }
+                cleared
+            }
+        };
+        self.identifier.write(interpreter, value)?;
+        Ok(Flow::Next)
+    }
+}
@@ -20,6 +20,7 @@ use anyhow::{Error, Result, anyhow, ensure};
 use std::collections::BTreeSet;
 
 use crate::{ast::*, program::statements::SourceLocation, resolve::*, transpile::*};
+use crate::interpret::{Flow, Interpret, Interpreter};
 
 #[derive(Debug)]
 pub struct BreakStatement;
@@ -78,3 +79,8 @@ impl Transpile for BreakStatement {
         })
     }
 }
+impl Interpret for BreakStatement {
+    fn interpret<'a>(&'a self, _interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        Ok(Flow::Break)
+    }
+}
@@ -0,0 +1,321 @@
+//! # CHECKPOINT Statement
+//!
+//! Saves the state of a long-running program at the end of a `LOOP`
+//! iteration, so that it can be resumed there after a crash or a job time
+//! limit instead of starting over.
+//!
+//! ## Syntax
+//!
+//! ```text
+//! LOOP i start end [step];
+//!     <statements>
+//!     CHECKPOINT file [every];
+//! ENDLOOP;
+//! ```
+//!
+//! `CHECKPOINT` must be the last statement of a `LOOP` written directly in
+//! the main program; that loop becomes the program's *checkpointed loop*,
+//! and a program has at most one. `file` (ST) names the checkpoint file,
+//! which is replaced on every save. With `every` (RE), only every `every`-th
+//! iteration is saved.
+//!
+//! A checkpoint holds:
+//!
+//! - every variable declared before the loop,
+//! - the DA configuration: DAINI order and number of variables, DANOT
+//!   truncation order, DAEPS epsilon, DANOTW weights and DAFSET template,
+//! - the position of the random number generator,
+//! - the open file units and how far they have been read or written,
+//! - the number of iterations done.
+//!
+//! ## Resuming
+//!
+//! Start the compiled program with `--resume <file>` (or `rosy run
+//! --resume <file>`). The statements before the loop are skipped, except
+//! declarations, everything in the list above is restored, and the loop
+//! carries on with the first iteration the checkpoint did not cover. Files
+//! written before the checkpoint are cut back to where they were, so the
+//! output of a resumed run matches that of an uninterrupted one.
+//!
+//! `ARGGET` does not count `--resume` and its file.
+//!
+//! ## Rosy Example
+//! ```text
+#![doc = include_str!("test.rosy")]
+//! ```
+//! **Output**:
+//! ```text
+#![doc = include_str!("rosy_output.txt")]
+//! ```
+
+use anyhow::{Context, Error, Result, anyhow, bail, ensure};
+use std::collections::BTreeSet;
+
+use crate::{
+    ast::*,
+    program::{expressions::Expr, statements::SourceLocation},
+    resolve::{ScopeContext, TypeResolver},
+    rosy_lib::{RosyBaseType, RosyType},
+    transpile::{
+        CheckpointTarget, InferenceEdgeResult, TranspilationInputContext, TranspilationOutput,
+        Transpile, TranspileableExpr, TranspileableStatement, TypeHydrationResult,
+        TypeslotDeclarationResult,
+        add_context_to_all, indent,
+    },
+};
+use crate::interpret::{Flow, Interpret, Interpreter, Slot, Value};
+use crate::rosy_lib::core::checkpoint::{Checkpoint, Checkpointed, Entry, Value as Scalar};
+use crate::rosy_lib::{CD, CM, DA, LO, RE, ST, VE};
+
+/// Binding for the checkpoint given by `--resume`, if any.
+pub const RESUME_BINDING: &str = "rosy_resume";
+/// Index (0-based) of the current iteration of the checkpointed loop.
+pub const ITERATION_BINDING: &str = "__checkpoint_iteration";
+
+/// AST node for the `CHECKPOINT file [every];` statement.
+#[derive(Debug)]
+pub struct CheckpointStatement {
+    pub file: Expr,
+    pub every: Option<Expr>,
+}
+
+impl FromRule for CheckpointStatement {
+    fn from_rule(pair: pest::iterators::Pair<Rule>) -> Result<Option<Self>> {
+        ensure!(
+            pair.as_rule() == Rule::checkpoint,
+            "Expected `checkpoint` rule when building CHECKPOINT statement, found: {:?}",
+            pair.as_rule()
+        );
+
+        let mut inner = pair.into_inner();
+
+        let file_pair = inner
+            .next()
+            .context("Missing file parameter in CHECKPOINT statement!")?;
+        let file = Expr::from_rule(file_pair)
+            .context("Failed to build file expression in CHECKPOINT statement!")?
+            .ok_or_else(|| anyhow!("Expected expression for file in CHECKPOINT statement"))?;
+
+        let every = match inner.next() {
+            Some(every_pair) if every_pair.as_rule() == Rule::expr => Some(
+                Expr::from_rule(every_pair)
+                    .context("Failed to build interval expression in CHECKPOINT statement!")?
+                    .ok_or_else(|| anyhow!("Expected expression for interval in CHECKPOINT statement"))?,
+            ),
+            _ => None,
+        };
+
+        Ok(Some(CheckpointStatement { file, every }))
+    }
+}
+
+impl TranspileableStatement for CheckpointStatement {
+    fn register_typeslot_declaration(
+        &self,
+        _resolver: &mut TypeResolver,
+        _ctx: &mut ScopeContext,
+        _source_location: SourceLocation,
+    ) -> TypeslotDeclarationResult {
+        TypeslotDeclarationResult::NotAVarFuncOrProcedureDecl
+    }
+    fn wire_inference_edges(
+        &self,
+        _resolver: &mut TypeResolver,
+        _ctx: &mut ScopeContext,
+        _source_location: SourceLocation,
+    ) -> InferenceEdgeResult {
+        InferenceEdgeResult::NoEdges
+    }
+    fn hydrate_resolved_types(
+        &mut self,
+        _resolver: &TypeResolver,
+        _current_scope: &[String],
+    ) -> TypeHydrationResult {
+        TypeHydrationResult::NothingToHydrate
+    }
+    fn is_checkpoint(&self) -> bool {
+        true
+    }
+}
+
+impl Transpile for CheckpointStatement {
+    fn transpile(
+        &self,
+        context: &mut TranspilationInputContext,
+    ) -> Result<TranspilationOutput, Vec<Error>> {
+        let target = context.checkpoint.clone().ok_or_else(|| {
+            vec![anyhow!(
+                "CHECKPOINT must be the last statement of a LOOP in the main program"
+            )]
+        })?;
+
+        let file_type = self.file.type_of(context).map_err(|e| vec![e])?;
+        if file_type != RosyType::ST() {
+            return Err(vec![anyhow!(
+                "CHECKPOINT file must be of type 'ST', found '{}'",
+                file_type
+            )]);
+        }
+        let file_output = self.file.transpile(context).map_err(|e| {
+            add_context_to_all(e, "...while transpiling file expression in CHECKPOINT".to_string())
+        })?;
+        let mut requested_variables: BTreeSet<String> = file_output.requested_variables.clone();
+
+        let mut save = format!(
+            "let mut rosy_checkpoint = rosy_lib::core::checkpoint::Checkpoint::capture({} + 1)?;",
+            ITERATION_BINDING
+        );
+        for variable in &target.variables {
+            save.push_str(&format!(
+                "\nrosy_checkpoint.put::<{}, _>(\"{}\", &{});",
+                shape(&variable.r#type).map_err(|e| vec![e])?,
+                variable.name,
+                variable.name
+            ));
+        }
+        save.push_str(&format!("\nrosy_checkpoint.save({})?;", file_output.as_ref()));
+
+        let serialization = match &self.every {
+            Some(every) => {
+                let every_type = every.type_of(context).map_err(|e| vec![e])?;
+                if every_type != RosyType::RE() {
+                    return Err(vec![anyhow!(
+                        "CHECKPOINT interval must be of type 'RE', found '{}'",
+                        every_type
+                    )]);
+                }
+                let every_output = every.transpile(context).map_err(|e| {
+                    add_context_to_all(e, "...while transpiling interval in CHECKPOINT".to_string())
+                })?;
+                requested_variables.extend(every_output.requested_variables.iter().cloned());
+                format!(
+                    "if ({} + 1) % (({} as usize).max(1)) == 0 {{\n{}\n}}",
+                    ITERATION_BINDING,
+                    every_output.as_value(),
+                    indent(save)
+                )
+            }
+            None => format!("{{\n{}\n}}", indent(save)),
+        };
+
+        Ok(TranspilationOutput {
+            serialization,
+            requested_variables,
+            ..Default::default()
+        })
+    }
+}
+
+/// Restores the checkpointed loop's variables when the program is resumed,
+/// and leaves in `__checkpoint_skip` how many iterations to skip.
+pub fn restore_serialization(target: &CheckpointTarget) -> Result<String> {
+    let mut restore = format!("{}.restore_runtime()?;", RESUME_BINDING);
+    for variable in &target.variables {
+        restore.push_str(&format!(
+            "\n{} = {}.get::<{}, _>(\"{}\")?;",
+            variable.name,
+            RESUME_BINDING,
+            shape(&variable.r#type)?,
+            variable.name
+        ));
+    }
+    restore.push_str(&format!("\n__checkpoint_skip = {}.iterations;", RESUME_BINDING));
+    Ok(format!(
+        "let mut __checkpoint_skip = 0usize;\nif let Some({}) = &{} {{\n{}\n}}",
+        RESUME_BINDING,
+        RESUME_BINDING,
+        indent(restore)
+    ))
+}
+
+/// The `Checkpointed` shape of a variable of type `r#type`.
+fn shape(r#type: &RosyType) -> Result<String> {
+    ensure!(
+        r#type.base_type != RosyBaseType::GR,
+        "CHECKPOINT cannot save graphics (GR) variables"
+    );
+    let mut shape = String::from("rosy_lib::core::checkpoint::Value");
+    for _ in 0..r#type.dimensions {
+        shape = format!("rosy_lib::core::checkpoint::Array<{}>", shape);
+    }
+    Ok(shape)
+}
+impl Interpret for CheckpointStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let target = interpreter.checkpoint.as_ref().ok_or_else(|| {
+            anyhow!("CHECKPOINT must be the last statement of a LOOP in the main program")
+        })?;
+        let iteration = target.iteration;
+        let variables = target.variables.clone();
+
+        if let Some(every) = &self.every {
+            let every = interpreter.evaluate_as::<f64>(every)? as usize;
+            if (iteration + 1) % every.max(1) != 0 {
+                return Ok(Flow::Next);
+            }
+        }
+
+        let mut checkpoint = Checkpoint::capture(iteration + 1)?;
+        for (name, slot) in &variables {
+            checkpoint.put_entry(name, to_entry(&slot.borrow().value)?);
+        }
+        let file: String = interpreter.evaluate_as(&self.file)?;
+        checkpoint.save(&file)?;
+        Ok(Flow::Next)
+    }
+}
+
+/// Restores `variables` from the checkpoint given by `--resume`, returning
+/// how many iterations of the checkpointed loop it covers.
+pub fn restore(checkpoint: &Checkpoint, variables: &[(String, Slot)]) -> Result<usize> {
+    checkpoint.restore_runtime()?;
+    for (name, slot) in variables {
+        let mut variable = slot.borrow_mut();
+        variable.value = from_entry(checkpoint.entry(name)?, &variable.r#type)
+            .with_context(|| format!("...while restoring '{}'", name))?;
+    }
+    Ok(checkpoint.iterations)
+}
+
+/// An interpreted value as it is saved in a checkpoint.
+fn to_entry(value: &Value) -> Result<Entry> {
+    Ok(match value {
+        Value::RE(x) => <RE as Checkpointed<Scalar>>::to_entry(x),
+        Value::ST(x) => <ST as Checkpointed<Scalar>>::to_entry(x),
+        Value::LO(x) => <LO as Checkpointed<Scalar>>::to_entry(x),
+        Value::CM(x) => <CM as Checkpointed<Scalar>>::to_entry(x),
+        Value::VE(x) => <VE as Checkpointed<Scalar>>::to_entry(x),
+        Value::DA(x) => <DA as Checkpointed<Scalar>>::to_entry(x),
+        Value::CD(x) => <CD as Checkpointed<Scalar>>::to_entry(x),
+        Value::GR(_) => bail!("CHECKPOINT cannot save graphics (GR) variables"),
+        Value::Array(elements) => {
+            Entry::Array(elements.iter().map(to_entry).collect::<Result<_>>()?)
+        }
+    })
+}
+
+/// A checkpoint entry as an interpreted value of type `r#type`.
+fn from_entry(entry: &Entry, r#type: &RosyType) -> Result<Value> {
+    if r#type.dimensions > 0 {
+        let Entry::Array(elements) = entry else {
+            bail!("Expected an array of {} in the checkpoint", r#type);
+        };
+        let element_type = RosyType::new(r#type.base_type, r#type.dimensions - 1);
+        return Ok(Value::Array(
+            elements
+                .iter()
+                .map(|element| from_entry(element, &element_type))
+                .collect::<Result<_>>()?,
+        ));
+    }
+    Ok(match r#type.base_type {
+        RosyBaseType::RE => Value::RE(<RE as Checkpointed<Scalar>>::from_entry(entry)?),
+        RosyBaseType::ST => Value::ST(<ST as Checkpointed<Scalar>>::from_entry(entry)?),
+        RosyBaseType::LO => Value::LO(<LO as Checkpointed<Scalar>>::from_entry(entry)?),
+        RosyBaseType::CM => Value::CM(<CM as Checkpointed<Scalar>>::from_entry(entry)?),
+        RosyBaseType::VE => Value::VE(<VE as Checkpointed<Scalar>>::from_entry(entry)?),
+        RosyBaseType::DA => Value::DA(<DA as Checkpointed<Scalar>>::from_entry(entry)?),
+        RosyBaseType::CD => Value::CD(<CD as Checkpointed<Scalar>>::from_entry(entry)?),
+        RosyBaseType::GR => bail!("CHECKPOINT cannot save graphics (GR) variables"),
+    })
+}
@@ -32,6 +32,7 @@ use anyhow::{Context, Error, Result, anyhow, ensure};
 use std::collections::BTreeSet;
 
 use crate::{ast::*, program::statements::*, resolve::*, rosy_lib::RosyType, transpile::*};
+use crate::interpret::{Flow, Interpret, Interpreter};
 
 /// AST node for a user-defined function declaration.
 #[derive(Debug)]
@@ -292,6 +293,9 @@ impl TranspileableStatement for FunctionStatement {
 
         TypeHydrationResult::Hydrated { result: Ok(()) }
     }
+    fn is_declaration(&self) -> bool {
+        true
+    }
 }
 impl Transpile for FunctionStatement {
     fn transpile(
@@ -451,10 +455,11 @@ impl Transpile for FunctionStatement {
         // function `__fn_FIB` rather than trying to index the local `FIB: f64`.
         let rust_fn_name = format!("__fn_{}", self.name);
         let serialization = format!(
-            "fn {} ( {} ) -> Result<{}> {{\n{}\n\tOk({})\n}}",
+            "fn {} ( {} ) -> Result<{}> {{\n\trosy_lib::core::trace::enter(\"FUNCTION {}\");\n{}\n\trosy_lib::core::trace::leave();\n\tOk({})\n}}",
             rust_fn_name,
             serialized_args.join(", "),
             serialized_return_type,
+            self.name,
             indent(serialized_statements.join("\n")),
             self.name
         );
@@ -469,3 +474,9 @@ impl Transpile for FunctionStatement {
         }
     }
 }
+impl Interpret for FunctionStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        interpreter.define_function(self);
+        Ok(Flow::Next)
+    }
+}
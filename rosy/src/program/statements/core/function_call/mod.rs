@@ -37,6 +37,8 @@ use crate::{
     resolve::*,
     transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::program::expressions::core::var_expr::function_call_evaluate_helper;
 
 /// AST node for a function call used as a statement.
 #[derive(Debug)]
@@ -111,3 +113,9 @@ impl Transpile for FunctionCallStatement {
         function_call_transpile_helper(&self.name, &self.args, context)
     }
 }
+impl Interpret for FunctionCallStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        function_call_evaluate_helper(&self.name, &self.args, interpreter)?;
+        Ok(Flow::Next)
+    }
+}
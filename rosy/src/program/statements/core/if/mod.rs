@@ -49,6 +49,7 @@ use crate::{
     rosy_lib::RosyType,
     transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
 
 /// AST node for the `IF ... [ELSEIF ...] [ELSE] ENDIF;` statement.
 #[derive(Debug)]
@@ -448,3 +449,27 @@ impl TranspileableStatement for ElseIfClause {
         TypeHydrationResult::NothingToHydrate
     }
 }
+impl Interpret for IfStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        if interpreter.evaluate_as(&self.condition)? {
+            return interpreter.run_block(&self.then_body);
+        }
+        for clause in &self.elseif_clauses {
+            if interpreter.evaluate_as(&clause.condition)? {
+                return interpreter.run_block(&clause.body);
+            }
+        }
+        match &self.else_body {
+            Some(body) => interpreter.run_block(body),
+            None => Ok(Flow::Next),
+        }
+    }
+}
+impl Interpret for ElseIfClause {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        if interpreter.evaluate_as(&self.condition)? {
+            return interpreter.run_block(&self.body);
+        }
+        Ok(Flow::Next)
+    }
+}
@@ -32,6 +32,7 @@ use crate::{
     ast::*, program::expressions::core::variable_identifier::VariableIdentifier,
     program::statements::SourceLocation, resolve::*, transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
 
 #[derive(Debug)]
 pub struct ImunitStatement {
@@ -129,3 +130,10 @@ impl Transpile for ImunitStatement {
         })
     }
 }
+impl Interpret for ImunitStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let i = num_complex::Complex64::new(0.0, 1.0);
+        self.identifier.write(interpreter, Value::CM(i))?;
+        Ok(Flow::Next)
+    }
+}
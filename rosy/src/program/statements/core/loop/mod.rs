@@ -37,12 +37,13 @@ use crate::{
     ast::*,
     program::{
         expressions::Expr,
-        statements::{SourceLocation, Statement},
+        statements::{SourceLocation, Statement, core::checkpoint},
     },
     resolve::*,
     rosy_lib::RosyType,
     transpile::*,
 };
+use crate::interpret::{CheckpointTarget, Flow, Interpret, Interpreter, Value};
 
 /// AST node for the counted `LOOP i start end [step]; ... ENDLOOP;` statement.
 #[derive(Debug)]
@@ -176,6 +177,9 @@ impl TranspileableStatement for LoopStatement {
         }
         TypeHydrationResult::Hydrated { result: Ok(()) }
     }
+    fn ends_with_checkpoint(&self) -> bool {
+        self.body.last().is_some_and(|stmt| stmt.inner.is_checkpoint())
+    }
 }
 impl Transpile for LoopStatement {
     fn transpile(
@@ -210,6 +214,12 @@ impl Transpile for LoopStatement {
         // Define and raise the level of any existing variables
         let mut inner_context: TranspilationInputContext = context.clone();
         inner_context.in_loop = true;
+        // Only the final CHECKPOINT sees the checkpointed loop's variables;
+        // the loop sets its own iterator on every iteration anyway
+        let checkpoint = inner_context.checkpoint.take().map(|mut target| {
+            target.variables.retain(|variable| variable.name != self.iterator);
+            target
+        });
         let mut requested_variables = BTreeSet::new();
         let mut serialized_statements = Vec::new();
         let mut errors = Vec::new();
@@ -227,7 +237,10 @@ impl Transpile for LoopStatement {
         );
 
         // Transpile each inner statement
-        for stmt in &self.body {
+        for (index, stmt) in self.body.iter().enumerate() {
+            if index + 1 == self.body.len() {
+                inner_context.checkpoint = checkpoint.clone();
+            }
             match stmt.transpile(&mut inner_context) {
                 Ok(output) => {
                     serialized_statements.push(output.serialization);
@@ -288,16 +301,32 @@ impl Transpile for LoopStatement {
             String::from("")
         };
 
-        let serialization = format!(
-            "for {} in (({} as usize)..=({} as usize)){} {{\n\tlet mut {} = {} as RE;\n{}\n}}",
-            self.iterator,
-            start_output.as_value(),
-            end_output.as_value(),
-            step_serialization,
-            self.iterator,
-            self.iterator,
-            indent(serialized_statements.join("\n"))
-        );
+        let body = indent(serialized_statements.join("\n"));
+        let serialization = match &checkpoint {
+            // Resuming skips the iterations the checkpoint covers
+            Some(target) => format!(
+                "{}\nfor ({}, {}) in (({} as usize)..=({} as usize)){}.enumerate().skip(__checkpoint_skip) {{\n\tlet mut {} = {} as RE;\n{}\n}}",
+                checkpoint::restore_serialization(target).map_err(|e| vec![e])?,
+                checkpoint::ITERATION_BINDING,
+                self.iterator,
+                start_output.as_value(),
+                end_output.as_value(),
+                step_serialization,
+                self.iterator,
+                self.iterator,
+                body
+            ),
+            None => format!(
+                "for {} in (({} as usize)..=({} as usize)){} {{\n\tlet mut {} = {} as RE;\n{}\n}}",
+                self.iterator,
+                start_output.as_value(),
+                end_output.as_value(),
+                step_serialization,
+                self.iterator,
+                self.iterator,
+                body
+            ),
+        };
         if errors.is_empty() {
             Ok(TranspilationOutput {
                 serialization,
@@ -309,3 +338,55 @@ impl Transpile for LoopStatement {
         }
     }
 }
+impl Interpret for LoopStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        // Only the final CHECKPOINT sees the checkpointed loop's variables,
+        // as in `transpile`
+        let mut target = interpreter.checkpoint.take().map(|mut target| {
+            target.variables.retain(|(name, _)| *name != self.iterator);
+            target
+        });
+
+        let start = interpreter.evaluate_as::<f64>(&self.start)? as usize;
+        let end = interpreter.evaluate_as::<f64>(&self.end)? as usize;
+        let step = match &self.step {
+            Some(step) => interpreter.evaluate_as::<f64>(step)? as usize,
+            None => 1,
+        };
+
+        // Resuming skips the iterations the checkpoint covers
+        let mut skip = 0;
+        if let (Some(target), Some(resume)) = (&target, &interpreter.resume) {
+            skip = super::checkpoint::restore(resume, &target.variables)?;
+        }
+
+        for (iteration, i) in (start..=end).step_by(step).enumerate().skip(skip) {
+            let flow = interpreter.block(|interpreter| {
+                interpreter.declare(&self.iterator, RosyType::RE(), Value::RE(i as f64))?;
+                for (index, statement) in self.body.iter().enumerate() {
+                    let last = index + 1 == self.body.len();
+                    if last {
+                        interpreter.checkpoint = target
+                            .take()
+                            .map(|target| CheckpointTarget { iteration, ..target });
+                    }
+                    let flow = statement.interpret(interpreter);
+                    if last {
+                        target = interpreter.checkpoint.take();
+                    }
+                    let flow = flow?;
+                    if flow != Flow::Next {
+                        return Ok(flow);
+                    }
+                }
+                Ok(Flow::Next)
+            })?;
+            match flow {
+                Flow::Next => {}
+                Flow::Break => break,
+                quit @ Flow::Quit(_) => return Ok(quit),
+            }
+        }
+        Ok(Flow::Next)
+    }
+}
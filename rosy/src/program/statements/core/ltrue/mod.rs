@@ -32,6 +32,7 @@ use crate::{
     ast::*, program::expressions::core::variable_identifier::VariableIdentifier,
     program::statements::SourceLocation, resolve::*, transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
 
 #[derive(Debug)]
 pub struct LtrueStatement {
@@ -125,3 +126,9 @@ impl Transpile for LtrueStatement {
         })
     }
 }
+impl Interpret for LtrueStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        self.identifier.write(interpreter, Value::LO(true))?;
+        Ok(Flow::Next)
+    }
+}
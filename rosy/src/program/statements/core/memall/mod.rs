@@ -35,6 +35,8 @@ use crate::{
     ast::*, program::expressions::core::variable_identifier::VariableIdentifier,
     program::statements::SourceLocation, resolve::*, transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::core::memory::rosy_memall;
 
 #[derive(Debug)]
 pub struct MemallStatement {
@@ -133,3 +135,9 @@ impl Transpile for MemallStatement {
         })
     }
 }
+impl Interpret for MemallStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        self.identifier.write(interpreter, Value::RE(rosy_memall()))?;
+        Ok(Flow::Next)
+    }
+}
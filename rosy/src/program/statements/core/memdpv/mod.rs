@@ -38,6 +38,7 @@ use crate::{
     ast::*, program::expressions::Expr, program::statements::SourceLocation, resolve::*,
     transpile::*,
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter};
 
 /// AST node for `MEMDPV unit var;`.
 #[derive(Debug)]
@@ -136,3 +137,11 @@ impl Transpile for MemdpvStatement {
         })
     }
 }
+impl Interpret for MemdpvStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        self.unit_expr.evaluate(interpreter)?;
+        let value = self.var_expr.evaluate(interpreter)?;
+        eprintln!("MEMDPV: {:?}", value);
+        Ok(Flow::Next)
+    }
+}
@@ -34,6 +34,8 @@ use crate::{
     ast::*, program::expressions::core::variable_identifier::VariableIdentifier,
     program::statements::SourceLocation, resolve::*, transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::core::memory::rosy_memfre;
 
 #[derive(Debug)]
 pub struct MemfreStatement {
@@ -132,3 +134,9 @@ impl Transpile for MemfreStatement {
         })
     }
 }
+impl Interpret for MemfreStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        self.identifier.write(interpreter, Value::RE(rosy_memfre()))?;
+        Ok(Flow::Next)
+    }
+}
@@ -39,6 +39,7 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter};
 
 /// AST node for `MEMWRT c;`.
 #[derive(Debug)]
@@ -119,3 +120,9 @@ impl Transpile for MemwrtStatement {
         })
     }
 }
+impl Interpret for MemwrtStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        self.unit_expr.evaluate(interpreter)?;
+        Ok(Flow::Next)
+    }
+}
@@ -14,6 +14,7 @@
 //! - **[`ploop`]** — `PLOOP ... ENDPLOOP;` (MPI parallel)
 //! - **[`if`]** — `IF cond; ... [ELSEIF ...;] [ELSE;] ENDIF;`
 //! - **[`break`]** — `BREAK;`
+//! - **[`checkpoint`]** — `CHECKPOINT file [every];` — save state at the end of a LOOP iteration
 //! - **[`quit`]** — `QUIT;`
 //!
 //! ## Functions & Procedures
@@ -41,6 +42,7 @@
 
 pub mod assign;
 pub mod r#break;
+pub mod checkpoint;
 pub mod function;
 pub mod function_call;
 pub mod r#if;
@@ -6,11 +6,29 @@
 //! ## Syntax
 //!
 //! ```text
-//! PLOOP i start end [commut];
+//! PLOOP i start end;
 //!     <statements>
-//! ENDPLOOP output;
+//! ENDPLOOP [commut] output;
 //! ```
 //!
+//! ## Communication standards
+//!
+//! `commut` selects how `output` is shared between the ranks (default 1):
+//!
+//! | `commut` | Effect | Element types |
+//! |----------|--------|---------------|
+//! | 1 | all-to-all: every rank receives every slot | RE, ST, LO, VE, DA |
+//! | 2 | gather: only the root rank receives every slot | RE, ST, LO, VE, DA |
+//! | 3 | sum of all slots, stored in every slot on every rank | RE, VE, DA |
+//! | 4 | minimum of all slots (element-wise for VE) | RE, VE |
+//! | 5 | maximum of all slots (element-wise for VE) | RE, VE |
+//! | 6 | scatter: each rank receives its own slot from the root | RE, ST, LO, VE, DA |
+//!
+//! The root is rank 0 when every rank runs its own iteration. Scatter is
+//! for inputs: it runs *before* the body, so each rank reads `output(I)`
+//! as filled in by the root, and nothing is exchanged afterwards. The
+//! standards themselves are described in `rosy_lib::ploop`.
+//!
 //! ## Partitioning convention (COSY INFINITY compatible)
 //!
 //! The PLOOP iterator partitions the **last (innermost)** dimension of the
@@ -27,6 +45,16 @@
 //! ENDPLOOP results;
 //! ```
 //!
+//! ## Example (sum reduction)
+//!
+//! ```text
+//! VARIABLE (RE NP) PARTIAL;
+//! PLOOP I 1 NP;
+//!     PARTIAL(I) := I*I;
+//! ENDPLOOP 3 PARTIAL;
+//! { Every PARTIAL(J) now holds the sum over all ranks }
+//! ```
+//!
 //! ## Example (2D — per-rank column)
 //!
 //! ```text
@@ -38,6 +66,15 @@
 //!     ENDLOOP;
 //! ENDPLOOP X;
 //! ```
+//!
+//! ## Rosy Example
+//! ```text
+#![doc = include_str!("test.rosy")]
+//! ```
+//! **Output** (with any number of processes):
+//! ```text
+#![doc = include_str!("rosy_output.txt")]
+//! ```
 
 use anyhow::{Context, Error, Result, anyhow, bail, ensure};
 use std::collections::BTreeSet;
@@ -48,10 +85,16 @@ use crate::{
         expressions::{Expr, core::variable_identifier::VariableIdentifier},
         statements::{SourceLocation, Statement},
     },
+    parallel_config::{ParallelBackend, parallel_backend},
     resolve::*,
-    rosy_lib::RosyType,
+    rosy_lib::{
+        RosyType,
+        ploop::{Reduction, Standard},
+    },
     transpile::*,
 };
+use crate::interpret::{Flow, FromValue, Interpret, Interpreter, Value};
+use crate::rosy_lib::{RE, VE, DA, ploop::Reducible};
 
 /// AST node for the parallel loop `PLOOP ... ENDPLOOP output;`.
 #[derive(Debug)]
@@ -299,6 +342,42 @@ impl Transpile for PLoopStatement {
             )]);
         }
 
+        // Check the chosen communication standard can carry the output's
+        // elements: one per group, in the last dimension (the `RE`s of a `VE`)
+        let commut = self.commutivityfrom_rule.unwrap_or(1);
+        let standard = Standard::from_code(commut).map_err(|e| {
+            vec![e.context(format!("...in the PLOOP gathering '{}'", self.output.name))]
+        })?;
+        let element_type = if output_type == RosyType::VE() {
+            RosyType::RE()
+        } else {
+            RosyType::new(output_type.base_type, 0)
+        };
+        let transportable = [RosyType::RE(), RosyType::ST(), RosyType::LO(), RosyType::VE(), RosyType::DA()];
+        if !transportable.contains(&element_type) {
+            return Err(vec![anyhow!(
+                "Output variable '{}' for a PLOOP must hold RE, ST, LO, VE or DA elements to be sent between processes, found '{}'",
+                self.output.name,
+                output_type
+            )]);
+        }
+        if let Standard::Reduce(reduction) = standard {
+            let reducible = match reduction {
+                Reduction::Sum => vec![RosyType::RE(), RosyType::VE(), RosyType::DA()],
+                Reduction::Min | Reduction::Max => vec![RosyType::RE(), RosyType::VE()],
+            };
+            if !reducible.contains(&element_type) {
+                return Err(vec![anyhow!(
+                    "Cannot take the {} of the '{}' elements of PLOOP output variable '{}' (communication standard {}); supported element types are {}",
+                    reduction,
+                    element_type,
+                    self.output.name,
+                    commut,
+                    reducible.iter().map(|t| t.to_string()).collect::<Vec<_>>().join(", ")
+                )]);
+            }
+        }
+
         // Serialize the output identifier
         let output_serialization = match self.output.transpile(context) {
             Ok(output) => {
@@ -316,14 +395,13 @@ impl Transpile for PLoopStatement {
             }
         };
 
-        let iterator_declaration_serialization = {
-            requested_variables.insert("rosy_mpi_context".to_string());
-            format!(
-                "let mut __ploop_end: f64 = {};\n\tlet mut {} = rosy_mpi_context.get_group_num(&mut __ploop_end)? + 1.0f64;",
-                end_output.as_value(),
-                self.iterator,
-            )
-        };
+        requested_variables.insert("rosy_mpi_context".to_string());
+        let end_declaration_serialization =
+            format!("let mut __ploop_end: f64 = {};", end_output.as_value());
+        let iterator_declaration_serialization = format!(
+            "let mut {} = rosy_mpi_context.get_group_num(&mut __ploop_end)? + 1.0f64;",
+            self.iterator,
+        );
         // Partition the OUTPUT array's last (innermost) dimension across MPI
         // ranks, matching COSY INFINITY's convention. For a 2D output declared
         // `(RE D1 NP) X`, each rank g writes into `X[i][g]` for every `i`, and
@@ -338,9 +416,12 @@ impl Transpile for PLoopStatement {
                 output_type.dimensions
             };
             let outer_loop_count = rank.saturating_sub(1);
-            let commut = self.commutivityfrom_rule.unwrap_or(1);
 
-            let mut accessor = output_serialization.clone();
+            // Procedure arguments and captured variables are `&mut` bindings
+            let mut accessor = match context.variables.get(&self.output.name).map(|v| &v.scope) {
+                Some(VariableScope::Arg | VariableScope::Higher) => format!("(*{})", output_serialization),
+                _ => output_serialization.clone(),
+            };
             let mut opens = String::new();
             for i in 0..outer_loop_count {
                 let idx = format!("__ploop_dim_{}", i);
@@ -361,12 +442,28 @@ impl Transpile for PLoopStatement {
                 format!("{}{}\n\t{}", opens, inner_call, closes)
             }
         };
-        let serialization = format!(
-            "{{\n\t{}\n\n{}\n\n\t{}\n}}",
+        // Scatter hands out an input array, so it runs before the body
+        let (before_body, after_body) = if standard == Standard::Scatter {
+            (format!("\n\t{}", coordination_serialization), String::new())
+        } else {
+            (String::new(), coordination_serialization)
+        };
+        let rank_serialization = format!(
+            "{}{}\n\n{}\n\n\t{}",
             iterator_declaration_serialization,
+            before_body,
             indent(serialized_statements.join("\n")),
-            coordination_serialization
+            after_body
         );
+        let serialization = match parallel_backend() {
+            ParallelBackend::Mpi => format!(
+                "{{\n\t{}\n\t{}\n}}",
+                end_declaration_serialization, rank_serialization
+            ),
+            ParallelBackend::Threads => {
+                thread_pool_serialization(&end_declaration_serialization, &rank_serialization, &self.iterator, context)
+            }
+        };
         if errors.is_empty() {
             Ok(TranspilationOutput {
                 serialization,
@@ -378,3 +475,185 @@ impl Transpile for PLoopStatement {
         }
     }
 }
+
+/// Wraps what every rank runs in a fork-join over the thread pool (see
+/// `rosy_lib::threads`). Each thread works on its own copies of the
+/// variables the loop mentions, as an MPI rank would, and the program
+/// carries on with rank 0's copies.
+fn thread_pool_serialization(
+    end_declaration: &str,
+    rank_serialization: &str,
+    iterator: &str,
+    context: &TranspilationInputContext,
+) -> String {
+    let mut shared: Vec<(&String, &ScopedVariableData)> = context
+        .variables
+        .iter()
+        .filter(|(name, _)| *name != iterator && mentions(rank_serialization, name))
+        .collect();
+    shared.sort_by_key(|(name, _)| *name);
+
+    let mut copies = String::new();
+    let mut returns = String::new();
+    let mut write_backs = String::new();
+    for (index, (name, data)) in shared.iter().enumerate() {
+        match data.scope {
+            VariableScope::Local => {
+                copies.push_str(&format!("\t\tlet mut {name} = {name}.clone();\n"));
+                returns.push_str(&format!("{name}, "));
+                write_backs.push_str(&format!("\t{name} = __ploop_state.{index};\n"));
+            }
+            // Bound to `&mut T`, so the copy is borrowed under the same name
+            VariableScope::Arg | VariableScope::Higher => {
+                copies.push_str(&format!(
+                    "\t\tlet mut __ploop_{name} = (*{name}).clone();\n\t\tlet {name} = &mut __ploop_{name};\n"
+                ));
+                returns.push_str(&format!("__ploop_{name}, "));
+                write_backs.push_str(&format!("\t*{name} = __ploop_state.{index};\n"));
+            }
+        }
+    }
+
+    format!(
+        "{{\n\t{}\n\tlet __ploop_state = rosy_mpi_context.ploop(__ploop_end, |rosy_mpi_context: &mut RosyThreadContext| -> Result<_> {{\n\t\tlet mut __ploop_end: f64 = __ploop_end;\n{}\t\t{{\n{}\n\t\t}}\n\t\tOk(({}))\n\t}})?;\n{}}}",
+        end_declaration,
+        copies,
+        indent(indent(format!("\t{}", rank_serialization))),
+        returns.trim_end(),
+        write_backs
+    )
+}
+
+/// Whether `name` occurs in generated code as a whole identifier.
+fn mentions(code: &str, name: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    code.match_indices(name).any(|(start, _)| {
+        let before = code[..start].chars().next_back();
+        let after = code[start + name.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+impl Interpret for PLoopStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let groups: f64 = interpreter.evaluate_as(&self.end)?;
+        ensure!(groups >= 1.0, "A PLOOP needs at least one iteration, found {}", groups);
+        let groups = groups as usize;
+        let standard = Standard::from_code(self.commutivityfrom_rule.unwrap_or(1))
+            .with_context(|| format!("...in the PLOOP gathering '{}'", self.output.name))?;
+        let output_type = interpreter
+            .variable_type(&self.output.name)
+            .ok_or_else(|| anyhow!("Variable '{}' is not defined in this scope!", self.output.name))?;
+        let empty_slot = if output_type == RosyType::VE() {
+            Value::RE(0.0)
+        } else {
+            Value::zero(&RosyType::new(output_type.base_type, 0))
+        };
+
+        // The groups run one after another, each on its own copy of the
+        // variables as a process would, and the program carries on with
+        // group 0's copy
+        let variables = interpreter.visible_variables();
+        let before: Vec<Value> = variables
+            .iter()
+            .map(|(_, slot)| slot.borrow().value.clone())
+            .collect();
+        let mut after = Vec::new();
+        let mut outputs = Vec::new();
+        for group in 0..groups {
+            for ((_, slot), value) in variables.iter().zip(&before) {
+                slot.borrow_mut().value = value.clone();
+            }
+            let flow = interpreter.block(|interpreter| {
+                interpreter.declare(&self.iterator, RosyType::RE(), Value::RE((group + 1) as f64))?;
+                interpreter.run(&self.body)
+            })?;
+            if let quit @ Flow::Quit(_) = flow {
+                return Ok(quit);
+            }
+            outputs.push(self.output.read(interpreter)?);
+            if group == 0 {
+                after = variables
+                    .iter()
+                    .map(|(_, slot)| slot.borrow().value.clone())
+                    .collect();
+            }
+        }
+        for ((_, slot), value) in variables.iter().zip(after) {
+            slot.borrow_mut().value = value;
+        }
+
+        // Every copy started from the root's slots, which is all a scatter
+        // hands out
+        if standard != Standard::Scatter {
+            let mut output = outputs[0].clone();
+            coordinate(&mut output, &outputs, standard, &empty_slot)?;
+            self.output.write(interpreter, output)?;
+        }
+        Ok(Flow::Next)
+    }
+}
+
+/// Shares the groups' `outputs` over the last dimension of `value`, group
+/// 0's output, as `coordinate` does between processes.
+fn coordinate(value: &mut Value, outputs: &[Value], standard: Standard, empty_slot: &Value) -> Result<()> {
+    if let Value::Array(rows) = value
+        && matches!(rows.first(), Some(Value::Array(_)))
+    {
+        for (index, row) in rows.iter_mut().enumerate() {
+            let group_rows: Vec<Value> = outputs
+                .iter()
+                .map(|output| match output {
+                    Value::Array(rows) => rows.get(index).cloned().unwrap_or_else(|| row.clone()),
+                    other => other.clone(),
+                })
+                .collect();
+            coordinate(row, &group_rows, standard, empty_slot)?;
+        }
+        return Ok(());
+    }
+
+    let groups = outputs.len();
+    let mut slots = slots_of(value)?;
+    if slots.len() < groups {
+        slots.resize(groups, empty_slot.clone());
+    }
+    for (group, output) in outputs.iter().enumerate().skip(1) {
+        slots[group] = slots_of(output)?
+            .get(group)
+            .cloned()
+            .unwrap_or_else(|| empty_slot.clone());
+    }
+    if let Standard::Reduce(reduction) = standard {
+        let result = match slots.first() {
+            Some(Value::RE(_)) => reduce_as::<RE>(&slots[..groups], reduction)?,
+            Some(Value::VE(_)) => reduce_as::<VE>(&slots[..groups], reduction)?,
+            Some(Value::DA(_)) => reduce_as::<DA>(&slots[..groups], reduction)?,
+            Some(other) => bail!("Cannot take the {} of {} values", reduction, other.type_name()),
+            None => bail!("Cannot take the {} of no values", reduction),
+        };
+        slots[..groups].fill(result);
+    }
+
+    *value = match value {
+        Value::VE(_) => Value::VE(slots.iter().map(Value::to).collect::<Result<_>>()?),
+        _ => Value::Array(slots),
+    };
+    Ok(())
+}
+
+/// The slots of the last dimension of a PLOOP output.
+fn slots_of(value: &Value) -> Result<Vec<Value>> {
+    match value {
+        Value::VE(components) => Ok(components.iter().map(|x| Value::RE(*x)).collect()),
+        Value::Array(elements) => Ok(elements.clone()),
+        other => bail!("A PLOOP output must be an array, found {}", other.type_name()),
+    }
+}
+
+fn reduce_as<T: FromValue + Reducible>(slots: &[Value], reduction: Reduction) -> Result<Value>
+where
+    Value: From<T>,
+{
+    let slots = slots.iter().map(Value::to).collect::<Result<Vec<T>>>()?;
+    Ok(Value::from(crate::rosy_lib::ploop::reduce(&slots, reduction)?))
+}
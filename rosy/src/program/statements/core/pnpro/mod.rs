@@ -1,6 +1,7 @@
 //! # PNPRO Statement
 //!
-//! Returns the number of concurrent processes. Always 1 in serial mode.
+//! Returns the number of concurrent processes. Always 1 in serial mode; with
+//! `--threads`, the number of PLOOP threads.
 //!
 //! ## Syntax
 //!
@@ -32,6 +33,7 @@ use crate::{
     ast::*, program::expressions::core::variable_identifier::VariableIdentifier,
     program::statements::SourceLocation, resolve::*, transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
 
 #[derive(Debug)]
 pub struct PnproStatement {
@@ -131,3 +133,10 @@ impl Transpile for PnproStatement {
         })
     }
 }
+impl Interpret for PnproStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        // The interpreter runs every PLOOP in this one process
+        self.identifier.write(interpreter, Value::RE(1.0))?;
+        Ok(Flow::Next)
+    }
+}
@@ -34,10 +34,12 @@ use std::collections::BTreeSet;
 
 use crate::{
     ast::*,
+    parallel_config::parallel_backend,
     program::statements::*,
     resolve::{ScopeContext, TypeResolver, TypeSlot},
     transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
 
 /// AST node for a user-defined procedure declaration.
 #[derive(Debug)]
@@ -207,6 +209,9 @@ impl TranspileableStatement for ProcedureStatement {
 
         TypeHydrationResult::Hydrated { result: Ok(()) }
     }
+    fn is_declaration(&self) -> bool {
+        true
+    }
 }
 impl Transpile for ProcedureStatement {
     fn transpile(
@@ -344,8 +349,10 @@ impl Transpile for ProcedureStatement {
                 // procedure call chain as a typed reference so PLOOP works
                 // inside nested procedures, not just at top-level.
                 if var_name == "rosy_mpi_context" {
-                    serialized_args
-                        .push("rosy_mpi_context: &mut RosyMPIContext".to_string());
+                    serialized_args.push(format!(
+                        "rosy_mpi_context: &mut {}",
+                        parallel_backend().context_type()
+                    ));
                     continue;
                 }
 
@@ -377,9 +384,10 @@ impl Transpile for ProcedureStatement {
         };
 
         let serialization = format!(
-            "fn {} ( {} ) -> Result<()> {{\n{}\n\n\tOk(())\n}}",
+            "fn {} ( {} ) -> Result<()> {{\n\trosy_lib::core::trace::enter(\"PROCEDURE {}\");\n{}\n\n\trosy_lib::core::trace::leave();\n\tOk(())\n}}",
             self.name,
             serialized_args.join(", "),
+            self.name,
             indent(serialized_statements.join("\n"))
         );
         if errors.is_empty() {
@@ -393,3 +401,9 @@ impl Transpile for ProcedureStatement {
         }
     }
 }
+impl Interpret for ProcedureStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        interpreter.define_procedure(self);
+        Ok(Flow::Next)
+    }
+}
@@ -39,6 +39,7 @@ use crate::{
     resolve::*,
     transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
 
 /// AST node for a procedure call statement.
 #[derive(Debug)]
@@ -299,3 +300,20 @@ impl Transpile for ProcedureCallStatement {
         }
     }
 }
+impl Interpret for ProcedureCallStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let callable = interpreter.procedure(&self.name)?;
+        let procedure = callable.definition;
+        interpreter
+            .call(
+                format!("PROCEDURE {}", self.name),
+                callable.scope,
+                &procedure.args,
+                &self.args,
+                &procedure.body,
+                |_| Ok(()),
+            )
+            .with_context(|| format!("...while calling procedure '{}'", self.name))?;
+        Ok(Flow::Next)
+    }
+}
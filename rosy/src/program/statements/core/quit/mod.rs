@@ -38,6 +38,7 @@ use crate::{
     ast::*, program::expressions::Expr, program::statements::SourceLocation, resolve::*,
     transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
 
 /// AST node for `QUIT c;`.
 #[derive(Debug)]
@@ -122,3 +123,12 @@ impl Transpile for QuitStatement {
         })
     }
 }
+impl Interpret for QuitStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let code = interpreter.evaluate_as::<f64>(&self.code_expr)? as i32;
+        if code == 1 {
+            panic!("QUIT with traceback requested");
+        }
+        Ok(Flow::Quit(code))
+    }
+}
@@ -33,6 +33,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::core::rng::set_rng_seed;
 
 /// AST node for the `RANSEED seed;` statement.
 #[derive(Debug)]
@@ -110,3 +112,9 @@ impl Transpile for RanseedStatement {
         })
     }
 }
+impl Interpret for RanseedStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        set_rng_seed(interpreter.evaluate_as(&self.seed)?);
+        Ok(Flow::Next)
+    }
+}
@@ -39,6 +39,8 @@ use crate::{
     resolve::*,
     transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::core::recst::rosy_recst;
 
 #[derive(Debug)]
 pub struct RecstStatement {
@@ -169,3 +171,12 @@ impl Transpile for RecstStatement {
         })
     }
 }
+impl Interpret for RecstStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let value: f64 = interpreter.evaluate_as(&self.value_expr)?;
+        let format: String = interpreter.evaluate_as(&self.format_expr)?;
+        self.output_var
+            .write(interpreter, Value::ST(rosy_recst(value, &format)))?;
+        Ok(Flow::Next)
+    }
+}
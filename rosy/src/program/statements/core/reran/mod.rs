@@ -38,6 +38,8 @@ use crate::{
     rosy_lib::RosyType,
     transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::core::reran::rosy_reran;
 
 #[derive(Debug)]
 pub struct ReranStatement {
@@ -151,3 +153,9 @@ impl Transpile for ReranStatement {
         })
     }
 }
+impl Interpret for ReranStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        self.output_var.write(interpreter, Value::RE(rosy_reran()))?;
+        Ok(Flow::Next)
+    }
+}
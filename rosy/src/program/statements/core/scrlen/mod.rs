@@ -37,6 +37,7 @@ use crate::{
     ast::*, program::expressions::Expr, program::statements::SourceLocation, resolve::*,
     transpile::*,
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter};
 
 /// AST node for `SCRLEN c;`.
 #[derive(Debug)]
@@ -115,3 +116,9 @@ impl Transpile for ScrlenStatement {
         })
     }
 }
+impl Interpret for ScrlenStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        self.size_expr.evaluate(interpreter)?;
+        Ok(Flow::Next)
+    }
+}
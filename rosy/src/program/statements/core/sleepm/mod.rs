@@ -36,6 +36,7 @@ use crate::{
     ast::*, program::expressions::Expr, program::statements::SourceLocation, resolve::*,
     transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
 
 /// AST node for `SLEEPM c;`.
 #[derive(Debug)]
@@ -115,3 +116,10 @@ impl Transpile for SleepmStatement {
         })
     }
 }
+impl Interpret for SleepmStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let millis: f64 = interpreter.evaluate_as(&self.duration_expr)?;
+        std::thread::sleep(std::time::Duration::from_millis(millis as u64));
+        Ok(Flow::Next)
+    }
+}
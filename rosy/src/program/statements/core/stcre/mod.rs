@@ -38,6 +38,7 @@ use crate::{
     resolve::*,
     transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
 
 #[derive(Debug)]
 pub struct StcreStatement {
@@ -156,3 +157,14 @@ impl Transpile for StcreStatement {
         })
     }
 }
+impl Interpret for StcreStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let string: String = interpreter.evaluate_as(&self.string_expr)?;
+        let number = string
+            .trim()
+            .parse::<f64>()
+            .expect("STCRE: failed to parse string as real number");
+        self.output_var.write(interpreter, Value::RE(number))?;
+        Ok(Flow::Next)
+    }
+}
@@ -40,6 +40,7 @@ use crate::{
     resolve::*,
     transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
 
 /// AST node for `SUBSTR source first last destination;`.
 #[derive(Debug)]
@@ -205,3 +206,16 @@ impl Transpile for SubstrStatement {
         })
     }
 }
+impl Interpret for SubstrStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let source: String = interpreter.evaluate_as(&self.source_expr)?;
+        let first = interpreter.evaluate_as::<f64>(&self.first_expr)? as usize;
+        let last = interpreter.evaluate_as::<f64>(&self.last_expr)? as usize;
+        let substring = first
+            .checked_sub(1)
+            .and_then(|start| source.get(start..last))
+            .unwrap_or("");
+        self.dest.write(interpreter, Value::ST(substring.to_string()))?;
+        Ok(Flow::Next)
+    }
+}
@@ -43,6 +43,7 @@ use crate::{
     syntax_config,
     transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
 
 #[derive(Debug)]
 pub struct VariableDeclarationData {
@@ -77,6 +78,7 @@ impl Transpile for VariableDeclarationData {
             RosyBaseType::VE => "vec![]",
             RosyBaseType::DA => "DA::zero()",
             RosyBaseType::CD => "CD::zero()",
+            RosyBaseType::GR => "GR::default()",
         }
         .to_string();
 
@@ -292,6 +294,9 @@ impl TranspileableStatement for VarDeclStatement {
         }
         TypeHydrationResult::Hydrated { result: Ok(()) }
     }
+    fn is_declaration(&self) -> bool {
+        true
+    }
 }
 impl Transpile for VarDeclStatement {
     fn transpile(
@@ -351,3 +356,17 @@ impl Transpile for VarDeclStatement {
         })
     }
 }
+impl Interpret for VarDeclStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        // Unused variables have no resolved type, as in `transpile`
+        let Some(r#type) = self.data.r#type else {
+            return Ok(Flow::Next);
+        };
+        let mut lengths = Vec::new();
+        for dimension in &self.data.dimension_exprs {
+            lengths.push(interpreter.evaluate_as::<f64>(dimension)? as usize);
+        }
+        interpreter.declare(&self.data.name, r#type, Value::declared(&r#type, &lengths))?;
+        Ok(Flow::Next)
+    }
+}
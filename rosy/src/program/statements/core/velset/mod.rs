@@ -41,6 +41,7 @@ use crate::{
     resolve::{ScopeContext, TypeResolver},
     transpile::*,
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter};
 
 /// AST node for `VELSET vector component value;`.
 #[derive(Debug)]
@@ -187,3 +188,16 @@ impl Transpile for VelsetStatement {
         })
     }
 }
+impl Interpret for VelsetStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let component: f64 = interpreter.evaluate_as(&self.component_expr)?;
+        let value = self.value_expr.evaluate(interpreter)?;
+        let slot = interpreter.slot(&self.vector_ident.name)?;
+        let mut variable = slot.borrow_mut();
+        let r#type = variable.r#type;
+        variable
+            .value
+            .set(&[component], value, &r#type, &self.vector_ident.name)?;
+        Ok(Flow::Next)
+    }
+}
@@ -39,6 +39,7 @@ use crate::{
     rosy_lib::RosyType,
     transpile::*,
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
 
 #[derive(Debug)]
 pub struct WhileStatement {
@@ -188,3 +189,15 @@ impl Transpile for WhileStatement {
         }
     }
 }
+impl Interpret for WhileStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        while interpreter.evaluate_as(&self.condition)? {
+            match interpreter.run_block(&self.body)? {
+                Flow::Next => {}
+                Flow::Break => break,
+                quit @ Flow::Quit(_) => return Ok(quit),
+            }
+        }
+        Ok(Flow::Next)
+    }
+}
@@ -37,6 +37,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{CD, core::da_ops::rosy_cdf2};
 
 /// AST node for the `CDF2 input tune1 tune2 tune3 result;` statement.
 #[derive(Debug)]
@@ -165,3 +167,15 @@ impl Transpile for Cdf2Statement {
         })
     }
 }
+impl Interpret for Cdf2Statement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let input: Vec<CD> = interpreter.evaluate_as(&self.input)?;
+        let tune1 = interpreter.evaluate_as(&self.tune1)?;
+        let tune2 = interpreter.evaluate_as(&self.tune2)?;
+        let tune3 = interpreter.evaluate_as(&self.tune3)?;
+        interpreter.update(&self.result, |result: &mut Vec<CD>| {
+            rosy_cdf2(&input, tune1, tune2, tune3, result)
+        })?;
+        Ok(Flow::Next)
+    }
+}
@@ -38,6 +38,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{CD, core::da_ops::rosy_cdflo};
 
 /// AST node for the `CDFLO rhs ic result dim;` complex ODE flow statement.
 #[derive(Debug)]
@@ -148,3 +150,14 @@ impl Transpile for CdfloStatement {
         })
     }
 }
+impl Interpret for CdfloStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let rhs: Vec<CD> = interpreter.evaluate_as(&self.rhs)?;
+        let ic: Vec<CD> = interpreter.evaluate_as(&self.ic)?;
+        let dim = interpreter.evaluate_as::<f64>(&self.dim)? as usize;
+        interpreter.update(&self.result, |result: &mut Vec<CD>| {
+            rosy_cdflo(&rhs, &ic, result, dim)
+        })?;
+        Ok(Flow::Next)
+    }
+}
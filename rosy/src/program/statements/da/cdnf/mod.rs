@@ -36,6 +36,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{CD, core::da_ops::rosy_cdnf};
 
 /// AST node for `CDNF input t1 t2 t3 resonances res_dims n_res result;`.
 #[derive(Debug)]
@@ -179,3 +181,18 @@ impl Transpile for CdnfStatement {
         })
     }
 }
+impl Interpret for CdnfStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let input: Vec<CD> = interpreter.evaluate_as(&self.input)?;
+        let tune1 = interpreter.evaluate_as(&self.tune1)?;
+        let tune2 = interpreter.evaluate_as(&self.tune2)?;
+        let tune3 = interpreter.evaluate_as(&self.tune3)?;
+        let resonances: Vec<f64> = interpreter.evaluate_as(&self.resonances)?;
+        let res_dims: Vec<f64> = interpreter.evaluate_as(&self.res_dims)?;
+        let n_res = interpreter.evaluate_as::<f64>(&self.n_res)? as usize;
+        interpreter.update(&self.result, |result: &mut Vec<CD>| {
+            rosy_cdnf(&input, tune1, tune2, tune3, &resonances, &res_dims, n_res, result)
+        })?;
+        Ok(Flow::Next)
+    }
+}
@@ -35,6 +35,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{CD, core::da_ops::rosy_cdnfda};
 
 /// AST node for `CDNFDA input moduli arguments coord total epsilon result;`.
 #[derive(Debug)]
@@ -139,3 +141,17 @@ impl Transpile for CdnfdaStatement {
         })
     }
 }
+impl Interpret for CdnfdaStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let input: Vec<CD> = interpreter.evaluate_as(&self.input)?;
+        let moduli: Vec<f64> = interpreter.evaluate_as(&self.moduli)?;
+        let arguments: Vec<f64> = interpreter.evaluate_as(&self.arguments)?;
+        let coord = interpreter.evaluate_as::<f64>(&self.coord)? as usize;
+        let total = interpreter.evaluate_as::<f64>(&self.total)? as usize;
+        let epsilon = interpreter.evaluate_as(&self.epsilon)?;
+        interpreter.update(&self.result, |result: &mut Vec<CD>| {
+            rosy_cdnfda(&input, &moduli, &arguments, coord, total, epsilon, result)
+        })?;
+        Ok(Flow::Next)
+    }
+}
@@ -35,6 +35,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{CD, core::da_ops::rosy_cdnfds};
 
 /// AST node for `CDNFDS input moduli arguments spin_arg total epsilon result;`.
 #[derive(Debug)]
@@ -139,3 +141,17 @@ impl Transpile for CdnfdsStatement {
         })
     }
 }
+impl Interpret for CdnfdsStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let input: Vec<CD> = interpreter.evaluate_as(&self.input)?;
+        let moduli: Vec<f64> = interpreter.evaluate_as(&self.moduli)?;
+        let arguments: Vec<f64> = interpreter.evaluate_as(&self.arguments)?;
+        let spin_arg = interpreter.evaluate_as(&self.spin_arg)?;
+        let total = interpreter.evaluate_as::<f64>(&self.total)? as usize;
+        let epsilon = interpreter.evaluate_as(&self.epsilon)?;
+        interpreter.update(&self.result, |result: &mut Vec<CD>| {
+            rosy_cdnfds(&input, &moduli, &arguments, spin_arg, total, epsilon, result)
+        })?;
+        Ok(Flow::Next)
+    }
+}
@@ -7,10 +7,25 @@
 //!
 //! ```text
 //! DAINI order nvars; { note - Rosy doesn't need the 3rd or 4th args }
+//! DAINI order nvars unit nm weights;
 //! ```
 //!
 //! Must be called before any DA or CD operations.
 //!
+//! The optional 5th argument is a VE of per-variable order weights (a Rosy
+//! extension, equivalent to calling `DANOTW weights nvars;` first). A power
+//! of variable i then counts `weights(i)` towards the order, so with
+//! `DAINI 4 3 0 0 W;` and `W = 1&1&2` the third variable is carried to
+//! second order while the first two reach fourth order. Missing weights
+//! default to 1, and a weight above `order` is an error. Truncation in all
+//! DA arithmetic and intrinsics uses the weighted order, which is also the
+//! ORDER column of `DAPRV` output (exponents are shown multiplied by their
+//! weight, as in COSY); `DANOW` can report the norm of each weighted order.
+//!
+//! When every `nvars` argument in a program is a literal, the generated
+//! project's monomials are sized to the largest one (at least 6); otherwise
+//! they fall back to 16 variables.
+//!
 //! ## Rosy Example
 //! ```text
 #![doc = include_str!("test.rosy")]
@@ -40,6 +55,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult,
     },
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::taylor;
 
 /// AST node for the `DAINI order nvars [output_unit num_monomials_out];` statement.
 #[derive(Debug)]
@@ -52,6 +69,8 @@ pub struct DAInitStatement {
     /// Optional 4th argument: variable to receive the total number of monomials.
     /// COSY writes back C(order+nvars, nvars) into this variable.
     pub num_monomials_out: Option<Expr>,
+    /// Optional 5th argument: VE of per-variable order weights.
+    pub weights: Option<Expr>,
 }
 
 impl FromRule for DAInitStatement {
@@ -85,6 +104,7 @@ impl FromRule for DAInitStatement {
         // Parse optional 3rd argument (output unit for debug dump)
         let mut output_unit = None;
         let mut num_monomials_out = None;
+        let mut weights = None;
 
         if let Some(third_pair) = inner.next().filter(|p| p.as_rule() == Rule::expr) {
             let third_expr = Expr::from_rule(third_pair)
@@ -108,6 +128,12 @@ impl FromRule for DAInitStatement {
                         fourth_pair.as_rule()
                     ),
                 }
+
+                // Parse optional 5th argument (weights)
+                if let Some(fifth_pair) = inner.next().filter(|p| p.as_rule() == Rule::expr) {
+                    weights = Expr::from_rule(fifth_pair)
+                        .context("Failed to build weights expression in DAINI statement!")?;
+                }
             } else if syntax_config::is_cosy_syntax() {
                 anyhow::bail!(
                     "COSY syntax mode requires all 4 arguments in DAINI statements.\n\
@@ -128,6 +154,7 @@ impl FromRule for DAInitStatement {
             number_of_variables: num_vars_expr,
             output_unit,
             num_monomials_out,
+            weights,
         }))
     }
 }
@@ -183,12 +210,27 @@ impl Transpile for DAInitStatement {
         let mut requested_variables = order_output.requested_variables.clone();
         requested_variables.extend(num_vars_output.requested_variables.iter().cloned());
 
+        // Arg 5: weights, handed to init_taylor the same way DANOTW does
+        let mut serialization = String::from("taylor::cleanup_taylor();");
+        if let Some(ref weights_expr) = self.weights {
+            let weights_o = weights_expr.transpile(context).map_err(|errs| {
+                errs.into_iter()
+                    .map(|e| e.context("...while transpiling weights in DAINI"))
+                    .collect::<Vec<_>>()
+            })?;
+            requested_variables.extend(weights_o.requested_variables.iter().cloned());
+            serialization.push_str(&format!(
+                "\n\t\ttaylor::set_weight_vector(({}).iter().map(|&w| w as u32).collect())?;",
+                weights_o.as_ref()
+            ));
+        }
+
         // Base: init DA and capture monomial count
-        let mut serialization = format!(
-            "taylor::cleanup_taylor();\n\t\tlet __daini_nm = taylor::init_taylor({} as u32, {} as usize)?;",
+        serialization.push_str(&format!(
+            "\n\t\tlet __daini_nm = taylor::init_taylor({} as u32, {} as usize)?;",
             order_output.as_value(),
             num_vars_output.as_value()
-        );
+        ));
 
         // Arg 3: debug dump of addressing arrays if nonzero
         if let Some(ref unit_expr) = self.output_unit {
@@ -225,3 +267,34 @@ impl Transpile for DAInitStatement {
         })
     }
 }
+impl Interpret for DAInitStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let order: f64 = interpreter.evaluate_as(&self.order)?;
+        let number_of_variables: f64 = interpreter.evaluate_as(&self.number_of_variables)?;
+        // The interpreter runs on the runtime compiled into rosy itself, whose
+        // monomials are fixed at MAX_VARS wide; only compiled programs get a
+        // runtime sized to their DAINI
+        ensure!(
+            number_of_variables as usize <= taylor::MAX_VARS,
+            "DAINI asks for {} variables, but the interpreter supports at most {}; \
+             run the program without --interpret to compile it for more",
+            number_of_variables as usize,
+            taylor::MAX_VARS
+        );
+        taylor::cleanup_taylor();
+        if let Some(weights) = &self.weights {
+            let weights: Vec<f64> = interpreter.evaluate_as(weights)?;
+            taylor::set_weight_vector(weights.iter().map(|&w| w as u32).collect())?;
+        }
+        let monomials = taylor::init_taylor(order as u32, number_of_variables as usize)?;
+        if let Some(unit) = &self.output_unit
+            && interpreter.evaluate_as::<f64>(unit)? as i64 != 0
+        {
+            taylor::dump_addressing_arrays()?;
+        }
+        if let Some(num_monomials_out) = &self.num_monomials_out {
+            num_monomials_out.store(interpreter, Value::RE(monomials as f64))?;
+        }
+        Ok(Flow::Next)
+    }
+}
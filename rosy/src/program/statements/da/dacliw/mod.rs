@@ -43,6 +43,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::daprv::rosy_dacliw};
 
 /// AST node for `DACLIW da n linear;`.
 #[derive(Debug)]
@@ -152,3 +154,13 @@ impl Transpile for DacliwStatement {
         })
     }
 }
+impl Interpret for DacliwStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let da: Vec<DA> = interpreter.evaluate_as(&self.da_expr)?;
+        let n = interpreter.evaluate_as::<f64>(&self.n_expr)? as usize;
+        interpreter.update(&self.linear_expr, |linear: &mut Vec<f64>| {
+            rosy_dacliw(&da, n, linear)
+        })?;
+        Ok(Flow::Next)
+    }
+}
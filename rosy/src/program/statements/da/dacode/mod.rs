@@ -41,6 +41,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::core::da_ops::rosy_dacode;
 
 /// AST node for the `DACODE params size result;` monomial decode statement.
 #[derive(Debug)]
@@ -140,3 +142,13 @@ impl Transpile for DacodeStatement {
         })
     }
 }
+impl Interpret for DacodeStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let params: Vec<f64> = interpreter.evaluate_as(&self.params)?;
+        let size = interpreter.evaluate_as::<f64>(&self.size)? as usize;
+        interpreter.update(&self.result, |result: &mut Vec<Vec<f64>>| {
+            rosy_dacode(&params, size, result)
+        })?;
+        Ok(Flow::Next)
+    }
+}
@@ -45,6 +45,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::{DA, core::daprv::rosy_dacqlc};
 
 /// AST node for `DACQLC da n hessian linear constant;`.
 #[derive(Debug)]
@@ -186,3 +188,17 @@ impl Transpile for DacqlcStatement {
         })
     }
 }
+impl Interpret for DacqlcStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let da: Vec<DA> = interpreter.evaluate_as(&self.da_expr)?;
+        let n = interpreter.evaluate_as::<f64>(&self.n_expr)? as usize;
+        let mut hessian: Vec<Vec<f64>> = interpreter.evaluate_as(&self.hessian_expr)?;
+        let mut linear: Vec<f64> = interpreter.evaluate_as(&self.linear_expr)?;
+        let mut constant: f64 = interpreter.evaluate_as(&self.constant_expr)?;
+        rosy_dacqlc(&da, n, &mut hessian, &mut linear, &mut constant)?;
+        self.hessian_expr.store(interpreter, Value::from(hessian))?;
+        self.linear_expr.store(interpreter, Value::from(linear))?;
+        self.constant_expr.store(interpreter, Value::RE(constant))?;
+        Ok(Flow::Next)
+    }
+}
@@ -44,6 +44,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::da_ops::rosy_dader};
 
 /// AST node for `DADER da_var var_index;`.
 #[derive(Debug)]
@@ -140,3 +142,10 @@ impl Transpile for DaderStatement {
         })
     }
 }
+impl Interpret for DaderStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let index = interpreter.evaluate_as::<f64>(&self.index_expr)? as usize;
+        interpreter.update(&self.da_expr, |da: &mut Vec<DA>| rosy_dader(da, index))?;
+        Ok(Flow::Next)
+    }
+}
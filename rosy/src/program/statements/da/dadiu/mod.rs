@@ -43,6 +43,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::daprv::rosy_dadiu};
 
 /// AST node for `DADIU i da_in result;`.
 #[derive(Debug)]
@@ -148,3 +150,13 @@ impl Transpile for DadiuStatement {
         })
     }
 }
+impl Interpret for DadiuStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let var_idx = interpreter.evaluate_as::<f64>(&self.var_idx_expr)? as usize;
+        let da_in: Vec<DA> = interpreter.evaluate_as(&self.da_in_expr)?;
+        interpreter.update(&self.result_expr, |result: &mut Vec<DA>| {
+            rosy_dadiu(var_idx, &da_in, result)
+        })?;
+        Ok(Flow::Next)
+    }
+}
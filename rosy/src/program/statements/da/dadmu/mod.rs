@@ -44,6 +44,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::daprv::rosy_dadmu};
 
 /// AST node for `DADMU i j da_in result;`.
 #[derive(Debug)]
@@ -162,3 +164,14 @@ impl Transpile for DadmuStatement {
         })
     }
 }
+impl Interpret for DadmuStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let var_i = interpreter.evaluate_as::<f64>(&self.var_i_expr)? as usize;
+        let var_j = interpreter.evaluate_as::<f64>(&self.var_j_expr)? as usize;
+        let da_in: Vec<DA> = interpreter.evaluate_as(&self.da_in_expr)?;
+        interpreter.update(&self.result_expr, |result: &mut Vec<DA>| {
+            rosy_dadmu(var_i, var_j, &da_in, result)
+        })?;
+        Ok(Flow::Next)
+    }
+}
@@ -37,6 +37,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::taylor;
 
 /// AST node for the `DAEPS c;` DA epsilon statement.
 #[derive(Debug)]
@@ -114,3 +116,9 @@ impl Transpile for DaepsStatement {
         })
     }
 }
+impl Interpret for DaepsStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        taylor::set_epsilon(interpreter.evaluate_as(&self.epsilon)?)?;
+        Ok(Flow::Next)
+    }
+}
@@ -38,6 +38,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::taylor;
 
 /// AST node for the `DAEPSM v;` DA epsilon getter statement.
 #[derive(Debug)]
@@ -118,3 +120,10 @@ impl Transpile for DaepsmStatement {
         })
     }
 }
+impl Interpret for DaepsmStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let epsilon = taylor::get_config()?.epsilon;
+        self.result.store(interpreter, Value::RE(epsilon))?;
+        Ok(Flow::Next)
+    }
+}
@@ -39,6 +39,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::dapew::rosy_daest};
 
 /// AST node for `DAEST da_var i j result;`.
 #[derive(Debug)]
@@ -159,3 +161,12 @@ impl Transpile for DaestStatement {
         })
     }
 }
+impl Interpret for DaestStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let da: Vec<DA> = interpreter.evaluate_as(&self.da_var_expr)?;
+        let i = interpreter.evaluate_as::<f64>(&self.i_expr)? as usize;
+        let j = interpreter.evaluate_as::<f64>(&self.j_expr)? as u32;
+        interpreter.update(&self.result_expr, |result: &mut f64| rosy_daest(&da, i, j, result))?;
+        Ok(Flow::Next)
+    }
+}
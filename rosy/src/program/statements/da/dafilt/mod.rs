@@ -38,6 +38,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::da_ops::rosy_dafilt};
 
 /// AST node for the `DAFILT input result;` filter statement.
 #[derive(Debug)]
@@ -131,3 +133,10 @@ impl Transpile for DafiltStatement {
         })
     }
 }
+impl Interpret for DafiltStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let input: Vec<DA> = interpreter.evaluate_as(&self.input)?;
+        interpreter.update(&self.result, |result: &mut Vec<DA>| rosy_dafilt(&input, result))?;
+        Ok(Flow::Next)
+    }
+}
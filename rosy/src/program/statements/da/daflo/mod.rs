@@ -41,6 +41,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::da_ops::rosy_daflo};
 
 /// AST node for the `DAFLO rhs ic result dim;` ODE flow statement.
 #[derive(Debug)]
@@ -151,3 +153,14 @@ impl Transpile for DafloStatement {
         })
     }
 }
+impl Interpret for DafloStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let rhs: Vec<DA> = interpreter.evaluate_as(&self.rhs)?;
+        let ic: Vec<DA> = interpreter.evaluate_as(&self.ic)?;
+        let dim = interpreter.evaluate_as::<f64>(&self.dim)? as usize;
+        interpreter.update(&self.result, |result: &mut Vec<DA>| {
+            rosy_daflo(&rhs, &ic, result, dim)
+        })?;
+        Ok(Flow::Next)
+    }
+}
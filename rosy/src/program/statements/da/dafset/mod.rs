@@ -38,6 +38,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::core::da_ops::rosy_dafset;
 
 /// AST node for the `DAFSET template_da;` filter set statement.
 #[derive(Debug)]
@@ -116,3 +118,9 @@ impl Transpile for DafsetStatement {
         })
     }
 }
+impl Interpret for DafsetStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        rosy_dafset(interpreter.evaluate_as(&self.template)?)?;
+        Ok(Flow::Next)
+    }
+}
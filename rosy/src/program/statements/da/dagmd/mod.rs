@@ -38,6 +38,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::da_ops::rosy_dagmd};
 
 /// AST node for the `DAGMD g f result dim;` Lie derivative statement.
 #[derive(Debug)]
@@ -152,3 +154,12 @@ impl Transpile for DagmdStatement {
         })
     }
 }
+impl Interpret for DagmdStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let g: Vec<DA> = interpreter.evaluate_as(&self.g)?;
+        let f: Vec<DA> = interpreter.evaluate_as(&self.f)?;
+        let dim = interpreter.evaluate_as::<f64>(&self.dim)? as usize;
+        interpreter.update(&self.result, |result: &mut Vec<DA>| rosy_dagmd(&g, &f, result, dim))?;
+        Ok(Flow::Next)
+    }
+}
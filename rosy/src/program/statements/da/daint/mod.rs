@@ -45,6 +45,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::da_ops::rosy_daint};
 
 /// AST node for `DAINT da_var var_index;`.
 #[derive(Debug)]
@@ -141,3 +143,10 @@ impl Transpile for DaintStatement {
         })
     }
 }
+impl Interpret for DaintStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let index = interpreter.evaluate_as::<f64>(&self.index_expr)? as usize;
+        interpreter.update(&self.da_expr, |da: &mut Vec<DA>| rosy_daint(da, index))?;
+        Ok(Flow::Next)
+    }
+}
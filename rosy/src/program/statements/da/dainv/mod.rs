@@ -0,0 +1,163 @@
+//! # DAINV Statement (DA Map Inversion)
+//!
+//! Inverts a DA map whose linear part is invertible, so that composing the
+//! result with the map (in either order) gives the identity up to the
+//! current truncation order. Used for reverse tracking and for checking
+//! that a map is symplectic.
+//!
+//! ## Syntax
+//!
+//! ```text
+//! DAINV map n inverse;
+//! ```
+//!
+//! Arguments:
+//! 1. `map`     (DA vector, read)  - the map, one component per variable
+//! 2. `n`       (RE, read)         - number of map components / variables
+//! 3. `inverse` (DA vector, write) - the inverse map
+//!
+//! Only the first `n` DA variables are inverted; any further variables are
+//! treated as parameters and left unchanged. Constant parts of the map are
+//! ignored. The linear part is inverted with the same elimination as `LINV`
+//! and a singular linear part is a runtime error.
+//!
+//! ## Rosy Example
+//! ```text
+#![doc = include_str!("test.rosy")]
+//! ```
+//! **Output**:
+//! ```text
+#![doc = include_str!("rosy_output.txt")]
+//! ```
+//!
+//! Note: COSY has no map inversion intrinsic (it is done by procedures in
+//! the FOX library), so there is no `test.fox` parity fixture.
+
+use anyhow::{Context, Error, Result, ensure};
+use std::collections::BTreeSet;
+
+use crate::{
+    ast::*,
+    program::{expressions::Expr, statements::SourceLocation},
+    resolve::{ScopeContext, TypeResolver},
+    transpile::{
+        InferenceEdgeResult, TranspilationInputContext, TranspilationOutput, Transpile,
+        TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
+    },
+};
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::da_ops::rosy_dainv};
+
+/// AST node for `DAINV map n inverse;`.
+#[derive(Debug)]
+pub struct DainvStatement {
+    pub map_expr: Expr,
+    pub n_expr: Expr,
+    pub inverse_expr: Expr,
+}
+
+impl FromRule for DainvStatement {
+    fn from_rule(pair: pest::iterators::Pair<Rule>) -> Result<Option<Self>> {
+        ensure!(
+            pair.as_rule() == Rule::dainv,
+            "Expected `dainv` rule when building DAINV statement, found: {:?}",
+            pair.as_rule()
+        );
+
+        let mut inner = pair.into_inner();
+
+        let map_pair = inner.next().context("Missing map parameter in DAINV!")?;
+        let map_expr = Expr::from_rule(map_pair)
+            .context("Failed to build map expression in DAINV")?
+            .ok_or_else(|| anyhow::anyhow!("Expected map expression in DAINV"))?;
+
+        let n_pair = inner.next().context("Missing n parameter in DAINV!")?;
+        let n_expr = Expr::from_rule(n_pair)
+            .context("Failed to build n expression in DAINV")?
+            .ok_or_else(|| anyhow::anyhow!("Expected n expression in DAINV"))?;
+
+        let inverse_pair = inner.next().context("Missing inverse parameter in DAINV!")?;
+        let inverse_expr = Expr::from_rule(inverse_pair)
+            .context("Failed to build inverse expression in DAINV")?
+            .ok_or_else(|| anyhow::anyhow!("Expected inverse expression in DAINV"))?;
+
+        Ok(Some(DainvStatement {
+            map_expr,
+            n_expr,
+            inverse_expr,
+        }))
+    }
+}
+
+impl TranspileableStatement for DainvStatement {
+    fn register_typeslot_declaration(
+        &self,
+        _resolver: &mut TypeResolver,
+        _ctx: &mut ScopeContext,
+        _source_location: SourceLocation,
+    ) -> TypeslotDeclarationResult {
+        TypeslotDeclarationResult::NotAVarFuncOrProcedureDecl
+    }
+    fn wire_inference_edges(
+        &self,
+        _resolver: &mut TypeResolver,
+        _ctx: &mut ScopeContext,
+        _source_location: SourceLocation,
+    ) -> InferenceEdgeResult {
+        InferenceEdgeResult::NoEdges
+    }
+    fn hydrate_resolved_types(
+        &mut self,
+        _resolver: &TypeResolver,
+        _current_scope: &[String],
+    ) -> TypeHydrationResult {
+        TypeHydrationResult::NothingToHydrate
+    }
+}
+
+impl Transpile for DainvStatement {
+    fn transpile(
+        &self,
+        context: &mut TranspilationInputContext,
+    ) -> Result<TranspilationOutput, Vec<Error>> {
+        let mut requested_variables = BTreeSet::new();
+
+        let map_output = self.map_expr.transpile(context).map_err(|e| {
+            add_context_to_all(e, "...while transpiling map in DAINV".to_string())
+        })?;
+        requested_variables.extend(map_output.requested_variables.iter().cloned());
+
+        let n_output = self.n_expr.transpile(context).map_err(|e| {
+            add_context_to_all(e, "...while transpiling n in DAINV".to_string())
+        })?;
+        requested_variables.extend(n_output.requested_variables.iter().cloned());
+
+        let inverse_output = self.inverse_expr.transpile(context).map_err(|e| {
+            add_context_to_all(e, "...while transpiling inverse in DAINV".to_string())
+        })?;
+        requested_variables.extend(inverse_output.requested_variables.clone());
+
+        let serialization = format!(
+            "rosy_lib::core::da_ops::rosy_dainv({}, {} as usize, {})?;",
+            map_output.as_ref(),
+            n_output.as_value(),
+            inverse_output.as_mut_ref(),
+        );
+
+        Ok(TranspilationOutput {
+            serialization,
+            requested_variables,
+            ..Default::default()
+        })
+    }
+}
+impl Interpret for DainvStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let map: Vec<DA> = interpreter.evaluate_as(&self.map_expr)?;
+        let n = interpreter.evaluate_as::<f64>(&self.n_expr)? as usize;
+        interpreter.update(&self.inverse_expr, |inverse: &mut Vec<DA>| {
+            rosy_dainv(&map, n, inverse)
+        })?;
+        Ok(Flow::Next)
+    }
+}
@@ -44,6 +44,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::da_ops::rosy_danoro};
 
 /// AST node for `DANORO da_var;`.
 #[derive(Debug)]
@@ -119,3 +121,9 @@ impl Transpile for DanoroStatement {
         })
     }
 }
+impl Interpret for DanoroStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        interpreter.update(&self.da_expr, |da: &mut Vec<DA>| rosy_danoro(da))?;
+        Ok(Flow::Next)
+    }
+}
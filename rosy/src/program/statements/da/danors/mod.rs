@@ -45,6 +45,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::da_ops::rosy_danors};
 
 /// AST node for `DANORS da_var threshold;`.
 #[derive(Debug)]
@@ -143,3 +145,10 @@ impl Transpile for DanorsStatement {
         })
     }
 }
+impl Interpret for DanorsStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let threshold = interpreter.evaluate_as(&self.threshold_expr)?;
+        interpreter.update(&self.da_expr, |da: &mut Vec<DA>| rosy_danors(da, threshold))?;
+        Ok(Flow::Next)
+    }
+}
@@ -36,6 +36,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::taylor;
 
 /// AST node for the `DANOT c;` DA truncation order statement.
 #[derive(Debug)]
@@ -111,3 +113,10 @@ impl Transpile for DanotStatement {
         })
     }
 }
+impl Interpret for DanotStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let order: f64 = interpreter.evaluate_as(&self.order)?;
+        taylor::set_truncation_order(order as u32)?;
+        Ok(Flow::Next)
+    }
+}
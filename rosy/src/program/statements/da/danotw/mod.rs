@@ -4,6 +4,10 @@
 //! Must be called before DAINI. The next `DAINI` call enumerates monomials
 //! where `Σ wᵢ·eᵢ ≤ max_order`, then clears the weight vector.
 //!
+//! Exponents are still powers of the variables wherever a program supplies
+//! or sees them: in `|` extraction, `DAPEE`/`DAPEA`/`DAPEP`, `WRITE` and
+//! `DAPRV`. Only the ORDER column shows the weighted order `Σ wᵢ·eᵢ`.
+//!
 //! ## Syntax
 //!
 //! ```text
@@ -39,6 +43,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::taylor;
 
 /// AST node for the `DANOTW weights size;` weighted order statement.
 #[derive(Debug)]
@@ -125,3 +131,11 @@ impl Transpile for DanotwStatement {
         })
     }
 }
+impl Interpret for DanotwStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let weights: Vec<f64> = interpreter.evaluate_as(&self.weights)?;
+        let size = interpreter.evaluate_as::<f64>(&self.size)? as usize;
+        taylor::set_weight_vector(weights.iter().take(size).map(|&w| w as u32).collect())?;
+        Ok(Flow::Next)
+    }
+}
@@ -1,15 +1,22 @@
 //! # DANOW Statement (DA Order-Weighted Norm)
 //!
 //! Computes the order-weighted max norm of a DA variable.
-//! For each monomial of order k with coefficient c, computes |c| * k^weight,
+//! For each monomial of order k with coefficient c, computes |c| * weight^k,
 //! then returns the maximum over all monomials.
 //!
 //! ## Syntax
 //!
 //! ```text
 //! DANOW da_var weight result;
+//! DANOW da_var weight result by_order;
 //! ```
 //!
+//! The optional `by_order` VE receives one entry per order 0 to the current
+//! truncation order, holding the largest |c| * weight^k among the terms of
+//! that order. With weighted DA (see `DANOTW` and `DAINI`) the order of a
+//! term is its weighted order, so this shows how far each weighted order
+//! has been carried.
+//!
 //! ## Rosy Example
 //! ```text
 #![doc = include_str!("test.rosy")]
@@ -39,13 +46,17 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::{DA, core::da_ops::{rosy_danow, rosy_danow_by_order}};
 
-/// AST node for the `DANOW da_var weight result;` order-weighted norm statement.
+/// AST node for the `DANOW da_var weight result [by_order];` order-weighted norm statement.
 #[derive(Debug)]
 pub struct DanowStatement {
     pub da_var: Expr,
     pub weight: Expr,
     pub result: Expr,
+    /// Optional 4th argument: VE receiving the norm of each order separately.
+    pub by_order: Option<Expr>,
 }
 
 impl FromRule for DanowStatement {
@@ -70,10 +81,21 @@ impl FromRule for DanowStatement {
             .context("Failed to build result expression in DANOW")?
             .ok_or_else(|| anyhow::anyhow!("Expected result expression in DANOW"))?;
 
+        let by_order = inner
+            .next()
+            .filter(|p| p.as_rule() == Rule::expr)
+            .map(|p| {
+                Expr::from_rule(p)
+                    .context("Failed to build by_order expression in DANOW")?
+                    .ok_or_else(|| anyhow::anyhow!("Expected by_order expression in DANOW"))
+            })
+            .transpose()?;
+
         Ok(Some(DanowStatement {
             da_var,
             weight,
             result,
+            by_order,
         }))
     }
 }
@@ -131,12 +153,27 @@ impl Transpile for DanowStatement {
 
         let da_ref = da_var_output.as_ref();
 
-        let serialization = format!(
-            "rosy_lib::core::da_ops::rosy_danow({}, {}, {})?;",
-            da_ref,
-            weight_output.as_value(),
-            result_ref,
-        );
+        let serialization = match &self.by_order {
+            None => format!(
+                "rosy_lib::core::da_ops::rosy_danow({}, {}, {})?;",
+                da_ref,
+                weight_output.as_value(),
+                result_ref,
+            ),
+            Some(by_order) => {
+                let by_order_output = by_order.transpile(context).map_err(|e| {
+                    add_context_to_all(e, "...while transpiling by_order in DANOW".to_string())
+                })?;
+                requested_variables.extend(by_order_output.requested_variables.iter().cloned());
+                format!(
+                    "rosy_lib::core::da_ops::rosy_danow_by_order({}, {}, {}, {})?;",
+                    da_ref,
+                    weight_output.as_value(),
+                    result_ref,
+                    by_order_output.as_mut_ref(),
+                )
+            }
+        };
 
         Ok(TranspilationOutput {
             serialization,
@@ -145,3 +182,20 @@ impl Transpile for DanowStatement {
         })
     }
 }
+impl Interpret for DanowStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let da: DA = interpreter.evaluate_as(&self.da_var)?;
+        let weight = interpreter.evaluate_as(&self.weight)?;
+        let mut result: f64 = interpreter.evaluate_as(&self.result)?;
+        match &self.by_order {
+            None => rosy_danow(&da, weight, &mut result)?,
+            Some(by_order_expr) => {
+                let mut by_order: Vec<f64> = interpreter.evaluate_as(by_order_expr)?;
+                rosy_danow_by_order(&da, weight, &mut result, &mut by_order)?;
+                by_order_expr.store(interpreter, Value::from(by_order))?;
+            }
+        }
+        self.result.store(interpreter, Value::RE(result))?;
+        Ok(Flow::Next)
+    }
+}
@@ -38,6 +38,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::dapew::rosy_dapea};
 
 /// AST node for `DAPEA da_var exps_array size result;`.
 #[derive(Debug)]
@@ -159,3 +161,14 @@ impl Transpile for DapeaStatement {
         })
     }
 }
+impl Interpret for DapeaStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let da: Vec<DA> = interpreter.evaluate_as(&self.da_var_expr)?;
+        let exps: Vec<f64> = interpreter.evaluate_as(&self.exps_array_expr)?;
+        let size = interpreter.evaluate_as::<f64>(&self.size_expr)? as usize;
+        interpreter.update(&self.result_expr, |result: &mut f64| {
+            rosy_dapea(&da, &exps, size, result)
+        })?;
+        Ok(Flow::Next)
+    }
+}
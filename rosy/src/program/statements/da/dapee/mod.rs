@@ -38,6 +38,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::dapew::rosy_dapee};
 
 /// AST node for `DAPEE da_var id result;`.
 #[derive(Debug)]
@@ -144,3 +146,11 @@ impl Transpile for DapeeStatement {
         })
     }
 }
+impl Interpret for DapeeStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let da: Vec<DA> = interpreter.evaluate_as(&self.da_var_expr)?;
+        let id = interpreter.evaluate_as::<f64>(&self.id_expr)? as u64;
+        interpreter.update(&self.result_expr, |result: &mut f64| rosy_dapee(&da, id, result))?;
+        Ok(Flow::Next)
+    }
+}
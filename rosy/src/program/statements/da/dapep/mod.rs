@@ -39,6 +39,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::dapew::rosy_dapep};
 
 /// AST node for `DAPEP da_var id m result;`.
 #[derive(Debug)]
@@ -159,3 +161,14 @@ impl Transpile for DapepStatement {
         })
     }
 }
+impl Interpret for DapepStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let da: Vec<DA> = interpreter.evaluate_as(&self.da_var_expr)?;
+        let id = interpreter.evaluate_as::<f64>(&self.id_expr)? as u64;
+        let m = interpreter.evaluate_as::<f64>(&self.m_expr)? as usize;
+        interpreter.update(&self.result_expr, |result: &mut Vec<DA>| {
+            rosy_dapep(&da, id, m, result)
+        })?;
+        Ok(Flow::Next)
+    }
+}
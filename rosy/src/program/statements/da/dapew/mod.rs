@@ -38,6 +38,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::dapew::rosy_dapew};
 
 /// AST node for `DAPEW unit da_var var_i order_n;`.
 #[derive(Debug)]
@@ -158,3 +160,13 @@ impl Transpile for DapewStatement {
         })
     }
 }
+impl Interpret for DapewStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let unit = interpreter.evaluate_as::<f64>(&self.unit_expr)? as u64;
+        let da: Vec<DA> = interpreter.evaluate_as(&self.da_var_expr)?;
+        let var_i = interpreter.evaluate_as::<f64>(&self.var_i_expr)? as usize;
+        let order_n = interpreter.evaluate_as::<f64>(&self.order_n_expr)? as u32;
+        rosy_dapew(unit, &da, var_i, order_n)?;
+        Ok(Flow::Next)
+    }
+}
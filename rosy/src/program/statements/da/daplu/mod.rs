@@ -43,6 +43,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::daprv::rosy_daplu};
 
 /// AST node for `DAPLU da_in i C result;`.
 #[derive(Debug)]
@@ -173,3 +175,14 @@ impl Transpile for DapluStatement {
         })
     }
 }
+impl Interpret for DapluStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let da_in: Vec<DA> = interpreter.evaluate_as(&self.da_in_expr)?;
+        let var_idx = interpreter.evaluate_as::<f64>(&self.var_idx_expr)? as usize;
+        let c = interpreter.evaluate_as(&self.c_expr)?;
+        interpreter.update(&self.result_expr, |result: &mut Vec<DA>| {
+            rosy_daplu(&da_in, var_idx, c, result)
+        })?;
+        Ok(Flow::Next)
+    }
+}
@@ -37,6 +37,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::daprv::rosy_daprv};
 
 /// AST node for `DAPRV array num_components max_vars current_vars unit;`.
 /// DAPRV array num_components max_vars current_vars unit ;
@@ -176,3 +178,14 @@ impl Transpile for DaprvStatement {
         })
     }
 }
+impl Interpret for DaprvStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let array: Vec<DA> = interpreter.evaluate_as(&self.array_expr)?;
+        let num_components = interpreter.evaluate_as::<f64>(&self.num_components_expr)? as usize;
+        let max_vars = interpreter.evaluate_as::<f64>(&self.max_vars_expr)? as usize;
+        let current_vars = interpreter.evaluate_as::<f64>(&self.current_vars_expr)? as usize;
+        let unit = interpreter.evaluate_as::<f64>(&self.unit_expr)? as u64;
+        rosy_daprv(&array, num_components, max_vars, current_vars, unit)?;
+        Ok(Flow::Next)
+    }
+}
@@ -38,6 +38,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::da_ops::rosy_daran};
 
 /// AST node for the `DARAN da_var sparsity;` random DA fill statement.
 #[derive(Debug)]
@@ -128,3 +130,10 @@ impl Transpile for DaranStatement {
         })
     }
 }
+impl Interpret for DaranStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let sparsity = interpreter.evaluate_as(&self.sparsity)?;
+        interpreter.update(&self.da_var, |da: &mut Vec<DA>| rosy_daran(da, sparsity))?;
+        Ok(Flow::Next)
+    }
+}
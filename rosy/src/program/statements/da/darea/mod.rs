@@ -38,6 +38,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::dapew::rosy_darea};
 
 /// AST node for `DAREA unit da_var num_vars;`.
 #[derive(Debug)]
@@ -146,3 +148,11 @@ impl Transpile for DareaStatement {
         })
     }
 }
+impl Interpret for DareaStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let unit = interpreter.evaluate_as::<f64>(&self.unit_expr)? as u64;
+        let num_vars = interpreter.evaluate_as::<f64>(&self.num_vars_expr)? as usize;
+        interpreter.update(&self.da_var_expr, |da: &mut Vec<DA>| rosy_darea(unit, da, num_vars))?;
+        Ok(Flow::Next)
+    }
+}
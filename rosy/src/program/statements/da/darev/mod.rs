@@ -41,6 +41,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::daprv::rosy_darev};
 
 /// AST node for `DAREV array num_components max_vars current_vars unit;`.
 /// DAREV array num_components max_vars current_vars unit ;
@@ -180,3 +182,15 @@ impl Transpile for DarevStatement {
         })
     }
 }
+impl Interpret for DarevStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let num_components = interpreter.evaluate_as::<f64>(&self.num_components_expr)? as usize;
+        let max_vars = interpreter.evaluate_as::<f64>(&self.max_vars_expr)? as usize;
+        let current_vars = interpreter.evaluate_as::<f64>(&self.current_vars_expr)? as usize;
+        let unit = interpreter.evaluate_as::<f64>(&self.unit_expr)? as u64;
+        interpreter.update(&self.array_expr, |array: &mut Vec<DA>| {
+            rosy_darev(array, num_components, max_vars, current_vars, unit)
+        })?;
+        Ok(Flow::Next)
+    }
+}
@@ -45,6 +45,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::da_ops::rosy_dascl};
 
 /// AST node for `DASCL da_var scalar;`.
 #[derive(Debug)]
@@ -139,3 +141,10 @@ impl Transpile for DasclStatement {
         })
     }
 }
+impl Interpret for DasclStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let scalar = interpreter.evaluate_as(&self.scalar_expr)?;
+        interpreter.update(&self.da_expr, |da: &mut Vec<DA>| rosy_dascl(da, scalar))?;
+        Ok(Flow::Next)
+    }
+}
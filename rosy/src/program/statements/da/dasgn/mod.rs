@@ -43,6 +43,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::da_ops::rosy_dasgn};
 
 /// AST node for `DASGN da_var;`.
 #[derive(Debug)]
@@ -116,3 +118,9 @@ impl Transpile for DasgnStatement {
         })
     }
 }
+impl Interpret for DasgnStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        interpreter.update(&self.da_expr, |da: &mut Vec<DA>| rosy_dasgn(da))?;
+        Ok(Flow::Next)
+    }
+}
@@ -49,6 +49,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::daprv::rosy_datrn};
 
 /// AST node for `DATRN input scales shifts m1 m2 output;`.
 #[derive(Debug)]
@@ -195,3 +197,16 @@ impl Transpile for DatrnStatement {
         })
     }
 }
+impl Interpret for DatrnStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let input: Vec<DA> = interpreter.evaluate_as(&self.input_expr)?;
+        let scales: Vec<f64> = interpreter.evaluate_as(&self.scales_expr)?;
+        let shifts: Vec<f64> = interpreter.evaluate_as(&self.shifts_expr)?;
+        let m1 = interpreter.evaluate_as::<f64>(&self.m1_expr)? as usize;
+        let m2 = interpreter.evaluate_as::<f64>(&self.m2_expr)? as usize;
+        interpreter.update(&self.output_expr, |output: &mut Vec<DA>| {
+            rosy_datrn(&input, &scales, &shifts, m1, m2, output)
+        })?;
+        Ok(Flow::Next)
+    }
+}
@@ -38,6 +38,7 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter, Value};
 
 /// AST node for the `EPSMIN v;` machine underflow threshold statement.
 #[derive(Debug)]
@@ -116,3 +117,9 @@ impl Transpile for EpsminStatement {
         })
     }
 }
+impl Interpret for EpsminStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        self.result.store(interpreter, Value::RE(f64::MIN_POSITIVE))?;
+        Ok(Flow::Next)
+    }
+}
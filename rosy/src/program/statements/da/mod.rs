@@ -41,6 +41,7 @@
 //! - **[`daplu`]** — `DAPLU da_in i C result;` — plug (replace variable xᵢ with constant C)
 //! - **[`dadiu`]** — `DADIU i da_in result;` — divide by independent variable xᵢ
 //! - **[`dadmu`]** — `DADMU i j da_in result;` — divide by xᵢ then multiply by xⱼ
+//! - **[`dainv`]** — `DAINV map n inverse;` — invert a DA map
 //!
 //! ## Analysis
 //!
@@ -88,4 +89,5 @@ pub mod cdnf;
 pub mod cdnfda;
 pub mod cdnfds;
 pub mod daran;
-pub mod dacode;
\ No newline at end of file
+pub mod dacode;
+pub mod dainv;
\ No newline at end of file
@@ -49,6 +49,8 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::{DA, core::mtree::rosy_mtree};
 
 #[derive(Debug)]
 pub struct MtreeStatement {
@@ -245,3 +247,17 @@ impl Transpile for MtreeStatement {
         })
     }
 }
+impl Interpret for MtreeStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let da_array: Vec<DA> = interpreter.evaluate_as(&self.da_array_expr)?;
+        let elements = interpreter.evaluate_as::<f64>(&self.elements_expr)? as usize;
+        let (coefficients, steer1, steer2, elements2, tree_length) =
+            rosy_mtree(&da_array, elements)?;
+        self.coeff_array_expr.store(interpreter, Value::from(coefficients))?;
+        self.steer1_expr.store(interpreter, Value::from(steer1))?;
+        self.steer2_expr.store(interpreter, Value::from(steer2))?;
+        self.elements2_expr.store(interpreter, Value::RE(elements2))?;
+        self.tree_length_expr.store(interpreter, Value::RE(tree_length))?;
+        Ok(Flow::Next)
+    }
+}
@@ -0,0 +1,38 @@
+//! # GRCHAR Statement
+//!
+//! Builds a graphics object that places a string at the current pen position.
+//!
+//! ## Syntax
+//!
+//! ```text
+//! GRCHAR text G;
+//! ```
+//!
+//! - `text` — ST to draw
+//! - `G` — variable that receives the GR object
+//!
+//! The text is anchored at its lower-left corner and uses the current colour.
+//!
+//! ## Rosy Example
+//! ```text
+#![doc = include_str!("test.rosy")]
+//! ```
+//! **Output**:
+//! ```text
+#![doc = include_str!("rosy_output.txt")]
+//! ```
+
+use crate::{
+    ast::Rule,
+    rosy_lib::{GR, RosyBaseType},
+};
+
+use super::GrPrimitiveSpec;
+
+pub const SPEC: GrPrimitiveSpec = GrPrimitiveSpec {
+    keyword: "GRCHAR",
+    rule: Rule::grchar,
+    arg_types: &[RosyBaseType::ST],
+    constructor: "GR::grchar",
+    build: |args| GR::grchar(&args[0].to::<String>()?),
+};
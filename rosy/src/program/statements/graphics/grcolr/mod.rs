@@ -0,0 +1,47 @@
+//! # GRCOLR Statement
+//!
+//! Builds a graphics object that switches the drawing colour.
+//!
+//! ## Syntax
+//!
+//! ```text
+//! GRCOLR n G;
+//! ```
+//!
+//! - `n` — RE index into the COSY colour table (1–10)
+//! - `G` — variable that receives the GR object
+//!
+//! | n | Colour | n | Colour |
+//! |---|--------|---|--------|
+//! | 1 | black | 6 | yellow-green |
+//! | 2 | blue | 7 | cyan |
+//! | 3 | red | 8 | magenta |
+//! | 4 | yellow | 9 | navy |
+//! | 5 | green | 10 | background (white) |
+//!
+//! The colour applies to every primitive that follows it. Indices outside
+//! 1–10 are a runtime error.
+//!
+//! ## Rosy Example
+//! ```text
+#![doc = include_str!("test.rosy")]
+//! ```
+//! **Output**:
+//! ```text
+#![doc = include_str!("rosy_output.txt")]
+//! ```
+
+use crate::{
+    ast::Rule,
+    rosy_lib::{GR, RosyBaseType},
+};
+
+use super::GrPrimitiveSpec;
+
+pub const SPEC: GrPrimitiveSpec = GrPrimitiveSpec {
+    keyword: "GRCOLR",
+    rule: Rule::grcolr,
+    arg_types: &[RosyBaseType::RE],
+    constructor: "GR::grcolr",
+    build: |args| GR::grcolr(args[0].to()?),
+};
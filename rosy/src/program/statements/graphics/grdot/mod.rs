@@ -0,0 +1,39 @@
+//! # GRDOT Statement
+//!
+//! Builds a graphics object that marks a single point, the usual primitive
+//! for phase-space portraits.
+//!
+//! ## Syntax
+//!
+//! ```text
+//! GRDOT x y z G;
+//! ```
+//!
+//! - `x`, `y`, `z` — RE coordinates of the point
+//! - `G` — variable that receives the GR object
+//!
+//! The pen moves to the dot. The dot radius follows the current `GRWDTH`.
+//!
+//! ## Rosy Example
+//! ```text
+#![doc = include_str!("test.rosy")]
+//! ```
+//! **Output**:
+//! ```text
+#![doc = include_str!("rosy_output.txt")]
+//! ```
+
+use crate::{
+    ast::Rule,
+    rosy_lib::{GR, RosyBaseType},
+};
+
+use super::GrPrimitiveSpec;
+
+pub const SPEC: GrPrimitiveSpec = GrPrimitiveSpec {
+    keyword: "GRDOT",
+    rule: Rule::grdot,
+    arg_types: &[RosyBaseType::RE, RosyBaseType::RE, RosyBaseType::RE],
+    constructor: "GR::grdot",
+    build: |args| GR::grdot(args[0].to()?, args[1].to()?, args[2].to()?),
+};
@@ -0,0 +1,39 @@
+//! # GRDRAW Statement
+//!
+//! Builds a graphics object that draws a straight line from the pen position to a point.
+//!
+//! ## Syntax
+//!
+//! ```text
+//! GRDRAW x y z G;
+//! ```
+//!
+//! - `x`, `y`, `z` — RE coordinates of the end point
+//! - `G` — variable that receives the GR object
+//!
+//! Consecutive draws form one polyline. A draw that is not preceded by a
+//! `GRMOVE` starts at the origin.
+//!
+//! ## Rosy Example
+//! ```text
+#![doc = include_str!("test.rosy")]
+//! ```
+//! **Output**:
+//! ```text
+#![doc = include_str!("rosy_output.txt")]
+//! ```
+
+use crate::{
+    ast::Rule,
+    rosy_lib::{GR, RosyBaseType},
+};
+
+use super::GrPrimitiveSpec;
+
+pub const SPEC: GrPrimitiveSpec = GrPrimitiveSpec {
+    keyword: "GRDRAW",
+    rule: Rule::grdraw,
+    arg_types: &[RosyBaseType::RE, RosyBaseType::RE, RosyBaseType::RE],
+    constructor: "GR::grdraw",
+    build: |args| GR::grdraw(args[0].to()?, args[1].to()?, args[2].to()?),
+};
@@ -0,0 +1,39 @@
+//! # GRMOVE Statement
+//!
+//! Builds a graphics object that lifts the pen and moves it to a point.
+//!
+//! ## Syntax
+//!
+//! ```text
+//! GRMOVE x y z G;
+//! ```
+//!
+//! - `x`, `y`, `z` — RE coordinates of the new pen position
+//! - `G` — variable that receives the GR object
+//!
+//! Nothing is drawn by the move itself; it starts a new line for the
+//! following `GRDRAW`s and sets the anchor for `GRCHAR`.
+//!
+//! ## Rosy Example
+//! ```text
+#![doc = include_str!("test.rosy")]
+//! ```
+//! **Output**:
+//! ```text
+#![doc = include_str!("rosy_output.txt")]
+//! ```
+
+use crate::{
+    ast::Rule,
+    rosy_lib::{GR, RosyBaseType},
+};
+
+use super::GrPrimitiveSpec;
+
+pub const SPEC: GrPrimitiveSpec = GrPrimitiveSpec {
+    keyword: "GRMOVE",
+    rule: Rule::grmove,
+    arg_types: &[RosyBaseType::RE, RosyBaseType::RE, RosyBaseType::RE],
+    constructor: "GR::grmove",
+    build: |args| GR::grmove(args[0].to()?, args[1].to()?, args[2].to()?),
+};
@@ -0,0 +1,41 @@
+//! # GRPROJ Statement
+//!
+//! Builds a graphics object that sets the viewing angles used to project
+//! three-dimensional pictures onto the page.
+//!
+//! ## Syntax
+//!
+//! ```text
+//! GRPROJ phi theta G;
+//! ```
+//!
+//! - `phi` — RE azimuth in degrees (rotation about the Z axis)
+//! - `theta` — RE elevation in degrees (tilt towards the Z axis)
+//! - `G` — variable that receives the GR object
+//!
+//! The projection applies to the whole picture; if several are present the
+//! last one wins. The default `0 0` shows the X–Y plane.
+//!
+//! ## Rosy Example
+//! ```text
+#![doc = include_str!("test.rosy")]
+//! ```
+//! **Output**:
+//! ```text
+#![doc = include_str!("rosy_output.txt")]
+//! ```
+
+use crate::{
+    ast::Rule,
+    rosy_lib::{GR, RosyBaseType},
+};
+
+use super::GrPrimitiveSpec;
+
+pub const SPEC: GrPrimitiveSpec = GrPrimitiveSpec {
+    keyword: "GRPROJ",
+    rule: Rule::grproj,
+    arg_types: &[RosyBaseType::RE, RosyBaseType::RE],
+    constructor: "GR::grproj",
+    build: |args| GR::grproj(args[0].to()?, args[1].to()?),
+};
@@ -0,0 +1,38 @@
+//! # GRWDTH Statement
+//!
+//! Builds a graphics object that sets the line width.
+//!
+//! ## Syntax
+//!
+//! ```text
+//! GRWDTH w G;
+//! ```
+//!
+//! - `w` — positive RE width in points (also the dot radius)
+//! - `G` — variable that receives the GR object
+//!
+//! The default width is 1.
+//!
+//! ## Rosy Example
+//! ```text
+#![doc = include_str!("test.rosy")]
+//! ```
+//! **Output**:
+//! ```text
+#![doc = include_str!("rosy_output.txt")]
+//! ```
+
+use crate::{
+    ast::Rule,
+    rosy_lib::{GR, RosyBaseType},
+};
+
+use super::GrPrimitiveSpec;
+
+pub const SPEC: GrPrimitiveSpec = GrPrimitiveSpec {
+    keyword: "GRWDTH",
+    rule: Rule::grwdth,
+    arg_types: &[RosyBaseType::RE],
+    constructor: "GR::grwdth",
+    build: |args| GR::grwdth(args[0].to()?),
+};
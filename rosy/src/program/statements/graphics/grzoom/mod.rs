@@ -0,0 +1,52 @@
+//! # GRZOOM Statement
+//!
+//! Builds a graphics object that fixes the plot window instead of fitting it
+//! to the data.
+//!
+//! ## Syntax
+//!
+//! ```text
+//! GRZOOM xl xr yl yr zl zr G;
+//! ```
+//!
+//! - `xl`, `xr` — RE X range
+//! - `yl`, `yr` — RE Y range
+//! - `zl`, `zr` — RE Z range
+//! - `G` — variable that receives the GR object
+//!
+//! Like `GRPROJ`, the window applies to the whole picture and the last one
+//! wins. Primitives outside the window are clipped by the SVG and EPS
+//! backends.
+//!
+//! ## Rosy Example
+//! ```text
+#![doc = include_str!("test.rosy")]
+//! ```
+//! **Output**:
+//! ```text
+#![doc = include_str!("rosy_output.txt")]
+//! ```
+
+use crate::{
+    ast::Rule,
+    rosy_lib::{GR, RosyBaseType},
+};
+
+use super::GrPrimitiveSpec;
+
+pub const SPEC: GrPrimitiveSpec = GrPrimitiveSpec {
+    keyword: "GRZOOM",
+    rule: Rule::grzoom,
+    arg_types: &[RosyBaseType::RE, RosyBaseType::RE, RosyBaseType::RE, RosyBaseType::RE, RosyBaseType::RE, RosyBaseType::RE],
+    constructor: "GR::grzoom",
+    build: |args| {
+        GR::grzoom(
+            args[0].to()?,
+            args[1].to()?,
+            args[2].to()?,
+            args[3].to()?,
+            args[4].to()?,
+            args[5].to()?,
+        )
+    },
+};
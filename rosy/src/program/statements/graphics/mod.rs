@@ -0,0 +1,259 @@
+//! # Graphics Statements
+//!
+//! Procedures that build `GR` graphics objects. Each one stores a single
+//! primitive in its last argument; pictures are assembled with `&` and
+//! written with `WRITE` (see [`crate::rosy_lib::graphics`] for the output
+//! formats).
+//!
+//! - **[`grmove`]** — `GRMOVE x y z G;` — move the pen
+//! - **[`grdraw`]** — `GRDRAW x y z G;` — draw a line to a point
+//! - **[`grdot`]** — `GRDOT x y z G;` — mark a point
+//! - **[`grchar`]** — `GRCHAR text G;` — place text at the pen
+//! - **[`grcolr`]** — `GRCOLR n G;` — select a colour
+//! - **[`grwdth`]** — `GRWDTH w G;` — set the line width
+//! - **[`grproj`]** — `GRPROJ phi theta G;` — set the 3D viewing angles
+//! - **[`grzoom`]** — `GRZOOM xl xr yl yr zl zr G;` — set the plot window
+//!
+//! All eight share [`GrPrimitiveStatement`]; the sub-modules hold each
+//! procedure's [`GrPrimitiveSpec`], documentation and tests.
+
+pub mod grchar;
+pub mod grcolr;
+pub mod grdot;
+pub mod grdraw;
+pub mod grmove;
+pub mod grproj;
+pub mod grwdth;
+pub mod grzoom;
+
+use anyhow::{Context, Error, Result, anyhow};
+use std::collections::BTreeSet;
+
+use crate::{
+    ast::*,
+    program::{
+        expressions::{Expr, core::variable_identifier::VariableIdentifier},
+        statements::SourceLocation,
+    },
+    resolve::*,
+    rosy_lib::{GR, RosyBaseType, RosyType},
+    transpile::*,
+};
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter, Value};
+
+/// Signature of one graphics primitive procedure.
+#[derive(Debug)]
+pub struct GrPrimitiveSpec {
+    pub keyword: &'static str,
+    pub rule: Rule,
+    /// Expected scalar type of each argument before the output variable.
+    pub arg_types: &'static [RosyBaseType],
+    /// Runtime constructor called with the arguments, e.g. `GR::grmove`.
+    pub constructor: &'static str,
+    /// The same constructor, for the interpreter.
+    pub build: fn(&[Value]) -> Result<GR>,
+}
+
+const PRIMITIVES: &[&GrPrimitiveSpec] = &[
+    &grmove::SPEC,
+    &grdraw::SPEC,
+    &grdot::SPEC,
+    &grchar::SPEC,
+    &grcolr::SPEC,
+    &grwdth::SPEC,
+    &grproj::SPEC,
+    &grzoom::SPEC,
+];
+
+/// AST node for any of the `GR*` primitive procedures.
+#[derive(Debug)]
+pub struct GrPrimitiveStatement {
+    pub spec: &'static GrPrimitiveSpec,
+    pub args: Vec<Expr>,
+    pub output_var: VariableIdentifier,
+}
+
+impl FromRule for GrPrimitiveStatement {
+    fn from_rule(pair: pest::iterators::Pair<Rule>) -> Result<Option<Self>> {
+        let spec = PRIMITIVES
+            .iter()
+            .find(|spec| spec.rule == pair.as_rule())
+            .copied()
+            .ok_or_else(|| {
+                anyhow!("Expected a graphics primitive rule, found: {:?}", pair.as_rule())
+            })?;
+
+        let mut inner = pair.into_inner();
+
+        let mut args = Vec::with_capacity(spec.arg_types.len());
+        for i in 1..=spec.arg_types.len() {
+            let arg_pair = inner
+                .next()
+                .with_context(|| format!("Missing argument {} in {}!", i, spec.keyword))?;
+            let arg = Expr::from_rule(arg_pair)
+                .with_context(|| format!("Failed to build argument {} in {}", i, spec.keyword))?
+                .ok_or_else(|| anyhow!("Expected argument {} in {}", i, spec.keyword))?;
+            args.push(arg);
+        }
+
+        let output_pair = inner
+            .next()
+            .with_context(|| format!("Missing output variable in {}!", spec.keyword))?;
+        let output_var = VariableIdentifier::from_rule(output_pair)
+            .with_context(|| format!("Failed to build output variable in {}", spec.keyword))?
+            .ok_or_else(|| anyhow!("Expected output variable in {}", spec.keyword))?;
+
+        Ok(Some(GrPrimitiveStatement { spec, args, output_var }))
+    }
+}
+
+impl TranspileableStatement for GrPrimitiveStatement {
+    fn register_typeslot_declaration(
+        &self,
+        _resolver: &mut TypeResolver,
+        _ctx: &mut ScopeContext,
+        _source_location: SourceLocation,
+    ) -> TypeslotDeclarationResult {
+        TypeslotDeclarationResult::NotAVarFuncOrProcedureDecl
+    }
+    fn wire_inference_edges(
+        &self,
+        resolver: &mut TypeResolver,
+        ctx: &mut ScopeContext,
+        source_location: SourceLocation,
+    ) -> InferenceEdgeResult {
+        for arg in &self.args {
+            if let Err(e) = resolver.discover_expr_function_calls(arg, ctx) {
+                return InferenceEdgeResult::HasEdges {
+                    result: Err(e.context(format!(
+                        "...while discovering function call dependencies in {}",
+                        self.spec.keyword
+                    ))),
+                };
+            }
+        }
+
+        // Every primitive procedure assigns a GR to its output variable
+        if let Some(node) = ctx
+            .variables
+            .get(&self.output_var.name)
+            .and_then(|slot| resolver.nodes.get_mut(slot))
+            && node.resolved.is_none()
+        {
+            node.rule = ResolutionRule::InferredFrom {
+                recipe: ExprRecipe::Literal(RosyType::GR()),
+                reason: format!("inferred from {} at {}", self.spec.keyword, source_location),
+            };
+            node.assigned_at = Some(source_location);
+        }
+        InferenceEdgeResult::HasEdges { result: Ok(()) }
+    }
+    fn hydrate_resolved_types(
+        &mut self,
+        _resolver: &TypeResolver,
+        _current_scope: &[String],
+    ) -> TypeHydrationResult {
+        TypeHydrationResult::NothingToHydrate
+    }
+}
+
+impl Transpile for GrPrimitiveStatement {
+    fn transpile(
+        &self,
+        context: &mut TranspilationInputContext,
+    ) -> Result<TranspilationOutput, Vec<Error>> {
+        let keyword = self.spec.keyword;
+        let mut requested_variables = BTreeSet::new();
+        let mut errors = Vec::new();
+        let mut serialized_args = Vec::new();
+
+        for (i, (arg, expected)) in self.args.iter().zip(self.spec.arg_types).enumerate() {
+            let expected_type = RosyType::new(*expected, 0);
+            match arg.type_of(context) {
+                Ok(t) if t == expected_type => {}
+                Ok(t) => {
+                    errors.push(anyhow!(
+                        "{} argument {} must be {}, found {}",
+                        keyword, i + 1, expected_type, t
+                    ));
+                    continue;
+                }
+                Err(e) => {
+                    errors.push(e.context(format!(
+                        "...while determining type of argument {} in {}",
+                        i + 1, keyword
+                    )));
+                    continue;
+                }
+            }
+            match arg.transpile(context) {
+                Ok(output) => {
+                    requested_variables.extend(output.requested_variables.iter().cloned());
+                    serialized_args.push(if *expected == RosyBaseType::ST {
+                        output.as_ref()
+                    } else {
+                        output.as_value()
+                    });
+                }
+                Err(e) => errors.extend(add_context_to_all(
+                    e,
+                    format!("...while transpiling argument {} in {}", i + 1, keyword),
+                )),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let output_id_output = self.output_var.transpile(context).map_err(|e| {
+            add_context_to_all(e, format!("...while transpiling output variable in {}", keyword))
+        })?;
+        requested_variables.extend(output_id_output.requested_variables.clone());
+
+        let dereference = match context
+            .variables
+            .get(&self.output_var.name)
+            .ok_or_else(|| {
+                vec![anyhow!(
+                    "Variable '{}' is not defined in this scope!",
+                    self.output_var.name
+                )]
+            })?
+            .scope
+        {
+            VariableScope::Local => "",
+            VariableScope::Arg => "*",
+            VariableScope::Higher => {
+                requested_variables.insert(self.output_var.name.clone());
+                "*"
+            }
+        };
+
+        let serialization = format!(
+            "{deref}{dest} = {ctor}({args}).context(\"...while executing {keyword}\")?;",
+            deref = dereference,
+            dest = output_id_output.serialization,
+            ctor = self.spec.constructor,
+            args = serialized_args.join(", "),
+        );
+
+        Ok(TranspilationOutput {
+            serialization,
+            requested_variables,
+            ..Default::default()
+        })
+    }
+}
+
+impl Interpret for GrPrimitiveStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let mut args = Vec::with_capacity(self.args.len());
+        for arg in &self.args {
+            args.push(arg.evaluate(interpreter)?);
+        }
+        let gr = (self.spec.build)(&args)
+            .with_context(|| format!("...while executing {}", self.spec.keyword))?;
+        self.output_var.write(interpreter, Value::GR(gr))?;
+        Ok(Flow::Next)
+    }
+}
@@ -37,6 +37,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::core::file_io::rosy_backf;
 
 /// AST node for `BACKF unit;`.
 #[derive(Debug)]
@@ -114,3 +116,9 @@ impl Transpile for BackfStatement {
         })
     }
 }
+impl Interpret for BackfStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        rosy_backf(interpreter.evaluate_as(&self.unit_expr)?)?;
+        Ok(Flow::Next)
+    }
+}
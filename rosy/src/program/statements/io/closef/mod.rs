@@ -37,6 +37,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::core::file_io::rosy_closef;
 
 /// AST node for `CLOSEF unit;`.
 /// CLOSEF unit ;
@@ -115,3 +117,9 @@ impl Transpile for ClosefStatement {
         })
     }
 }
+impl Interpret for ClosefStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        rosy_closef(interpreter.evaluate_as(&self.unit_expr)?)?;
+        Ok(Flow::Next)
+    }
+}
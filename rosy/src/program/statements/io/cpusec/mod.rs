@@ -39,6 +39,7 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, VariableScope,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
 
 /// AST node for `CPUSEC v;`.
 #[derive(Debug)]
@@ -161,3 +162,10 @@ impl Transpile for CpusecStatement {
         })
     }
 }
+impl Interpret for CpusecStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let elapsed = interpreter.start.elapsed().as_secs_f64();
+        self.identifier.write(interpreter, Value::RE(elapsed))?;
+        Ok(Flow::Next)
+    }
+}
@@ -41,6 +41,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::core::file_io::rosy_openf;
 
 /// AST node for `OPENF unit filename status;`.
 /// OPENF unit filename status ;
@@ -157,3 +159,12 @@ impl Transpile for OpenfStatement {
         })
     }
 }
+impl Interpret for OpenfStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let unit = interpreter.evaluate_as(&self.unit_expr)?;
+        let filename: String = interpreter.evaluate_as(&self.filename_expr)?;
+        let status: String = interpreter.evaluate_as(&self.status_expr)?;
+        rosy_openf(unit, &filename, &status)?;
+        Ok(Flow::Next)
+    }
+}
@@ -40,6 +40,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::core::file_io::rosy_openfb;
 
 /// AST node for `OPENFB unit filename status;`.
 /// OPENFB unit filename status ;
@@ -156,3 +158,12 @@ impl Transpile for OpenfbStatement {
         })
     }
 }
+impl Interpret for OpenfbStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let unit = interpreter.evaluate_as(&self.unit_expr)?;
+        let filename: String = interpreter.evaluate_as(&self.filename_expr)?;
+        let status: String = interpreter.evaluate_as(&self.status_expr)?;
+        rosy_openfb(unit, &filename, &status)?;
+        Ok(Flow::Next)
+    }
+}
@@ -37,6 +37,7 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
 
 /// AST node for `OS expr;`.
 #[derive(Debug)]
@@ -114,3 +115,14 @@ impl Transpile for OsCallStatement {
         })
     }
 }
+impl Interpret for OsCallStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let command: String = interpreter.evaluate_as(&self.cmd_expr)?;
+        if cfg!(windows) {
+            std::process::Command::new("cmd").args(["/C", &command]).status().ok();
+        } else {
+            std::process::Command::new("sh").args(["-c", &command]).status().ok();
+        }
+        Ok(Flow::Next)
+    }
+}
@@ -40,6 +40,7 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
 
 #[derive(Debug)]
 pub struct PwtimeStatement {
@@ -138,3 +139,10 @@ impl Transpile for PwtimeStatement {
         })
     }
 }
+impl Interpret for PwtimeStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let elapsed = interpreter.start.elapsed().as_secs_f64();
+        self.identifier.write(interpreter, Value::RE(elapsed))?;
+        Ok(Flow::Next)
+    }
+}
@@ -41,6 +41,9 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::core::file_io::rosy_read_from_unit;
+use crate::rosy_lib::intrinsics::from_st::RosyFromST;
 
 /// AST node for the `READ unit variable;` statement.
 #[derive(Debug)]
@@ -182,3 +185,35 @@ impl Transpile for ReadStatement {
         }
     }
 }
+impl Interpret for ReadStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let unit = interpreter.evaluate_as::<f64>(&self.unit)?.round() as i64;
+        let name = &self.identifier.name;
+        // What the variable holds now says what to parse
+        let current = self.identifier.read(interpreter)?;
+        let value = if unit == 5 {
+            let mut line = String::new();
+            std::io::stdin()
+                .read_line(&mut line)
+                .context("Failed to read line from stdin!")?;
+            parse(&current, line.trim_end().to_string())
+                .with_context(|| format!("Failed to READ into {name}"))?
+        } else {
+            parse(&current, rosy_read_from_unit(unit as u64)?)
+                .with_context(|| format!("Failed to parse value from file unit into {name}"))?
+        };
+        self.identifier.write(interpreter, value)?;
+        Ok(Flow::Next)
+    }
+}
+
+/// Parses `text` as the type of `current`.
+fn parse(current: &Value, text: String) -> Result<Value> {
+    Ok(match current {
+        Value::RE(_) => Value::RE(f64::rosy_from_st(text)?),
+        Value::ST(_) => Value::ST(String::rosy_from_st(text)?),
+        Value::LO(_) => Value::LO(bool::rosy_from_st(text)?),
+        Value::VE(_) => Value::VE(Vec::<f64>::rosy_from_st(text)?),
+        other => anyhow::bail!("Cannot READ into a variable of type {}", other.type_name()),
+    })
+}
@@ -8,6 +8,9 @@
 //! READB unit variable;
 //! ```
 //!
+//! DA and CD records carry the `DAINI` order and variable count they were
+//! written under; reading one after a different `DAINI` is a runtime error.
+//!
 //! ## Rosy Example
 //! ```text
 #![doc = include_str!("test.rosy")]
@@ -41,6 +44,9 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::{CD, DA};
+use crate::rosy_lib::core::file_io::{RosyFromBinary, rosy_readb_from_unit};
 
 /// AST node for `READB unit variable;`.
 #[derive(Debug)]
@@ -163,3 +169,21 @@ impl Transpile for ReadbStatement {
         }
     }
 }
+impl Interpret for ReadbStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let unit = interpreter.evaluate_as::<f64>(&self.unit)?.round() as u64;
+        let data = rosy_readb_from_unit(unit)?;
+        // What the variable holds now says what to decode
+        let value = match self.identifier.read(interpreter)? {
+            Value::RE(_) => Value::RE(f64::from_binary(&data)?),
+            Value::ST(_) => Value::ST(String::from_binary(&data)?),
+            Value::LO(_) => Value::LO(bool::from_binary(&data)?),
+            Value::VE(_) => Value::VE(Vec::<f64>::from_binary(&data)?),
+            Value::DA(_) => Value::DA(DA::from_binary(&data)?),
+            Value::CD(_) => Value::CD(CD::from_binary(&data)?),
+            other => anyhow::bail!("Cannot READB into a variable of type {}", other.type_name()),
+        };
+        self.identifier.write(interpreter, value)?;
+        Ok(Flow::Next)
+    }
+}
@@ -45,6 +45,9 @@ use crate::{
         ValueKind, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::{CD, CM, DA};
+use crate::rosy_lib::core::mem_serial::RosyReadm;
 
 /// AST node for `READM output_var var_info length dp_array int_array da_params;`.
 #[derive(Debug)]
@@ -285,3 +288,32 @@ impl Transpile for ReadmStatement {
         })
     }
 }
+impl Interpret for ReadmStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let var_info: Vec<f64> = interpreter.evaluate_as(&self.var_info_expr)?;
+        let length: f64 = interpreter.evaluate_as(&self.length_expr)?;
+        let dp: Vec<f64> = interpreter.evaluate_as(&self.dp_array_expr)?;
+        let int: Vec<f64> = interpreter.evaluate_as(&self.int_array_expr)?;
+        let da: Vec<f64> = interpreter.evaluate_as(&self.da_params_expr)?;
+        let arrays = (&var_info[..], length, &dp[..], &int[..], &da[..]);
+        // What the variable holds now says what to decode
+        let value = match self.output_var.read(interpreter)? {
+            Value::RE(_) => Value::RE(readm(arrays)?),
+            Value::ST(_) => Value::ST(readm(arrays)?),
+            Value::LO(_) => Value::LO(readm(arrays)?),
+            Value::CM(_) => Value::CM(readm::<CM>(arrays)?),
+            Value::VE(_) => Value::VE(readm(arrays)?),
+            Value::DA(_) => Value::DA(readm::<DA>(arrays)?),
+            Value::CD(_) => Value::CD(readm::<CD>(arrays)?),
+            other => anyhow::bail!("Cannot READM into a variable of type {}", other.type_name()),
+        };
+        self.output_var.write(interpreter, value)?;
+        Ok(Flow::Next)
+    }
+}
+
+fn readm<T: RosyReadm>(
+    (var_info, length, dp, int, da): (&[f64], f64, &[f64], &[f64], &[f64]),
+) -> Result<T> {
+    T::readm(var_info, length, dp, int, da)
+}
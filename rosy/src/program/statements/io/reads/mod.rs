@@ -41,6 +41,8 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::core::file_io::rosy_reads_string_from_unit;
 
 /// AST node for `READS unit variable;`.
 #[derive(Debug)]
@@ -192,3 +194,19 @@ impl Transpile for ReadsStatement {
         })
     }
 }
+impl Interpret for ReadsStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let unit = interpreter.evaluate_as::<f64>(&self.unit_expr)? as u64;
+        let line = if unit == 5 {
+            let mut line = String::new();
+            std::io::stdin()
+                .read_line(&mut line)
+                .map_err(|e| anyhow::anyhow!("READS stdin error: {}", e))?;
+            line.trim_end_matches('\n').trim_end_matches('\r').to_string()
+        } else {
+            rosy_reads_string_from_unit(unit)?
+        };
+        self.identifier.write(interpreter, Value::ST(line))?;
+        Ok(Flow::Next)
+    }
+}
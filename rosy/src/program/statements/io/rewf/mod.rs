@@ -37,6 +37,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::core::file_io::rosy_rewf;
 
 /// AST node for `REWF unit;`.
 /// REWF unit ;
@@ -115,3 +117,9 @@ impl Transpile for RewfStatement {
         })
     }
 }
+impl Interpret for RewfStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        rosy_rewf(interpreter.evaluate_as(&self.unit_expr)?)?;
+        Ok(Flow::Next)
+    }
+}
@@ -39,6 +39,7 @@ use crate::{
     ast::*, program::expressions::Expr, program::statements::SourceLocation, resolve::*,
     transpile::*,
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter};
 
 /// AST node for `SAVE <expr> ;`.
 #[derive(Debug)]
@@ -122,3 +123,9 @@ impl Transpile for SaveStatement {
         })
     }
 }
+impl Interpret for SaveStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        self.filename_expr.evaluate(interpreter)?;
+        Ok(Flow::Next)
+    }
+}
@@ -45,6 +45,7 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
 
 /// AST node for `VELGET vec_expr component_expr output_var;`.
 #[derive(Debug)]
@@ -184,3 +185,21 @@ impl Transpile for VelgetStatement {
         })
     }
 }
+impl Interpret for VelgetStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let vector: Vec<f64> = interpreter.evaluate_as(&self.vec_expr)?;
+        let component = interpreter.evaluate_as::<f64>(&self.component_expr)? as usize;
+        let value = *component
+            .checked_sub(1)
+            .and_then(|index| vector.get(index))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "VELGET component {} is out of bounds (1-{})",
+                    component,
+                    vector.len()
+                )
+            })?;
+        self.output_var.write(interpreter, Value::RE(value))?;
+        Ok(Flow::Next)
+    }
+}
@@ -11,6 +11,11 @@
 //! Unit `6` writes to standard output. Each expression is converted
 //! to its string representation and printed.
 //!
+//! A `GR` graphics object must be written on its own. On unit 6 it prints
+//! a primitive listing; on a file unit it is rendered according to the
+//! file's extension (`.svg`, `.eps`/`.ps`, or `.gp` for a gnuplot script
+//! plus `.dat` data file) — see [`crate::rosy_lib::graphics`].
+//!
 //! ## Rosy Example
 //! ```text
 #![doc = include_str!("test.rosy")]
@@ -40,11 +45,15 @@ use crate::{
         statements::SourceLocation,
     },
     resolve::{ScopeContext, TypeResolver},
+    rosy_lib::RosyType,
     transpile::{
         InferenceEdgeResult, TranspilationInputContext, TranspilationOutput, Transpile,
-        TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
+        TranspileableExpr, TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::core::file_io::rosy_write_to_unit;
+use crate::rosy_lib::graphics::rosy_write_graphics;
 
 /// AST node for the `WRITE unit expr+;` statement.
 #[derive(Debug)]
@@ -145,6 +154,38 @@ impl Transpile for WriteStatement {
         })?;
         requested_variables.extend(unit_output.requested_variables.iter().cloned());
 
+        // A GR object is rendered as a whole (SVG / EPS / gnuplot on file
+        // units), so it has to be the only thing written.
+        let gr_count = self
+            .exprs
+            .iter()
+            .filter(|expr| matches!(expr.type_of(context), Ok(t) if t == RosyType::GR()))
+            .count();
+        if gr_count > 0 {
+            if self.exprs.len() != 1 {
+                return Err(vec![anyhow::anyhow!(
+                    "A GR object must be the only expression in a WRITE statement"
+                )]);
+            }
+            let gr_output = self.exprs[0].transpile(context).map_err(|e| {
+                add_context_to_all(e, "...while transpiling GR expression in WRITE".to_string())
+            })?;
+            requested_variables.extend(gr_output.requested_variables.iter().cloned());
+
+            let serialization = format!(
+                "{{ let __rosy_unit = ({}).round() as i64; \
+                if __rosy_unit == 6 {{ println!(\"{{}}\", RosyST::rosy_to_string({gr})); }} \
+                else {{ rosy_lib::graphics::rosy_write_graphics(__rosy_unit as u64, {gr})?; }} }}",
+                unit_output.as_value(),
+                gr = gr_output.as_ref(),
+            );
+            return Ok(TranspilationOutput {
+                serialization,
+                requested_variables,
+                ..Default::default()
+            });
+        }
+
         let mut serialized_exprs = Vec::new();
         for expr in &self.exprs {
             let TranspilationOutput {
@@ -189,3 +230,31 @@ impl Transpile for WriteStatement {
         })
     }
 }
+impl Interpret for WriteStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let unit = interpreter.evaluate_as::<f64>(&self.unit)?.round() as i64;
+        let mut values = Vec::new();
+        for expr in &self.exprs {
+            values.push(expr.evaluate(interpreter)?);
+        }
+
+        // A GR object is rendered as a whole, as in `transpile`
+        if let [Value::GR(gr)] = values.as_slice()
+            && unit != 6
+        {
+            rosy_write_graphics(unit as u64, gr)?;
+            return Ok(Flow::Next);
+        }
+
+        let mut line = String::new();
+        for value in &values {
+            line.push_str(&value.to_rosy_string()?);
+        }
+        if unit == 6 {
+            println!("{line}");
+        } else {
+            rosy_write_to_unit(unit as u64, &line)?;
+        }
+        Ok(Flow::Next)
+    }
+}
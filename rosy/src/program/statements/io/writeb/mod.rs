@@ -8,6 +8,10 @@
 //! WRITEB unit expr1 [expr2 ...];
 //! ```
 //!
+//! Each expression becomes one length-prefixed record. DA and CD values are
+//! stored sparsely (monomial index plus coefficient for every nonzero term),
+//! so they round-trip bit-for-bit, unlike the 16-digit `DAPRV` table.
+//!
 //! ## Rosy Example
 //! ```text
 #![doc = include_str!("test.rosy")]
@@ -37,6 +41,8 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::core::file_io::{RosyToBinary, rosy_writeb_to_unit};
 
 /// AST node for `WRITEB unit expr+;`.
 /// WRITEB unit expr+ ;
@@ -138,7 +144,7 @@ impl Transpile for WritebStatement {
             requested_variables.extend(output.requested_variables.iter().cloned());
 
             serialized_stmts.push(format!(
-                "rosy_lib::core::file_io::rosy_writeb_to_unit(__rosy_unit, &rosy_lib::core::file_io::RosyToBinary::to_binary({}))?;",
+                "rosy_lib::core::file_io::rosy_writeb_to_unit(__rosy_unit, &rosy_lib::core::file_io::RosyToBinary::to_binary({})?)?;",
                 output.as_ref()
             ));
         }
@@ -150,3 +156,21 @@ impl Transpile for WritebStatement {
         })
     }
 }
+impl Interpret for WritebStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let unit = interpreter.evaluate_as::<f64>(&self.unit)?.round() as u64;
+        for expr in &self.exprs {
+            let data = match expr.evaluate(interpreter)? {
+                Value::RE(x) => x.to_binary()?,
+                Value::ST(x) => x.to_binary()?,
+                Value::LO(x) => x.to_binary()?,
+                Value::VE(x) => x.to_binary()?,
+                Value::DA(x) => x.to_binary()?,
+                Value::CD(x) => x.to_binary()?,
+                other => anyhow::bail!("Cannot WRITEB a value of type {}", other.type_name()),
+            };
+            rosy_writeb_to_unit(unit, &data)?;
+        }
+        Ok(Flow::Next)
+    }
+}
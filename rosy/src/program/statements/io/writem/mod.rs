@@ -35,6 +35,8 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::core::mem_serial::RosyWritem;
 
 /// AST node for `WRITEM input var_info length dp_array int_array da_params;`.
 #[derive(Debug)]
@@ -258,3 +260,23 @@ impl Transpile for WritemStatement {
         })
     }
 }
+impl Interpret for WritemStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        self.length_expr.evaluate(interpreter)?;
+        let (var_info, dp, int, da) = match self.input_expr.evaluate(interpreter)? {
+            Value::RE(x) => x.writem(),
+            Value::ST(x) => x.writem(),
+            Value::LO(x) => x.writem(),
+            Value::CM(x) => x.writem(),
+            Value::VE(x) => x.writem(),
+            Value::DA(x) => x.writem(),
+            Value::CD(x) => x.writem(),
+            other => anyhow::bail!("Cannot WRITEM a value of type {}", other.type_name()),
+        };
+        self.var_info.write(interpreter, Value::VE(var_info))?;
+        self.dp_array.write(interpreter, Value::VE(dp))?;
+        self.int_array.write(interpreter, Value::VE(int))?;
+        self.da_params.write(interpreter, Value::VE(da))?;
+        Ok(Flow::Next)
+    }
+}
@@ -44,6 +44,8 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter};
+use crate::rosy_lib::{CD, core::polval::rosy_polval_cd};
 
 /// AST node for `CPOLVAL L P NP A NA R NR;`.
 #[derive(Debug)]
@@ -226,3 +228,17 @@ impl Transpile for CpolvalStatement {
         })
     }
 }
+impl Interpret for CpolvalStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let l = interpreter.evaluate_as(&self.l_expr)?;
+        let p: Vec<CD> = interpreter.evaluate_as(&self.p_expr)?;
+        let np = interpreter.evaluate_as::<f64>(&self.np_expr)? as usize;
+        let a: Vec<CD> = interpreter.evaluate_as(&self.a_expr)?;
+        let na = interpreter.evaluate_as::<f64>(&self.na_expr)? as usize;
+        let nr = interpreter.evaluate_as::<f64>(&self.nr_expr)? as usize;
+        interpreter.update(&self.r_expr, |r: &mut Vec<CD>| {
+            rosy_polval_cd(l, &p, np, &a, na, r, nr)
+        })?;
+        Ok(Flow::Next)
+    }
+}
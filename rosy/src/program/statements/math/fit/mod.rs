@@ -3,19 +3,63 @@
 //! ## Syntax
 //! ```text
 //! FIT var1 var2 ...; <statements> ENDFIT eps max algo obj1 obj2 ...;
+//! FIT var1[lo, hi] var2[lo,] ...; <statements> ENDFIT eps max algo obj1 con1[, hi] ...;
+//! PFIT var1 var2 ...; <statements> ENDPFIT eps max algo obj1 obj2 ...;
 //! ```
 //!
 //! The FIT loop is an optimization construct that repeatedly executes its body
 //! while an optimizer adjusts the specified variables to minimize the objective
 //! value(s). The loop terminates when the optimizer can't improve by more than
 //! `eps`, or when `max` iterations are reached. If `max` is 0, the body executes
-//! exactly once (no optimization).
+//! exactly once (no optimization). Otherwise the body runs once more with the
+//! final variables, so whatever it computes matches them after the loop.
 //!
 //! Algorithms:
 //!   1 = Nelder-Mead Simplex
-//!   3 = Simulated Annealing (not yet implemented)
+//!   3 = Simulated Annealing
 //!   4 = LMDIF (Levenberg-Marquardt least squares)
 //!
+//! ## Bounds and constraints
+//! Any fit variable or objective may carry an interval, `X[lower, upper]`,
+//! with either side left empty for a one-sided bound (`L[0,]`):
+//!
+//! - a bounded **variable** is never given a value outside its interval,
+//!   so a drift length can be kept positive or a quadrupole strength within
+//!   what the magnet can deliver;
+//! - a bounded **objective** is a constraint: rather than being minimised it
+//!   only has to end up inside its interval, and is penalised in proportion
+//!   to how far outside it lies.
+//!
+//! When bounds or constraints are present, the final values of the bounded
+//! variables and constraints are printed after the fit, marking variables
+//! that ended on a bound and constraints that could not be satisfied.
+//!
+//! ## DA-derived Jacobians
+//! If the fit variables are declared `(DA)`, each one is seeded as a DA
+//! parameter for every run of the body. The `n` fit variables take the last
+//! `n` DA variables that `DAINI` set up: with `NV` of them, the `i`-th fit
+//! variable holds its current value plus `DA(NV-n+i)`. The objectives (which
+//! may then be `(DA)` or `(RE)`) carry their exact first derivatives, and
+//! LMDIF uses those as its Jacobian instead of one extra body run per
+//! variable for finite differences. `DAINI` must have been called with an
+//! order of at least 1 and enough variables for the fit variables plus any
+//! the body uses itself, such as the phase-space variables of a lattice; a
+//! body that asks for one of the seeded variables with `DA(i)` stops with an
+//! error. A DA FIT inside the body of another one takes the variables below
+//! those of the outer one. The other algorithms fit the constant parts.
+//! Afterwards the fit variables hold their optimal values as DA constants.
+//!
+//! ## Parallel evaluation
+//! `PFIT ... ENDPFIT` is a FIT whose body evaluations are spread over the
+//! MPI ranks (see `rosy_lib::optimizer::parallel`). Rank 0 runs the
+//! optimizer and hands the other ranks the points it can evaluate
+//! independently: the simplex vertices, the finite-difference Jacobian
+//! columns of LMDIF, and one simulated-annealing proposal per rank. When the
+//! fit ends every rank holds the same final variables and has run the body
+//! once with them. The body already runs on all ranks, so it cannot contain
+//! a `PLOOP`. Built with `--threads`, a PFIT evaluates its body sequentially,
+//! like a FIT.
+//!
 //! ## Rosy Example
 //! ```text
 #![doc = include_str!("test.rosy")]
@@ -38,6 +82,7 @@ use std::collections::BTreeSet;
 
 use crate::{
     ast::*,
+    parallel_config::{ParallelBackend, parallel_backend},
     program::{
         expressions::Expr,
         statements::{SourceLocation, Statement},
@@ -47,14 +92,68 @@ use crate::{
     transpile::{
         InferenceEdgeResult, TranspilationInputContext, TranspilationOutput, Transpile,
         TranspileableExpr, TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult,
-        indent,
+        add_context_to_all, indent,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::{DA, optimizer::{self, Bound, Evaluation}};
+
+/// A name in `FIT` or `ENDFIT` with its optional `[lower, upper]` interval.
+#[derive(Debug)]
+pub struct FitBounded {
+    pub name: String,
+    pub lower: Option<Expr>,
+    pub upper: Option<Expr>,
+}
+
+impl FitBounded {
+    fn is_bounded(&self) -> bool {
+        self.lower.is_some() || self.upper.is_some()
+    }
+}
+
+impl FromRule for FitBounded {
+    fn from_rule(pair: pest::iterators::Pair<Rule>) -> Result<Option<Self>> {
+        ensure!(
+            pair.as_rule() == Rule::fit_variable,
+            "Expected `fit_variable` rule, found: {:?}",
+            pair.as_rule()
+        );
+
+        let mut inner = pair.into_inner();
+        let name = inner
+            .next()
+            .context("Missing variable name in FIT!")?
+            .as_str()
+            .to_string();
+
+        let mut lower = None;
+        let mut upper = None;
+        if let Some(bounds) = inner.next() {
+            for side in bounds.into_inner() {
+                let rule = side.as_rule();
+                let expr_pair = side
+                    .into_inner()
+                    .next()
+                    .with_context(|| format!("Missing bound expression for '{}'", name))?;
+                let expr = Expr::from_rule(expr_pair)
+                    .with_context(|| format!("Failed to build bound expression for '{}'", name))?;
+                match rule {
+                    Rule::fit_lower => lower = expr,
+                    Rule::fit_upper => upper = expr,
+                    other => anyhow::bail!("Unexpected rule in FIT bounds: {:?}", other),
+                }
+            }
+        }
+
+        Ok(Some(FitBounded { name, lower, upper }))
+    }
+}
 
 #[derive(Debug)]
 pub struct FitStatement {
-    /// Variable names to be optimized (the "knobs")
-    pub fit_variables: Vec<String>,
+    /// Variables to be optimized (the "knobs"), with optional bounds
+    pub fit_variables: Vec<FitBounded>,
     /// Body statements executed each iteration
     pub body: Vec<Statement>,
     /// Convergence tolerance (eps)
@@ -65,6 +164,10 @@ pub struct FitStatement {
     pub algorithm: Expr,
     /// Objective variable name(s) to minimize
     pub objectives: Vec<String>,
+    /// Bounded objectives, which only have to end up inside their interval
+    pub constraints: Vec<FitBounded>,
+    /// Whether this is a `PFIT`, evaluating the body on all MPI ranks
+    pub parallel: bool,
 }
 impl FromRule for FitStatement {
     fn from_rule(pair: pest::iterators::Pair<Rule>) -> Result<Option<Self>> {
@@ -77,19 +180,21 @@ impl FromRule for FitStatement {
         let mut inner = pair.into_inner();
 
         // Parse start_fit to get variable names
-        let fit_variables = {
+        let (fit_variables, parallel) = {
             let start_fit = inner.next().context("Missing first token `start_fit`!")?;
-            ensure!(
-                start_fit.as_rule() == Rule::start_fit,
-                "Expected `start_fit`, found: {:?}",
-                start_fit.as_rule()
-            );
+            let parallel = match start_fit.as_rule() {
+                Rule::start_fit => false,
+                Rule::start_pfit => true,
+                other => anyhow::bail!("Expected `start_fit` or `start_pfit`, found: {:?}", other),
+            };
 
             let start_fit_inner = start_fit.into_inner();
             let mut vars = Vec::new();
             for pair in start_fit_inner {
-                if pair.as_rule() == Rule::variable_name {
-                    vars.push(pair.as_str().to_string());
+                if pair.as_rule() == Rule::fit_variable
+                    && let Some(var) = FitBounded::from_rule(pair)?
+                {
+                    vars.push(var);
                 }
             }
 
@@ -97,7 +202,7 @@ impl FromRule for FitStatement {
                 anyhow::bail!("FIT statement requires at least one variable!");
             }
 
-            vars
+            (vars, parallel)
         };
 
         // Parse body statements until we hit end_fit
@@ -107,7 +212,7 @@ impl FromRule for FitStatement {
                 .next()
                 .ok_or_else(|| anyhow::anyhow!("Expected `end_fit` at end of FIT block!"))?;
 
-            if element.as_rule() == Rule::end_fit {
+            if matches!(element.as_rule(), Rule::end_fit | Rule::end_pfit) {
                 break element;
             }
 
@@ -120,7 +225,7 @@ impl FromRule for FitStatement {
         };
 
         // Parse end_fit: ENDFIT eps max algo obj1 obj2 ... ;
-        let (eps, max_iter, algorithm, objectives) = {
+        let (eps, max_iter, algorithm, objectives, constraints) = {
             let mut end_fit_inner = end_fit_pair.into_inner();
 
             let eps_pair = end_fit_inner
@@ -145,17 +250,24 @@ impl FromRule for FitStatement {
                 .ok_or_else(|| anyhow::anyhow!("Expected expression for `algorithm` in ENDFIT"))?;
 
             let mut objectives = Vec::new();
+            let mut constraints = Vec::new();
             for pair in end_fit_inner {
-                if pair.as_rule() == Rule::variable_name {
-                    objectives.push(pair.as_str().to_string());
+                if pair.as_rule() == Rule::fit_variable
+                    && let Some(objective) = FitBounded::from_rule(pair)?
+                {
+                    if objective.is_bounded() {
+                        constraints.push(objective);
+                    } else {
+                        objectives.push(objective.name);
+                    }
                 }
             }
 
-            if objectives.is_empty() {
+            if objectives.is_empty() && constraints.is_empty() {
                 anyhow::bail!("ENDFIT requires at least one objective variable!");
             }
 
-            (eps, max_iter, algorithm, objectives)
+            (eps, max_iter, algorithm, objectives, constraints)
         };
 
         Ok(Some(FitStatement {
@@ -165,6 +277,8 @@ impl FromRule for FitStatement {
             max_iter,
             algorithm,
             objectives,
+            constraints,
+            parallel,
         }))
     }
 }
@@ -206,17 +320,21 @@ impl Transpile for FitStatement {
         let mut errors = Vec::new();
         let mut requested_variables = BTreeSet::new();
 
-        // Verify all fit variables exist and are RE type
-        for var_name in &self.fit_variables {
+        // Verify all fit variables exist and are all RE, or all DA for a
+        // fit with DA-derived Jacobians
+        let mut fit_types = Vec::new();
+        for var_name in self.fit_variables.iter().map(|v| &v.name) {
             match context.variables.get(var_name) {
                 Some(scoped_var) => {
-                    if scoped_var.data.r#type != RosyType::RE() {
+                    let var_type = scoped_var.data.r#type;
+                    if var_type != RosyType::RE() && var_type != RosyType::DA() {
                         errors.push(anyhow!(
-                            "FIT variable '{}' must be of type (RE), found '{}'",
+                            "FIT variable '{}' must be of type (RE) or (DA), found '{}'",
                             var_name,
-                            scoped_var.data.r#type
+                            var_type
                         ));
                     }
+                    fit_types.push(var_type);
                 }
                 None => {
                     errors.push(anyhow!(
@@ -226,18 +344,36 @@ impl Transpile for FitStatement {
                 }
             }
         }
+        let da_mode = fit_types.contains(&RosyType::DA());
+        if da_mode && fit_types.contains(&RosyType::RE()) {
+            errors.push(anyhow!(
+                "FIT variables must be either all (RE) or all (DA), found both"
+            ));
+        }
 
-        // Verify all objective variables exist and are RE type
-        for obj_name in &self.objectives {
+        // Verify all objective and constraint variables exist and are RE
+        // type, or DA in a fit over DA variables
+        let mut objective_types = Vec::new();
+        for obj_name in self.objectives.iter().chain(self.constraints.iter().map(|c| &c.name)) {
             match context.variables.get(obj_name) {
                 Some(scoped_var) => {
-                    if scoped_var.data.r#type != RosyType::RE() {
+                    let obj_type = scoped_var.data.r#type;
+                    if da_mode {
+                        if obj_type != RosyType::RE() && obj_type != RosyType::DA() {
+                            errors.push(anyhow!(
+                                "ENDFIT objective '{}' must be of type (RE) or (DA), found '{}'",
+                                obj_name,
+                                obj_type
+                            ));
+                        }
+                    } else if obj_type != RosyType::RE() {
                         errors.push(anyhow!(
                             "ENDFIT objective '{}' must be of type (RE), found '{}'",
                             obj_name,
-                            scoped_var.data.r#type
+                            obj_type
                         ));
                     }
+                    objective_types.push(obj_type);
                 }
                 None => {
                     errors.push(anyhow!(
@@ -275,6 +411,14 @@ impl Transpile for FitStatement {
             )]);
         }
 
+        // Evaluate the bounds once, before the optimizer starts
+        let variable_bounds = serialize_bounds(&self.fit_variables, context, &mut requested_variables)?;
+        let constraint_bounds = serialize_bounds(&self.constraints, context, &mut requested_variables)?;
+
+        // A PFIT only spreads its body over MPI ranks; on the thread
+        // backend it evaluates sequentially like a FIT
+        let distributed = self.parallel && parallel_backend() == ParallelBackend::Mpi;
+
         // Transpile body statements
         let mut inner_context = context.clone();
         let mut serialized_statements = Vec::new();
@@ -282,6 +426,11 @@ impl Transpile for FitStatement {
         for stmt in &self.body {
             match stmt.transpile(&mut inner_context) {
                 Ok(output) => {
+                    if distributed && output.requested_variables.contains("rosy_mpi_context") {
+                        errors.push(anyhow!(
+                            "A PFIT body already runs on every MPI rank and cannot contain a PLOOP"
+                        ));
+                    }
                     serialized_statements.push(output.serialization);
                     requested_variables.extend(output.requested_variables);
                 }
@@ -342,29 +491,54 @@ impl Transpile for FitStatement {
         let num_objs = self.objectives.len();
 
         // Build: let mut fit_vars = vec![var1, var2, ...];
-        // FIT variables are always RE (Copy), so we can use them directly.
+        // RE fit variables are Copy, so we can use them directly; DA fit
+        // variables start from their constant parts.
         let vars_init = self
             .fit_variables
             .iter()
-            .map(|v| v.clone())
+            .map(|v| if da_mode {
+                format!("{}.constant_part()", v.name)
+            } else {
+                v.name.clone()
+            })
             .collect::<Vec<_>>()
             .join(", ");
 
-        // Build inside closure: assign from slice to variables
+        // Build inside closure: assign from slice to variables, seeding DA
+        // fit variables as DA parameters
         let vars_assign = self
             .fit_variables
             .iter()
             .enumerate()
-            .map(|(i, v)| format!("{} = __rosy_fit_vars[{}];", v, i))
+            .map(|(i, v)| if da_mode {
+                format!("{} = __rosy_fit_parameters.parameter({}, __rosy_fit_vars[{}])?;", v.name, i, i)
+            } else {
+                format!("{} = __rosy_fit_vars[{}];", v.name, i)
+            })
             .collect::<Vec<_>>()
             .join("\n");
+        let vars_assign = if da_mode {
+            format!(
+                "let __rosy_fit_parameters = rosy_lib::optimizer::DaParameters::seed({})?;\n{}",
+                self.fit_variables.len(),
+                vars_assign
+            )
+        } else {
+            vars_assign
+        };
 
-        // Build inside closure: collect objectives into vec
-        // Objective variables are always RE (Copy), so we can use them directly.
+        // Build inside closure: collect objectives, then constraint values, into vec
+        // In a DA fit, RE objectives are widened so every entry is a DA.
         let objs_collect = self
             .objectives
             .iter()
-            .map(|o| o.clone())
+            .chain(self.constraints.iter().map(|c| &c.name))
+            .zip(&objective_types)
+            .map(|(o, t)| match (da_mode, *t == RosyType::DA()) {
+                (true, true) => format!("{}.clone()", o),
+                (true, false) => format!("DA::constant({})", o),
+                (false, _) => o.clone(),
+            })
             .collect::<Vec<_>>()
             .join(", ");
 
@@ -373,37 +547,64 @@ impl Transpile for FitStatement {
             .fit_variables
             .iter()
             .enumerate()
-            .map(|(i, v)| format!("{} = __rosy_fit_vars[{}];", v, i))
+            .map(|(i, v)| if da_mode {
+                format!("{} = DA::constant(__rosy_fit_vars[{}]);", v.name, i)
+            } else {
+                format!("{} = __rosy_fit_vars[{}];", v.name, i)
+            })
             .collect::<Vec<_>>()
             .join("\n");
+        let evaluation = if da_mode {
+            format!(
+                "rosy_lib::optimizer::Evaluation::from_da(&[{}], &__rosy_fit_parameters)",
+                objs_collect
+            )
+        } else {
+            format!("Ok(vec![{}].into())", objs_collect)
+        };
 
         let body_code = serialized_statements.join("\n");
+        let closure = format!(
+            "|__rosy_fit_vars: &mut [f64]| -> anyhow::Result<rosy_lib::optimizer::Evaluation> {{\n\
+            {assign}\n\
+            {body}\n\
+            \t\t\t{evaluation}\n\
+            \t\t}}",
+            assign = indent(indent(indent(vars_assign))),
+            body = indent(indent(indent(body_code))),
+            evaluation = evaluation,
+        );
+        // A PFIT hands its body to the MPI ranks; only rank 0 optimizes
+        let fit_body = if distributed {
+            requested_variables.insert("rosy_mpi_context".to_string());
+            format!("rosy_lib::optimizer::Distributed::new(rosy_mpi_context, {})", closure)
+        } else {
+            closure
+        };
 
         let serialization = format!(
             "{{\n\
             \tlet mut __rosy_fit_vars: Vec<f64> = vec![{vars_init}];\n\
             \trosy_lib::optimizer::run_fit(\n\
             \t\t&mut __rosy_fit_vars,\n\
+            \t\t&[{variable_bounds}],\n\
+            \t\t&[{constraint_bounds}],\n\
             \t\t{eps_val},\n\
             \t\t{max_val} as usize,\n\
             \t\t{algo_val} as usize,\n\
             \t\t{num_objs},\n\
-            \t\t|__rosy_fit_vars: &mut [f64]| -> anyhow::Result<Vec<f64>> {{\n\
-            {assign}\n\
-            {body}\n\
-            \t\t\tOk(vec![{objs_collect}])\n\
-            \t\t}},\n\
+            \t\t{fit_body},\n\
             \t).expect(\"FIT optimization failed\");\n\
             {writeback}\n\
             }}",
             vars_init = vars_init,
+            variable_bounds = variable_bounds,
+            constraint_bounds = constraint_bounds,
             eps_val = eps_output.as_value(),
             max_val = max_output.as_value(),
             algo_val = algo_output.as_value(),
             num_objs = num_objs,
-            assign = indent(indent(indent(vars_assign))),
-            body = indent(indent(indent(body_code))),
-            objs_collect = objs_collect,
+            fit_body = fit_body,
             writeback = indent(vars_writeback),
         );
 
@@ -414,3 +615,165 @@ impl Transpile for FitStatement {
         })
     }
 }
+
+/// Serialize `rosy_lib::optimizer::Bound` literals for each named entry,
+/// checking that the bound expressions are (RE).
+fn serialize_bounds(
+    entries: &[FitBounded],
+    context: &mut TranspilationInputContext,
+    requested_variables: &mut BTreeSet<String>,
+) -> Result<String, Vec<Error>> {
+    let mut errors = Vec::new();
+    let mut serialized = Vec::new();
+
+    for entry in entries {
+        let mut sides = Vec::new();
+        for (side, expr) in [("lower", &entry.lower), ("upper", &entry.upper)] {
+            let Some(expr) = expr else {
+                sides.push("None".to_string());
+                continue;
+            };
+            match expr.type_of(context) {
+                Ok(t) if t == RosyType::RE() => {}
+                Ok(t) => {
+                    errors.push(anyhow!(
+                        "FIT {} bound of '{}' must be of type (RE), found '{}'",
+                        side, entry.name, t
+                    ));
+                    continue;
+                }
+                Err(e) => {
+                    errors.push(e.context(format!(
+                        "...while determining type of the {} bound of '{}'",
+                        side, entry.name
+                    )));
+                    continue;
+                }
+            }
+            match expr.transpile(context) {
+                Ok(output) => {
+                    requested_variables.extend(output.requested_variables.iter().cloned());
+                    sides.push(format!("Some({})", output.as_value()));
+                }
+                Err(e) => errors.extend(add_context_to_all(
+                    e,
+                    format!("...while transpiling the {} bound of '{}'", side, entry.name),
+                )),
+            }
+        }
+        if sides.len() == 2 {
+            serialized.push(format!(
+                "rosy_lib::optimizer::Bound {{ name: \"{}\", lower: {}, upper: {} }}",
+                entry.name, sides[0], sides[1]
+            ));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(serialized.join(", "))
+}
+impl Interpret for FitStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let da_mode = self
+            .fit_variables
+            .iter()
+            .any(|v| interpreter.variable_type(&v.name) == Some(RosyType::DA()));
+
+        // DA fit variables start from their constant parts
+        let mut variables = Vec::new();
+        for v in &self.fit_variables {
+            variables.push(match &interpreter.slot(&v.name)?.borrow().value {
+                Value::DA(da) => da.constant_part(),
+                value => value.to::<f64>()?,
+            });
+        }
+        let variable_bounds = bounds(&self.fit_variables, interpreter)?;
+        let constraint_bounds = bounds(&self.constraints, interpreter)?;
+        let eps = interpreter.evaluate_as(&self.eps)?;
+        let max_iter = interpreter.evaluate_as::<f64>(&self.max_iter)? as usize;
+        let algorithm = interpreter.evaluate_as::<f64>(&self.algorithm)? as usize;
+
+        // A PFIT evaluates its body sequentially here, as with `--threads`
+        let body = |fit_variables: &mut [f64]| -> Result<Evaluation> {
+            let parameters = if da_mode {
+                Some(optimizer::DaParameters::seed(self.fit_variables.len())?)
+            } else {
+                None
+            };
+            for (index, v) in self.fit_variables.iter().enumerate() {
+                let value = if let Some(parameters) = &parameters {
+                    Value::DA(parameters.parameter(index, fit_variables[index])?)
+                } else {
+                    Value::RE(fit_variables[index])
+                };
+                interpreter.slot(&v.name)?.borrow_mut().value = value;
+            }
+            if let Flow::Quit(code) = interpreter.run_block(&self.body)? {
+                std::process::exit(code);
+            }
+            let mut results = Vec::new();
+            for name in self
+                .objectives
+                .iter()
+                .chain(self.constraints.iter().map(|c| &c.name))
+            {
+                results.push(interpreter.slot(name)?.borrow().value.clone());
+            }
+            if let Some(parameters) = &parameters {
+                let results = results
+                    .into_iter()
+                    .map(|result| match result {
+                        Value::DA(da) => Ok(da),
+                        other => Ok(DA::constant(other.to::<f64>()?)),
+                    })
+                    .collect::<Result<Vec<DA>>>()?;
+                Evaluation::from_da(&results, parameters)
+            } else {
+                Ok(results
+                    .iter()
+                    .map(Value::to::<f64>)
+                    .collect::<Result<Vec<f64>>>()?
+                    .into())
+            }
+        };
+        optimizer::run_fit(
+            &mut variables,
+            &variable_bounds,
+            &constraint_bounds,
+            eps,
+            max_iter,
+            algorithm,
+            self.objectives.len(),
+            body,
+        )
+        .expect("FIT optimization failed");
+
+        for (v, value) in self.fit_variables.iter().zip(variables) {
+            interpreter.slot(&v.name)?.borrow_mut().value = if da_mode {
+                Value::DA(DA::constant(value))
+            } else {
+                Value::RE(value)
+            };
+        }
+        Ok(Flow::Next)
+    }
+}
+
+/// The `optimizer::Bound` of each named entry.
+fn bounds(entries: &[FitBounded], interpreter: &mut Interpreter<'_>) -> Result<Vec<Bound>> {
+    let mut bounds = Vec::new();
+    for entry in entries {
+        let lower = match &entry.lower {
+            Some(expr) => Some(interpreter.evaluate_as(expr)?),
+            None => None,
+        };
+        let upper = match &entry.upper {
+            Some(expr) => Some(interpreter.evaluate_as(expr)?),
+            None => None,
+        };
+        bounds.push(Bound { name: interpreter.intern(&entry.name), lower, upper });
+    }
+    Ok(bounds)
+}
@@ -50,6 +50,7 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
 
 /// AST node for `INTPOL v c;`.
 #[derive(Debug)]
@@ -173,3 +174,16 @@ impl Transpile for IntpolStatement {
         })
     }
 }
+impl Interpret for IntpolStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let n = interpreter.evaluate_as::<f64>(&self.n_expr)? as usize;
+        let mut coefficients: Vec<f64> = self.coeff_var.read(interpreter)?.to()?;
+        if coefficients.len() < n + 1 {
+            coefficients.resize(n + 1, 0.0);
+        }
+        coefficients[..=n].fill(0.0);
+        coefficients[0] = 1.0;
+        self.coeff_var.write(interpreter, Value::from(coefficients))?;
+        Ok(Flow::Next)
+    }
+}
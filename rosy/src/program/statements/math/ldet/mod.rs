@@ -36,6 +36,8 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::core::ldet::rosy_ldet;
 
 /// AST node for `LDET matrix n alloc_dim result ;`.
 #[derive(Debug)]
@@ -168,3 +170,13 @@ impl Transpile for LdetStatement {
         })
     }
 }
+impl Interpret for LdetStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let matrix: Vec<Vec<f64>> = interpreter.evaluate_as(&self.matrix_expr)?;
+        let n = interpreter.evaluate_as::<f64>(&self.n_expr)? as usize;
+        let alloc_dim = interpreter.evaluate_as::<f64>(&self.alloc_dim_expr)? as usize;
+        let determinant = rosy_ldet(&matrix, n, alloc_dim)?;
+        self.result_expr.store(interpreter, Value::RE(determinant))?;
+        Ok(Flow::Next)
+    }
+}
@@ -37,6 +37,8 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::core::lev::rosy_lev;
 
 #[derive(Debug)]
 pub struct LevStatement {
@@ -206,3 +208,15 @@ impl Transpile for LevStatement {
         })
     }
 }
+impl Interpret for LevStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let matrix: Vec<Vec<f64>> = interpreter.evaluate_as(&self.matrix_expr)?;
+        let n = interpreter.evaluate_as::<f64>(&self.n_expr)? as usize;
+        let alloc_dim = interpreter.evaluate_as::<f64>(&self.alloc_dim_expr)? as usize;
+        let (eig_real, eig_imag, eigvecs) = rosy_lev(&matrix, n, alloc_dim)?;
+        self.eig_real_expr.store(interpreter, Value::from(eig_real))?;
+        self.eig_imag_expr.store(interpreter, Value::from(eig_imag))?;
+        self.eigvecs_expr.store(interpreter, Value::from(eigvecs))?;
+        Ok(Flow::Next)
+    }
+}
@@ -36,6 +36,8 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::core::linv::rosy_linv;
 
 /// AST node for `LINV matrix inverse n alloc_dim error_flag;`.
 #[derive(Debug)]
@@ -195,3 +197,14 @@ impl Transpile for LinvStatement {
         })
     }
 }
+impl Interpret for LinvStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let matrix: Vec<Vec<f64>> = interpreter.evaluate_as(&self.matrix_expr)?;
+        let n = interpreter.evaluate_as::<f64>(&self.n_expr)? as usize;
+        let alloc_dim = interpreter.evaluate_as::<f64>(&self.alloc_dim_expr)? as usize;
+        let (inverse, error_flag) = rosy_linv(&matrix, n, alloc_dim)?;
+        self.inverse_expr.store(interpreter, Value::from(inverse))?;
+        self.error_flag_expr.store(interpreter, Value::RE(error_flag))?;
+        Ok(Flow::Next)
+    }
+}
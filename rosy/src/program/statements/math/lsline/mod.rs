@@ -39,6 +39,8 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::core::lsline::rosy_lsline;
 
 /// AST node for `LSLINE x y n a b;`.
 #[derive(Debug)]
@@ -201,3 +203,14 @@ impl Transpile for LslineStatement {
         })
     }
 }
+impl Interpret for LslineStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let x: Vec<f64> = interpreter.evaluate_as(&self.x_expr)?;
+        let y: Vec<f64> = interpreter.evaluate_as(&self.y_expr)?;
+        let n = interpreter.evaluate_as::<f64>(&self.n_expr)? as usize;
+        let (a, b) = rosy_lsline(&x, &y, n)?;
+        self.a_var.write(interpreter, Value::RE(a))?;
+        self.b_var.write(interpreter, Value::RE(b))?;
+        Ok(Flow::Next)
+    }
+}
@@ -36,6 +36,8 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::core::mblock::rosy_mblock;
 
 #[derive(Debug)]
 pub struct MblockStatement {
@@ -193,3 +195,14 @@ impl Transpile for MblockStatement {
         })
     }
 }
+impl Interpret for MblockStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let matrix: Vec<Vec<f64>> = interpreter.evaluate_as(&self.matrix_expr)?;
+        let n = interpreter.evaluate_as::<f64>(&self.n_expr)? as usize;
+        let alloc_dim = interpreter.evaluate_as::<f64>(&self.alloc_dim_expr)? as usize;
+        let (transform, inverse) = rosy_mblock(&matrix, n, alloc_dim)?;
+        self.transform_expr.store(interpreter, Value::from(transform))?;
+        self.inverse_expr.store(interpreter, Value::from(inverse))?;
+        Ok(Flow::Next)
+    }
+}
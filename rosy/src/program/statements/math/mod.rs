@@ -2,7 +2,7 @@
 //!
 //! Mathematical and linear algebra operations.
 //!
-//! - **[`fit`]** — `FIT ... ENDFIT;` — optimization loop
+//! - **[`fit`]** — `FIT ... ENDFIT;` — optimization loop (`PFIT ... ENDPFIT;` over MPI ranks)
 //! - **[`ldet`]** — `LDET mat var;` — matrix determinant
 //! - **[`lev`]** — `LEV mat er ei ev n d;` — eigenvalues and eigenvectors
 //! - **[`linv`]** — `LINV mat inv;` — matrix inverse
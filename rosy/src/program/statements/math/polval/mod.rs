@@ -8,15 +8,15 @@
 //! POLVAL L P NP A NA R NR;
 //! ```
 //!
-//! | Arg | Role                                    |
-//! |-----|-----------------------------------------|
-//! | L   | evaluation mode (1 = Horner, normally 1)|
-//! | P   | array of NP DA polynomial vectors       |
-//! | NP  | number of polynomials                   |
-//! | A   | array of NA arguments (RE or DA)        |
-//! | NA  | number of arguments                     |
-//! | R   | result array (output variable)          |
-//! | NR  | number of results                       |
+//! | Arg | Role                                               |
+//! |-----|----------------------------------------------------|
+//! | L   | mode: 0 reuses P's prepared form, else re-checks P |
+//! | P   | array of NP DA polynomial vectors                  |
+//! | NP  | number of polynomials                              |
+//! | A   | array of NA arguments (RE, VE or DA)               |
+//! | NA  | number of arguments                                |
+//! | R   | result array (output variable)                     |
+//! | NR  | number of results                                  |
 //!
 //! ## Rosy Example
 //! ```text
@@ -41,6 +41,8 @@ use crate::{
         TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Evaluate, Flow, Interpret, Interpreter};
+use crate::rosy_lib::{DA, core::polval};
 
 /// AST node for `POLVAL L P NP A NA R NR;`.
 #[derive(Debug)]
@@ -236,3 +238,35 @@ impl Transpile for PolvalStatement {
         })
     }
 }
+impl Interpret for PolvalStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let l = interpreter.evaluate_as(&self.l_expr)?;
+        let p: Vec<DA> = interpreter.evaluate_as(&self.p_expr)?;
+        let np = interpreter.evaluate_as::<f64>(&self.np_expr)? as usize;
+        let a = self.a_expr.evaluate(interpreter)?;
+        let na = interpreter.evaluate_as::<f64>(&self.na_expr)? as usize;
+        let nr = interpreter.evaluate_as::<f64>(&self.nr_expr)? as usize;
+        // Dispatch on A's elements, as in `transpile`
+        match a.base_type() {
+            Some(RosyBaseType::DA) => {
+                let a: Vec<DA> = a.to()?;
+                interpreter.update(&self.r_expr, |r: &mut Vec<DA>| {
+                    polval::rosy_polval_da(l, &p, np, &a, na, r, nr)
+                })?;
+            }
+            Some(RosyBaseType::VE) => {
+                let a: Vec<Vec<f64>> = a.to()?;
+                interpreter.update(&self.r_expr, |r: &mut Vec<Vec<f64>>| {
+                    polval::rosy_polval_ve(l, &p, np, &a, na, r, nr)
+                })?;
+            }
+            _ => {
+                let a: Vec<f64> = a.to()?;
+                interpreter.update(&self.r_expr, |r: &mut Vec<f64>| {
+                    polval::rosy_polval_re(l, &p, np, &a, na, r, nr)
+                })?;
+            }
+        }
+        Ok(Flow::Next)
+    }
+}
@@ -40,6 +40,8 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
+use crate::rosy_lib::core::rkco::rosy_rkco;
 
 /// AST node for `RKCO c b e a1 a2;`.
 #[derive(Debug)]
@@ -194,3 +196,14 @@ impl Transpile for RkcoStatement {
         })
     }
 }
+impl Interpret for RkcoStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let (c, b, e, a1, a2) = rosy_rkco()?;
+        self.c_var.write(interpreter, Value::from(c))?;
+        self.b_var.write(interpreter, Value::from(b))?;
+        self.e_var.write(interpreter, Value::from(e))?;
+        self.a1_var.write(interpreter, Value::from(a1))?;
+        self.a2_var.write(interpreter, Value::from(a2))?;
+        Ok(Flow::Next)
+    }
+}
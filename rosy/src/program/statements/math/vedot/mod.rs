@@ -45,6 +45,7 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
 
 #[derive(Debug)]
 pub struct VedotStatement {
@@ -175,3 +176,12 @@ impl Transpile for VedotStatement {
         })
     }
 }
+impl Interpret for VedotStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let vec1: Vec<f64> = interpreter.evaluate_as(&self.vec1_expr)?;
+        let vec2: Vec<f64> = interpreter.evaluate_as(&self.vec2_expr)?;
+        let dot = vec1.iter().zip(&vec2).map(|(a, b)| a * b).sum::<f64>();
+        self.output_var.write(interpreter, Value::RE(dot))?;
+        Ok(Flow::Next)
+    }
+}
@@ -44,6 +44,7 @@ use crate::{
         add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
 
 #[derive(Debug)]
 pub struct VeunitStatement {
@@ -163,3 +164,12 @@ impl Transpile for VeunitStatement {
         })
     }
 }
+impl Interpret for VeunitStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let vector: Vec<f64> = interpreter.evaluate_as(&self.vec_expr)?;
+        let norm = vector.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let unit = vector.iter().map(|x| x / norm).collect::<Vec<f64>>();
+        self.output_var.write(interpreter, Value::from(unit))?;
+        Ok(Flow::Next)
+    }
+}
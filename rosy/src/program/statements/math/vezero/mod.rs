@@ -46,6 +46,7 @@ use crate::{
         TranspileableStatement, TypeHydrationResult, TypeslotDeclarationResult, add_context_to_all,
     },
 };
+use crate::interpret::{Flow, Interpret, Interpreter, Value};
 
 #[derive(Debug)]
 pub struct VezeroStatement {
@@ -166,3 +167,24 @@ impl Transpile for VezeroStatement {
         })
     }
 }
+impl Interpret for VezeroStatement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        let n = interpreter.evaluate_as::<f64>(&self.num_elements_expr)? as usize;
+        let threshold = interpreter.evaluate_as::<f64>(&self.threshold_expr)?.abs();
+        let mut array: Vec<f64> = self.array_ident.read(interpreter)?.to()?;
+        ensure!(
+            n <= array.len(),
+            "VEZERO: {} components requested, but '{}' has {}",
+            n,
+            self.array_ident.name,
+            array.len()
+        );
+        for component in array[..n].iter_mut() {
+            if component.abs() > threshold {
+                *component = 0.0;
+            }
+        }
+        self.array_ident.write(interpreter, Value::from(array))?;
+        Ok(Flow::Next)
+    }
+}
@@ -28,11 +28,13 @@
 //! | Filter DA terms | **[`da::danoro`]**, **[`da::danors`]** |
 //! | Substitute variable in DA | **[`da::daplu`]** |
 //! | DA division / shift | **[`da::dadiu`]**, **[`da::dadmu`]** |
+//! | Invert a DA map | **[`da::dainv`]** |
 //! | Extract DA coefficients | **[`da::dacliw`]**, **[`da::dacqlc`]**, **[`da::dapee`]**, **[`da::dapea`]**, **[`da::dapep`]** |
 //! | Estimate DA term size | **[`da::daest`]** |
 //! | DA tree evaluation | **[`da::mtree`]** |
 //! | Use `FIT` (optimization) | **[`math::fit`]** |
 //! | Use `BREAK` or `QUIT` | **[`core::break`]**, **[`core::quit`]** |
+//! | Checkpoint a long run and resume it | **[`core::checkpoint`]** |
 //! | Measure time | **[`io::cpusec`]**, **[`io::pwtime`]** |
 //! | Run a shell command | **[`io::os_call`]** |
 //! | Read vectors from files | **[`io::velget`]** |
@@ -47,14 +49,17 @@
 //! | Matrix operations | **[`math::linv`]**, **[`math::ldet`]**, **[`math::lev`]**, **[`math::mblock`]** |
 //! | Polynomial evaluation | **[`math::polval`]** |
 //! | Vector math | **[`math::vedot`]**, **[`math::veunit`]**, **[`math::vezero`]** |
+//! | Build plots (`GR`) | **[`graphics`]** |
 
 pub mod core;
 pub mod da;
+pub mod graphics;
 pub mod io;
 pub mod math;
 
 pub use core::assign::AssignStatement;
 pub use core::r#break::BreakStatement;
+pub use core::checkpoint::CheckpointStatement;
 pub use core::function::FunctionStatement;
 pub use core::function_call::FunctionCallStatement;
 pub use core::r#if::IfStatement;
@@ -145,6 +150,7 @@ pub use da::cdnfda::CdnfdaStatement;
 pub use da::cdnfds::CdnfdsStatement;
 pub use da::daran::DaranStatement;
 pub use da::dacode::DacodeStatement;
+pub use da::dainv::DainvStatement;
 pub use da::daint::DaintStatement;
 pub use da::danoro::DanoroStatement;
 pub use da::danors::DanorsStatement;
@@ -160,9 +166,12 @@ pub use da::dasgn::DasgnStatement;
 pub use da::datrn::DatrnStatement;
 pub use da::mtree::MtreeStatement;
 
+pub use graphics::GrPrimitiveStatement;
+
 use crate::{
     ast::{FromRule, Rule},
     errors::WithLocation,
+    interpret::{Flow, Interpret, Interpreter},
     resolve::*,
     transpile::*,
 };
@@ -666,6 +675,15 @@ impl FromRule for Statement {
                         source_location: loc.clone(),
                     })
                 }),
+            Rule::checkpoint => CheckpointStatement::from_rule(pair)
+                .context("...while building CHECKPOINT statement!")
+                .with_location(&loc)
+                .map(|opt| {
+                    opt.map(|stmt| Statement {
+                        inner: Box::new(stmt),
+                        source_location: loc.clone(),
+                    })
+                }),
             Rule::ranseed => RanseedStatement::from_rule(pair)
                 .context("...while building RANSEED statement!")
                 .with_location(&loc)
@@ -747,6 +765,22 @@ impl FromRule for Statement {
                         source_location: loc.clone(),
                     })
                 }),
+            Rule::grmove
+            | Rule::grdraw
+            | Rule::grdot
+            | Rule::grchar
+            | Rule::grcolr
+            | Rule::grwdth
+            | Rule::grproj
+            | Rule::grzoom => GrPrimitiveStatement::from_rule(pair)
+                .context("...while building graphics primitive statement!")
+                .with_location(&loc)
+                .map(|opt| {
+                    opt.map(|stmt| Statement {
+                        inner: Box::new(stmt),
+                        source_location: loc.clone(),
+                    })
+                }),
             Rule::dascl => DasclStatement::from_rule(pair)
                 .context("...while building DASCL statement!")
                 .with_location(&loc)
@@ -1035,6 +1069,15 @@ impl FromRule for Statement {
                         source_location: loc.clone(),
                     })
                 }),
+            Rule::dainv => DainvStatement::from_rule(pair)
+                .context("...while building DAINV statement!")
+                .with_location(&loc)
+                .map(|opt| {
+                    opt.map(|stmt| Statement {
+                        inner: Box::new(stmt),
+                        source_location: loc.clone(),
+                    })
+                }),
 
             Rule::sleepm => SleepmStatement::from_rule(pair)
                 .context("...while building SLEEPM statement!")
@@ -1138,36 +1181,61 @@ impl FromRule for Statement {
         }
     }
 }
+impl Interpret for Statement {
+    fn interpret<'a>(&'a self, interpreter: &mut Interpreter<'a>) -> Result<Flow> {
+        // The interpreter's counterpart of the marker `transpile` emits
+        interpreter.mark(self);
+        self.inner.interpret(interpreter)
+    }
+}
 impl Transpile for Statement {
     fn transpile(
         &self,
         context: &mut TranspilationInputContext,
     ) -> Result<TranspilationOutput, Vec<Error>> {
-        self.inner.transpile(context).map_err(|err_vec| {
-            let loc = self.source_location.clone();
-            err_vec
-                .into_iter()
-                .map(|err| {
-                    // If the error already has a RosyError with a location
-                    // (from an inner statement), preserve it and just add context.
-                    // Otherwise, wrap the error with this statement's location.
-                    let has_location = err.chain().any(|cause| {
-                        cause
-                            .downcast_ref::<crate::errors::RosyError>()
-                            .is_some_and(|r| r.location.is_some())
-                    });
-                    if has_location {
-                        err
-                    } else {
-                        let message = format!("{}", err.root_cause());
-                        anyhow::Error::from(crate::errors::RosyError {
-                            message,
-                            location: Some(loc.clone()),
-                            severity: crate::errors::RosyErrorSeverity::Error,
-                        })
-                    }
-                })
-                .collect()
-        })
+        // Record the statement as running, for runtime errors
+        // (see `rosy_lib::core::trace`)
+        let location = &self.source_location;
+        let file = match &location.file {
+            Some(path) => path.display().to_string(),
+            None => context.source_file.clone().unwrap_or_default(),
+        };
+        let marker = format!(
+            "rosy_lib::core::trace::at(&rosy_lib::core::trace::Location {{ file: {:?}, line: {}, col: {}, snippet: {:?} }});",
+            file, location.line, location.col, location.snippet
+        );
+
+        self.inner
+            .transpile(context)
+            .map(|output| TranspilationOutput {
+                serialization: format!("{}\n{}", marker, output.serialization),
+                ..output
+            })
+            .map_err(|err_vec| {
+                let loc = self.source_location.clone();
+                err_vec
+                    .into_iter()
+                    .map(|err| {
+                        // If the error already has a RosyError with a location
+                        // (from an inner statement), preserve it and just add context.
+                        // Otherwise, wrap the error with this statement's location.
+                        let has_location = err.chain().any(|cause| {
+                            cause
+                                .downcast_ref::<crate::errors::RosyError>()
+                                .is_some_and(|r| r.location.is_some())
+                        });
+                        if has_location {
+                            err
+                        } else {
+                            let message = format!("{}", err.root_cause());
+                            anyhow::Error::from(crate::errors::RosyError {
+                                message,
+                                location: Some(loc.clone()),
+                                severity: crate::errors::RosyErrorSeverity::Error,
+                            })
+                        }
+                    })
+                    .collect()
+            })
     }
 }
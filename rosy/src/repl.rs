@@ -0,0 +1,195 @@
+//! `rosy repl`: an interactive session on the interpreter.
+//!
+//! Every input is checked together with the session so far, as the program
+//! `BEGIN; <earlier inputs> <input> END;`, so it sees everything declared
+//! before it and type errors read as they would from `rosy run`. Only the
+//! new statements are then run, in the session's outermost scope, so
+//! variables keep their values from one input to the next.
+//!
+//! An input that is not a statement is printed as an expression (`X+1`
+//! runs `WRITE 6 X+1;`), and one that stops short, such as a `LOOP` without
+//! its `ENDLOOP`, asks for more lines.
+
+use std::io::{BufRead, Write};
+use std::panic::{self, AssertUnwindSafe};
+
+use anyhow::{Context, Result, anyhow};
+use pest::Parser;
+use rosy::{
+    ast,
+    errors::RosyError,
+    interpret::{self, Flow, Interpreter},
+    program::{IncludeTracker, Program, statements::Statement},
+    resolve,
+    rosy_lib::core::trace,
+    transpile::*,
+};
+
+use crate::{BOLD, DIM, RED, RESET, transpile_error};
+
+/// How runtime errors name the session.
+const SOURCE_FILE: &str = "<repl>";
+
+/// Where an input starts in the program it is checked as.
+const PROLOGUE: &str = "BEGIN;\n";
+
+/// What a complete input turned out to be.
+enum Input {
+    /// Statements to run, as source
+    Statements(String),
+    /// The input stops short; read another line
+    Incomplete,
+}
+
+/// Read, check and run inputs until end of input or `QUIT`.
+pub fn run() -> Result<()> {
+    eprintln!(
+        "{BOLD}        Rosy{RESET} v{} {DIM}(end statements with ';', leave with QUIT 0; or Ctrl-D){RESET}",
+        env!("CARGO_PKG_VERSION")
+    );
+    trace::install_panic_hook();
+
+    let mut interpreter: Interpreter<'static> = Interpreter::new(SOURCE_FILE)?;
+    let mut history = String::new();
+    // Top-level statements in `history`, all of which have run
+    let mut ran = 0;
+    let mut buffer = String::new();
+    let stdin = std::io::stdin();
+    loop {
+        eprint!(
+            "{}",
+            if buffer.is_empty() {
+                "rosy> "
+            } else {
+                "  ... "
+            }
+        );
+        std::io::stderr().flush().ok();
+        if stdin.lock().read_line(&mut buffer)? == 0 {
+            eprintln!();
+            return Ok(());
+        }
+        if buffer.trim().is_empty() {
+            buffer.clear();
+            continue;
+        }
+
+        let input = classify(&buffer);
+        let source = match input {
+            Ok(Input::Incomplete) => continue,
+            Ok(Input::Statements(source)) => source,
+            Err(e) => {
+                eprintln!("{BOLD}{RED}error{RESET}: {e}");
+                buffer.clear();
+                continue;
+            }
+        };
+        buffer.clear();
+
+        // Lines are reported within their input, after the prologue and
+        // the inputs before it
+        interpreter
+            .input_lines
+            .push(format!("{PROLOGUE}{history}").lines().count() + 1);
+        let checked = check(&history, &source, ran, |line| interpreter.input_line(line));
+        let (statements, total) = match checked {
+            Ok(checked) => checked,
+            Err(e) => {
+                interpreter.input_lines.pop();
+                eprintln!("{BOLD}{RED}error{RESET}: {e:#}");
+                continue;
+            }
+        };
+        history.push_str(&source);
+        history.push('\n');
+        ran = total;
+
+        // The panic hook has already reported a panic by the time it is caught
+        match panic::catch_unwind(AssertUnwindSafe(|| interpreter.run(statements))) {
+            Ok(Ok(Flow::Quit(code))) => std::process::exit(code),
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => eprintln!("{BOLD}{RED}error{RESET}: {}", interpret::report(&e)),
+            Err(_) => interpreter.leave_blocks(),
+        }
+        trace::restore(trace::Trace::default());
+    }
+}
+
+/// Decide whether `buffer` is statements, an expression to print, or not
+/// finished yet.
+fn classify(buffer: &str) -> Result<Input> {
+    let statements = buffer.trim_end();
+    let error = match parse(statements) {
+        Ok(()) => return Ok(Input::Statements(statements.to_string())),
+        Err(error) => error,
+    };
+
+    let expression = format!("WRITE 6 {};", statements.trim_end_matches(';'));
+    if parse(&expression).is_ok() {
+        return Ok(Input::Statements(expression));
+    }
+
+    // An error past the end of the input is the parser wanting more of it
+    let position = match error.location {
+        pest::error::InputLocation::Pos(position) => position,
+        pest::error::InputLocation::Span((start, _)) => start,
+    };
+    if position >= PROLOGUE.len() + statements.len() {
+        return Ok(Input::Incomplete);
+    }
+    Err(anyhow!("Couldn't parse: {}", error.variant.message()))
+}
+
+/// Parse `statements` as the body of a program.
+fn parse(statements: &str) -> Result<(), Box<pest::error::Error<ast::Rule>>> {
+    ast::CosyParser::parse(ast::Rule::program, &program(statements))
+        .map(|_| ())
+        .map_err(Box::new)
+}
+
+fn program(statements: &str) -> String {
+    format!("{PROLOGUE}{statements}\nEND;\n")
+}
+
+/// Check `source` as the next input after `history`, whose first `ran`
+/// top-level statements have run. Returns the statements still to run,
+/// kept for the rest of the session as the interpreter refers to them, and
+/// the number of top-level statements the session then has. Errors give
+/// their lines as `input_line` maps them.
+fn check(
+    history: &str,
+    source: &str,
+    ran: usize,
+    input_line: impl Fn(usize) -> usize,
+) -> Result<(&'static [Statement], usize)> {
+    let relocate = |mut error: anyhow::Error| {
+        if let Some(RosyError { location: Some(location), .. }) = error.downcast_mut()
+            && location.file.is_none()
+        {
+            location.line = input_line(location.line);
+        }
+        error
+    };
+    let text = program(&format!("{history}{source}"));
+    let pair = ast::CosyParser::parse(ast::Rule::program, &text)
+        .context("Couldn't parse!")?
+        .next()
+        .context("Expected a program")?;
+    let mut ast = Program::from_rule_with_includes(pair, None, &mut IncludeTracker::default())
+        .context("Failed to build AST!")?
+        .context("Expected a program")?;
+    resolve::TypeResolver::resolve(&mut ast)
+        .map_err(relocate)
+        .context("Failed to resolve types!")?;
+
+    let mut context = TranspilationInputContext {
+        source_file: Some(SOURCE_FILE.to_string()),
+        ..Default::default()
+    };
+    ast.transpile(&mut context)
+        .map_err(|errors| transpile_error(errors.into_iter().map(relocate).collect()))?;
+
+    let total = ast.statements.len();
+    let statements = ast.statements.split_off(ran);
+    Ok((Box::leak(statements.into_boxed_slice()), total))
+}
@@ -5,18 +5,23 @@
 //! left as `None` during parsing.
 //!
 //! ## Algorithm
-//!s
+//!
 //! 1. Walk the AST to discover all "type slots" (variables, function args,
 //!    function return types, procedure args)
 //! 2. Build a dependency graph between unresolved slots
 //! 3. Topologically sort (Kahn's algorithm) and resolve from leaves inward
-//! 4. Report cycles as errors
+//! 4. Resolve whatever is left (cycles such as recursive functions or
+//!    `X := X*Y` in a loop, and everything downstream of them) by fixpoint
+//!    iteration over the type lattice in [`crate::rosy_lib::operators::widen`],
+//!    seeded from the slots step 3 resolved
+//! 5. Report slots the fixpoint leaves unknown as errors
 
 use crate::errors::RosyError;
 use crate::program::Program;
 use crate::program::expressions::*;
 use crate::program::statements::*;
 use crate::rosy_lib::RosyType;
+use crate::rosy_lib::operators::widen;
 use crate::transpile::{
     ExprFunctionCallResult, InferenceEdgeResult, TypeHydrationResult, TypeslotDeclarationResult,
 };
@@ -125,6 +130,13 @@ pub enum ExprRecipe {
     RealFn(Box<ExprRecipe>),
     /// IMAG intrinsic — result depends on input type (RE/CM->RE, DA->DA).
     ImagFn(Box<ExprRecipe>),
+    /// `NAME(X)` where `NAME` is both a variable and a function in scope:
+    /// indexing if the variable is an array, otherwise a call. This is how
+    /// a recursive function refers to itself through its return variable.
+    IndexOrCall {
+        variable: TypeSlot,
+        function: TypeSlot,
+    },
     /// Wraps a recipe and adds dimensions to the result type.
     /// Used when inferring a variable's type from an indexed assignment:
     /// e.g., `X[0, 1] := 2` means the RHS is RE, but X should be (RE 2D).
@@ -140,6 +152,9 @@ impl ExprRecipe {
         match self {
             ExprRecipe::Literal(_) | ExprRecipe::Unknown(_) => false,
             ExprRecipe::Variable(s) | ExprRecipe::IndexedVariable(s, _) => s == target,
+            ExprRecipe::IndexOrCall { variable, function } => {
+                variable == target || function == target
+            }
             ExprRecipe::BinaryOp { left, right, .. } | ExprRecipe::Concat(left, right) => {
                 left.references_slot(target) || right.references_slot(target)
             }
@@ -160,6 +175,12 @@ pub enum BinaryOpKind {
     Extract,
     Derive,
     Pow,
+    Eq,
+    Neq,
+    Lt,
+    Gt,
+    Lte,
+    Gte,
 }
 
 // ─── Dependency Graph Node ──────────────────────────────────────────────────
@@ -177,6 +198,20 @@ pub struct GraphNode {
     pub declared_at: Option<SourceLocation>,
     /// Where the assignment that established the type inference rule is.
     pub assigned_at: Option<SourceLocation>,
+    /// Later assignments and call sites feeding the same slot. If the slot
+    /// is on a dependency cycle their types are joined with the rule's, so
+    /// it can widen along the lattice but never change to an unrelated type;
+    /// anywhere else they are ignored and the rule alone decides.
+    pub widenings: Vec<Widening>,
+}
+
+/// An additional source of type information for a slot beyond its rule.
+#[derive(Debug, Clone)]
+pub struct Widening {
+    pub recipe: ExprRecipe,
+    /// Human-readable explanation of where this inference came from.
+    pub reason: String,
+    pub at: Option<SourceLocation>,
 }
 
 // ─── Scope Context (used during graph construction) ─────────────────────────
@@ -198,12 +233,16 @@ pub struct ScopeContext {
 pub struct TypeResolver {
     /// All nodes in the dependency graph, keyed by their slot.
     pub nodes: HashMap<TypeSlot, GraphNode>,
+    /// Slots that depend on themselves, filled in by `topological_resolve`.
+    /// Only these are widened by their later assignments and call sites.
+    pub cyclic: HashSet<TypeSlot>,
 }
 
 impl TypeResolver {
     pub fn new() -> Self {
         TypeResolver {
             nodes: HashMap::new(),
+            cyclic: HashSet::new(),
         }
     }
 
@@ -248,6 +287,7 @@ impl TypeResolver {
                     resolved: Some(t.clone()),
                     declared_at,
                     assigned_at: None,
+                    widenings: Vec::new(),
                 },
             );
         } else {
@@ -259,10 +299,72 @@ impl TypeResolver {
                 resolved: None,
                 declared_at,
                 assigned_at: None,
+                widenings: Vec::new(),
             });
         }
     }
 
+    /// Record another source of type information for an inferred slot.
+    /// Its dependencies join the slot's own, including the slot itself when
+    /// the recipe is self-referential — such slots are then settled by the
+    /// fixpoint pass rather than by the topological one. The widening only
+    /// takes effect if the slot ends up on a cycle.
+    pub fn add_widening(
+        &mut self,
+        slot: &TypeSlot,
+        recipe: ExprRecipe,
+        deps: HashSet<TypeSlot>,
+        reason: String,
+        at: Option<SourceLocation>,
+    ) {
+        if let Some(node) = self.nodes.get_mut(slot) {
+            node.depends_on.extend(deps);
+            node.widenings.push(Widening { recipe, reason, at });
+        }
+    }
+
+    /// Whether `target` is among `from` or any slot they depend on,
+    /// directly or transitively.
+    pub fn reaches<'a>(
+        &self,
+        from: impl IntoIterator<Item = &'a TypeSlot>,
+        target: &TypeSlot,
+    ) -> bool {
+        let mut stack: Vec<&TypeSlot> = from.into_iter().collect();
+        let mut seen: HashSet<&TypeSlot> = HashSet::new();
+        while let Some(slot) = stack.pop() {
+            if slot == target {
+                return true;
+            }
+            if seen.insert(slot)
+                && let Some(node) = self.nodes.get(slot)
+            {
+                stack.extend(node.depends_on.iter());
+            }
+        }
+        false
+    }
+
+    /// Whether the variable `name`, as seen from `scope`, is a slot on a
+    /// dependency cycle. Looks through the enclosing scopes innermost first,
+    /// at their variables and then at the arguments of the enclosing callable.
+    pub fn is_cyclic_variable(&self, scope: &[String], name: &str) -> bool {
+        for depth in (0..=scope.len()).rev() {
+            let variable = TypeSlot::Variable(scope[..depth].to_vec(), name.to_string());
+            if self.nodes.contains_key(&variable) {
+                return self.cyclic.contains(&variable);
+            }
+            if let Some((callable, outer)) = scope[..depth].split_last() {
+                let argument =
+                    TypeSlot::Argument(outer.to_vec(), callable.clone(), name.to_string());
+                if self.nodes.contains_key(&argument) {
+                    return self.cyclic.contains(&argument);
+                }
+            }
+        }
+        false
+    }
+
     // ─── Phase 1: Discovery ─────────────────────────────────────────────
 
     /// Walk the AST, creating graph nodes for every type slot and recording
@@ -356,13 +458,18 @@ impl TypeResolver {
                     // Build recipe for the argument expression
                     let mut deps = HashSet::new();
                     let recipe = self.build_expr_recipe(arg_expr, ctx, &mut deps);
+                    let reason = format!("inferred from argument {} at call site", i + 1);
 
+                    // The first call site sets the rule; every later one
+                    // (including recursive calls from the callee's own body)
+                    // is joined with it.
                     let node = self.nodes.get_mut(param_slot).unwrap();
-                    node.rule = ResolutionRule::InferredFrom {
-                        recipe,
-                        reason: format!("inferred from argument {} at call site", i + 1),
-                    };
-                    node.depends_on = deps;
+                    if matches!(node.rule, ResolutionRule::InferredFrom { .. }) {
+                        self.add_widening(param_slot, recipe, deps, reason, None);
+                    } else {
+                        node.rule = ResolutionRule::InferredFrom { recipe, reason };
+                        node.depends_on = deps;
+                    }
                 }
             }
         }
@@ -467,8 +574,29 @@ impl TypeResolver {
             }
         }
 
-        // Any remaining unresolved nodes are cycles or truly unresolvable
-        // (exclude slots that were already warned about as unused variables)
+        // Whatever is left sits on or behind a cycle. Settle it by fixpoint
+        // iteration, seeded from everything resolved so far.
+        let mut pending: Vec<TypeSlot> = self
+            .nodes
+            .values()
+            .filter(|n| n.resolved.is_none() && !warned_slots.contains(&n.slot))
+            .map(|n| n.slot.clone())
+            .collect();
+        pending.sort_by_cached_key(|slot| slot.to_string());
+        self.cyclic = pending
+            .iter()
+            .filter(|slot| self.reaches(&self.nodes[*slot].depends_on, slot))
+            .cloned()
+            .collect();
+        self.fixpoint_resolve(&pending)?;
+        resolved_count += pending
+            .iter()
+            .filter(|slot| self.nodes.get(*slot).is_some_and(|n| n.resolved.is_some()))
+            .count();
+
+        // Any remaining unresolved nodes are ambiguous cycles or truly
+        // unresolvable (exclude slots that were already warned about as
+        // unused variables)
         let unresolved: Vec<&GraphNode> = self
             .nodes
             .values()
@@ -487,6 +615,124 @@ impl TypeResolver {
         self.build_resolution_error(&unresolved)
     }
 
+    /// Resolve the slots Kahn's algorithm could not order by fixpoint
+    /// iteration. Every pending slot starts unknown; each round joins the
+    /// types its rule (and, on a cycle, its widenings) evaluate to under the
+    /// current guesses, skipping any that still reference an unknown slot.
+    /// Guesses only move up the lattice, so this terminates. Slots still
+    /// unknown at the fixpoint are left unresolved for the caller to report;
+    /// two incompatible types for one slot are reported here as a conflict.
+    fn fixpoint_resolve(&mut self, pending: &[TypeSlot]) -> Result<()> {
+        // Each slot can move at most from unknown to CD (three steps), and
+        // every round that is not the last moves at least one slot.
+        let max_rounds = 4 * pending.len() + 1;
+        for _ in 0..max_rounds {
+            let mut changed = false;
+            for slot in pending {
+                let current = self.nodes.get(slot).and_then(|n| n.resolved);
+                let mut next = current;
+                for (candidate, reason, at) in self.candidate_types(slot) {
+                    next = match next {
+                        None => Some(candidate),
+                        Some(t) => Some(widen::join(&t, &candidate).ok_or_else(|| {
+                            self.conflict_error(slot, &t, &candidate, &reason, at)
+                        })?),
+                    };
+                }
+                if next != current {
+                    self.nodes.get_mut(slot).unwrap().resolved = next;
+                    changed = true;
+                }
+            }
+            if !changed {
+                return Ok(());
+            }
+        }
+
+        // Not reached for a finite lattice, but never hand out guesses
+        // that have not settled.
+        for slot in pending {
+            if let Some(node) = self.nodes.get_mut(slot) {
+                node.resolved = None;
+            }
+        }
+        Ok(())
+    }
+
+    /// Every type a slot's rule, and its widenings if it is on a cycle,
+    /// evaluate to right now, with where each came from. Sources that cannot
+    /// be evaluated yet are skipped.
+    fn candidate_types(&self, slot: &TypeSlot) -> Vec<(RosyType, String, Option<SourceLocation>)> {
+        let Some(node) = self.nodes.get(slot) else {
+            return Vec::new();
+        };
+        let mut candidates = Vec::new();
+        match &node.rule {
+            ResolutionRule::Explicit(t) => {
+                candidates.push((*t, "explicit type".to_string(), node.declared_at.clone()));
+            }
+            ResolutionRule::InferredFrom { recipe, reason } => {
+                if let Ok(t) = self.evaluate_recipe(recipe) {
+                    candidates.push((t, reason.clone(), node.assigned_at.clone()));
+                }
+            }
+            ResolutionRule::Mirror { source, reason } => {
+                if let Some(t) = self.nodes.get(source).and_then(|n| n.resolved) {
+                    candidates.push((t, reason.clone(), node.assigned_at.clone()));
+                }
+            }
+            ResolutionRule::Unresolved => {}
+        }
+        if !self.cyclic.contains(slot) {
+            return candidates;
+        }
+        for widening in &node.widenings {
+            if let Ok(t) = self.evaluate_recipe(&widening.recipe) {
+                candidates.push((t, widening.reason.clone(), widening.at.clone()));
+            }
+        }
+        candidates
+    }
+
+    /// Error for a slot that is given two types with no common supertype.
+    fn conflict_error(
+        &self,
+        slot: &TypeSlot,
+        existing: &RosyType,
+        incoming: &RosyType,
+        reason: &str,
+        at: Option<SourceLocation>,
+    ) -> anyhow::Error {
+        let node = self.nodes.get(slot);
+        let first_hint = node
+            .and_then(|n| n.assigned_at.as_ref().or(n.declared_at.as_ref()))
+            .map(|loc| format!("\n│  📍 First inferred at: {}", loc))
+            .unwrap_or_default();
+        let then_hint = at
+            .as_ref()
+            .map(|loc| format!("\n│  📍 Then given at:     {}", loc))
+            .unwrap_or_default();
+        let msg = format!(
+            "\n╭─ Type Conflict ──────────────────────────────────────────\n\
+            │\n\
+            │  {} is given incompatible types:\n\
+            │     • First inferred as:  {}\n\
+            │     • Then given:         {} ({}){}{}\n\
+            │\n\
+            │  Types may only widen along RE → CM/DA → CD; anything else\n\
+            │  needs separate variables or an explicit conversion.\n\
+            │\n\
+            ╰──────────────────────────────────────────────────────────",
+            slot, existing, incoming, reason, first_hint, then_hint,
+        );
+        RosyError {
+            message: msg,
+            location: at.or_else(|| node.and_then(|n| n.assigned_at.clone())),
+            severity: crate::errors::RosyErrorSeverity::Error,
+        }
+        .into()
+    }
+
     /// Build a detailed error message for unresolved type slots.
     fn build_resolution_error(&self, unresolved: &[&GraphNode]) -> Result<Vec<RosyError>> {
         // Partition into cycle nodes (have unresolved deps) vs no-info nodes
@@ -618,8 +864,9 @@ impl TypeResolver {
         }
 
         msg.push_str("\n│  The type resolver builds a dependency graph and resolves");
-        msg.push_str("\n│  types from leaves inward. If a slot has no path to a");
-        msg.push_str("\n│  known type, or is part of a cycle, it cannot be resolved.");
+        msg.push_str("\n│  types from leaves inward, then iterates any cycles. If a");
+        msg.push_str("\n│  slot has no path to a known type, even through a cycle,");
+        msg.push_str("\n│  it cannot be resolved.");
         msg.push_str("\n│");
         msg.push_str("\n╰──────────────────────────────────────────────────────────");
         // Use the location of the first unresolvable slot for diagnostic placement
@@ -747,6 +994,20 @@ impl TypeResolver {
                 }
                 Ok(RosyType::new(base.base_type, new_dim))
             }
+            ExprRecipe::IndexOrCall { variable, function } => {
+                let variable_type = self
+                    .nodes
+                    .get(variable)
+                    .and_then(|n| n.resolved)
+                    .ok_or_else(|| anyhow!("Variable slot {} not resolved", variable))?;
+                // Same disambiguation as VarExpr::classify: a scalar cannot
+                // be indexed, so the parentheses must be a call.
+                if variable_type.dimensions > 0 {
+                    self.evaluate_recipe(&ExprRecipe::IndexedVariable(variable.clone(), 1))
+                } else {
+                    self.evaluate_recipe(&ExprRecipe::Variable(function.clone()))
+                }
+            }
             ExprRecipe::WithDimensions(inner, extra_dims) => {
                 let mut t = self.evaluate_recipe(inner)?;
                 t.dimensions += extra_dims;
@@ -783,6 +1044,24 @@ impl TypeResolver {
                     BinaryOpKind::Pow => {
                         crate::rosy_lib::operators::pow::get_return_type(&left_type, &right_type)
                     }
+                    BinaryOpKind::Eq => {
+                        crate::rosy_lib::operators::eq::get_return_type(&left_type, &right_type)
+                    }
+                    BinaryOpKind::Neq => {
+                        crate::rosy_lib::operators::neq::get_return_type(&left_type, &right_type)
+                    }
+                    BinaryOpKind::Lt => {
+                        crate::rosy_lib::operators::lt::get_return_type(&left_type, &right_type)
+                    }
+                    BinaryOpKind::Gt => {
+                        crate::rosy_lib::operators::gt::get_return_type(&left_type, &right_type)
+                    }
+                    BinaryOpKind::Lte => {
+                        crate::rosy_lib::operators::lte::get_return_type(&left_type, &right_type)
+                    }
+                    BinaryOpKind::Gte => {
+                        crate::rosy_lib::operators::gte::get_return_type(&left_type, &right_type)
+                    }
                 };
                 result.ok_or_else(|| {
                     anyhow!(
@@ -837,3 +1116,82 @@ impl TypeResolver {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{CosyParser, FromRule, Rule};
+    use pest::Parser;
+
+    fn resolve(source: &str) -> Result<TypeResolver> {
+        let pair = CosyParser::parse(Rule::program, source)
+            .map_err(|e| anyhow!("parse error: {e}"))?
+            .next()
+            .ok_or_else(|| anyhow!("empty parse"))?;
+        let mut program = Program::from_rule(pair)?.ok_or_else(|| anyhow!("no program"))?;
+        TypeResolver::resolve(&mut program).map(|(resolver, _)| resolver)
+    }
+
+    fn resolved(resolver: &TypeResolver, slot: TypeSlot) -> Option<RosyType> {
+        resolver.nodes.get(&slot).and_then(|n| n.resolved)
+    }
+
+    fn var(scope: &[&str], name: &str) -> TypeSlot {
+        TypeSlot::Variable(scope.iter().map(|s| s.to_string()).collect(), name.to_string())
+    }
+
+    #[test]
+    fn recursive_function_resolves_from_its_base_case() {
+        let resolver = resolve(
+            "BEGIN;\n FUNCTION FACT N;\n  IF N > 1;\n   FACT := N * FACT(N - 1);\n  ELSEIF TRUE;\n   FACT := 1;\n  ENDIF;\n ENDFUNCTION;\n WRITE 6 FACT(5);\nEND;",
+        )
+        .unwrap();
+        let ret = TypeSlot::FunctionReturn(vec![], "FACT".to_string());
+        let arg = TypeSlot::Argument(vec![], "FACT".to_string(), "N".to_string());
+        assert_eq!(resolved(&resolver, ret), Some(RosyType::RE()));
+        assert_eq!(resolved(&resolver, arg), Some(RosyType::RE()));
+    }
+
+    #[test]
+    fn loop_carried_variable_widens_to_da() {
+        let resolver = resolve(
+            "BEGIN;\n VARIABLE X; VARIABLE Y; VARIABLE I;\n X := 1;\n Y := DA(1);\n LOOP I 1 3;\n  X := X*Y;\n ENDLOOP;\nEND;",
+        )
+        .unwrap();
+        assert_eq!(resolved(&resolver, var(&[], "X")), Some(RosyType::DA()));
+    }
+
+    #[test]
+    fn reassignment_off_a_cycle_keeps_the_first_type() {
+        let err = resolve("BEGIN;\n VARIABLE X;\n X := 1;\n WRITE 6 X;\n X := DA(1);\nEND;")
+            .err()
+            .expect("X is not on a cycle, so it may not widen");
+        assert!(err.to_string().contains("exactly one type"), "{err}");
+    }
+
+    #[test]
+    fn explicit_type_is_not_widened_into() {
+        let err = resolve("BEGIN;\n VARIABLE (DA) Y;\n Y := 1;\nEND;")
+            .err()
+            .expect("Y is declared DA but assigned RE");
+        assert!(err.to_string().contains("is declared as"), "{err}");
+    }
+
+    #[test]
+    fn incompatible_widening_is_a_conflict() {
+        let err = resolve(
+            "BEGIN;\n VARIABLE X; VARIABLE I;\n X := 1;\n LOOP I 1 3;\n  X := ST(X);\n ENDLOOP;\nEND;",
+        )
+        .err()
+        .expect("RE and ST have no common type");
+        assert!(err.to_string().contains("conflicting types"), "{err}");
+    }
+
+    #[test]
+    fn cycle_without_a_seed_is_still_an_error() {
+        let err = resolve("BEGIN;\n PROCEDURE P X;\n  P X-1;\n ENDPROCEDURE;\nEND;")
+            .err()
+            .expect("nothing fixes the type of X");
+        assert!(err.to_string().contains("Circular dependencies"), "{err}");
+    }
+}
@@ -0,0 +1,463 @@
+//! Checkpoint/resume runtime support for Rosy.
+//!
+//! Backs the `CHECKPOINT` statement and the `--resume <file>` flag of
+//! generated programs. A checkpoint is taken at the end of an iteration of
+//! the program's checkpointed `LOOP` and holds everything the next iteration
+//! starts from:
+//!
+//! - the variables declared before the loop,
+//! - the Taylor configuration ([`TaylorSnapshot`]: DAINI order and number of
+//!   variables, DANOT truncation order, DAEPS epsilon, weights, DAFSET filter),
+//! - the position of the random number generator ([`RngState`]),
+//! - the open file units ([`OpenFile`]),
+//! - the number of iterations done.
+//!
+//! ## File format
+//!
+//! Plain text, one item per line. Every value is stored in its WRITEM
+//! representation (see [`mem_serial`](super::mem_serial)), arrays as an
+//! `ARRAY` line followed by their elements. Numbers use Rust's shortest
+//! round-trip formatting, so restored values are bit-for-bit the saved ones.
+//!
+//! ```text
+//! ROSY CHECKPOINT 1
+//! ITERATIONS 3
+//! TAYLOR 3 3 6 0.000000000000001 1 1 1 1 1 1
+//! PENDING NONE
+//! FILTER NONE
+//! RNG 0a1b...ff 0 96
+//! FILE 20 0 0 1 42 results.dat
+//! VARIABLE X
+//! VALUE 1 1 1;2.5;;0
+//! VARIABLE V
+//! ARRAY 2
+//! VALUE 1 1 1;1;;0
+//! VALUE 1 1 1;2;;0
+//! ```
+
+use std::marker::PhantomData;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow, bail, ensure};
+
+use crate::rosy_lib::core::file_io::{OpenFile, open_files, reopen_files};
+use crate::rosy_lib::core::mem_serial::{RosyReadm, RosyWritem};
+use crate::rosy_lib::core::rng::{RngState, rng_state, set_rng_state};
+use crate::rosy_lib::taylor::{DA, TaylorConfig, TaylorSnapshot, restore_taylor, set_filter_da, taylor_snapshot};
+
+/// Command-line flag that resumes a program from a checkpoint file.
+pub const RESUME_FLAG: &str = "--resume";
+
+const HEADER: &str = "ROSY CHECKPOINT 1";
+
+/// One value in WRITEM form: `(var_info, dp_array, int_array, da_params)`.
+type Record = (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>);
+
+/// A saved variable: a single value, or an array of entries.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Entry {
+    Value(Record),
+    Array(Vec<Entry>),
+}
+
+/// Shape marker for a single value.
+pub struct Value;
+/// Shape marker for an array whose elements have shape `S`.
+pub struct Array<S>(PhantomData<S>);
+
+/// A variable that can go into a checkpoint with shape `S`.
+///
+/// The shape tells a one-dimensional RE array (`Array<Value>`) apart from a
+/// VE (`Value`), which are both `Vec<f64>` in Rust.
+pub trait Checkpointed<S>: Sized {
+    fn to_entry(&self) -> Entry;
+    fn from_entry(entry: &Entry) -> Result<Self>;
+}
+
+impl<T: RosyWritem + RosyReadm> Checkpointed<Value> for T {
+    fn to_entry(&self) -> Entry {
+        Entry::Value(self.writem())
+    }
+
+    fn from_entry(entry: &Entry) -> Result<Self> {
+        match entry {
+            Entry::Value((var_info, dp, int, da_params)) => {
+                let length = var_info.get(1).copied().unwrap_or_default();
+                T::readm(var_info, length, dp, int, da_params)
+            }
+            Entry::Array(_) => bail!("Expected a single value, found an array"),
+        }
+    }
+}
+
+impl<S, T: Checkpointed<S>> Checkpointed<Array<S>> for Vec<T> {
+    fn to_entry(&self) -> Entry {
+        Entry::Array(self.iter().map(T::to_entry).collect())
+    }
+
+    fn from_entry(entry: &Entry) -> Result<Self> {
+        match entry {
+            Entry::Array(elements) => elements.iter().map(T::from_entry).collect(),
+            Entry::Value(_) => bail!("Expected an array, found a single value"),
+        }
+    }
+}
+
+/// The state of a program at the end of an iteration of its checkpointed loop.
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    /// Iterations of the checkpointed loop already done
+    pub iterations: usize,
+    /// The DA configuration, without the filter template
+    taylor: TaylorSnapshot,
+    /// DAFSET template, which can only be read back once `taylor` is restored
+    filter: Option<Vec<Entry>>,
+    rng: RngState,
+    files: Vec<OpenFile>,
+    variables: Vec<(String, Entry)>,
+}
+
+impl Checkpoint {
+    /// Capture the runtime state after `iterations` iterations; the
+    /// variables are added with [`Self::put`].
+    pub fn capture(iterations: usize) -> Result<Self> {
+        let mut taylor = taylor_snapshot().context("Failed to capture the DA configuration")?;
+        let filter = taylor.filter.take().map(|template| template.iter().map(DA::to_entry).collect());
+        Ok(Checkpoint {
+            iterations,
+            taylor,
+            filter,
+            rng: rng_state(),
+            files: open_files().context("Failed to capture the open files")?,
+            variables: Vec::new(),
+        })
+    }
+
+    pub fn put<S, T: Checkpointed<S>>(&mut self, name: &str, value: &T) {
+        self.put_entry(name, value.to_entry());
+    }
+
+    /// Add a variable that is already in checkpoint form.
+    pub fn put_entry(&mut self, name: &str, entry: Entry) {
+        self.variables.push((name.to_string(), entry));
+    }
+
+    /// The saved value of variable `name`. Call after [`Self::restore_runtime`],
+    /// since DA values are read against the restored DA configuration.
+    pub fn get<S, T: Checkpointed<S>>(&self, name: &str) -> Result<T> {
+        T::from_entry(self.entry(name)?).with_context(|| format!("...while restoring variable '{}'", name))
+    }
+
+    /// The saved entry of variable `name`, for callers that convert it
+    /// themselves.
+    pub fn entry(&self, name: &str) -> Result<&Entry> {
+        let (_, entry) = self
+            .variables
+            .iter()
+            .find(|(saved, _)| saved == name)
+            .with_context(|| format!("The checkpoint has no variable '{}'; was it written by another program?", name))?;
+        Ok(entry)
+    }
+
+    /// Put the DA configuration, random number generator and open files back
+    /// as they were.
+    pub fn restore_runtime(&self) -> Result<()> {
+        restore_taylor(&self.taylor).context("Failed to restore the DA configuration")?;
+        if let Some(entries) = &self.filter {
+            let template = entries.iter().map(DA::from_entry).collect::<Result<Vec<DA>>>()
+                .context("Failed to restore the DAFSET template")?;
+            set_filter_da(Some(template))?;
+        }
+        set_rng_state(self.rng);
+        reopen_files(&self.files).context("Failed to reopen the program's files")?;
+        Ok(())
+    }
+
+    /// Write the checkpoint to `path`. The file is replaced in one step, so a
+    /// crash while saving leaves the previous checkpoint intact.
+    pub fn save(&self, path: &str) -> Result<()> {
+        let temporary = format!("{}.{}.tmp", path, std::process::id());
+        std::fs::write(&temporary, self.to_text())
+            .with_context(|| format!("Failed to write checkpoint '{}'", temporary))?;
+        std::fs::rename(&temporary, path)
+            .with_context(|| format!("Failed to move checkpoint into place at '{}'", path))?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read checkpoint '{}'", path.display()))?;
+        Self::from_text(&text).with_context(|| format!("Failed to parse checkpoint '{}'", path.display()))
+    }
+
+    /// The checkpoint named by `--resume <file>` (or `--resume=<file>`) on
+    /// the command line, if any.
+    pub fn resume_from_args() -> Result<Option<Self>> {
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            if arg == RESUME_FLAG {
+                let path = args
+                    .next()
+                    .with_context(|| format!("{} needs the path of a checkpoint file", RESUME_FLAG))?;
+                return Self::load(Path::new(&path)).map(Some);
+            }
+            if let Some(path) = arg.strip_prefix(RESUME_FLAG).and_then(|rest| rest.strip_prefix('=')) {
+                return Self::load(Path::new(path)).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    fn to_text(&self) -> String {
+        let mut lines = vec![HEADER.to_string(), format!("ITERATIONS {}", self.iterations)];
+
+        let config = &self.taylor.config;
+        let mut taylor = format!(
+            "TAYLOR {} {} {} {}",
+            self.taylor.init_order, config.max_order, config.num_vars, config.epsilon
+        );
+        for weight in &self.taylor.weights {
+            taylor += &format!(" {}", weight);
+        }
+        lines.push(taylor);
+        lines.push(match &self.taylor.pending_weights {
+            Some(weights) => format!("PENDING {}", join(weights)),
+            None => "PENDING NONE".to_string(),
+        });
+        match &self.filter {
+            Some(entries) => {
+                lines.push(format!("FILTER {}", entries.len()));
+                for entry in entries {
+                    write_entry(&mut lines, entry);
+                }
+            }
+            None => lines.push("FILTER NONE".to_string()),
+        }
+
+        let seed: String = self.rng.seed.iter().map(|byte| format!("{:02x}", byte)).collect();
+        lines.push(format!("RNG {} {} {}", seed, self.rng.stream, self.rng.word_pos));
+
+        for file in &self.files {
+            lines.push(format!(
+                "FILE {} {} {} {} {} {}",
+                file.unit, file.is_binary as u8, file.reads as u8, file.writes as u8, file.position, file.path
+            ));
+        }
+        for (name, entry) in &self.variables {
+            lines.push(format!("VARIABLE {}", name));
+            write_entry(&mut lines, entry);
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    fn from_text(text: &str) -> Result<Self> {
+        let mut lines = text.lines().enumerate().peekable();
+        let (_, header) = lines.next().context("The checkpoint is empty")?;
+        ensure!(header == HEADER, "Not a Rosy checkpoint (expected '{}', found '{}')", HEADER, header);
+
+        let mut iterations = None;
+        let mut taylor: Option<(u32, TaylorConfig, Vec<u32>)> = None;
+        let mut pending_weights = None;
+        let mut filter = None;
+        let mut rng = None;
+        let mut files = Vec::new();
+        let mut variables = Vec::new();
+
+        while let Some((index, line)) = lines.next() {
+            let fail = |message: &str| anyhow!("line {}: {}", index + 1, message);
+            let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+            match keyword {
+                "" => {}
+                "ITERATIONS" => iterations = Some(rest.parse::<usize>().map_err(|_| fail("bad iteration count"))?),
+                "TAYLOR" => {
+                    let fields: Vec<&str> = rest.split_whitespace().collect();
+                    ensure!(fields.len() >= 4, fail("incomplete DA configuration"));
+                    let number = |field: &str| field.parse::<u32>().map_err(|_| fail("bad DA configuration"));
+                    let num_vars = number(fields[2])? as usize;
+                    let epsilon = fields[3].parse::<f64>().map_err(|_| fail("bad DA epsilon"))?;
+                    let weights = fields[4..].iter().map(|w| number(w)).collect::<Result<Vec<u32>>>()?;
+                    let config = TaylorConfig::new(number(fields[1])?, num_vars, epsilon)?;
+                    taylor = Some((number(fields[0])?, config, weights));
+                }
+                "PENDING" => {
+                    pending_weights = match rest {
+                        "NONE" => None,
+                        weights => Some(
+                            weights
+                                .split_whitespace()
+                                .map(|w| w.parse::<u32>().map_err(|_| fail("bad DANOTW weight")))
+                                .collect::<Result<Vec<u32>>>()?,
+                        ),
+                    };
+                }
+                "FILTER" => {
+                    if rest != "NONE" {
+                        let count = rest.parse::<usize>().map_err(|_| fail("bad filter length"))?;
+                        let entries = (0..count).map(|_| read_entry(&mut lines)).collect::<Result<Vec<_>>>()?;
+                        filter = Some(entries);
+                    }
+                }
+                "RNG" => {
+                    let fields: Vec<&str> = rest.split_whitespace().collect();
+                    ensure!(fields.len() == 3 && fields[0].len() == 64, fail("bad random number generator state"));
+                    let mut seed = [0u8; 32];
+                    for (byte, pair) in seed.iter_mut().zip(fields[0].as_bytes().chunks(2)) {
+                        let pair = std::str::from_utf8(pair).map_err(|_| fail("bad seed"))?;
+                        *byte = u8::from_str_radix(pair, 16).map_err(|_| fail("bad seed"))?;
+                    }
+                    rng = Some(RngState {
+                        seed,
+                        stream: fields[1].parse().map_err(|_| fail("bad stream"))?,
+                        word_pos: fields[2].parse().map_err(|_| fail("bad stream position"))?,
+                    });
+                }
+                "FILE" => {
+                    let fields: Vec<&str> = rest.splitn(6, ' ').collect();
+                    ensure!(fields.len() == 6, fail("incomplete file unit"));
+                    let flag = |field: &str| match field {
+                        "0" => Ok(false),
+                        "1" => Ok(true),
+                        _ => Err(fail("bad file mode")),
+                    };
+                    files.push(OpenFile {
+                        unit: fields[0].parse().map_err(|_| fail("bad unit"))?,
+                        is_binary: flag(fields[1])?,
+                        reads: flag(fields[2])?,
+                        writes: flag(fields[3])?,
+                        position: fields[4].parse().map_err(|_| fail("bad file position"))?,
+                        path: fields[5].to_string(),
+                    });
+                }
+                "VARIABLE" => variables.push((rest.to_string(), read_entry(&mut lines)?)),
+                other => bail!(fail(&format!("unknown item '{}'", other))),
+            }
+        }
+
+        let (init_order, config, weights) = taylor.context("The checkpoint has no DA configuration")?;
+        Ok(Checkpoint {
+            iterations: iterations.context("The checkpoint has no iteration count")?,
+            taylor: TaylorSnapshot { init_order, config, weights, pending_weights, filter: None },
+            filter,
+            rng: rng.context("The checkpoint has no random number generator state")?,
+            files,
+            variables,
+        })
+    }
+}
+
+/// The command-line arguments without `--resume <file>`, so that ARGGET
+/// numbers them the same whether or not the program was resumed.
+pub fn program_args() -> impl Iterator<Item = String> {
+    let mut args = std::env::args();
+    std::iter::from_fn(move || loop {
+        let arg = args.next()?;
+        if arg == RESUME_FLAG {
+            args.next();
+        } else if !arg.starts_with(&format!("{}=", RESUME_FLAG)) {
+            return Some(arg);
+        }
+    })
+}
+
+fn join<T: std::fmt::Display>(values: &[T]) -> String {
+    values.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+fn write_entry(lines: &mut Vec<String>, entry: &Entry) {
+    match entry {
+        Entry::Value((var_info, dp, int, da_params)) => lines.push(format!(
+            "VALUE {};{};{};{}",
+            join(var_info), join(dp), join(int), join(da_params)
+        )),
+        Entry::Array(elements) => {
+            lines.push(format!("ARRAY {}", elements.len()));
+            for element in elements {
+                write_entry(lines, element);
+            }
+        }
+    }
+}
+
+fn read_entry<'a>(lines: &mut impl Iterator<Item = (usize, &'a str)>) -> Result<Entry> {
+    let (index, line) = lines.next().context("The checkpoint ends in the middle of a value")?;
+    let fail = |message: &str| anyhow!("line {}: {}", index + 1, message);
+    if let Some(count) = line.strip_prefix("ARRAY ") {
+        let count = count.parse::<usize>().map_err(|_| fail("bad array length"))?;
+        return Ok(Entry::Array((0..count).map(|_| read_entry(lines)).collect::<Result<_>>()?));
+    }
+    let fields = line.strip_prefix("VALUE ").ok_or_else(|| fail("expected a value"))?;
+    let parts = fields
+        .split(';')
+        .map(|part| {
+            part.split_whitespace()
+                .map(|number| number.parse::<f64>().map_err(|_| fail("bad number")))
+                .collect::<Result<Vec<f64>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let [var_info, dp, int, da_params]: [Vec<f64>; 4] =
+        parts.try_into().map_err(|_| fail("a value has four parts"))?;
+    Ok(Entry::Value((var_info, dp, int, da_params)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rosy_lib::core::file_io::{rosy_closef, rosy_openf, rosy_write_to_unit};
+    use crate::rosy_lib::core::rng::{rosy_reran, set_rng_seed};
+    use crate::rosy_lib::taylor::{cleanup_taylor, get_config, init_taylor, set_truncation_order};
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn round_trip_restores_state() -> Result<()> {
+        cleanup_taylor();
+        init_taylor(4, 2)?;
+        set_truncation_order(3)?;
+        set_rng_seed(11.0);
+        let dir = tempfile::tempdir()?;
+        let log = dir.path().join("log.txt");
+        let log = log.to_str().unwrap();
+        rosy_openf(93.0, log, "UNKNOWN")?;
+        rosy_write_to_unit(93, "kept")?;
+
+        let x = (&DA::variable(1)? * (1.0 / 3.0))?;
+        let ve: Vec<f64> = vec![0.1, 2.0];
+        let res: Vec<Vec<f64>> = vec![vec![1.0, 2.0]];
+
+        let mut checkpoint = Checkpoint::capture(3)?;
+        checkpoint.put::<Value, _>("X", &x);
+        checkpoint.put::<Value, _>("V", &ve);
+        checkpoint.put::<Array<Array<Value>>, _>("R", &res);
+        let path = dir.path().join("state.ckpt");
+        checkpoint.save(path.to_str().unwrap())?;
+
+        let expected_draw = rosy_reran();
+        rosy_write_to_unit(93, "lost")?;
+        rosy_closef(93.0)?;
+        cleanup_taylor();
+        init_taylor(2, 1)?;
+
+        let resumed = Checkpoint::load(&path)?;
+        resumed.restore_runtime()?;
+        assert_eq!(resumed.iterations, 3);
+        assert_eq!(get_config()?.max_order, 3);
+        assert_eq!(get_config()?.num_vars, 2);
+        assert_eq!(rosy_reran().to_bits(), expected_draw.to_bits());
+
+        let restored: DA = resumed.get::<Value, _>("X")?;
+        for (mono, coeff) in x.coeffs_iter() {
+            assert_eq!(restored.get_coeff(&mono).to_bits(), coeff.to_bits());
+        }
+        assert_eq!(resumed.get::<Value, Vec<f64>>("V")?, ve);
+        assert_eq!(resumed.get::<Array<Array<Value>>, Vec<Vec<f64>>>("R")?, res);
+        assert!(resumed.get::<Array<Value>, Vec<f64>>("V").is_err());
+
+        rosy_write_to_unit(93, "again")?;
+        rosy_closef(93.0)?;
+        assert_eq!(std::fs::read_to_string(log)?, "kept\nagain\n");
+
+        cleanup_taylor();
+        Ok(())
+    }
+}
@@ -1,12 +1,14 @@
 //! DA coefficient-level operations: DASCL, DASGN, DADER, DAINT, DANORO, DANORS,
-//! DAFSET, DAFILT, DARAN, DAGMD, DACODE, DAFLO, CDFLO, DANOW, CDF2, CDNF, CDNFDA, CDNFDS.
+//! DAFSET, DAFILT, DARAN, DAGMD, DACODE, DAFLO, CDFLO, DANOW, DAINV, CDF2, CDNF, CDNFDA,
+//! CDNFDS.
 
 use anyhow::{Context, Result, bail, ensure};
 use num_complex::Complex64;
 
 use crate::rosy_lib::taylor::config::DERIV_INVALID;
 use crate::rosy_lib::taylor::da::{DA as GenericDA, DACoefficient};
-use crate::rosy_lib::taylor::{CD, DA, get_filter_da, get_runtime, set_filter_da};
+use crate::rosy_lib::core::{linv::rosy_linv, polval::rosy_polval_da};
+use crate::rosy_lib::taylor::{CD, DA, Monomial, get_filter_da, get_runtime, set_filter_da};
 
 /// DASCL: Scale all coefficients of every DA element in the array by `scalar`.
 ///
@@ -336,15 +338,17 @@ pub fn rosy_dagmd(g: &Vec<DA>, f: &Vec<DA>, result: &mut Vec<DA>, dim: usize) ->
 /// exponent vector into `result[m]`. The `params` vector should contain at least
 /// `[order, num_vars]` for validation against the current DAINI setup.
 ///
-/// `result` is a 2D RE array: `result[m][v]` = exponent of variable v+1 in monomial m.
+/// `result` is a 2D RE array: `result[m][v]` = exponent of variable v+1 in monomial m,
+/// as a power of the variable also under weighted DA.
 pub fn rosy_dacode(params: &Vec<f64>, size: usize, result: &mut Vec<Vec<f64>>) -> Result<()> {
-    let (num_monomials, num_vars, monomial_list) = {
+    let (num_monomials, num_vars, monomial_list, weights) = {
         let rt =
             get_runtime().context("DACODE requires DA to be initialized (call DAINI first)")?;
         (
             rt.num_monomials,
             rt.config.num_vars,
             rt.monomial_list.clone(),
+            rt.weights,
         )
     };
 
@@ -372,11 +376,11 @@ pub fn rosy_dacode(params: &Vec<f64>, size: usize, result: &mut Vec<Vec<f64>>) -
     let count = size.min(num_monomials).min(result.len());
 
     for m in 0..count {
-        let mono = &monomial_list[m];
+        let powers = monomial_list[m].powers(&weights);
         let inner = &mut result[m];
         let vlen = inner.len().min(num_vars);
         for v in 0..vlen {
-            inner[v] = mono.exponents[v] as f64;
+            inner[v] = powers[v] as f64;
         }
     }
 
@@ -539,7 +543,8 @@ pub fn rosy_cdflo(rhs: &Vec<CD>, ic: &Vec<CD>, result: &mut Vec<CD>, dim: usize)
 /// DANOW: Compute the order-weighted max norm of a DA variable.
 ///
 /// For each monomial of order k with coefficient c, computes |c| * weight^k.
-/// Returns the maximum over all monomials.
+/// Returns the maximum over all monomials. Under weighted DA, k is the
+/// weighted order.
 pub fn rosy_danow(da: &DA, weight: f64, result: &mut f64) -> Result<()> {
     *result = 0.0;
 
@@ -554,6 +559,138 @@ pub fn rosy_danow(da: &DA, weight: f64, result: &mut f64) -> Result<()> {
     Ok(())
 }
 
+/// DANOW with a per-order breakdown: `by_order[k]` receives the largest
+/// |c| * weight^k over the monomials of (weighted) order k, for k = 0 up to
+/// the current truncation order, so the overall norm is the largest entry.
+pub fn rosy_danow_by_order(da: &DA, weight: f64, result: &mut f64, by_order: &mut Vec<f64>) -> Result<()> {
+    let max_order = get_runtime()
+        .context("DANOW requires DA to be initialized (call DAINI first)")?
+        .config
+        .max_order as usize;
+
+    *by_order = vec![0.0; max_order + 1];
+    for (monomial, coeff) in da.coeffs_iter() {
+        let order = monomial.total_order as usize;
+        if order > max_order {
+            continue;
+        }
+        let weighted = coeff.abs() * weight.powf(order as f64);
+        if weighted > by_order[order] {
+            by_order[order] = weighted;
+        }
+    }
+
+    rosy_danow(da, weight, result)
+}
+
+/// DAINV: Invert a DA map.
+///
+/// `map[..n]` is a map of the first `n` DA variables; any further variables
+/// are parameters and pass through unchanged. Splitting the map as
+/// M = L + N, with L its linear part in those `n` variables and N every other
+/// non-constant term, the inverse is the fixed point of
+///
+/// ```text
+/// A = L⁻¹ ∘ (I − N ∘ A)
+/// ```
+///
+/// L is inverted with LINV and each pass fixes one more order, so the
+/// iteration ends after at most the current truncation order passes.
+/// Constant parts are ignored — the inverse is taken about the origin.
+pub fn rosy_dainv(map: &[DA], n: usize, result: &mut Vec<DA>) -> Result<()> {
+    let (num_vars, max_order, variable_monomials) = {
+        let rt = get_runtime().context("DAINV requires DA to be initialized (call DAINI first)")?;
+        let monomials: Vec<Monomial> = rt.variable_indices[..rt.config.num_vars]
+            .iter()
+            .map(|&idx| rt.monomial_list[idx as usize])
+            .collect();
+        (rt.config.num_vars, rt.config.max_order, monomials)
+    };
+    ensure!(
+        n >= 1 && n <= num_vars,
+        "DAINV: number of components ({}) must be between 1 and the number of DA variables ({})",
+        n, num_vars
+    );
+    ensure!(
+        map.len() >= n,
+        "DAINV: map has {} components but {} were requested",
+        map.len(), n
+    );
+
+    // Linear part and its inverse
+    let linear: Vec<Vec<f64>> = map[..n]
+        .iter()
+        .map(|component| {
+            variable_monomials[..n]
+                .iter()
+                .map(|mono| component.get_coeff(mono))
+                .collect()
+        })
+        .collect();
+    let (inverse, error_flag) = rosy_linv(&linear, n, n)?;
+    if error_flag != 0.0 {
+        bail!("DAINV: the linear part of the map is singular and cannot be inverted");
+    }
+
+    // Everything but the constant and the linear part in the map variables
+    let nonlinear: Vec<DA> = map[..n]
+        .iter()
+        .map(|component| {
+            let mut rest = component.clone();
+            rest.set_coeff(Monomial::constant(), 0.0);
+            for mono in &variable_monomials[..n] {
+                rest.set_coeff(*mono, 0.0);
+            }
+            rest
+        })
+        .collect();
+
+    let identity = (1..=num_vars)
+        .map(DA::variable)
+        .collect::<Result<Vec<DA>>>()?;
+    let apply_inverse = |rhs: &[DA]| -> Result<Vec<DA>> {
+        inverse[..n]
+            .iter()
+            .map(|row| {
+                let mut sum = DA::zero();
+                for (coefficient, term) in row[..n].iter().zip(rhs) {
+                    if *coefficient != 0.0 {
+                        sum = (sum + (term * *coefficient)?)?;
+                    }
+                }
+                Ok(sum)
+            })
+            .collect()
+    };
+
+    let mut current = apply_inverse(&identity[..n])?;
+    let mut args = identity.clone();
+    let mut composed = Vec::with_capacity(n);
+    for _ in 0..max_order {
+        args[..n].clone_from_slice(&current);
+        rosy_polval_da(1.0, &nonlinear, n, &args, num_vars, &mut composed, n)
+            .context("DAINV: failed to compose the nonlinear part")?;
+        let rhs = identity[..n]
+            .iter()
+            .zip(&composed)
+            .map(|(x, y)| x - y)
+            .collect::<Result<Vec<DA>>>()?;
+        let next = apply_inverse(&rhs)?;
+        if next == current {
+            break;
+        }
+        current = next;
+    }
+
+    if result.len() < n {
+        result.resize_with(n, DA::zero);
+    }
+    for (slot, component) in result.iter_mut().zip(current) {
+        *slot = component;
+    }
+    Ok(())
+}
+
 /// CDF2: Apply exp(:f2:) to a CD vector in Floquet variables.
 ///
 /// For each monomial with exponent pairs (a_k, b_k) for conjugate variable pairs,
@@ -585,10 +722,11 @@ pub fn rosy_cdf2(
 
         for (monomial, coeff) in cd_in.coeffs_iter() {
             // Compute net phase: sum_k mu_k * (e_{2k} - e_{2k+1})
+            let powers = monomial.powers(&rt.weights);
             let mut net_phase = 0.0;
             for k in 0..n_pairs {
-                let e_pos = monomial.exponents[2 * k] as f64;
-                let e_neg = monomial.exponents[2 * k + 1] as f64;
+                let e_pos = powers[2 * k] as f64;
+                let e_neg = powers[2 * k + 1] as f64;
                 net_phase += tunes[k] * (e_pos - e_neg);
             }
 
@@ -634,10 +772,11 @@ pub fn rosy_cdnf(
 
         for (monomial, coeff) in cd_in.coeffs_iter() {
             // Compute net phase: sum_k mu_k * (e_{2k} - e_{2k+1})
+            let powers = monomial.powers(&rt.weights);
             let mut net_phase = 0.0;
             for k in 0..n_pairs {
-                let e_pos = monomial.exponents[2 * k] as f64;
-                let e_neg = monomial.exponents[2 * k + 1] as f64;
+                let e_pos = powers[2 * k] as f64;
+                let e_neg = powers[2 * k + 1] as f64;
                 net_phase += tunes[k] * (e_pos - e_neg);
             }
 
@@ -667,8 +806,7 @@ pub fn rosy_cdnf(
                     };
                     let mut matches = true;
                     for k in 0..dim.min(n_pairs) {
-                        let diff =
-                            monomial.exponents[2 * k] as i64 - monomial.exponents[2 * k + 1] as i64;
+                        let diff = powers[2 * k] as i64 - powers[2 * k + 1] as i64;
                         if offset + k < resonances.len() && diff != resonances[offset + k] as i64 {
                             matches = false;
                             break;
@@ -739,9 +877,10 @@ pub fn rosy_cdnfda(
 
         for (monomial, coeff) in cd_in.coeffs_iter() {
             // Compute product of eigenvalues raised to exponent powers
+            let powers = monomial.powers(&rt.weights);
             let mut prod = Complex64::new(1.0, 0.0);
             for k in 0..total.min(num_vars).min(moduli.len()).min(arguments.len()) {
-                let exp = monomial.exponents[k] as f64;
+                let exp = powers[k] as f64;
                 if exp != 0.0 {
                     let r = moduli[k];
                     let theta = arguments[k];
@@ -790,9 +929,10 @@ pub fn rosy_cdnfds(
         let mut cd_out = CD::zero();
 
         for (monomial, coeff) in cd_in.coeffs_iter() {
+            let powers = monomial.powers(&rt.weights);
             let mut prod = Complex64::new(1.0, 0.0);
             for k in 0..total.min(num_vars).min(moduli.len()).min(arguments.len()) {
-                let exp = monomial.exponents[k] as f64;
+                let exp = powers[k] as f64;
                 if exp != 0.0 {
                     let r = moduli[k];
                     let theta = arguments[k];
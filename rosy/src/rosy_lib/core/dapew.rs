@@ -9,8 +9,8 @@
 
 use anyhow::{Context, Result};
 
-use crate::rosy_lib::core::display::RosyDisplay;
-use crate::rosy_lib::taylor::{DA, MAX_VARS};
+use crate::rosy_lib::core::display::{RosyDisplay, exponent_columns};
+use crate::rosy_lib::taylor::{DA, MAX_VARS, get_weights};
 use crate::rosy_lib::taylor::Monomial;
 
 // ============================================================================
@@ -69,6 +69,7 @@ pub fn rosy_darea(unit: u64, da: &mut Vec<DA>, num_vars: usize) -> Result<()> {
     }
     da[0] = DA::zero();
 
+    let weights = get_weights();
     for line in &lines {
         let trimmed = line.trim();
         if trimmed.is_empty() {
@@ -88,14 +89,14 @@ pub fn rosy_darea(unit: u64, da: &mut Vec<DA>, num_vars: usize) -> Result<()> {
 
         // Exponents start at token index 3 (after idx, coeff, order)
         let exp_start = 3;
-        let mut exponents = [0u8; MAX_VARS];
+        let mut powers = [0u8; MAX_VARS];
         for i in 0..num_vars.min(MAX_VARS) {
             if exp_start + i < tokens.len() {
-                exponents[i] = tokens[exp_start + i].parse().unwrap_or(0);
+                powers[i] = tokens[exp_start + i].parse().unwrap_or(0);
             }
         }
 
-        let monomial = Monomial::new(exponents);
+        let monomial = Monomial::from_powers(powers, &weights);
         da[0].set_coeff(monomial, coeff);
     }
 
@@ -108,7 +109,11 @@ pub fn rosy_darea(unit: u64, da: &mut Vec<DA>, num_vars: usize) -> Result<()> {
 
 /// Print the terms of a DA vector that have exponent `order_n` in variable `var_i`.
 ///
-/// If `var_i == 0` all terms of total order `order_n` are printed.
+/// If `var_i == 0` all terms of total order `order_n` are printed. Under
+/// weighted DA (`DANOTW` or the weights argument of `DAINI`) the exponent
+/// filter and the EXPONENTS columns count powers of the variable, while the
+/// total-order filter and the ORDER column use the weighted order, as in
+/// DAPRV.
 ///
 /// Arguments:
 /// - `unit`: output unit (6 = stdout)
@@ -130,12 +135,13 @@ pub fn rosy_dapew(unit: u64, da: &Vec<DA>, var_i: usize, order_n: u32) -> Result
         var_i, order_n
     ));
 
+    let weights = get_weights();
     let mut terms: Vec<(Monomial, f64)> = da0
         .coeffs_iter()
         .into_iter()
         .filter(|(monomial, _coeff)| {
             if var_i > 0 && var_i <= MAX_VARS {
-                monomial.exponents[var_i - 1] as u32 == order_n
+                (monomial.exponents[var_i - 1] / weights[var_i - 1]) as u32 == order_n
             } else {
                 monomial.total_order as u32 == order_n
             }
@@ -151,8 +157,9 @@ pub fn rosy_dapew(unit: u64, da: &Vec<DA>, var_i: usize, order_n: u32) -> Result
 
     for (idx, (monomial, coeff)) in terms.iter().enumerate() {
         let order = monomial.total_order;
-        let exp_parts: Vec<String> = (0..MAX_VARS)
-            .map(|i| format!("{:>2}", monomial.exponents[i]))
+        let powers = monomial.powers(&weights);
+        let exp_parts: Vec<String> = (0..exponent_columns())
+            .map(|i| format!("{:>2}", powers[i]))
             .collect();
         let exp_str = exp_parts.join(" ");
         output.push_str(&format!(
@@ -200,8 +207,7 @@ pub fn rosy_dapee(da: &Vec<DA>, id: u64, result: &mut f64) -> Result<()> {
         *result = 0.0;
         return Ok(());
     }
-    let exponents = decode_transport_id(id);
-    let monomial = Monomial::new(exponents);
+    let monomial = Monomial::from_powers(decode_transport_id(id), &get_weights());
     *result = da[0].get_coeff(&monomial);
     Ok(())
 }
@@ -222,13 +228,13 @@ pub fn rosy_dapea(da: &Vec<DA>, exps: &Vec<f64>, size: usize, result: &mut f64)
         *result = 0.0;
         return Ok(());
     }
-    let mut exponents = [0u8; MAX_VARS];
+    let mut powers = [0u8; MAX_VARS];
     for i in 0..size.min(MAX_VARS) {
         if i < exps.len() {
-            exponents[i] = exps[i] as u8;
+            powers[i] = exps[i] as u8;
         }
     }
-    let monomial = Monomial::new(exponents);
+    let monomial = Monomial::from_powers(powers, &get_weights());
     *result = da[0].get_coeff(&monomial);
     Ok(())
 }
@@ -263,12 +269,14 @@ pub fn rosy_dapep(da: &Vec<DA>, id: u64, m: usize, result: &mut Vec<DA>) -> Resu
     }
 
     let target = decode_transport_id(id);
+    let weights = get_weights();
 
     for (monomial, coeff) in da[0].coeffs_iter() {
-        // Check whether the first m variables match the target exponents
+        // Check whether the first m variables match the target powers
+        let powers = monomial.powers(&weights);
         let mut matches = true;
         for i in 0..m.min(MAX_VARS) {
-            if monomial.exponents[i] != target[i] {
+            if powers[i] != target[i] {
                 matches = false;
                 break;
             }
@@ -298,6 +306,9 @@ pub fn rosy_dapep(da: &Vec<DA>, id: u64, m: usize, result: &mut Vec<DA>) -> Resu
 /// - If `i > 0`: sums |coeff| for all terms where the exponent of variable i equals j.
 /// - If `i == 0`: sums |coeff| for all terms of total order j.
 ///
+/// As in DAPEW, the exponent of variable i is its power and the total order
+/// is the weighted order when DA is weighted.
+///
 /// Arguments:
 /// - `da`: DA array — operates on element 0
 /// - `i`: variable index (1-based; 0 = aggregate over all variables at order j)
@@ -309,9 +320,10 @@ pub fn rosy_daest(da: &Vec<DA>, i: usize, j: u32, result: &mut f64) -> Result<()
         return Ok(());
     }
 
+    let weights = get_weights();
     for (monomial, coeff) in da[0].coeffs_iter() {
         let matches = if i > 0 && i <= MAX_VARS {
-            monomial.exponents[i - 1] as u32 == j
+            (monomial.exponents[i - 1] / weights[i - 1]) as u32 == j
         } else {
             monomial.total_order as u32 == j
         };
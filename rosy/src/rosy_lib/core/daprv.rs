@@ -5,7 +5,7 @@
 
 use anyhow::{Result, Context, bail};
 
-use crate::rosy_lib::taylor::{DA, get_config};
+use crate::rosy_lib::taylor::{DA, MAX_VARS, get_config, get_weights};
 use crate::rosy_lib::taylor::da::DACoefficient;
 use crate::rosy_lib::taylor::Monomial;
 use crate::rosy_lib::core::display::RosyDisplay;
@@ -86,10 +86,12 @@ fn format_daprv(
     }
     output.push_str("ORDER EXPONENTS\n");
 
-    // Print each monomial row
+    // Print each monomial row. ORDER is the weighted order, while the
+    // exponents are powers of the variables.
+    let weights = get_weights();
     for (idx, monomial) in all_monomials.iter().enumerate() {
         let order = monomial.total_order;
-        let exp_str = build_exp_str(&monomial.exponents, current_vars);
+        let exp_str = build_exp_str(&monomial.powers(&weights), current_vars);
 
         output.push_str(&format!("{:>3}  ", idx + 1));
 
@@ -156,6 +158,7 @@ pub fn rosy_darev(
     }
 
     // Parse each line
+    let weights = get_weights();
     for line in &lines {
         let trimmed = line.trim();
         if trimmed.is_empty() {
@@ -189,16 +192,16 @@ pub fn rosy_darev(
         
         // Exponents start after order
         let exp_start = order_idx + 1;
-        let mut exponents = [0u8; 6];
-        for i in 0..current_vars.min(6) {
+        let mut powers = [0u8; MAX_VARS];
+        for i in 0..current_vars.min(MAX_VARS) {
             if exp_start + i < tokens.len() {
                 if let Ok(exp) = tokens[exp_start + i].parse::<u8>() {
-                    exponents[i] = exp;
+                    powers[i] = exp;
                 }
             }
         }
 
-        let monomial = Monomial::new(exponents);
+        let monomial = Monomial::from_powers(powers, &weights);
 
         // Set coefficients for each component
         for (i, &coeff) in coeffs.iter().enumerate() {
@@ -228,7 +231,6 @@ pub fn rosy_datrn(
     m2: usize,
     output: &mut Vec<DA>,
 ) -> Result<()> {
-    use crate::rosy_lib::taylor::MAX_VARS;
 
     let config = get_config().context("DATRN requires DA to be initialized (call OV first)")?;
     let num_vars = config.num_vars;
@@ -264,6 +266,7 @@ pub fn rosy_datrn(
     output.resize_with(input.len(), DA::zero);
 
     // For each DA in input, perform polynomial composition
+    let weights = get_weights();
     for (comp_idx, da_in) in input.iter().enumerate() {
         let mut result = DA::zero();
 
@@ -277,7 +280,7 @@ pub fn rosy_datrn(
             // Monomial contribution = coeff * prod_i (substitutions[i])^exponents[i]
             let mut term = DA::from_coeff(coeff);
             for var_0idx in 0..num_vars.min(MAX_VARS) {
-                let exp = monomial.exponents[var_0idx] as usize;
+                let exp = (monomial.exponents[var_0idx] / weights[var_0idx]) as usize;
                 if exp == 0 {
                     continue;
                 }
@@ -327,7 +330,6 @@ fn build_exp_str(exponents: &[u8], num_vars: usize) -> String {
 /// - `result`:  output DA array
 pub fn rosy_daplu(da_in: &Vec<DA>, var_idx: usize, c: f64, result: &mut Vec<DA>) -> Result<()> {
     use rustc_hash::FxHashMap;
-    use crate::rosy_lib::taylor::MAX_VARS;
 
     let config = get_config().context("DAPLU requires DA to be initialized (call OV first)")?;
     let var_0idx = var_idx
@@ -338,6 +340,7 @@ pub fn rosy_daplu(da_in: &Vec<DA>, var_idx: usize, c: f64, result: &mut Vec<DA>)
     }
 
     result.resize_with(da_in.len(), DA::zero);
+    let weight = get_weights()[var_0idx];
 
     for (comp_idx, da) in da_in.iter().enumerate() {
         let mut accum: FxHashMap<Monomial, f64> = FxHashMap::default();
@@ -346,7 +349,7 @@ pub fn rosy_daplu(da_in: &Vec<DA>, var_idx: usize, c: f64, result: &mut Vec<DA>)
             if coeff.abs() <= config.epsilon {
                 continue;
             }
-            let e_v = monomial.exponents[var_0idx] as i32;
+            let e_v = (monomial.exponents[var_0idx] / weight) as i32;
             let contribution = coeff * c.powi(e_v);
             if contribution.abs() <= config.epsilon {
                 continue;
@@ -384,6 +387,7 @@ pub fn rosy_dadiu(var_idx: usize, da_in: &Vec<DA>, result: &mut Vec<DA>) -> Resu
     }
 
     result.resize_with(da_in.len(), DA::zero);
+    let weight = get_weights()[var_0idx];
 
     for (comp_idx, da) in da_in.iter().enumerate() {
         let mut accum: FxHashMap<Monomial, f64> = FxHashMap::default();
@@ -397,7 +401,7 @@ pub fn rosy_dadiu(var_idx: usize, da_in: &Vec<DA>, result: &mut Vec<DA>) -> Resu
                 continue; // term not divisible by xi — dropped
             }
             let mut new_exps = monomial.exponents;
-            new_exps[var_0idx] = e_v - 1;
+            new_exps[var_0idx] = e_v - weight;
             let new_mono = Monomial::new(new_exps);
             *accum.entry(new_mono).or_insert(0.0) += coeff;
         }
@@ -437,6 +441,7 @@ pub fn rosy_dadmu(var_i: usize, var_j: usize, da_in: &Vec<DA>, result: &mut Vec<
     }
 
     result.resize_with(da_in.len(), DA::zero);
+    let weights = get_weights();
 
     for (comp_idx, da) in da_in.iter().enumerate() {
         let mut accum: FxHashMap<Monomial, f64> = FxHashMap::default();
@@ -450,9 +455,10 @@ pub fn rosy_dadmu(var_i: usize, var_j: usize, da_in: &Vec<DA>, result: &mut Vec<
                 continue; // not divisible by xi — dropped
             }
             let mut new_exps = monomial.exponents;
-            new_exps[i_0idx] = e_i - 1;
-            new_exps[j_0idx] = new_exps[j_0idx].saturating_add(1);
-            // total_order is unchanged (div by xi cancels mul by xj)
+            new_exps[i_0idx] = e_i - weights[i_0idx];
+            new_exps[j_0idx] = new_exps[j_0idx].saturating_add(weights[j_0idx]);
+            // total_order is unchanged for equal weights; otherwise terms
+            // pushed above the DAINI order are dropped by from_coeffs
             let new_mono = Monomial::new(new_exps);
             *accum.entry(new_mono).or_insert(0.0) += coeff;
         }
@@ -466,8 +472,8 @@ pub fn rosy_dadmu(var_i: usize, var_j: usize, da_in: &Vec<DA>, result: &mut Vec<
 /// DACLIW: Extract the linear (first-order) coefficients of a DA.
 ///
 /// The result array `linear[i]` receives the coefficient of xᵢ₊₁ (1-based)
-/// in the first DA component. When order-weighted DA is in use, the weighted
-/// linear coefficients are extracted.
+/// in the first DA component. When order-weighted DA is in use, this is
+/// still the coefficient of the first power of xᵢ₊₁, whatever its weight.
 ///
 /// Arguments:
 /// - `da`:     source DA array (first component used)
@@ -479,10 +485,13 @@ pub fn rosy_dacliw(da: &Vec<DA>, n: usize, linear: &mut Vec<f64>) -> Result<()>
     let da_ref = da.first().context("DACLIW: DA vector is empty")?;
 
     linear.resize(n, 0.0);
+    let weights = get_weights();
 
     for i in 0..n {
         if i < config.num_vars {
-            let mono = Monomial::variable(i);
+            let mut powers = [0u8; MAX_VARS];
+            powers[i] = 1;
+            let mono = Monomial::from_powers(powers, &weights);
             linear[i] = da_ref.get_coeff(&mono);
         } else {
             linear[i] = 0.0;
@@ -513,7 +522,6 @@ pub fn rosy_dacqlc(
     linear: &mut Vec<f64>,
     constant: &mut f64,
 ) -> Result<()> {
-    use crate::rosy_lib::taylor::MAX_VARS;
 
     let config = get_config().context("DACQLC requires DA to be initialized (call OV first)")?;
 
@@ -523,10 +531,13 @@ pub fn rosy_dacqlc(
     *constant = da_ref.get_coeff(&Monomial::constant());
 
     // Linear terms
+    let weights = get_weights();
     linear.resize(n, 0.0);
     for i in 0..n {
         if i < config.num_vars {
-            linear[i] = da_ref.get_coeff(&Monomial::variable(i));
+            let mut powers = [0u8; MAX_VARS];
+            powers[i] = 1;
+            linear[i] = da_ref.get_coeff(&Monomial::from_powers(powers, &weights));
         } else {
             linear[i] = 0.0;
         }
@@ -543,13 +554,13 @@ pub fn rosy_dacqlc(
             let coeff = if i == j {
                 let mut exps = [0u8; MAX_VARS];
                 exps[i] = 2;
-                let mono = Monomial::new(exps);
+                let mono = Monomial::from_powers(exps, &weights);
                 2.0 * da_ref.get_coeff(&mono)
             } else {
                 let mut exps = [0u8; MAX_VARS];
                 exps[i] = 1;
                 exps[j] = 1;
-                let mono = Monomial::new(exps);
+                let mono = Monomial::from_powers(exps, &weights);
                 da_ref.get_coeff(&mono)
             };
             hessian[i][j] = coeff;
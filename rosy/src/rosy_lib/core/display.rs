@@ -83,10 +83,16 @@ fn display_re (
         )
     }
 }
+/// Number of exponent columns shown for DA/CD terms: the classic six, widened
+/// when `DAINI` declared more variables than that.
+pub fn exponent_columns() -> usize {
+    let num_vars = crate::rosy_lib::taylor::get_config().map_or(0, |c| c.num_vars);
+    num_vars.clamp(6, crate::rosy_lib::taylor::MAX_VARS)
+}
 fn build_exp_str (
     exps: &[u8],
 ) -> String {
-    exps.iter()
+    exps[..exponent_columns()].iter()
         .enumerate()
         .fold(String::new(), |mut acc, (i, exp)| {
             if i % 2 == 0 {
@@ -167,13 +173,15 @@ impl RosyDisplay for &DA {
         
         let mut output = String::new();
         output.push_str("I  COEFFICIENT            ORDER EXPONENTS\n");
+        // Exponents are shown as powers, also under weighted DA
+        let weights = crate::rosy_lib::taylor::get_weights();
         for (idx, (monomial, coeff)) in sorted.iter().enumerate() {
             let order = monomial.total_order;
             let exp_str = {
                 // For 6 exponents, should match: '1 0  1 0  0 0'
-                let exps = &monomial.exponents;
+                let exps = monomial.powers(&weights);
 
-                build_exp_str(exps)
+                build_exp_str(&exps)
             };
             output.push_str(&format!(
                 "{}  {} {}  {}\n", 
@@ -233,15 +241,17 @@ impl RosyDisplay for &CD {
         
         let mut output = String::new();
         output.push_str("     I  COEFFICIENTS                           ORDER EXPONENTS\n");
+        // Exponents are shown as powers, also under weighted DA
+        let weights = crate::rosy_lib::taylor::get_weights();
         for (idx, monomial) in sorted.iter().enumerate() {
             let real_coeff = real_part.get_coeff(monomial);
             let imag_coeff = imag_part.get_coeff(monomial);
             let order = monomial.total_order;
             let exp_str = {
                 // For 6 exponents, should match: '1 0  1 0  0 0'
-                let exps = &monomial.exponents;
+                let exps = monomial.powers(&weights);
 
-                build_exp_str(exps)
+                build_exp_str(&exps)
             };
             output.push_str(&format!(
                 "     {} {} {} {:>3}  {}\n",
@@ -10,6 +10,9 @@ use std::sync::Mutex;
 use anyhow::{Result, Context, bail};
 
 use crate::rosy_lib::core::display::RosyDisplay;
+use crate::rosy_lib::taylor::{DACoefficient, get_runtime};
+use crate::rosy_lib::taylor::da::DA;
+use num_complex::Complex64;
 
 /// Global file handle registry, mapping unit numbers to open file handles.
 static FILE_REGISTRY: Mutex<Option<HashMap<u64, FileHandle>>> = Mutex::new(None);
@@ -240,6 +243,90 @@ pub fn rosy_closef(unit: f64) -> Result<()> {
     }
 }
 
+/// An open unit as recorded by a `CHECKPOINT`: enough to reopen the same
+/// file in the same mode and carry on from the same position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenFile {
+    pub unit: u64,
+    pub path: String,
+    pub is_binary: bool,
+    pub reads: bool,
+    pub writes: bool,
+    pub position: u64,
+}
+
+/// Flush every open unit and record where it stands, in unit order.
+pub fn open_files() -> Result<Vec<OpenFile>> {
+    ensure_registry();
+    let mut reg = FILE_REGISTRY.lock().unwrap();
+    let registry = reg.as_mut().unwrap();
+
+    let mut files = Vec::with_capacity(registry.len());
+    for (&unit, handle) in registry.iter_mut() {
+        if let Some(ref mut writer) = handle.writer {
+            writer.flush()
+                .with_context(|| format!("Failed to flush file on unit {}", unit))?;
+        }
+        let position = match (&mut handle.reader, &mut handle.writer) {
+            (Some(reader), _) => reader.stream_position(),
+            (None, Some(writer)) => writer.stream_position(),
+            (None, None) => Ok(0),
+        }
+        .with_context(|| format!("Failed to get stream position on unit {}", unit))?;
+        files.push(OpenFile {
+            unit,
+            path: handle.path.clone(),
+            is_binary: handle.is_binary,
+            reads: handle.reader.is_some(),
+            writes: handle.writer.is_some(),
+            position,
+        });
+    }
+    files.sort_by_key(|file| file.unit);
+    Ok(files)
+}
+
+/// Reopen units recorded by [`open_files`] where they stood. Anything a
+/// write-only unit gained after that point is cut off, since the resumed
+/// program writes it again.
+pub fn reopen_files(files: &[OpenFile]) -> Result<()> {
+    ensure_registry();
+    let mut reg = FILE_REGISTRY.lock().unwrap();
+    let registry = reg.as_mut().unwrap();
+
+    for file in files {
+        let mut handle = OpenOptions::new()
+            .read(file.reads)
+            .write(file.writes)
+            .create(file.writes)
+            .open(&file.path)
+            .with_context(|| format!("Failed to reopen file '{}' (unit {})", file.path, file.unit))?;
+        if file.writes && !file.reads {
+            handle.set_len(file.position)
+                .with_context(|| format!("Failed to truncate file '{}' (unit {})", file.path, file.unit))?;
+        }
+        handle.seek(SeekFrom::Start(file.position))
+            .with_context(|| format!("Failed to seek in file '{}' (unit {})", file.path, file.unit))?;
+
+        let (reader, writer) = match (file.reads, file.writes) {
+            (true, true) => {
+                let read_handle = handle.try_clone()
+                    .with_context(|| format!("Failed to clone file handle for '{}' (unit {})", file.path, file.unit))?;
+                (Some(BufReader::new(read_handle)), Some(BufWriter::new(handle)))
+            }
+            (true, false) => (Some(BufReader::new(handle)), None),
+            _ => (None, Some(BufWriter::new(handle))),
+        };
+        registry.insert(file.unit, FileHandle {
+            reader,
+            writer,
+            path: file.path.clone(),
+            is_binary: file.is_binary,
+        });
+    }
+    Ok(())
+}
+
 /// Write a string to a file unit (ASCII WRITE to file).
 pub fn rosy_write_to_unit(unit: u64, content: &str) -> Result<()> {
     ensure_registry();
@@ -259,6 +346,21 @@ pub fn rosy_write_to_unit(unit: u64, content: &str) -> Result<()> {
     Ok(())
 }
 
+/// Path of the file currently open on a unit, as it was given to OPENF/OPENFB.
+///
+/// Used by writers whose output format depends on the file name, such as
+/// the graphics backends that pick SVG, EPS or gnuplot from the extension.
+pub fn rosy_unit_path(unit: u64) -> Result<String> {
+    ensure_registry();
+
+    let reg = FILE_REGISTRY.lock().unwrap();
+    let registry = reg.as_ref().unwrap();
+
+    registry.get(&unit)
+        .map(|handle| handle.path.clone())
+        .with_context(|| format!("No file open on unit {}. Use OPENF to open a file first.", unit))
+}
+
 /// Read a line from a file unit (ASCII READ from file).
 /// Returns the trimmed line as a string. Bails on EOF — used by READ
 /// (numerical / DA reads) and DAREA / DAPRV header parsing where missing
@@ -372,7 +474,7 @@ pub fn rosy_readb_from_unit(unit: u64) -> Result<Vec<u8>> {
 
 /// Trait for serializing Rosy types to binary.
 pub trait RosyToBinary {
-    fn to_binary(&self) -> Vec<u8>;
+    fn to_binary(&self) -> Result<Vec<u8>>;
 }
 
 /// Trait for deserializing Rosy types from binary.
@@ -382,8 +484,8 @@ pub trait RosyFromBinary: Sized {
 
 // Binary serialization for RE (f64)
 impl RosyToBinary for f64 {
-    fn to_binary(&self) -> Vec<u8> {
-        self.to_le_bytes().to_vec()
+    fn to_binary(&self) -> Result<Vec<u8>> {
+        Ok(self.to_le_bytes().to_vec())
     }
 }
 
@@ -400,10 +502,10 @@ impl RosyFromBinary for f64 {
 
 // Binary serialization for String
 impl RosyToBinary for String {
-    fn to_binary(&self) -> Vec<u8> {
+    fn to_binary(&self) -> Result<Vec<u8>> {
         let mut bytes = (self.len() as u64).to_le_bytes().to_vec();
         bytes.extend(self.as_bytes());
-        bytes
+        Ok(bytes)
     }
 }
 
@@ -425,12 +527,12 @@ impl RosyFromBinary for String {
 
 // Binary serialization for VE (Vec<f64>)
 impl RosyToBinary for Vec<f64> {
-    fn to_binary(&self) -> Vec<u8> {
+    fn to_binary(&self) -> Result<Vec<u8>> {
         let mut bytes = (self.len() as u64).to_le_bytes().to_vec();
         for val in self {
             bytes.extend(val.to_le_bytes());
         }
-        bytes
+        Ok(bytes)
     }
 }
 
@@ -459,8 +561,8 @@ impl RosyFromBinary for Vec<f64> {
 
 // Binary serialization for bool
 impl RosyToBinary for bool {
-    fn to_binary(&self) -> Vec<u8> {
-        vec![if *self { 1 } else { 0 }]
+    fn to_binary(&self) -> Result<Vec<u8>> {
+        Ok(vec![if *self { 1 } else { 0 }])
     }
 }
 
@@ -473,15 +575,210 @@ impl RosyFromBinary for bool {
     }
 }
 
-// Binary serialization for DA (stub — DA binary I/O not yet fully supported)
-impl RosyToBinary for crate::rosy_lib::taylor::DA {
-    fn to_binary(&self) -> Vec<u8> {
-        unimplemented!("Binary I/O for DA vectors is not yet supported in Rosy. Use DAPRV/DAREV for ASCII DA I/O.")
+// Binary serialization for DA and CD
+//
+// Layout (all integers little-endian):
+//
+//   magic    4 bytes  "RDA1"
+//   kind     u8       0 = DA, 1 = CD
+//   order    u32      DAINI order the monomial addressing was built for
+//   nv       u32      DAINI number of variables
+//   nmono    u32      total monomials in that addressing
+//   count    u32      number of stored terms
+//   terms    count × (index u32, coefficient)
+//
+// A coefficient is one f64 for DA and (re, im) f64s for CD. Only nonzero
+// terms are stored, so a sparse high-order map costs 12 (or 20) bytes per
+// term. The order/nv/nmono tag must match the reading program's DAINI, since
+// flat monomial indices are only meaningful within one addressing scheme.
+
+const DA_BINARY_MAGIC: &[u8; 4] = b"RDA1";
+const DA_BINARY_HEADER_LEN: usize = 4 + 1 + 4 * 4;
+
+/// Coefficient types that can be stored in a binary DA record.
+trait BinaryCoefficient: DACoefficient {
+    /// Record kind tag.
+    const KIND: u8;
+    /// Encoded width in bytes.
+    const WIDTH: usize;
+    fn encode(&self, out: &mut Vec<u8>);
+    fn decode(bytes: &[u8]) -> Self;
+}
+
+impl BinaryCoefficient for f64 {
+    const KIND: u8 = 0;
+    const WIDTH: usize = 8;
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend(self.to_le_bytes());
+    }
+    fn decode(bytes: &[u8]) -> Self {
+        f64::from_le_bytes(bytes[..8].try_into().unwrap())
     }
 }
 
-impl RosyFromBinary for crate::rosy_lib::taylor::DA {
-    fn from_binary(_data: &[u8]) -> Result<Self> {
-        unimplemented!("Binary I/O for DA vectors is not yet supported in Rosy. Use DAPRV/DAREV for ASCII DA I/O.")
+impl BinaryCoefficient for Complex64 {
+    const KIND: u8 = 1;
+    const WIDTH: usize = 16;
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend(self.re.to_le_bytes());
+        out.extend(self.im.to_le_bytes());
+    }
+    fn decode(bytes: &[u8]) -> Self {
+        Complex64::new(
+            f64::from_le_bytes(bytes[..8].try_into().unwrap()),
+            f64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+        )
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn da_type_name(kind: u8) -> &'static str {
+    if kind == 0 { "DA" } else { "CD" }
+}
+
+impl<T: BinaryCoefficient> RosyToBinary for DA<T> {
+    fn to_binary(&self) -> Result<Vec<u8>> {
+        let rt = get_runtime()
+            .context("Cannot write a DA vector in binary before DAINI")?;
+
+        let mut bytes = Vec::with_capacity(DA_BINARY_HEADER_LEN + self.nonzero.len() * (4 + T::WIDTH));
+        bytes.extend(DA_BINARY_MAGIC);
+        bytes.push(T::KIND);
+        bytes.extend(rt.init_order.to_le_bytes());
+        bytes.extend((rt.config.num_vars as u32).to_le_bytes());
+        bytes.extend((rt.num_monomials as u32).to_le_bytes());
+        bytes.extend((self.nonzero.len() as u32).to_le_bytes());
+        for &idx in &self.nonzero {
+            bytes.extend(idx.to_le_bytes());
+            self.coeffs[idx as usize].encode(&mut bytes);
+        }
+        Ok(bytes)
+    }
+}
+
+impl<T: BinaryCoefficient> RosyFromBinary for DA<T> {
+    fn from_binary(data: &[u8]) -> Result<Self> {
+        let expected = da_type_name(T::KIND);
+        if data.len() < DA_BINARY_HEADER_LEN || &data[..4] != DA_BINARY_MAGIC {
+            bail!("READB: record is not a binary {} vector", expected);
+        }
+        if data[4] != T::KIND {
+            bail!("READB: record holds a {} vector but the target is {}", da_type_name(data[4]), expected);
+        }
+
+        let order = read_u32(data, 5);
+        let num_vars = read_u32(data, 9) as usize;
+        let num_monomials = read_u32(data, 13) as usize;
+        let count = read_u32(data, 17) as usize;
+
+        let rt = get_runtime()
+            .context("Cannot read a DA vector in binary before DAINI")?;
+        if order != rt.init_order || num_vars != rt.config.num_vars || num_monomials != rt.num_monomials {
+            bail!(
+                "READB: {} vector was written with DAINI order {} and {} variables ({} monomials), \
+                 but this program uses order {} and {} variables ({} monomials)",
+                expected, order, num_vars, num_monomials,
+                rt.init_order, rt.config.num_vars, rt.num_monomials
+            );
+        }
+
+        let stride = 4 + T::WIDTH;
+        if data.len() != DA_BINARY_HEADER_LEN + count * stride {
+            bail!(
+                "READB: {} record should hold {} terms ({} bytes) but has {} bytes",
+                expected, count, DA_BINARY_HEADER_LEN + count * stride, data.len()
+            );
+        }
+
+        let mut coeffs = T::pool_alloc(rt.num_monomials);
+        let mut nonzero = Vec::with_capacity(count);
+        for term in data[DA_BINARY_HEADER_LEN..].chunks_exact(stride) {
+            let idx = read_u32(term, 0);
+            if idx as usize >= rt.num_monomials {
+                bail!("READB: monomial index {} out of range for {} monomials", idx, rt.num_monomials);
+            }
+            coeffs[idx as usize] = T::decode(&term[4..]);
+            nonzero.push(idx);
+        }
+        Ok(DA { coeffs, nonzero })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rosy_lib::core::daprv::{rosy_daprv, rosy_darev};
+    use crate::rosy_lib::taylor::{CD, DA, cleanup_taylor, init_taylor};
+    use serial_test::serial;
+
+    /// A dense order-4 map component with awkward (non-decimal) coefficients.
+    fn sample_da() -> Result<DA> {
+        let x = DA::variable(1)?;
+        let y = DA::variable(2)?;
+        let xy = (&x * &y)?;
+        let poly = (&(&(&x * (1.0 / 3.0))? + &(&xy * -2.5e-7)?)? + &(&(&xy * &xy)? * 7.125)?)?;
+        poly + std::f64::consts::PI
+    }
+
+    #[test]
+    #[serial]
+    fn da_binary_round_trip_matches_daprv_darev() -> Result<()> {
+        cleanup_taylor();
+        init_taylor(4, 2)?;
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("map.txt");
+        let path = path.to_str().unwrap();
+
+        let original = vec![sample_da()?, DA::variable(2)?];
+
+        // ASCII round trip through DAPRV / DAREV
+        rosy_openf(91.0, path, "UNKNOWN")?;
+        rosy_daprv(&original, 2, 2, 2, 91)?;
+        rosy_closef(91.0)?;
+        let mut ascii = Vec::new();
+        rosy_openf(91.0, path, "OLD")?;
+        rosy_darev(&mut ascii, 2, 2, 2, 91)?;
+        rosy_closef(91.0)?;
+
+        for (da, via_ascii) in original.iter().zip(&ascii) {
+            let via_binary = DA::from_binary(&da.to_binary()?)?;
+            assert_eq!(via_binary.coeffs_iter().len(), via_ascii.coeffs_iter().len());
+            for (mono, coeff) in via_ascii.coeffs_iter() {
+                assert!((via_binary.get_coeff(&mono) - coeff).abs() < 1e-14);
+            }
+            // Binary is exact where the 16-digit ASCII table is not.
+            for (mono, coeff) in da.coeffs_iter() {
+                assert_eq!(via_binary.get_coeff(&mono).to_bits(), coeff.to_bits());
+            }
+        }
+
+        cleanup_taylor();
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn cd_binary_round_trip_and_header_validation() -> Result<()> {
+        cleanup_taylor();
+        init_taylor(4, 2)?;
+
+        let re = sample_da()?;
+        let im = (&DA::variable(1)? * -0.75)?;
+        let cd = CD::from_da_parts(&re, &im);
+        let bytes = cd.to_binary()?;
+        let back = CD::from_binary(&bytes)?;
+        assert_eq!(back.coeffs_iter(), cd.coeffs_iter());
+
+        // A CD record cannot be read into a DA, nor after a different DAINI.
+        assert!(DA::from_binary(&bytes).is_err());
+        cleanup_taylor();
+        init_taylor(5, 2)?;
+        assert!(CD::from_binary(&bytes).is_err());
+
+        cleanup_taylor();
+        Ok(())
     }
 }
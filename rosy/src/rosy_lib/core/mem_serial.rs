@@ -54,6 +54,25 @@ pub trait RosyReadm: Sized {
     ) -> Result<Self>;
 }
 
+/// The powers of one exponent row of a DA or CD buffer. The buffer may come
+/// from a program built for more DA variables than this one; the extra
+/// columns are fine as long as they are all zero.
+fn exponent_row(row: &[f64]) -> Result<[u8; crate::rosy_lib::taylor::MAX_VARS]> {
+    let mut powers = [0u8; crate::rosy_lib::taylor::MAX_VARS];
+    for (j, &exponent) in row.iter().enumerate() {
+        match powers.get_mut(j) {
+            Some(power) => *power = exponent as u8,
+            None if exponent == 0.0 => {}
+            None => bail!(
+                "READM: buffer has a term in variable {} but this program supports at most {} DA variables",
+                j + 1,
+                powers.len()
+            ),
+        }
+    }
+    Ok(powers)
+}
+
 // ──────────────────────────────────────────────────────────────────────────────
 // RE (f64)
 // ──────────────────────────────────────────────────────────────────────────────
@@ -284,28 +303,29 @@ impl RosyReadm for Vec<f64> {
 //
 // Serialization format:
 //   dp_array  = [coeff_0, coeff_1, ...]    (one entry per nonzero term)
-//   int_array = [exp_0_0, exp_0_1, ..., exp_0_{MAX_VARS-1},
-//                exp_1_0, ..., ...]         (MAX_VARS entries per term)
+//   int_array = [exp_0_0, exp_0_1, ..., exp_0_{nv-1},
+//                exp_1_0, ..., ...]         (num_vars entries per term,
+//                                            powers also under weighted DA)
 //   da_params = [max_order, num_vars]
 // ──────────────────────────────────────────────────────────────────────────────
 
 impl RosyWritem for crate::rosy_lib::taylor::DA {
     fn writem(&self) -> (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>) {
-        use crate::rosy_lib::taylor::{MAX_VARS, get_config};
+        use crate::rosy_lib::taylor::get_config;
 
         let rt_cfg = get_config().expect("Taylor system not initialized");
         let order = rt_cfg.max_order as f64;
-        let nv = rt_cfg.num_vars as f64;
+        let stride = rt_cfg.num_vars;
+        let nv = stride as f64;
 
+        let weights = crate::rosy_lib::taylor::get_weights();
         let terms = self.coeffs_iter();
         let mut dp: Vec<f64> = Vec::with_capacity(terms.len());
-        let mut int: Vec<f64> = Vec::with_capacity(terms.len() * MAX_VARS);
+        let mut int: Vec<f64> = Vec::with_capacity(terms.len() * stride);
 
         for (monomial, coeff) in &terms {
             dp.push(*coeff);
-            for i in 0..MAX_VARS {
-                int.push(monomial.exponents[i] as f64);
-            }
+            int.extend(monomial.powers(&weights)[..stride].iter().map(|&e| e as f64));
         }
 
         let da_params = vec![order, nv];
@@ -326,7 +346,7 @@ impl RosyReadm for crate::rosy_lib::taylor::DA {
         int_array: &[f64],
         da_params: &[f64],
     ) -> Result<Self> {
-        use crate::rosy_lib::taylor::{DA, MAX_VARS, Monomial, get_config};
+        use crate::rosy_lib::taylor::{DA, Monomial, get_config};
         use rustc_hash::FxHashMap;
 
         // Fix #1: bounds check before var_info[1] access
@@ -358,8 +378,17 @@ impl RosyReadm for crate::rosy_lib::taylor::DA {
             }
         }
 
+        // Exponent rows are `num_vars` wide; buffers without da_params fall
+        // back to the current DAINI configuration.
+        let stride = match da_params.get(1) {
+            Some(&nv) => nv as usize,
+            None => get_config()
+                .map_err(|e| anyhow::anyhow!("READM: Taylor system not initialized: {}", e))?
+                .num_vars,
+        };
+
         let n_terms = var_info[1] as usize;
-        let expected_int_len = n_terms * MAX_VARS;
+        let expected_int_len = n_terms * stride;
 
         if dp_array.len() < n_terms {
             bail!(
@@ -374,19 +403,17 @@ impl RosyReadm for crate::rosy_lib::taylor::DA {
                 int_array.len(),
                 expected_int_len,
                 n_terms,
-                MAX_VARS
+                stride
             );
         }
 
+        let weights = crate::rosy_lib::taylor::get_weights();
         let mut hash_coeffs: FxHashMap<Monomial, f64> = FxHashMap::default();
         for i in 0..n_terms {
             let coeff = dp_array[i];
-            let base = i * MAX_VARS;
-            let mut exponents = [0u8; MAX_VARS];
-            for j in 0..MAX_VARS {
-                exponents[j] = int_array[base + j] as u8;
-            }
-            let mono = Monomial::new(exponents);
+            let base = i * stride;
+            let powers = exponent_row(&int_array[base..base + stride])?;
+            let mono = Monomial::from_powers(powers, &weights);
             hash_coeffs.insert(mono, coeff);
         }
 
@@ -400,22 +427,22 @@ impl RosyReadm for crate::rosy_lib::taylor::DA {
 
 impl RosyWritem for crate::rosy_lib::taylor::CD {
     fn writem(&self) -> (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>) {
-        use crate::rosy_lib::taylor::{MAX_VARS, get_config};
+        use crate::rosy_lib::taylor::get_config;
 
         let rt_cfg = get_config().expect("Taylor system not initialized");
         let order = rt_cfg.max_order as f64;
-        let nv = rt_cfg.num_vars as f64;
+        let stride = rt_cfg.num_vars;
+        let nv = stride as f64;
 
+        let weights = crate::rosy_lib::taylor::get_weights();
         let terms = self.coeffs_iter();
         let mut dp: Vec<f64> = Vec::with_capacity(terms.len() * 2);
-        let mut int: Vec<f64> = Vec::with_capacity(terms.len() * MAX_VARS);
+        let mut int: Vec<f64> = Vec::with_capacity(terms.len() * stride);
 
         for (monomial, coeff) in &terms {
             dp.push(coeff.re);
             dp.push(coeff.im);
-            for i in 0..MAX_VARS {
-                int.push(monomial.exponents[i] as f64);
-            }
+            int.extend(monomial.powers(&weights)[..stride].iter().map(|&e| e as f64));
         }
 
         // n_terms = dp.len() / 2  — store that as the payload length
@@ -438,7 +465,7 @@ impl RosyReadm for crate::rosy_lib::taylor::CD {
         int_array: &[f64],
         da_params: &[f64],
     ) -> Result<Self> {
-        use crate::rosy_lib::taylor::{CD, MAX_VARS, Monomial, get_config};
+        use crate::rosy_lib::taylor::{CD, Monomial, get_config};
         use num_complex::Complex64;
         use rustc_hash::FxHashMap;
 
@@ -471,9 +498,16 @@ impl RosyReadm for crate::rosy_lib::taylor::CD {
             }
         }
 
+        let stride = match da_params.get(1) {
+            Some(&nv) => nv as usize,
+            None => get_config()
+                .map_err(|e| anyhow::anyhow!("READM: Taylor system not initialized: {}", e))?
+                .num_vars,
+        };
+
         let n_terms = var_info[1] as usize;
         let expected_dp = n_terms * 2;
-        let expected_int = n_terms * MAX_VARS;
+        let expected_int = n_terms * stride;
 
         if dp_array.len() < expected_dp {
             bail!(
@@ -491,20 +525,39 @@ impl RosyReadm for crate::rosy_lib::taylor::CD {
             );
         }
 
+        let weights = crate::rosy_lib::taylor::get_weights();
         let mut hash_coeffs: FxHashMap<Monomial, Complex64> = FxHashMap::default();
         for i in 0..n_terms {
             let re = dp_array[i * 2];
             let im = dp_array[i * 2 + 1];
             let coeff = Complex64::new(re, im);
-            let base = i * MAX_VARS;
-            let mut exponents = [0u8; MAX_VARS];
-            for j in 0..MAX_VARS {
-                exponents[j] = int_array[base + j] as u8;
-            }
-            let mono = Monomial::new(exponents);
+            let base = i * stride;
+            let powers = exponent_row(&int_array[base..base + stride])?;
+            let mono = Monomial::from_powers(powers, &weights);
             hash_coeffs.insert(mono, coeff);
         }
 
         Ok(CD::from_coeffs(hash_coeffs))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rosy_lib::taylor::MAX_VARS;
+
+    #[test]
+    fn exponent_rows_wider_than_max_vars() {
+        // Zeros past MAX_VARS are only padding
+        let mut row = vec![0.0; MAX_VARS + 4];
+        row[0] = 2.0;
+        let powers = exponent_row(&row).unwrap();
+        assert_eq!(powers[0], 2);
+        assert!(powers[1..].iter().all(|&p| p == 0));
+
+        // A power in a variable this program lacks can't be read
+        row[MAX_VARS + 1] = 1.0;
+        let error = exponent_row(&row).unwrap_err().to_string();
+        assert!(error.contains(&format!("variable {}", MAX_VARS + 2)), "{error}");
+    }
+}
@@ -16,6 +16,8 @@ pub mod reran;
 pub mod rkco;
 pub mod rng;
 pub mod mem_serial;
+pub mod checkpoint;
+pub mod trace;
 
 pub use da_ops::*;
 pub use display::*;
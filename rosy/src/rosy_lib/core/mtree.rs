@@ -45,7 +45,7 @@ pub fn rosy_mtree(
             let mono = &rt.monomial_list[idx as usize];
             let coeff = da.coeffs[idx as usize];
             if coeff.abs() > rt.config.epsilon {
-                let exps: Vec<u8> = mono.exponents[..num_vars].to_vec();
+                let exps: Vec<u8> = mono.powers(&rt.weights)[..num_vars].to_vec();
                 terms.push((exps, coeff, comp));
             }
         }
@@ -5,24 +5,40 @@
 //! Lets the polynomial described by NP DA vectors stored in the array P
 //! act on the NA arguments A, and stores the NR results in R.
 //!
-//! In the normal case L == 1 (Horner evaluation).
-//! The current implementation supports RE (f64) arguments (plain polynomial evaluation)
-//! and returns an error for other argument types at runtime.
+//! RE, VE (particle batch) and DA arguments share a prepare/evaluate split:
+//! the first call flattens P into a monomial tree ([`PreparedPolynomial`]),
+//! which is cached per thread and reused by later calls on the same array.
+//! The mode argument `L` controls how far the cache is trusted:
+//!
+//! - `L = 0` — P has not changed since it was last prepared; reuse the tree
+//!   without looking at the coefficients.
+//! - any other `L` (normally 1) — fingerprint P's coefficients and re-prepare
+//!   only when they differ from the previous call.
+//!
+//! Either way a tree is only reused for the array it was built from: it is
+//! keyed by the Taylor runtime generation, so a new `DAINI` prepares afresh,
+//! and it is dropped as soon as one of the array's DA vectors is, so a new
+//! array that happens to reuse the memory is not mistaken for the old one.
 
-use crate::rosy_lib::taylor::{CD, DA};
+use std::cell::RefCell;
+use std::hash::{Hash, Hasher};
+
+use crate::rosy_lib::taylor::{CD, DA, MAX_VARS, Monomial, get_weights, taylor_generation};
 use anyhow::{Result, bail};
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
 
-#[cfg(feature = "nightly-simd")]
-use std::simd::prelude::*;
+/// Particles processed per block by the batch (VE) evaluator. Keeps the
+/// per-node scratch rows (`nodes × PARTICLE_BLOCK` doubles) cache-resident.
+const PARTICLE_BLOCK: usize = 256;
 
-#[cfg(feature = "nightly-simd")]
-const LANES: usize = 4;
+/// Prepared polynomial arrays kept per thread before the cache is flushed.
+const MAX_PREPARED: usize = 32;
 
 /// Evaluate NP polynomials (stored in `p_array` as DA vectors) at the NA real
 /// arguments in `a_array`, writing NR results into `r_array`.
 ///
 /// # Arguments
-/// * `_l`       - evaluation mode flag (1 = Horner; currently ignored, always Horner)
+/// * `l`        - preparation mode (0 = reuse the prepared P unchecked, otherwise re-check it)
 /// * `p_array`  - slice of NP DA polynomials
 /// * `np`       - number of polynomials to evaluate
 /// * `a_array`  - slice of NA real-valued arguments
@@ -30,7 +46,7 @@ const LANES: usize = 4;
 /// * `r_array`  - output vector, must be large enough to hold NR results
 /// * `nr`       - number of results to write
 pub fn rosy_polval_re(
-    _l: f64,
+    l: f64,
     p_array: &[DA],
     np: usize,
     a_array: &[f64],
@@ -47,14 +63,11 @@ pub fn rosy_polval_re(
         r_array.push(0.0);
     }
 
-    for i in 0..nr {
-        if i >= p_array.len() {
-            bail!("POLVAL: polynomial array too short at index {}", i);
-        }
-        r_array[i] = evaluate_da_at_re(&p_array[i], a_array, na)?;
-    }
-
-    Ok(())
+    with_prepared(l, p_array, np, |prepared| {
+        prepared.check_shape(nr, a_array.len(), na)?;
+        prepared.eval_re(a_array, &mut r_array[..nr]);
+        Ok(())
+    })
 }
 
 /// Batch-evaluate NP polynomials at multiple particles simultaneously.
@@ -66,9 +79,10 @@ pub fn rosy_polval_re(
 /// Results are written the same way: `r_array\[i\]` will hold the i-th result
 /// component for all particles.
 ///
-/// With `nightly-simd`: processes 4 particles per monomial using f64x4 SIMD.
+/// Particles are processed in blocks of [`PARTICLE_BLOCK`]; within a block each
+/// tree node is a single element-wise multiply, which the compiler vectorizes.
 pub fn rosy_polval_ve(
-    _l: f64,
+    l: f64,
     p_array: &[DA],
     np: usize,
     a_array: &[Vec<f64>],
@@ -93,159 +107,26 @@ pub fn rosy_polval_ve(
         r_array.push(Vec::new());
     }
 
-    for i in 0..nr {
-        if i >= p_array.len() {
-            bail!("POLVAL: polynomial array too short at index {}", i);
-        }
-        r_array[i].resize(num_particles, 0.0);
-        evaluate_poly_batch(&p_array[i], a_array, na, num_particles, &mut r_array[i]);
-    }
-
-    Ok(())
-}
-
-/// Evaluate a single DA polynomial at all particles, writing results into `out`.
-///
-/// Iterates monomials once, processing particles in SIMD chunks of 4.
-#[inline]
-fn evaluate_poly_batch(
-    poly: &DA,
-    a_array: &[Vec<f64>],
-    na: usize,
-    num_particles: usize,
-    out: &mut [f64],
-) {
-    // Zero the output
-    out.iter_mut().for_each(|v| *v = 0.0);
-
-    for (monomial, coeff) in poly.coeffs_iter().into_iter() {
-        let exponents = &monomial.exponents;
-
-        // Collect active variables (non-zero exponents) for this monomial
-        let mut active_vars: [(usize, u8); 6] = [(0, 0); 6];
-        let mut num_active = 0;
-        for (var_idx, &exp) in exponents.iter().enumerate() {
-            if exp != 0 && var_idx < na {
-                active_vars[num_active] = (var_idx, exp);
-                num_active += 1;
-                if num_active >= 6 {
-                    break;
-                }
-            }
-        }
-
-        // Constant monomial (no variables) — just add coefficient to all particles
-        if num_active == 0 {
-            for j in 0..num_particles {
-                out[j] += coeff;
-            }
-            continue;
-        }
-
-        #[cfg(feature = "nightly-simd")]
+    with_prepared(l, p_array, np, |prepared| {
+        prepared.check_shape(nr, a_array.len(), na)?;
+        if let Some((axis, values)) = a_array[..prepared.num_vars_used]
+            .iter()
+            .enumerate()
+            .find(|(_, values)| values.len() < num_particles)
         {
-            let chunks = num_particles / LANES;
-            let coeff_v = Simd::<f64, LANES>::splat(coeff);
-
-            for c in 0..chunks {
-                let base = c * LANES;
-                let mut term = coeff_v;
-
-                for a in 0..num_active {
-                    let (var_idx, exp) = active_vars[a];
-                    let vals = Simd::<f64, LANES>::from_slice(&a_array[var_idx][base..]);
-                    term *= simd_powi(vals, exp);
-                }
-
-                let current = Simd::<f64, LANES>::from_slice(&out[base..]);
-                (current + term).copy_to_slice(&mut out[base..base + LANES]);
-            }
-
-            // Scalar remainder
-            for j in (chunks * LANES)..num_particles {
-                let mut term = coeff;
-                for a in 0..num_active {
-                    let (var_idx, exp) = active_vars[a];
-                    term *= scalar_powi(a_array[var_idx][j], exp);
-                }
-                out[j] += term;
-            }
+            bail!(
+                "POLVAL: argument {} holds {} particles but argument 1 holds {}",
+                axis + 1,
+                values.len(),
+                num_particles
+            );
         }
-
-        #[cfg(not(feature = "nightly-simd"))]
-        {
-            for j in 0..num_particles {
-                let mut term = coeff;
-                for a in 0..num_active {
-                    let (var_idx, exp) = active_vars[a];
-                    term *= scalar_powi(a_array[var_idx][j], exp);
-                }
-                out[j] += term;
-            }
+        for result in r_array[..nr].iter_mut() {
+            result.resize(num_particles, 0.0);
         }
-    }
-}
-
-/// SIMD power: compute vals^exp for small exponents via repeated multiply.
-#[cfg(feature = "nightly-simd")]
-#[inline(always)]
-fn simd_powi(vals: Simd<f64, LANES>, exp: u8) -> Simd<f64, LANES> {
-    match exp {
-        0 => Simd::<f64, LANES>::splat(1.0),
-        1 => vals,
-        2 => vals * vals,
-        3 => vals * vals * vals,
-        4 => {
-            let v2 = vals * vals;
-            v2 * v2
-        }
-        5 => {
-            let v2 = vals * vals;
-            v2 * v2 * vals
-        }
-        6 => {
-            let v2 = vals * vals;
-            v2 * v2 * v2
-        }
-        _ => {
-            // General case via repeated squaring
-            let mut result = Simd::<f64, LANES>::splat(1.0);
-            let mut base = vals;
-            let mut e = exp;
-            while e > 0 {
-                if e & 1 == 1 {
-                    result *= base;
-                }
-                base *= base;
-                e >>= 1;
-            }
-            result
-        }
-    }
-}
-
-/// Scalar power for small exponents.
-#[inline(always)]
-fn scalar_powi(val: f64, exp: u8) -> f64 {
-    match exp {
-        0 => 1.0,
-        1 => val,
-        2 => val * val,
-        3 => val * val * val,
-        4 => {
-            let v2 = val * val;
-            v2 * v2
-        }
-        5 => {
-            let v2 = val * val;
-            v2 * v2 * val
-        }
-        6 => {
-            let v2 = val * val;
-            v2 * v2 * v2
-        }
-        _ => val.powi(exp as i32),
-    }
+        prepared.eval_ve(a_array, num_particles, &mut r_array[..nr]);
+        Ok(())
+    })
 }
 
 /// Substitute NA Taylor-series arguments into NP Taylor-series polynomials,
@@ -259,8 +140,11 @@ fn scalar_powi(val: f64, exp: u8) -> f64 {
 ///
 /// COSY's `ANM N M O` lowers to `POLVAL 1 N TWOND MM NV O TWOND` where MM is N's
 /// map padded with identity DAs for non-physical slots — see libcosy/physics/map_ops.rosy.
+///
+/// Every monomial of P costs one DA multiply, shared across all NP components,
+/// so composing a full map is far cheaper than expanding each term separately.
 pub fn rosy_polval_da(
-    _l: f64,
+    l: f64,
     p_array: &[DA],
     np: usize,
     a_array: &[DA],
@@ -283,48 +167,260 @@ pub fn rosy_polval_da(
         r_array.push(DA::zero());
     }
 
-    for i in 0..nr {
-        if i >= p_array.len() {
-            bail!("POLVAL: polynomial array too short at index {}", i);
+    with_prepared(l, p_array, np, |prepared| {
+        prepared.check_shape(nr, a_array.len(), na)?;
+        prepared.eval_da(a_array, &mut r_array[..nr])
+    })
+}
+
+/// A polynomial array flattened into one shared monomial tree.
+///
+/// Node 0 is the constant monomial; every other node `k` is its parent's
+/// monomial times a single variable, `value[k] = value[parent[k]] · x[var[k]]`.
+/// Nodes are stored in graded order, so one forward sweep produces every
+/// monomial with a single multiply each (the same chaining MTREE encodes in
+/// its steering arrays). Each component is then a sparse dot product over
+/// its `terms`.
+struct PreparedPolynomial {
+    /// Fingerprint of the coefficients the tree was built from.
+    fingerprint: u64,
+    /// Coefficient buffers of the DA vectors the tree was built from.
+    buffers: Vec<usize>,
+    /// Nonzero terms across all of P, a cheap sanity check under `L = 0`.
+    num_terms: usize,
+    parent: Vec<u32>,
+    var: Vec<u32>,
+    /// `(node, coefficient)` pairs for each component of P.
+    terms: Vec<Vec<(u32, f64)>>,
+    /// One past the highest variable index P refers to.
+    num_vars_used: usize,
+}
+
+/// Address, NP and Taylor runtime generation of a prepared array.
+type PreparedKey = (usize, usize, u64);
+
+thread_local! {
+    /// Prepared trees, keyed by the address and NP of the polynomial array
+    /// and the Taylor runtime they were built under.
+    static PREPARED: RefCell<FxHashMap<PreparedKey, PreparedPolynomial>> =
+        RefCell::new(FxHashMap::default());
+
+    /// Coefficient buffers behind the prepared trees.
+    static WATCHED: RefCell<FxHashSet<usize>> = RefCell::new(FxHashSet::default());
+
+    /// Watched buffers whose DA vectors have been dropped since the last
+    /// POLVAL; their trees are discarded before the next lookup.
+    static RELEASED: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Called when a DA vector is dropped: if a prepared tree was built from
+/// its coefficient buffer, mark that tree stale.
+pub(crate) fn release_buffer(buffer: usize) {
+    let watched = WATCHED
+        .try_with(|watched| {
+            let mut watched = watched.borrow_mut();
+            !watched.is_empty() && watched.remove(&buffer)
+        })
+        .unwrap_or(false);
+    if watched {
+        let _ = RELEASED.try_with(|released| released.borrow_mut().push(buffer));
+    }
+}
+
+fn buffer_of(poly: &DA) -> usize {
+    poly.coeffs.as_ptr() as usize
+}
+
+/// Run `f` against the prepared form of `p_array[..np]`, (re-)preparing it
+/// when the cache holds nothing usable for this array under mode `l`.
+fn with_prepared<R>(
+    l: f64,
+    p_array: &[DA],
+    np: usize,
+    f: impl FnOnce(&PreparedPolynomial) -> Result<R>,
+) -> Result<R> {
+    let polys = &p_array[..np.min(p_array.len())];
+    let key = (polys.as_ptr() as usize, np, taylor_generation());
+    let num_terms: usize = polys.iter().map(DA::num_terms).sum();
+
+    PREPARED.with_borrow_mut(|cache| {
+        let released = RELEASED.take();
+        if !released.is_empty() {
+            cache.retain(|_, prepared| !prepared.buffers.iter().any(|b| released.contains(b)));
+        }
+
+        let same_array = |prepared: &PreparedPolynomial| {
+            prepared.num_terms == num_terms
+                && prepared.buffers.iter().copied().eq(polys.iter().map(buffer_of))
+        };
+        let stale = match cache.get(&key) {
+            Some(prepared) if same_array(prepared) => {
+                if l == 0.0 {
+                    None
+                } else {
+                    let fingerprint = fingerprint(polys);
+                    (prepared.fingerprint != fingerprint).then_some(fingerprint)
+                }
+            }
+            _ => Some(fingerprint(polys)),
+        };
+        if let Some(fingerprint) = stale {
+            if cache.len() >= MAX_PREPARED && !cache.contains_key(&key) {
+                cache.clear();
+            }
+            cache.insert(key, PreparedPolynomial::new(polys, fingerprint, num_terms));
+            WATCHED.with_borrow_mut(|watched| {
+                watched.clear();
+                watched.extend(cache.values().flat_map(|prepared| prepared.buffers.iter().copied()));
+            });
+        }
+        f(&cache[&key])
+    })
+}
+
+/// Hash every nonzero (index, coefficient) pair of the polynomial array,
+/// along with the variable weights the indices refer to.
+fn fingerprint(polys: &[DA]) -> u64 {
+    let mut hasher = FxHasher::default();
+    get_weights().hash(&mut hasher);
+    for poly in polys {
+        poly.nonzero.len().hash(&mut hasher);
+        for &idx in &poly.nonzero {
+            idx.hash(&mut hasher);
+            poly.coeffs[idx as usize].to_bits().hash(&mut hasher);
         }
-        r_array[i] = evaluate_da_at_da(&p_array[i], a_array, na)?;
     }
+    hasher.finish()
+}
 
-    Ok(())
+/// The monomial one multiply below `mono` — one power of its last variable
+/// removed, i.e. that exponent lowered by the variable's weight — together
+/// with that variable's 0-based index.
+fn parent_of(mono: &Monomial, weights: &[u8; MAX_VARS]) -> (Monomial, usize) {
+    let var = mono
+        .exponents
+        .iter()
+        .rposition(|&e| e != 0)
+        .expect("constant monomial has no parent");
+    let mut exponents = mono.exponents;
+    exponents[var] -= weights[var];
+    (Monomial::new(exponents), var)
 }
 
-/// Compose a single DA polynomial with NA Taylor-series substitutions.
-///
-/// For each monomial `c · x_1^{e_1} · … · x_na^{e_na}` of `poly` we form
-/// `c · args[0]^{e_1} · … · args[na-1]^{e_na}` (DA × DA), then sum across
-/// monomials. DA `*` and `+` return `Result` (truncation-buffer overflow is
-/// rare but surfaceable), so we propagate with `?` rather than panic.
-fn evaluate_da_at_da(poly: &DA, args: &[DA], na: usize) -> Result<DA> {
-    let mut result = DA::zero();
+impl PreparedPolynomial {
+    fn new(polys: &[DA], fingerprint: u64, num_terms: usize) -> Self {
+        let components: Vec<Vec<(Monomial, f64)>> =
+            polys.iter().map(DA::coeffs_iter).collect();
+        let weights = get_weights();
+
+        // Every monomial of P plus its parent chain down to the constant.
+        let mut seen: FxHashSet<Monomial> = FxHashSet::default();
+        seen.insert(Monomial::constant());
+        let mut monomials = vec![Monomial::constant()];
+        for (mono, _) in components.iter().flatten() {
+            let mut current = *mono;
+            while seen.insert(current) {
+                monomials.push(current);
+                current = parent_of(&current, &weights).0;
+            }
+        }
+        monomials.sort();
+
+        let node_of: FxHashMap<Monomial, u32> = monomials
+            .iter()
+            .enumerate()
+            .map(|(k, mono)| (*mono, k as u32))
+            .collect();
+
+        let mut parent = vec![0u32; monomials.len()];
+        let mut var = vec![0u32; monomials.len()];
+        let mut num_vars_used = 0;
+        for (k, mono) in monomials.iter().enumerate().skip(1) {
+            let (parent_mono, v) = parent_of(mono, &weights);
+            parent[k] = node_of[&parent_mono];
+            var[k] = v as u32;
+            num_vars_used = num_vars_used.max(v + 1);
+        }
 
-    for (monomial, coeff) in poly.coeffs_iter().into_iter() {
-        let exponents = &monomial.exponents;
-        let mut term = DA::from_coeff(coeff);
+        let terms = components
+            .iter()
+            .map(|component| component.iter().map(|(mono, c)| (node_of[mono], *c)).collect())
+            .collect();
 
-        for (var_idx, &exp) in exponents.iter().enumerate() {
-            if exp == 0 {
-                continue;
+        let buffers = polys.iter().map(buffer_of).collect();
+        Self { fingerprint, buffers, num_terms, parent, var, terms, num_vars_used }
+    }
+
+    /// Ensure NR components exist and every variable P uses has an argument.
+    fn check_shape(&self, nr: usize, num_args: usize, na: usize) -> Result<()> {
+        if nr > self.terms.len() {
+            bail!("POLVAL: polynomial array too short at index {}", self.terms.len());
+        }
+        if self.num_vars_used > num_args.min(na) {
+            bail!(
+                "POLVAL: variable index {} out of range (NA={})",
+                self.num_vars_used,
+                na
+            );
+        }
+        Ok(())
+    }
+
+    fn eval_re(&self, args: &[f64], results: &mut [f64]) {
+        let mut values = vec![0.0; self.parent.len()];
+        values[0] = 1.0;
+        for k in 1..values.len() {
+            values[k] = values[self.parent[k] as usize] * args[self.var[k] as usize];
+        }
+        for (result, terms) in results.iter_mut().zip(&self.terms) {
+            *result = terms.iter().map(|&(node, c)| c * values[node as usize]).sum();
+        }
+    }
+
+    fn eval_ve(&self, args: &[Vec<f64>], num_particles: usize, results: &mut [Vec<f64>]) {
+        let mut values = vec![0.0; self.parent.len() * PARTICLE_BLOCK];
+        values[..PARTICLE_BLOCK].fill(1.0);
+
+        for start in (0..num_particles).step_by(PARTICLE_BLOCK) {
+            let len = PARTICLE_BLOCK.min(num_particles - start);
+            for k in 1..self.parent.len() {
+                // Parents sort before their children, so the parent row is in `done`.
+                let (done, rest) = values.split_at_mut(k * PARTICLE_BLOCK);
+                let row = self.parent[k] as usize * PARTICLE_BLOCK;
+                let axis = &args[self.var[k] as usize][start..start + len];
+                for ((v, &p), &x) in rest[..len].iter_mut().zip(&done[row..row + len]).zip(axis) {
+                    *v = p * x;
+                }
             }
-            if var_idx >= na || var_idx >= args.len() {
-                bail!(
-                    "POLVAL: variable index {} out of range (NA={})",
-                    var_idx + 1,
-                    na
-                );
+            for (result, terms) in results.iter_mut().zip(&self.terms) {
+                let result = &mut result[start..start + len];
+                result.fill(0.0);
+                for &(node, c) in terms {
+                    let row = node as usize * PARTICLE_BLOCK;
+                    for (r, &v) in result.iter_mut().zip(&values[row..row + len]) {
+                        *r += c * v;
+                    }
+                }
             }
-            let pow = da_powi(&args[var_idx], exp)?;
-            term = (term * pow)?;
         }
-
-        result = (result + term)?;
     }
 
-    Ok(result)
+    fn eval_da(&self, args: &[DA], results: &mut [DA]) -> Result<()> {
+        let mut values: Vec<DA> = Vec::with_capacity(self.parent.len());
+        values.push(DA::from_coeff(1.0));
+        for k in 1..self.parent.len() {
+            let value = (&values[self.parent[k] as usize] * &args[self.var[k] as usize])?;
+            values.push(value);
+        }
+        for (result, terms) in results.iter_mut().zip(&self.terms) {
+            let mut sum = DA::zero();
+            for &(node, c) in terms {
+                sum = (sum + (&values[node as usize] * c)?)?;
+            }
+            *result = sum;
+        }
+        Ok(())
+    }
 }
 
 /// CPOLVAL — complex-DA polynomial composition. Companion to `rosy_polval_da`
@@ -335,8 +431,8 @@ fn evaluate_da_at_da(poly: &DA, args: &[DA], na: usize) -> Result<DA> {
 /// constructed it lives in CD-space, and the resonance / tune / Twiss
 /// extraction relies on substituting CD bases into CD polynomials.
 ///
-/// Algorithmically identical to `rosy_polval_da` — only the coefficient
-/// type differs. The DA struct is generic over `T: DACoefficient` and
+/// Unlike `rosy_polval_da` this composes term by term on every call and
+/// ignores `L`. The DA struct is generic over `T: DACoefficient` and
 /// `Complex64` implements that trait, so the same `*` / `+` / `clone()`
 /// API works through the type alias.
 pub fn rosy_polval_cd(
@@ -374,10 +470,11 @@ pub fn rosy_polval_cd(
 }
 
 /// Compose a single CD polynomial with NA Taylor-series substitutions over
-/// the complex-DA algebra. Mirrors `evaluate_da_at_da` exactly — see its
-/// header for the per-monomial substitution logic.
+/// the complex-DA algebra: each monomial `c · x_1^{e_1} · … · x_na^{e_na}`
+/// becomes `c · args[0]^{e_1} · … · args[na-1]^{e_na}`, summed over monomials.
 fn evaluate_cd_at_cd(poly: &CD, args: &[CD], na: usize) -> Result<CD> {
     let mut result = CD::zero();
+    let weights = get_weights();
 
     for (monomial, coeff) in poly.coeffs_iter().into_iter() {
         let exponents = &monomial.exponents;
@@ -394,7 +491,7 @@ fn evaluate_cd_at_cd(poly: &CD, args: &[CD], na: usize) -> Result<CD> {
                     na
                 );
             }
-            let pow = cd_powi(&args[var_idx], exp)?;
+            let pow = cd_powi(&args[var_idx], exp / weights[var_idx])?;
             term = (term * pow)?;
         }
 
@@ -404,7 +501,7 @@ fn evaluate_cd_at_cd(poly: &CD, args: &[CD], na: usize) -> Result<CD> {
     Ok(result)
 }
 
-/// CD-typed exponentiation by repeated squaring. Mirrors `da_powi`.
+/// CD-typed exponentiation by repeated squaring for small u8 exponents.
 #[inline]
 fn cd_powi(base: &CD, exp: u8) -> Result<CD> {
     Ok(match exp {
@@ -445,75 +542,43 @@ fn cd_powi(base: &CD, exp: u8) -> Result<CD> {
     })
 }
 
-/// Compute base^exp for small u8 exponents using exponentiation-by-squaring.
-#[inline]
-fn da_powi(base: &DA, exp: u8) -> Result<DA> {
-    Ok(match exp {
-        0 => DA::from_coeff(1.0),
-        1 => base.clone(),
-        2 => (base.clone() * base)?,
-        3 => {
-            let v2 = (base.clone() * base)?;
-            (v2 * base)?
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rosy_lib::taylor::{cleanup_taylor, init_taylor, set_weight_vector};
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn l0_does_not_reuse_trees_of_dropped_arrays() -> Result<()> {
+        cleanup_taylor();
+        init_taylor(3, 2)?;
+        let mut results = Vec::new();
+        for k in 1..=3 {
+            // A map rebuilt on every call, as a procedure-local one would be
+            let p = vec![(&DA::variable(1)? * k as f64)?];
+            rosy_polval_re(0.0, &p, 1, &[2.0, 5.0], 2, &mut results, 1)?;
+            assert_eq!(results[0], 2.0 * k as f64);
         }
-        4 => {
-            let v2 = (base.clone() * base)?;
-            (v2.clone() * &v2)?
-        }
-        5 => {
-            let v2 = (base.clone() * base)?;
-            let v4 = (v2.clone() * &v2)?;
-            (v4 * base)?
-        }
-        6 => {
-            let v2 = (base.clone() * base)?;
-            let v3 = (v2 * base)?;
-            (v3.clone() * &v3)?
-        }
-        _ => {
-            let mut result = DA::from_coeff(1.0);
-            let mut current = base.clone();
-            let mut e = exp;
-            while e > 0 {
-                if e & 1 == 1 {
-                    result = (result * &current)?;
-                }
-                current = (current.clone() * &current)?;
-                e >>= 1;
-            }
-            result
-        }
-    })
-}
-
-/// Evaluate a single DA polynomial at the given real-valued point.
-///
-/// For each monomial c * x1^e1 * x2^e2 * ... we substitute the values from
-/// `args` (1-based variable indices mapped to 0-based slice positions) and
-/// sum all contributions.
-fn evaluate_da_at_re(poly: &DA, args: &[f64], na: usize) -> Result<f64> {
-    let mut result = 0.0_f64;
-
-    for (monomial, coeff) in poly.coeffs_iter().into_iter() {
-        let exponents = &monomial.exponents;
-        let mut term = coeff;
+        cleanup_taylor();
+        Ok(())
+    }
 
-        for (var_idx, &exp) in exponents.iter().enumerate() {
-            if exp == 0 {
-                continue;
-            }
-            if var_idx >= na || var_idx >= args.len() {
-                bail!(
-                    "POLVAL: variable index {} out of range (NA={})",
-                    var_idx + 1,
-                    na
-                );
-            }
-            term *= args[var_idx].powi(exp as i32);
+    #[test]
+    #[serial]
+    fn l0_does_not_reuse_trees_across_daini() -> Result<()> {
+        // x2² unweighted, then x2 alone stored with the same exponent 2
+        let mut results = Vec::new();
+        for (weights, expected) in [(vec![1, 1], 25.0), (vec![1, 2], 5.0)] {
+            cleanup_taylor();
+            set_weight_vector(weights)?;
+            init_taylor(2, 2)?;
+            let x2 = DA::variable(2)?;
+            let p = vec![if expected == 25.0 { (&x2 * &x2)? } else { x2 }];
+            rosy_polval_re(0.0, &p, 1, &[2.0, 5.0], 2, &mut results, 1)?;
+            assert_eq!(results[0], expected);
         }
-
-        result += term;
+        cleanup_taylor();
+        Ok(())
     }
-
-    Ok(result)
 }
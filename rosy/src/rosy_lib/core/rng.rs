@@ -1,60 +1,108 @@
 //! Global seeded random number generator.
 //!
-//! Provides a single, program-wide `StdRng` initialized with seed `0` by default.
-//! All randomness in Rosy (RERAN, FIT optimizer, etc.) draws from this RNG,
-//! ensuring reproducible results across runs.
+//! Provides a single, program-wide `StdRng`-style generator initialized with
+//! seed `0` by default. All randomness in Rosy (RERAN, FIT optimizer, etc.)
+//! draws from it, ensuring reproducible results across runs. A thread can be
+//! given a generator of its own ([`own_rng_state`]): inside a `PLOOP` built
+//! with `--threads`, every thread starts from a copy of the spawning thread's
+//! generator, just as every MPI rank has its own generator in the same
+//! position when the `PLOOP` begins.
 //!
 //! Use [`set_rng_seed`] (the `RANSEED` statement) to change the seed at runtime.
 //! A negative value switches to system-entropy seeding; a positive value is
 //! truncated to `u64` and used as a deterministic seed.
+//!
+//! The generator is the ChaCha12 generator behind `StdRng`, used directly so
+//! that a `CHECKPOINT` can save its exact position ([`rng_state`]) and a
+//! resumed program continue the same random sequence ([`set_rng_state`]).
+
+use std::cell::RefCell;
+use std::sync::{PoisonError, RwLock};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
 
-use std::sync::RwLock;
-use rand::{Rng, SeedableRng, rngs::StdRng};
+/// The RNG of every thread without one of its own, seeded to 0 on first
+/// use for reproducibility.
+static GLOBAL_RNG: RwLock<Option<ChaCha12Rng>> = RwLock::new(None);
+
+thread_local! {
+    /// This thread's own RNG, if [`own_rng_state`] gave it one.
+    static LOCAL_RNG: RefCell<Option<ChaCha12Rng>> = const { RefCell::new(None) };
+}
 
-/// The global RNG, seeded to 0 by default for reproducibility.
-static GLOBAL_RNG: RwLock<Option<StdRng>> = RwLock::new(None);
+/// Use the RNG this thread draws from.
+fn with_rng<R>(f: impl FnOnce(&mut ChaCha12Rng) -> R) -> R {
+    LOCAL_RNG.with_borrow_mut(|local| match local {
+        Some(rng) => f(rng),
+        None => {
+            let mut guard = GLOBAL_RNG.write().unwrap_or_else(PoisonError::into_inner);
+            f(guard.get_or_insert_with(|| ChaCha12Rng::seed_from_u64(0)))
+        }
+    })
+}
+
+/// Where the RNG is in its random sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RngState {
+    pub seed: [u8; 32],
+    pub stream: u64,
+    pub word_pos: u128,
+}
+
+/// The current position of the RNG this thread draws from.
+pub fn rng_state() -> RngState {
+    with_rng(|rng| RngState {
+        seed: rng.get_seed(),
+        stream: rng.get_stream(),
+        word_pos: rng.get_word_pos(),
+    })
+}
+
+fn rng_at(state: RngState) -> ChaCha12Rng {
+    let mut rng = ChaCha12Rng::from_seed(state.seed);
+    rng.set_stream(state.stream);
+    rng.set_word_pos(state.word_pos);
+    rng
+}
+
+/// Move the RNG this thread draws from to a position saved by [`rng_state`].
+pub fn set_rng_state(state: RngState) {
+    with_rng(|rng| *rng = rng_at(state));
+}
 
-/// Ensure the global RNG is initialized (lazily, on first use).
-fn ensure_init(guard: &mut Option<StdRng>) {
-    if guard.is_none() {
-        *guard = Some(StdRng::seed_from_u64(0));
-    }
+/// Give this thread an RNG of its own, at a position saved by
+/// [`rng_state`], so its draws leave other threads' sequences alone.
+pub fn own_rng_state(state: RngState) {
+    LOCAL_RNG.set(Some(rng_at(state)));
 }
 
-/// Set the global RNG seed.
+/// Set the RNG seed.
 ///
-/// - If `seed < 0.0`, the RNG is reseeded from OS entropy (`StdRng::from_os_rng()`).
+/// - If `seed < 0.0`, the RNG is reseeded from OS entropy (`from_os_rng()`).
 /// - If `seed >= 0.0`, the value is truncated to `u64` and used as a deterministic seed.
 pub fn set_rng_seed(seed: f64) {
     let new_rng = if seed < 0.0 {
-        StdRng::from_os_rng()
+        ChaCha12Rng::from_os_rng()
     } else {
-        StdRng::seed_from_u64(seed as u64)
+        ChaCha12Rng::seed_from_u64(seed as u64)
     };
-    let mut guard = GLOBAL_RNG.write().unwrap();
-    *guard = Some(new_rng);
+    with_rng(|rng| *rng = new_rng);
 }
 
-/// Generate a random `f64` in `[-1, 1]` from the global RNG.
+/// Generate a random `f64` in `[-1, 1]` from the RNG.
 /// This is the runtime backing for the `RERAN` statement.
 pub fn rosy_reran() -> f64 {
-    let mut guard = GLOBAL_RNG.write().unwrap();
-    ensure_init(&mut guard);
-    guard.as_mut().unwrap().random_range(-1.0..=1.0)
+    with_rng(|rng| rng.random_range(-1.0..=1.0))
 }
 
-/// Generate a random `f64` in `[0, 1)` from the global RNG.
+/// Generate a random `f64` in `[0, 1)` from the RNG.
 /// Used by the FIT optimizer for acceptance probability tests.
 pub fn rng_f64() -> f64 {
-    let mut guard = GLOBAL_RNG.write().unwrap();
-    ensure_init(&mut guard);
-    guard.as_mut().unwrap().random_range(0.0..1.0)
+    with_rng(|rng| rng.random_range(0.0..1.0))
 }
 
-/// Generate a random `f64` in `[-1, 1)` from the global RNG.
+/// Generate a random `f64` in `[-1, 1)` from the RNG.
 /// Used by the FIT optimizer for symmetric perturbation.
 pub fn rng_f64_symmetric() -> f64 {
-    let mut guard = GLOBAL_RNG.write().unwrap();
-    ensure_init(&mut guard);
-    2.0 * guard.as_mut().unwrap().random_range(0.0..1.0) - 1.0
+    with_rng(|rng| 2.0 * rng.random_range(0.0..1.0) - 1.0)
 }
@@ -0,0 +1,197 @@
+//! Rosy source locations for runtime errors.
+//!
+//! A generated program records which Rosy statement it is running, and the
+//! `PROCEDURE`s and `FUNCTION`s it got there through, so that an error or
+//! panic can be reported against the `.rosy` source instead of the
+//! generated Rust.
+//!
+//! The transpiler starts every statement with a call to [`at`], which is a
+//! single thread-local store, and brackets every procedure and function body
+//! with [`enter`] and [`leave`]. Nothing is popped when an error propagates,
+//! so the state at the failure is still there when `main` reports it.
+//!
+//! A `PLOOP` thread starts from the trace of the thread that spawned it
+//! ([`capture`]/[`restore`]), and an error leaving the thread carries the
+//! thread's trace as context (see [`Trace::of_error`]).
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+
+/// Where a statement is in the Rosy source.
+#[derive(Debug)]
+pub struct Location {
+    pub file: &'static str,
+    pub line: u32,
+    pub col: u32,
+    /// The first line of the statement
+    pub snippet: &'static str,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.col)
+    }
+}
+
+/// A procedure or function being run, and the statement that called it.
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    /// Such as `PROCEDURE RUN`
+    name: &'static str,
+    call: Option<&'static Location>,
+}
+
+thread_local! {
+    static CURRENT: Cell<Option<&'static Location>> = const { Cell::new(None) };
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Mark the statement at `location` as running.
+#[inline(always)]
+pub fn at(location: &'static Location) {
+    CURRENT.set(Some(location));
+}
+
+/// Start running the body of a procedure or function, called by the
+/// current statement.
+pub fn enter(name: &'static str) {
+    let call = CURRENT.get();
+    FRAMES.with_borrow_mut(|frames| frames.push(Frame { name, call }));
+}
+
+/// Return from the innermost procedure or function to its call.
+pub fn leave() {
+    if let Some(frame) = FRAMES.with_borrow_mut(Vec::pop) {
+        CURRENT.set(frame.call);
+    }
+}
+
+/// The running statement and call stack of one thread.
+#[derive(Debug, Clone, Default)]
+pub struct Trace {
+    current: Option<&'static Location>,
+    /// Outermost first
+    frames: Vec<Frame>,
+}
+
+/// The trace of this thread.
+pub fn capture() -> Trace {
+    Trace {
+        current: CURRENT.get(),
+        frames: FRAMES.with_borrow(Vec::clone),
+    }
+}
+
+/// Continue from `trace` on this thread.
+pub fn restore(trace: Trace) {
+    CURRENT.set(trace.current);
+    FRAMES.set(trace.frames);
+}
+
+impl Trace {
+    /// The trace an error carries as context, or this thread's.
+    pub fn of_error(error: &anyhow::Error) -> Trace {
+        error.downcast_ref::<Trace>().cloned().unwrap_or_else(capture)
+    }
+
+    /// The statement and the call stack, innermost call first:
+    ///
+    /// ```text
+    ///  --> run.rosy:12:9
+    ///    |
+    /// 12 | X(I) := 1;
+    ///    |
+    ///    = in PROCEDURE FILL, called at run.rosy:20:5
+    /// ```
+    ///
+    /// Empty if no statement ran yet.
+    pub fn report(&self) -> String {
+        let Some(location) = self.current else {
+            return String::new();
+        };
+        let gutter = " ".repeat(location.line.to_string().len());
+        let mut report = format!(
+            "{gutter}--> {location}\n{gutter} |\n{} | {}\n{gutter} |",
+            location.line, location.snippet
+        );
+        for frame in self.frames.iter().rev() {
+            match frame.call {
+                Some(call) => {
+                    report += &format!("\n{gutter} = in {}, called at {call}", frame.name)
+                }
+                None => report += &format!("\n{gutter} = in {}", frame.name),
+            }
+        }
+        report
+    }
+}
+
+/// As error context: where the error happened, in one line.
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.current {
+            Some(location) => write!(f, "...at {location}"),
+            None => write!(f, "...before the first statement"),
+        }
+    }
+}
+
+/// Report panics like errors: the message and where in the Rosy source it
+/// happened. The Rust location and backtrace follow with `RUST_BACKTRACE=1`.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info.payload_as_str().unwrap_or("explicit panic");
+        let report = capture().report();
+        if report.is_empty() {
+            eprintln!("Error: {message}");
+        } else {
+            eprintln!("Error: {message}\n{report}");
+        }
+        if std::env::var("RUST_BACKTRACE").unwrap_or_default() == "1" {
+            default_hook(info);
+        } else {
+            eprintln!("\nSet RUST_BACKTRACE=1 for a complete backtrace.");
+        }
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static CALL: Location = Location {
+        file: "run.rosy",
+        line: 20,
+        col: 5,
+        snippet: "FILL 3;",
+    };
+    static FAILING: Location = Location {
+        file: "run.rosy",
+        line: 12,
+        col: 9,
+        snippet: "X(I) := 1;",
+    };
+
+    #[test]
+    fn report_names_the_statement_and_its_callers() {
+        at(&CALL);
+        enter("PROCEDURE FILL");
+        at(&FAILING);
+        let inside = capture();
+        assert_eq!(
+            inside.report(),
+            "  --> run.rosy:12:9\n   |\n12 | X(I) := 1;\n   |\n   = in PROCEDURE FILL, called at run.rosy:20:5"
+        );
+        assert_eq!(inside.to_string(), "...at run.rosy:12:9");
+
+        leave();
+        assert_eq!(capture().report(), "  --> run.rosy:20:5\n   |\n20 | FILL 3;\n   |");
+
+        // Errors carry the trace of the thread they failed on
+        let error = anyhow::anyhow!("Index 5 into 'X' is out of bounds").context(inside);
+        restore(Trace::default());
+        assert!(capture().report().is_empty());
+        assert!(Trace::of_error(&error).report().contains("PROCEDURE FILL"));
+    }
+}
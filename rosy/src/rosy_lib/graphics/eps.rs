@@ -0,0 +1,86 @@
+//! Encapsulated PostScript backend for [`GR`](super::GR) objects.
+//!
+//! Emits a self-contained EPSF-3.0 file that uses only Level 2 operators,
+//! so it can be included in LaTeX documents or converted with Ghostscript.
+
+use std::fmt::Write;
+
+use super::{CANVAS_HEIGHT, CANVAS_MARGIN, CANVAS_WIDTH, Scene, SceneItem, Style, color_rgb};
+
+/// Escape text for a PostScript string literal.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '(' | ')' => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ if c.is_ascii() => out.push(c),
+            _ => out.push('?'),
+        }
+    }
+    out
+}
+
+fn set_style(out: &mut String, style: &Style) {
+    let (r, g, b) = color_rgb(style.color);
+    let _ = writeln!(
+        out,
+        "{:.4} {:.4} {:.4} setrgbcolor {} setlinewidth",
+        r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0, style.width
+    );
+}
+
+pub fn render(scene: &Scene) -> String {
+    let w = CANVAS_WIDTH - 2.0 * CANVAS_MARGIN;
+    let h = CANVAS_HEIGHT - 2.0 * CANVAS_MARGIN;
+    let mut out = String::new();
+
+    // Writing to a String cannot fail.
+    let _ = writeln!(out, "%!PS-Adobe-3.0 EPSF-3.0");
+    let _ = writeln!(out, "%%BoundingBox: 0 0 {} {}", CANVAS_WIDTH, CANVAS_HEIGHT);
+    let _ = writeln!(out, "%%Creator: Rosy");
+    let _ = writeln!(out, "%%LanguageLevel: 2");
+    let _ = writeln!(out, "%%EndComments");
+    let _ = writeln!(out, "gsave");
+    let _ = writeln!(out, "1 setlinecap 1 setlinejoin");
+    let _ = writeln!(out, "/Helvetica findfont 12 scalefont setfont");
+    let _ = writeln!(out, "0 setgray 0.5 setlinewidth");
+    let _ = writeln!(out, "{0} {0} {1} {2} rectstroke", CANVAS_MARGIN, w, h);
+    if scene.zoomed {
+        let _ = writeln!(out, "{0} {0} {1} {2} rectclip", CANVAS_MARGIN, w, h);
+    }
+
+    for item in &scene.items {
+        match item {
+            SceneItem::Path { points, style } => {
+                set_style(&mut out, style);
+                let _ = writeln!(out, "newpath");
+                for (i, p) in points.iter().enumerate() {
+                    let (x, y) = scene.bounds.to_canvas(*p);
+                    let op = if i == 0 { "moveto" } else { "lineto" };
+                    let _ = writeln!(out, "{:.3} {:.3} {}", x, y, op);
+                }
+                let _ = writeln!(out, "stroke");
+            }
+            SceneItem::Dots { points, style } => {
+                set_style(&mut out, style);
+                for p in points {
+                    let (x, y) = scene.bounds.to_canvas(*p);
+                    let _ = writeln!(out, "newpath {:.3} {:.3} {} 0 360 arc fill", x, y, style.width);
+                }
+            }
+            SceneItem::Text { at, text, style } => {
+                set_style(&mut out, style);
+                let (x, y) = scene.bounds.to_canvas(*at);
+                let _ = writeln!(out, "{:.3} {:.3} moveto ({}) show", x, y, escape(text));
+            }
+        }
+    }
+
+    let _ = writeln!(out, "grestore");
+    let _ = writeln!(out, "showpage");
+    let _ = writeln!(out, "%%EOF");
+    out
+}
@@ -0,0 +1,79 @@
+//! gnuplot backend for [`GR`](super::GR) objects.
+//!
+//! Produces a script and a data file. Each path or run of dots becomes one
+//! data block (separated by two blank lines, so it can be addressed with
+//! `index`), and text becomes a `set label`. The script renders to SVG with
+//! gnuplot's built-in `svg` terminal, which needs no display.
+
+use std::fmt::Write;
+
+use super::{Scene, SceneItem, color_hex};
+
+/// Script and data produced for one graphics object.
+pub struct GnuplotOutput {
+    pub script: String,
+    pub data: String,
+}
+
+/// Escape text for a double-quoted gnuplot string.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Render a scene. `data_path` is the data file name written into the
+/// script; the image is `script_path` with `.svg` appended, so it cannot
+/// clobber an SVG written directly from the same picture.
+pub fn render(scene: &Scene, data_path: &str, script_path: &str) -> GnuplotOutput {
+    let image_path = format!("{}.svg", script_path);
+    let b = &scene.bounds;
+    let mut script = String::new();
+    let mut data = String::new();
+    let mut plots = Vec::new();
+
+    // Writing to a String cannot fail.
+    let _ = writeln!(script, "# Render with: gnuplot {}", script_path);
+    let _ = writeln!(script, "set terminal svg size 640,480 background rgb '#ffffff'");
+    let _ = writeln!(script, "set output \"{}\"", escape(&image_path));
+    let _ = writeln!(script, "unset key");
+    let _ = writeln!(script, "set xrange [{:e}:{:e}]", b.xmin, b.xmax);
+    let _ = writeln!(script, "set yrange [{:e}:{:e}]", b.ymin, b.ymax);
+
+    let data_name = escape(data_path);
+    for item in &scene.items {
+        match item {
+            SceneItem::Path { points, style } | SceneItem::Dots { points, style } => {
+                if !plots.is_empty() {
+                    data.push_str("\n\n");
+                }
+                for (x, y) in points {
+                    let _ = writeln!(data, "{:e} {:e}", x, y);
+                }
+                let how = if matches!(item, SceneItem::Path { .. }) {
+                    format!("with lines lw {}", style.width)
+                } else {
+                    format!("with points pt 7 ps {}", 0.25 * style.width)
+                };
+                plots.push(format!(
+                    "\"{}\" index {} {} lc rgb '{}'",
+                    data_name, plots.len(), how, color_hex(style.color)
+                ));
+            }
+            SceneItem::Text { at, text, style } => {
+                let _ = writeln!(
+                    script,
+                    "set label \"{}\" at {:e},{:e} textcolor rgb '{}'",
+                    escape(text), at.0, at.1, color_hex(style.color)
+                );
+            }
+        }
+    }
+
+    if plots.is_empty() {
+        // Nothing to draw but labels and the frame.
+        let _ = writeln!(script, "plot 1/0");
+    } else {
+        let _ = writeln!(script, "plot {}", plots.join(", \\\n     "));
+    }
+
+    GnuplotOutput { script, data }
+}
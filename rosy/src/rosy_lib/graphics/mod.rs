@@ -0,0 +1,515 @@
+//! # Graphics (GR)
+//!
+//! The `GR` type is an ordered list of drawing primitives, mirroring COSY
+//! INFINITY's graphics objects. Each primitive procedure (`GRMOVE`,
+//! `GRDRAW`, `GRDOT`, `GRCHAR`, `GRCOLR`, `GRWDTH`, `GRPROJ`, `GRZOOM`)
+//! produces a one-element object, and pictures are assembled with `&`:
+//!
+//! ```text
+//! GRMOVE 0 0 0 G1;  GRDRAW 1 1 0 G2;
+//! PIC := G1 & G2;
+//! WRITE 20 PIC;
+//! ```
+//!
+//! ## Output
+//!
+//! Writing a `GR` to unit 6 prints a primitive listing. Writing it to a
+//! file unit renders it according to the extension of the file opened on
+//! that unit — no display or X server is required, so plots can be made on
+//! cluster nodes:
+//!
+//! | Extension | Backend |
+//! |-----------|---------|
+//! | `.svg` | [`svg`] — standalone SVG document |
+//! | `.eps`, `.ps` | [`eps`] — Encapsulated PostScript |
+//! | `.gp`, `.gnuplot`, `.plt` | [`gnuplot`] — script on the unit, data in a sibling `.dat` |
+//! | anything else | the same listing as unit 6 |
+//!
+//! All backends share one [`Scene`]: the primitives are projected to 2D,
+//! pen moves are folded into polylines and the plot window is taken from
+//! the last `GRZOOM` (or the data bounds when there is none).
+
+pub mod eps;
+pub mod gnuplot;
+pub mod svg;
+
+use std::path::Path;
+
+use anyhow::{Result, bail, ensure};
+
+use crate::rosy_lib::core::display::RosyDisplay;
+use crate::rosy_lib::core::file_io::{rosy_unit_path, rosy_write_to_unit};
+
+/// Canvas size used by the SVG and EPS backends, in pixels / points.
+pub const CANVAS_WIDTH: f64 = 640.0;
+pub const CANVAS_HEIGHT: f64 = 480.0;
+/// Blank border between the canvas edge and the plot window.
+pub const CANVAS_MARGIN: f64 = 40.0;
+
+/// Number of entries in the COSY colour table accepted by `GRCOLR`.
+pub const NUM_COLORS: u32 = 10;
+
+/// A single graphics primitive.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GrElement {
+    /// Lift the pen and move it to a point.
+    Move([f64; 3]),
+    /// Draw a line from the pen position to a point.
+    Draw([f64; 3]),
+    /// Mark a point and move the pen there.
+    Dot([f64; 3]),
+    /// Place text at the pen position.
+    Char(String),
+    /// Switch to a colour from the COSY colour table (1–10).
+    Color(u32),
+    /// Set the line width, in points.
+    Width(f64),
+    /// Viewing angles in degrees: azimuth `phi` about Z, elevation `theta`.
+    Proj { phi: f64, theta: f64 },
+    /// Plot window `[xl, xr, yl, yr, zl, zr]`.
+    Zoom([f64; 6]),
+}
+
+/// Graphics object: an ordered list of primitives.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GR {
+    pub elements: Vec<GrElement>,
+}
+
+fn check_point(name: &str, x: f64, y: f64, z: f64) -> Result<[f64; 3]> {
+    ensure!(
+        x.is_finite() && y.is_finite() && z.is_finite(),
+        "{} requires finite coordinates, got ({}, {}, {})", name, x, y, z
+    );
+    Ok([x, y, z])
+}
+
+impl GR {
+    fn single(element: GrElement) -> Self {
+        GR { elements: vec![element] }
+    }
+
+    /// `GRMOVE x y z G`
+    pub fn grmove(x: f64, y: f64, z: f64) -> Result<Self> {
+        Ok(Self::single(GrElement::Move(check_point("GRMOVE", x, y, z)?)))
+    }
+
+    /// `GRDRAW x y z G`
+    pub fn grdraw(x: f64, y: f64, z: f64) -> Result<Self> {
+        Ok(Self::single(GrElement::Draw(check_point("GRDRAW", x, y, z)?)))
+    }
+
+    /// `GRDOT x y z G`
+    pub fn grdot(x: f64, y: f64, z: f64) -> Result<Self> {
+        Ok(Self::single(GrElement::Dot(check_point("GRDOT", x, y, z)?)))
+    }
+
+    /// `GRCHAR text G`
+    pub fn grchar(text: &str) -> Result<Self> {
+        Ok(Self::single(GrElement::Char(text.to_string())))
+    }
+
+    /// `GRCOLR n G` — `n` is rounded to the nearest integer.
+    pub fn grcolr(color: f64) -> Result<Self> {
+        let n = color.round();
+        if !(1.0..=NUM_COLORS as f64).contains(&n) {
+            bail!("GRCOLR colour must be between 1 and {}, got {}", NUM_COLORS, color);
+        }
+        Ok(Self::single(GrElement::Color(n as u32)))
+    }
+
+    /// `GRWDTH w G`
+    pub fn grwdth(width: f64) -> Result<Self> {
+        ensure!(width.is_finite() && width > 0.0, "GRWDTH width must be positive, got {}", width);
+        Ok(Self::single(GrElement::Width(width)))
+    }
+
+    /// `GRPROJ phi theta G`
+    pub fn grproj(phi: f64, theta: f64) -> Result<Self> {
+        ensure!(
+            phi.is_finite() && theta.is_finite(),
+            "GRPROJ requires finite angles, got ({}, {})", phi, theta
+        );
+        Ok(Self::single(GrElement::Proj { phi, theta }))
+    }
+
+    /// `GRZOOM xl xr yl yr zl zr G`
+    pub fn grzoom(xl: f64, xr: f64, yl: f64, yr: f64, zl: f64, zr: f64) -> Result<Self> {
+        let window = [xl, xr, yl, yr, zl, zr];
+        ensure!(
+            window.iter().all(|v| v.is_finite()),
+            "GRZOOM requires finite bounds, got {:?}", window
+        );
+        ensure!(
+            xl < xr && yl < yr && zl <= zr,
+            "GRZOOM bounds must be increasing (xl < xr, yl < yr, zl <= zr), got {:?}", window
+        );
+        Ok(Self::single(GrElement::Zoom(window)))
+    }
+
+    /// Plain-text listing, one primitive per line. Used for unit 6 and `ST`.
+    pub fn listing(&self) -> String {
+        let point = |p: &[f64; 3]| {
+            p.iter().map(|v| v.rosy_display()).collect::<Vec<_>>().join(" ")
+        };
+        let mut out = format!("GR with {} primitive(s)", self.elements.len());
+        for element in &self.elements {
+            let line = match element {
+                GrElement::Move(p) => format!(" MOVE  {}", point(p)),
+                GrElement::Draw(p) => format!(" DRAW  {}", point(p)),
+                GrElement::Dot(p) => format!(" DOT   {}", point(p)),
+                GrElement::Char(text) => format!(" CHAR  '{}'", text),
+                GrElement::Color(n) => format!(" COLR  {}", n),
+                GrElement::Width(w) => format!(" WDTH  {}", w.rosy_display()),
+                GrElement::Proj { phi, theta } => {
+                    format!(" PROJ  {} {}", phi.rosy_display(), theta.rosy_display())
+                }
+                GrElement::Zoom(window) => format!(
+                    " ZOOM  {}",
+                    window.iter().map(|v| v.rosy_display()).collect::<Vec<_>>().join(" ")
+                ),
+            };
+            out.push('\n');
+            out.push_str(line.trim_end());
+        }
+        out
+    }
+}
+
+/// RGB values of the COSY colour table: black, blue, red, yellow, green,
+/// yellow-green, cyan, magenta, navy and background (white).
+pub fn color_rgb(color: u32) -> (u8, u8, u8) {
+    match color {
+        2 => (0, 0, 255),
+        3 => (255, 0, 0),
+        4 => (255, 255, 0),
+        5 => (0, 160, 0),
+        6 => (154, 205, 50),
+        7 => (0, 255, 255),
+        8 => (255, 0, 255),
+        9 => (0, 0, 128),
+        10 => (255, 255, 255),
+        _ => (0, 0, 0),
+    }
+}
+
+/// `#rrggbb` form of a colour table entry.
+pub fn color_hex(color: u32) -> String {
+    let (r, g, b) = color_rgb(color);
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Pen state attached to every scene item.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Style {
+    pub color: u32,
+    pub width: f64,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Style { color: 1, width: 1.0 }
+    }
+}
+
+/// A projected, backend-independent drawing item.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SceneItem {
+    /// Connected line segments (at least two points).
+    Path { points: Vec<(f64, f64)>, style: Style },
+    /// A run of consecutive dots sharing one style.
+    Dots { points: Vec<(f64, f64)>, style: Style },
+    /// Text anchored at its lower-left corner.
+    Text { at: (f64, f64), text: String, style: Style },
+}
+
+/// Plot window in projected 2D coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub xmin: f64,
+    pub xmax: f64,
+    pub ymin: f64,
+    pub ymax: f64,
+}
+
+impl Bounds {
+    fn around(points: impl Iterator<Item = (f64, f64)>) -> Option<Self> {
+        points.fold(None, |acc: Option<Bounds>, (x, y)| {
+            Some(match acc {
+                None => Bounds { xmin: x, xmax: x, ymin: y, ymax: y },
+                Some(b) => Bounds {
+                    xmin: b.xmin.min(x),
+                    xmax: b.xmax.max(x),
+                    ymin: b.ymin.min(y),
+                    ymax: b.ymax.max(y),
+                },
+            })
+        })
+    }
+
+    /// Widen any zero-extent axis so the window can be mapped to a canvas.
+    fn padded(mut self) -> Self {
+        for (lo, hi) in [(&mut self.xmin, &mut self.xmax), (&mut self.ymin, &mut self.ymax)] {
+            if *hi - *lo <= f64::EPSILON * lo.abs().max(hi.abs()).max(1.0) {
+                let pad = if *lo == 0.0 { 1.0 } else { lo.abs() * 0.05 };
+                *lo -= pad;
+                *hi += pad;
+            }
+        }
+        self
+    }
+
+    /// Map a point into canvas coordinates with the origin at the bottom left.
+    pub fn to_canvas(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let w = CANVAS_WIDTH - 2.0 * CANVAS_MARGIN;
+        let h = CANVAS_HEIGHT - 2.0 * CANVAS_MARGIN;
+        (
+            CANVAS_MARGIN + (x - self.xmin) / (self.xmax - self.xmin) * w,
+            CANVAS_MARGIN + (y - self.ymin) / (self.ymax - self.ymin) * h,
+        )
+    }
+}
+
+/// A graphics object flattened for rendering.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scene {
+    pub items: Vec<SceneItem>,
+    pub bounds: Bounds,
+    /// True when the window came from `GRZOOM`, so backends should clip.
+    pub zoomed: bool,
+}
+
+impl Scene {
+    pub fn from_gr(gr: &GR) -> Self {
+        // Projection and zoom apply to the whole picture; the last one wins.
+        let (phi, theta) = gr.elements.iter().rev()
+            .find_map(|e| match e {
+                GrElement::Proj { phi, theta } => Some((*phi, *theta)),
+                _ => None,
+            })
+            .unwrap_or((0.0, 0.0));
+        let zoom = gr.elements.iter().rev().find_map(|e| match e {
+            GrElement::Zoom(window) => Some(*window),
+            _ => None,
+        });
+        let (sp, cp) = phi.to_radians().sin_cos();
+        let (st, ct) = theta.to_radians().sin_cos();
+        let project = |[x, y, z]: [f64; 3]| {
+            let u = x * cp + y * sp;
+            let w = -x * sp + y * cp;
+            (u, w * ct + z * st)
+        };
+
+        let mut items = Vec::new();
+        let mut style = Style::default();
+        let mut pen = [0.0; 3];
+        let mut path: Vec<(f64, f64)> = Vec::new();
+
+        fn flush(items: &mut Vec<SceneItem>, path: &mut Vec<(f64, f64)>, style: Style) {
+            if path.len() > 1 {
+                items.push(SceneItem::Path { points: std::mem::take(path), style });
+            } else {
+                path.clear();
+            }
+        }
+
+        for element in &gr.elements {
+            match element {
+                GrElement::Move(p) => {
+                    flush(&mut items, &mut path, style);
+                    pen = *p;
+                }
+                GrElement::Draw(p) => {
+                    if path.is_empty() {
+                        path.push(project(pen));
+                    }
+                    path.push(project(*p));
+                    pen = *p;
+                }
+                GrElement::Dot(p) => {
+                    flush(&mut items, &mut path, style);
+                    let at = project(*p);
+                    match items.last_mut() {
+                        Some(SceneItem::Dots { points, style: s }) if *s == style => points.push(at),
+                        _ => items.push(SceneItem::Dots { points: vec![at], style }),
+                    }
+                    pen = *p;
+                }
+                GrElement::Char(text) => {
+                    flush(&mut items, &mut path, style);
+                    items.push(SceneItem::Text { at: project(pen), text: text.clone(), style });
+                }
+                GrElement::Color(n) => {
+                    flush(&mut items, &mut path, style);
+                    style.color = *n;
+                }
+                GrElement::Width(w) => {
+                    flush(&mut items, &mut path, style);
+                    style.width = *w;
+                }
+                GrElement::Proj { .. } | GrElement::Zoom(_) => {}
+            }
+        }
+        flush(&mut items, &mut path, style);
+
+        let bounds = match zoom {
+            Some([xl, xr, yl, yr, zl, zr]) => {
+                let corners = (0..8).map(|i| {
+                    project([
+                        if i & 1 == 0 { xl } else { xr },
+                        if i & 2 == 0 { yl } else { yr },
+                        if i & 4 == 0 { zl } else { zr },
+                    ])
+                });
+                Bounds::around(corners)
+            }
+            None => Bounds::around(items.iter().flat_map(|item| match item {
+                SceneItem::Path { points, .. } | SceneItem::Dots { points, .. } => points.clone(),
+                SceneItem::Text { at, .. } => vec![*at],
+            })),
+        }
+        .unwrap_or(Bounds { xmin: 0.0, xmax: 0.0, ymin: 0.0, ymax: 0.0 })
+        .padded();
+
+        Scene { items, bounds, zoomed: zoom.is_some() }
+    }
+}
+
+/// Output formats selected by file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsFormat {
+    Svg,
+    Eps,
+    Gnuplot,
+    Listing,
+}
+
+impl GraphicsFormat {
+    pub fn from_path(path: &str) -> Self {
+        let ext = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        match ext.as_deref() {
+            Some("svg") => GraphicsFormat::Svg,
+            Some("eps") | Some("ps") => GraphicsFormat::Eps,
+            Some("gp") | Some("gnuplot") | Some("plt") => GraphicsFormat::Gnuplot,
+            _ => GraphicsFormat::Listing,
+        }
+    }
+}
+
+/// Write a graphics object to a file unit (WRITE with a GR argument).
+///
+/// The backend is chosen from the extension of the file open on `unit`.
+/// For gnuplot the script goes to the unit and the point data to a file
+/// next to it with the extension replaced by `.dat`.
+pub fn rosy_write_graphics(unit: u64, gr: &GR) -> Result<()> {
+    let path = rosy_unit_path(unit)?;
+    let content = match GraphicsFormat::from_path(&path) {
+        GraphicsFormat::Svg => svg::render(&Scene::from_gr(gr)),
+        GraphicsFormat::Eps => eps::render(&Scene::from_gr(gr)),
+        GraphicsFormat::Gnuplot => {
+            let data_path = Path::new(&path).with_extension("dat");
+            let data_name = data_path.to_string_lossy().into_owned();
+            let plot = gnuplot::render(&Scene::from_gr(gr), &data_name, &path);
+            std::fs::write(&data_path, plot.data).map_err(|e| {
+                anyhow::anyhow!("Failed to write gnuplot data file '{}': {}", data_name, e)
+            })?;
+            plot.script
+        }
+        GraphicsFormat::Listing => gr.listing(),
+    };
+    rosy_write_to_unit(unit, content.trim_end_matches('\n'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square_with_dots() -> Result<GR> {
+        let mut gr = GR::default();
+        for part in [
+            GR::grcolr(3.0)?,
+            GR::grmove(0.0, 0.0, 0.0)?,
+            GR::grdraw(2.0, 0.0, 0.0)?,
+            GR::grdraw(2.0, 1.0, 0.0)?,
+            GR::grdot(1.0, 0.5, 0.0)?,
+            GR::grdot(1.5, 0.5, 0.0)?,
+            GR::grchar("x<y & 'z'")?,
+        ] {
+            gr.elements.extend(part.elements);
+        }
+        Ok(gr)
+    }
+
+    #[test]
+    fn scene_folds_moves_and_draws_into_paths() -> Result<()> {
+        let scene = Scene::from_gr(&square_with_dots()?);
+        let red = Style { color: 3, width: 1.0 };
+        assert_eq!(scene.items, vec![
+            SceneItem::Path { points: vec![(0.0, 0.0), (2.0, 0.0), (2.0, 1.0)], style: red },
+            SceneItem::Dots { points: vec![(1.0, 0.5), (1.5, 0.5)], style: red },
+            SceneItem::Text { at: (1.5, 0.5), text: "x<y & 'z'".to_string(), style: red },
+        ]);
+        assert_eq!(scene.bounds, Bounds { xmin: 0.0, xmax: 2.0, ymin: 0.0, ymax: 1.0 });
+        assert!(!scene.zoomed);
+        Ok(())
+    }
+
+    #[test]
+    fn projection_and_zoom_set_the_window() -> Result<()> {
+        let mut gr = square_with_dots()?;
+        gr.elements.extend(GR::grproj(90.0, 0.0)?.elements);
+        gr.elements.extend(GR::grzoom(-1.0, 1.0, -2.0, 2.0, 0.0, 0.0)?.elements);
+        let scene = Scene::from_gr(&gr);
+        // phi = 90 degrees looks down the Y axis: u = y, v = -x.
+        let b = scene.bounds;
+        assert!((b.xmin + 2.0).abs() < 1e-12 && (b.xmax - 2.0).abs() < 1e-12);
+        assert!((b.ymin + 1.0).abs() < 1e-12 && (b.ymax - 1.0).abs() < 1e-12);
+        assert!(scene.zoomed);
+        Ok(())
+    }
+
+    #[test]
+    fn primitives_reject_bad_arguments() {
+        assert!(GR::grcolr(11.0).is_err());
+        assert!(GR::grwdth(0.0).is_err());
+        assert!(GR::grmove(f64::NAN, 0.0, 0.0).is_err());
+        assert!(GR::grzoom(1.0, 0.0, 0.0, 1.0, 0.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn file_units_render_by_extension() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let gr = square_with_dots()?;
+        let render = |unit: f64, name: &str| -> Result<String> {
+            let path = dir.path().join(name);
+            let path = path.to_str().unwrap();
+            crate::rosy_lib::core::file_io::rosy_openf(unit, path, "UNKNOWN")?;
+            rosy_write_graphics(unit as u64, &gr)?;
+            crate::rosy_lib::core::file_io::rosy_closef(unit)?;
+            Ok(std::fs::read_to_string(path)?)
+        };
+
+        let svg = render(81.0, "plot.svg")?;
+        assert!(svg.starts_with("<?xml") && svg.contains("<polyline") && svg.contains("</svg>"));
+        assert!(svg.contains("x&lt;y &amp; &apos;z&apos;"));
+        assert!(svg.contains("stroke=\"#ff0000\""));
+
+        let eps = render(82.0, "plot.eps")?;
+        assert!(eps.starts_with("%!PS-Adobe-3.0 EPSF-3.0"));
+        assert!(eps.contains("%%BoundingBox: 0 0 640 480") && eps.contains("lineto"));
+        assert!(eps.contains("(x<y & 'z') show"));
+
+        let script = render(83.0, "plot.gp")?;
+        let data = std::fs::read_to_string(dir.path().join("plot.dat"))?;
+        assert!(script.contains("set terminal svg") && script.contains("index 0 with lines"));
+        assert!(script.contains("index 1 with points"));
+        assert_eq!(data.split("\n\n\n").count(), 2);
+
+        let listing = render(84.0, "plot.txt")?;
+        assert_eq!(listing.trim_end(), gr.listing());
+        Ok(())
+    }
+}
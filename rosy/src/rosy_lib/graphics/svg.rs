@@ -0,0 +1,102 @@
+//! SVG backend for [`GR`](super::GR) objects.
+//!
+//! Produces a standalone SVG 1.1 document on a fixed
+//! [`CANVAS_WIDTH`](super::CANVAS_WIDTH) × [`CANVAS_HEIGHT`](super::CANVAS_HEIGHT)
+//! canvas with a frame around the plot window.
+
+use std::fmt::Write;
+
+use super::{Bounds, CANVAS_HEIGHT, CANVAS_MARGIN, CANVAS_WIDTH, Scene, SceneItem, color_hex};
+
+/// Escape text for use in SVG character data and attribute values.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// SVG's y axis points down, so flip the canvas coordinates.
+fn to_svg(bounds: &Bounds, p: (f64, f64)) -> (f64, f64) {
+    let (x, y) = bounds.to_canvas(p);
+    (x, CANVAS_HEIGHT - y)
+}
+
+pub fn render(scene: &Scene) -> String {
+    let w = CANVAS_WIDTH - 2.0 * CANVAS_MARGIN;
+    let h = CANVAS_HEIGHT - 2.0 * CANVAS_MARGIN;
+    let mut out = String::new();
+
+    // Writing to a String cannot fail.
+    let _ = writeln!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">",
+        CANVAS_WIDTH, CANVAS_HEIGHT
+    );
+    let _ = writeln!(
+        out,
+        "<defs><clipPath id=\"window\"><rect x=\"{0}\" y=\"{0}\" width=\"{1}\" height=\"{2}\"/></clipPath></defs>",
+        CANVAS_MARGIN, w, h
+    );
+    let _ = writeln!(out, "<rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>");
+    let _ = writeln!(
+        out,
+        "<rect x=\"{0}\" y=\"{0}\" width=\"{1}\" height=\"{2}\" fill=\"none\" stroke=\"#000000\" stroke-width=\"0.5\"/>",
+        CANVAS_MARGIN, w, h
+    );
+
+    if scene.zoomed {
+        let _ = writeln!(out, "<g clip-path=\"url(#window)\">");
+    } else {
+        let _ = writeln!(out, "<g>");
+    }
+    for item in &scene.items {
+        match item {
+            SceneItem::Path { points, style } => {
+                let coords = points
+                    .iter()
+                    .map(|p| {
+                        let (x, y) = to_svg(&scene.bounds, *p);
+                        format!("{:.3},{:.3}", x, y)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let _ = writeln!(
+                    out,
+                    "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" stroke-linejoin=\"round\"/>",
+                    coords, color_hex(style.color), style.width
+                );
+            }
+            SceneItem::Dots { points, style } => {
+                for p in points {
+                    let (x, y) = to_svg(&scene.bounds, *p);
+                    let _ = writeln!(
+                        out,
+                        "<circle cx=\"{:.3}\" cy=\"{:.3}\" r=\"{}\" fill=\"{}\"/>",
+                        x, y, style.width, color_hex(style.color)
+                    );
+                }
+            }
+            SceneItem::Text { at, text, style } => {
+                let (x, y) = to_svg(&scene.bounds, *at);
+                let _ = writeln!(
+                    out,
+                    "<text x=\"{:.3}\" y=\"{:.3}\" font-family=\"Helvetica, Arial, sans-serif\" font-size=\"12\" fill=\"{}\">{}</text>",
+                    x, y, color_hex(style.color), escape(text)
+                );
+            }
+        }
+    }
+    let _ = writeln!(out, "</g>");
+    let _ = writeln!(out, "</svg>");
+    out
+}
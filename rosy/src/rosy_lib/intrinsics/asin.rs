@@ -1,20 +1,24 @@
 use std::collections::HashMap;
 
 use crate::rosy_lib::{IntrinsicTypeRule, RosyType};
-use crate::rosy_lib::{RE, VE, DA};
+use crate::rosy_lib::{RE, CM, VE, DA, CD};
 
 /// Type registry for ASIN intrinsic function.
 ///
 /// According to COSY INFINITY manual, ASIN supports:
 /// - RE -> RE
+/// - CM -> CM (complex arcsine, principal branch)
 /// - VE -> VE (elementwise)
 /// - DA -> DA (Taylor composition)
+/// - CD -> CD (complex Taylor composition)
 ///
-/// Note: CM is NOT supported for ASIN in COSY.
+/// CM and CD are Rosy extensions; COSY only defines ASIN over real types.
 pub const ASIN_REGISTRY: &[IntrinsicTypeRule] = &[
     IntrinsicTypeRule::new("RE", "RE", "0.5"),
+    IntrinsicTypeRule::new("CM", "CM", "CM(0.5&0.25)"),
     IntrinsicTypeRule::new("VE", "VE", "0.1&0.2&0.3"),
     IntrinsicTypeRule::new("DA", "DA", "DA(1)"),
+    IntrinsicTypeRule::new("CD", "CD", "CD(1)"),
 ];
 
 /// Get the return type of ASIN for a given input type.
@@ -23,8 +27,10 @@ pub fn get_return_type(input: &RosyType) -> Option<RosyType> {
         let mut m = HashMap::new();
         let all = vec![
             (RosyType::RE(), RosyType::RE()),
+            (RosyType::CM(), RosyType::CM()),
             (RosyType::VE(), RosyType::VE()),
             (RosyType::DA(), RosyType::DA()),
+            (RosyType::CD(), RosyType::CD()),
         ];
         for (input_type, result_type) in all {
             m.insert(input_type, result_type);
@@ -49,6 +55,14 @@ impl RosyASIN for RE {
     }
 }
 
+/// ASIN for complex numbers (principal branch)
+impl RosyASIN for CM {
+    type Output = CM;
+    fn rosy_asin(&self) -> anyhow::Result<Self::Output> {
+        Ok(self.asin())
+    }
+}
+
 /// ASIN for vectors (elementwise)
 impl RosyASIN for VE {
     type Output = VE;
@@ -68,6 +82,14 @@ impl RosyASIN for DA {
     }
 }
 
+/// ASIN for CD (complex Taylor composition)
+impl RosyASIN for CD {
+    type Output = CD;
+    fn rosy_asin(&self) -> anyhow::Result<Self::Output> {
+        cd_asin(self)
+    }
+}
+
 /// Compute arcsine of a DA object using Taylor series composition.
 fn da_asin(da: &DA) -> anyhow::Result<DA> {
     use crate::rosy_lib::taylor::DACoefficient;
@@ -110,3 +132,44 @@ fn da_asin(da: &DA) -> anyhow::Result<DA> {
     DA::horner_eval_with_rt(&da_prime, &xf, &rt)
 }
 
+/// Compute arcsine of a CD object using Taylor series composition.
+///
+/// Mirrors `da_asin` over `Complex64`, with the principal branch of the
+/// square root in the first derivative.
+fn cd_asin(cd: &CD) -> anyhow::Result<CD> {
+    use num_complex::Complex64;
+
+    let config = crate::rosy_lib::taylor::get_config()?;
+    let nocut = config.max_order as usize;
+
+    let f0 = cd.constant_part();
+    let cd_prime = cd.make_prime();
+
+    let one_minus_sq = 1.0 - f0 * f0;
+    if nocut >= 1 && one_minus_sq.norm() < 1e-15 {
+        return Err(anyhow::anyhow!("ASIN is not differentiable at x = ±1"));
+    }
+
+    let mut derivs = vec![Complex64::new(0.0, 0.0); nocut + 1];
+    derivs[0] = f0.asin();
+    if nocut >= 1 {
+        derivs[1] = 1.0 / one_minus_sq.sqrt();
+    }
+    for n in 1..nocut {
+        let n_f = n as f64;
+        derivs[n + 1] = ((2.0 * n_f - 1.0) * f0 * derivs[n] + (n_f - 1.0).powi(2) * derivs[n - 1]) / one_minus_sq;
+    }
+
+    // Taylor coefficients c_n = derivs[n] / n!
+    let mut factorial = 1.0;
+    let xf: Vec<Complex64> = derivs
+        .iter()
+        .enumerate()
+        .map(|(n, d)| {
+            if n > 0 { factorial *= n as f64; }
+            d / factorial
+        })
+        .collect();
+
+    CD::horner_eval(&cd_prime, &xf)
+}
@@ -1,20 +1,24 @@
 use std::collections::HashMap;
 
 use crate::rosy_lib::{IntrinsicTypeRule, RosyType};
-use crate::rosy_lib::{RE, VE, DA};
+use crate::rosy_lib::{RE, CM, VE, DA, CD};
 
 /// Type registry for ATAN intrinsic function.
 ///
 /// According to COSY INFINITY manual, ATAN supports:
 /// - RE -> RE
+/// - CM -> CM (complex arctangent)
 /// - VE -> VE (elementwise)
 /// - DA -> DA (Taylor composition)
+/// - CD -> CD (complex Taylor composition)
 ///
-/// Note: CM is NOT supported for ATAN in COSY.
+/// CM and CD are Rosy extensions; COSY defines ATAN over real types only.
 pub const ATAN_REGISTRY: &[IntrinsicTypeRule] = &[
     IntrinsicTypeRule::new("RE", "RE", "1.5"),
+    IntrinsicTypeRule::new("CM", "CM", "CM(0.5&0.25)"),
     IntrinsicTypeRule::new("VE", "VE", "1.5&2.5&3.5"),
     IntrinsicTypeRule::new("DA", "DA", "DA(1)"),
+    IntrinsicTypeRule::new("CD", "CD", "CD(1)"),
 ];
 
 /// Get the return type of ATAN for a given input type.
@@ -23,8 +27,10 @@ pub fn get_return_type(input: &RosyType) -> Option<RosyType> {
         let mut m = HashMap::new();
         let all = vec![
             (RosyType::RE(), RosyType::RE()),
+            (RosyType::CM(), RosyType::CM()),
             (RosyType::VE(), RosyType::VE()),
             (RosyType::DA(), RosyType::DA()),
+            (RosyType::CD(), RosyType::CD()),
         ];
         for (input_type, result_type) in all {
             m.insert(input_type, result_type);
@@ -49,6 +55,14 @@ impl RosyATAN for RE {
     }
 }
 
+/// ATAN for complex numbers
+impl RosyATAN for CM {
+    type Output = CM;
+    fn rosy_atan(&self) -> anyhow::Result<Self::Output> {
+        Ok(self.atan())
+    }
+}
+
 /// ATAN for vectors (elementwise)
 impl RosyATAN for VE {
     type Output = VE;
@@ -67,6 +81,14 @@ impl RosyATAN for DA {
     }
 }
 
+/// ATAN for CD (complex Taylor composition)
+impl RosyATAN for CD {
+    type Output = CD;
+    fn rosy_atan(&self) -> anyhow::Result<Self::Output> {
+        cd_atan(self)
+    }
+}
+
 /// Compute arctangent of a DA object using Taylor series composition.
 ///
 /// Uses the recurrence for atan derived from (1+x²)*f'(x) = 1:
@@ -106,3 +128,44 @@ fn da_atan(da: &DA) -> anyhow::Result<DA> {
     DA::horner_eval_with_rt(&da_prime, &xf, &rt)
 }
 
+/// Compute arctangent of a CD object using Taylor series composition.
+///
+/// Same derivative recurrence as `da_atan`, carried out over `Complex64`;
+/// the expansion point must stay away from the branch points ±i.
+fn cd_atan(cd: &CD) -> anyhow::Result<CD> {
+    use num_complex::Complex64;
+
+    let config = crate::rosy_lib::taylor::get_config()?;
+    let nocut = config.max_order as usize;
+
+    let f0 = cd.constant_part();
+    let cd_prime = cd.make_prime();
+
+    let denom = 1.0 + f0 * f0;
+    if denom.norm() < 1e-15 {
+        return Err(anyhow::anyhow!("ATAN is not differentiable at x = ±i"));
+    }
+
+    let mut derivs = vec![Complex64::new(0.0, 0.0); nocut + 1];
+    derivs[0] = f0.atan();
+    if nocut >= 1 {
+        derivs[1] = 1.0 / denom;
+    }
+    for n in 1..nocut {
+        let n_f = n as f64;
+        derivs[n + 1] = -(2.0 * n_f * f0 * derivs[n] + n_f * (n_f - 1.0) * derivs[n - 1]) / denom;
+    }
+
+    // Taylor coefficients c_n = derivs[n] / n!
+    let mut factorial = 1.0;
+    let xf: Vec<Complex64> = derivs
+        .iter()
+        .enumerate()
+        .map(|(n, d)| {
+            if n > 0 { factorial *= n as f64; }
+            d / factorial
+        })
+        .collect();
+
+    CD::horner_eval(&cd_prime, &xf)
+}
@@ -1,21 +1,26 @@
 use std::collections::HashMap;
 
 use crate::rosy_lib::{IntrinsicTypeRule, RosyType};
-use crate::rosy_lib::{RE, VE, DA};
+use crate::rosy_lib::{RE, CM, VE, DA, CD};
 
 /// Type registry for ISRT intrinsic function (inverse square root, x^(-1/2)).
 ///
 /// According to COSY INFINITY manual, ISRT supports:
 /// - RE -> RE
+/// - CM -> CM (principal branch)
 /// - VE -> VE (elementwise)
 /// - DA -> DA (Taylor composition)
+/// - CD -> CD (complex Taylor composition)
 ///
 /// Note: DA test value uses `4.0 + DA(1)` (constant part = 4, linear = x1)
 /// because ISRT requires a positive constant part for the binomial expansion.
+/// CD only needs a nonzero constant part (principal branch).
 pub const ISRT_REGISTRY: &[IntrinsicTypeRule] = &[
     IntrinsicTypeRule::new("RE", "RE", "4.0"),
+    IntrinsicTypeRule::new("CM", "CM", "CM(3.0&4.0)"),
     IntrinsicTypeRule::new("VE", "VE", "4.0&9.0&16.0"),
     IntrinsicTypeRule::new("DA", "DA", "4.0 + DA(1)"),
+    IntrinsicTypeRule::new("CD", "CD", "CM(3&4)+DA(1)"),
 ];
 
 /// Get the return type of ISRT for a given input type.
@@ -24,8 +29,10 @@ pub fn get_return_type(input: &RosyType) -> Option<RosyType> {
         let mut m = HashMap::new();
         let all = vec![
             (RosyType::RE(), RosyType::RE()),
+            (RosyType::CM(), RosyType::CM()),
             (RosyType::VE(), RosyType::VE()),
             (RosyType::DA(), RosyType::DA()),
+            (RosyType::CD(), RosyType::CD()),
         ];
         for (input_type, result_type) in all {
             m.insert(input_type, result_type);
@@ -50,6 +57,14 @@ impl RosyISRT for RE {
     }
 }
 
+/// ISRT for complex numbers (principal branch of the square root)
+impl RosyISRT for CM {
+    type Output = CM;
+    fn rosy_isrt(&self) -> anyhow::Result<Self::Output> {
+        Ok(1.0 / self.sqrt())
+    }
+}
+
 /// ISRT for vectors (elementwise)
 impl RosyISRT for VE {
     type Output = VE;
@@ -66,6 +81,14 @@ impl RosyISRT for DA {
     }
 }
 
+/// ISRT for CD (complex Taylor composition via binomial series)
+impl RosyISRT for CD {
+    type Output = CD;
+    fn rosy_isrt(&self) -> anyhow::Result<Self::Output> {
+        cd_isrt(self)
+    }
+}
+
 /// Compute inverse square root of a DA object using binomial series.
 ///
 /// Uses: f^alpha where alpha = -0.5
@@ -100,3 +123,31 @@ fn da_isrt(da: &DA) -> anyhow::Result<DA> {
     Ok(result)
 }
 
+/// Compute inverse square root of a CD object using binomial series.
+///
+/// f^(-1/2) = f0^(-1/2) * (1 + u)^(-1/2) with the principal branch for f0^(-1/2).
+fn cd_isrt(cd: &CD) -> anyhow::Result<CD> {
+    use num_complex::Complex64;
+
+    let config = crate::rosy_lib::taylor::get_config()?;
+    let nocut = config.max_order as usize;
+
+    let f0 = cd.constant_part();
+    anyhow::ensure!(f0.norm() != 0.0, "ISRT: constant part of CD must be non-zero");
+
+    let alpha = -0.5_f64;
+    let cd_prime = cd.make_prime();
+    let cd_delta = (&cd_prime * CD::from_coeff(1.0 / f0))?;
+
+    // Binomial coefficients C(alpha, n) via recurrence
+    let mut xf = Vec::with_capacity(nocut + 1);
+    xf.push(Complex64::new(1.0, 0.0));
+    let mut binom_coeff = alpha;
+    for n in 1..=nocut {
+        xf.push(Complex64::new(binom_coeff, 0.0));
+        binom_coeff *= (alpha - n as f64) / (n as f64 + 1.0);
+    }
+
+    let result = CD::horner_eval(&cd_delta, &xf)?;
+    &result * CD::from_coeff(1.0 / f0.sqrt())
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::rosy_lib::{IntrinsicTypeRule, RosyType};
-use crate::rosy_lib::{RE, ST, LO, CM, VE, DA, CD};
+use crate::rosy_lib::{RE, ST, LO, CM, VE, DA, CD, GR};
 
 /// Type registry for LENGTH intrinsic function.
 /// 
@@ -13,7 +13,7 @@ use crate::rosy_lib::{RE, ST, LO, CM, VE, DA, CD};
 /// - VE -> RE
 /// - DA -> RE
 /// - CD -> RE
-/// - GR -> RE (not implemented yet)
+/// - GR -> RE
 pub const LENGTH_REGISTRY: &[IntrinsicTypeRule] = &[
     IntrinsicTypeRule::new("RE", "RE", "1.5"),
     IntrinsicTypeRule::new("ST", "RE", "\"Hello\""),
@@ -22,6 +22,7 @@ pub const LENGTH_REGISTRY: &[IntrinsicTypeRule] = &[
     IntrinsicTypeRule::new("VE", "RE", "1.5&2.5&3.5"),
     IntrinsicTypeRule::new("DA", "RE", "1.5+DA(1)"),
     IntrinsicTypeRule::new("CD", "RE", "CM(1.5&2.5)+CD(1)"),
+    IntrinsicTypeRule::new("GR", "RE", "GR(1)&GR(2)"),
 ];
 
 
@@ -37,6 +38,7 @@ pub fn get_return_type(input: &RosyType) -> Option<RosyType> {
             (RosyType::VE(), RosyType::RE()),
             (RosyType::DA(), RosyType::RE()),
             (RosyType::CD(), RosyType::RE()),
+            (RosyType::GR(), RosyType::RE()),
         ];
         for (input_type, result_type) in all {
             m.insert(input_type, result_type);
@@ -103,4 +105,11 @@ impl RosyLENGTH for CD {
         // Each complex coefficient = 2 f64 values = 2 blocks (16 bytes)
         self.num_terms() as f64 * 2.0
     }
+}
+
+/// LENGTH for GR - number of graphics primitives
+impl RosyLENGTH for GR {
+    fn rosy_length(&self) -> RE {
+        self.elements.len() as f64
+    }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::rosy_lib::{IntrinsicTypeRule, RosyType};
-use crate::rosy_lib::{RE, CM, VE, DA};
+use crate::rosy_lib::{RE, CM, VE, DA, CD};
 
 /// Type registry for LOG intrinsic function.
 ///
@@ -10,15 +10,18 @@ use crate::rosy_lib::{RE, CM, VE, DA};
 /// - CM -> CM
 /// - VE -> VE (elementwise)
 /// - DA -> DA (Taylor composition)
+/// - CD -> CD (complex Taylor composition)
 ///
 /// Note: DA test value uses DA(1) + 1.0 (= 1 + x) instead of DA(1) (= x)
 /// because LOG requires a non-zero constant part. DA(1) has constant part 0,
-/// which would cause a domain error (ln(0) is undefined).
+/// which would cause a domain error (ln(0) is undefined). The CD test value
+/// likewise starts from a nonzero complex constant.
 pub const LOG_REGISTRY: &[IntrinsicTypeRule] = &[
     IntrinsicTypeRule::new("RE", "RE", "1.5"),
     IntrinsicTypeRule::new("CM", "CM", "CM(1.5&2.5)"),
     IntrinsicTypeRule::new("VE", "VE", "1.5&2.5&3.5"),
     IntrinsicTypeRule::new("DA", "DA", "DA(1) + 1.0"),
+    IntrinsicTypeRule::new("CD", "CD", "CM(1&1)+DA(1)"),
 ];
 
 /// Get the return type of LOG for a given input type.
@@ -30,6 +33,7 @@ pub fn get_return_type(input: &RosyType) -> Option<RosyType> {
             (RosyType::CM(), RosyType::CM()),
             (RosyType::VE(), RosyType::VE()),
             (RosyType::DA(), RosyType::DA()),
+            (RosyType::CD(), RosyType::CD()),
         ];
         for (input_type, result_type) in all {
             m.insert(input_type, result_type);
@@ -81,6 +85,14 @@ impl RosyLOG for DA {
     }
 }
 
+/// LOG for CD (complex Taylor composition)
+impl RosyLOG for CD {
+    type Output = CD;
+    fn rosy_log(&self) -> anyhow::Result<Self::Output> {
+        cd_log(self)
+    }
+}
+
 fn da_log(da: &DA) -> anyhow::Result<DA> {
     let rt = crate::rosy_lib::taylor::get_runtime()?;
     let nocut = rt.config.max_order as usize;
@@ -106,3 +118,27 @@ fn da_log(da: &DA) -> anyhow::Result<DA> {
     DA::horner_eval_with_rt(&u, &xf, &rt)
 }
 
+/// Complex counterpart of `da_log`; ln(f₀) is the principal logarithm.
+fn cd_log(cd: &CD) -> anyhow::Result<CD> {
+    use num_complex::Complex64;
+
+    let config = crate::rosy_lib::taylor::get_config()?;
+    let nocut = config.max_order as usize;
+
+    let f0 = cd.constant_part();
+    anyhow::ensure!(f0.norm() != 0.0, "LOG: constant part of CD argument must be non-zero");
+
+    let cd_prime = cd.make_prime();
+
+    // u = δf / f₀
+    let u = (&cd_prime * CD::from_coeff(1.0 / f0))?;
+
+    let mut xf = Vec::with_capacity(nocut + 1);
+    xf.push(f0.ln());
+    for n in 1..=nocut {
+        let sign = if n % 2 == 1 { 1.0 } else { -1.0 };
+        xf.push(Complex64::new(sign / (n as f64), 0.0));
+    }
+
+    CD::horner_eval(&u, &xf)
+}
@@ -103,3 +103,54 @@ impl IntrinsicTypeRule {
         Self { input, result, test_val }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rosy_lib::taylor::{cleanup_taylor, init_taylor};
+    use crate::rosy_lib::{CD, DA};
+    use num_complex::Complex64;
+    use serial_test::serial;
+
+    fn assert_cd_close(lhs: &CD, rhs: &CD, what: &str) {
+        for (idx, (l, r)) in lhs.coeffs.iter().zip(&rhs.coeffs).enumerate() {
+            assert!((l - r).norm() < 1e-10, "{what}: coefficient {idx} differs ({l} vs {r})");
+        }
+    }
+
+    /// CD inverse pairs must compose back to the identity, and real-valued CD
+    /// arguments must agree with the DA implementation.
+    #[test]
+    #[serial]
+    fn complex_da_intrinsics_round_trip() -> anyhow::Result<()> {
+        cleanup_taylor();
+        init_taylor(6, 2)?;
+
+        let dx = CD::from_da(&DA::variable(1)?);
+        let dy = CD::from_da(&DA::variable(2)?);
+        let z = (&(&dx + &(&dy * Complex64::new(0.5, -0.25))?)? + Complex64::new(0.3, 0.2))?;
+
+        assert_cd_close(&z.rosy_tan()?.rosy_atan()?, &z, "ATAN(TAN(z))");
+        assert_cd_close(&z.rosy_asin()?.rosy_sin()?, &z, "SIN(ASIN(z))");
+        assert_cd_close(&z.rosy_acos()?.rosy_cos()?, &z, "COS(ACOS(z))");
+        assert_cd_close(&z.rosy_log()?.rosy_exp()?, &z, "EXP(LOG(z))");
+        assert_cd_close(&z.rosy_sqrt()?.rosy_sqr()?, &z, "SQR(SQRT(z))");
+
+        let one = CD::from_coeff(Complex64::new(1.0, 0.0));
+        let isrt_sq_z = (&z.rosy_isrt()?.rosy_sqr()? * &z)?;
+        assert_cd_close(&isrt_sq_z, &one, "ISRT(z)^2 * z");
+        let hyperbolic = (&z.rosy_cosh()?.rosy_sqr()? - &z.rosy_sinh()?.rosy_sqr()?)?;
+        assert_cd_close(&hyperbolic, &one, "COSH(z)^2 - SINH(z)^2");
+        let tanh_cosh = (&z.rosy_tanh()? * &z.rosy_cosh()?)?;
+        assert_cd_close(&tanh_cosh, &z.rosy_sinh()?, "TANH(z) * COSH(z)");
+
+        let x = (&DA::variable(1)? + 0.4)?;
+        let real = CD::from_da(&x);
+        assert_cd_close(&real.rosy_atan()?, &CD::from_da(&x.rosy_atan()?), "ATAN on real CD");
+        assert_cd_close(&real.rosy_asin()?, &CD::from_da(&x.rosy_asin()?), "ASIN on real CD");
+        assert_cd_close(&real.rosy_isrt()?, &CD::from_da(&x.rosy_isrt()?), "ISRT on real CD");
+
+        cleanup_taylor();
+        Ok(())
+    }
+}
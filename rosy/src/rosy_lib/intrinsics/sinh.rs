@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::rosy_lib::{IntrinsicTypeRule, RosyType};
-use crate::rosy_lib::{RE, CM, VE, DA};
+use crate::rosy_lib::{RE, CM, VE, DA, CD};
 
 /// Type registry for SINH intrinsic function.
 ///
@@ -10,11 +10,13 @@ use crate::rosy_lib::{RE, CM, VE, DA};
 /// - CM -> CM (complex hyperbolic sine)
 /// - VE -> VE (elementwise)
 /// - DA -> DA (Taylor composition)
+/// - CD -> CD (complex Taylor composition)
 pub const SINH_REGISTRY: &[IntrinsicTypeRule] = &[
     IntrinsicTypeRule::new("RE", "RE", "1.5"),
     IntrinsicTypeRule::new("CM", "CM", "CM(1.5&2.5)"),
     IntrinsicTypeRule::new("VE", "VE", "1.5&2.5&3.5"),
     IntrinsicTypeRule::new("DA", "DA", "DA(1)"),
+    IntrinsicTypeRule::new("CD", "CD", "CD(1)"),
 ];
 
 /// Get the return type of SINH for a given input type.
@@ -26,6 +28,7 @@ pub fn get_return_type(input: &RosyType) -> Option<RosyType> {
             (RosyType::CM(), RosyType::CM()),
             (RosyType::VE(), RosyType::VE()),
             (RosyType::DA(), RosyType::DA()),
+            (RosyType::CD(), RosyType::CD()),
         ];
         for (input_type, result_type) in all {
             m.insert(input_type, result_type);
@@ -74,6 +77,14 @@ impl RosySINH for DA {
     }
 }
 
+/// SINH for CD (complex Taylor composition)
+impl RosySINH for CD {
+    type Output = CD;
+    fn rosy_sinh(&self) -> anyhow::Result<Self::Output> {
+        cd_sinh(self)
+    }
+}
+
 /// Compute hyperbolic sine of a DA object using Horner's method.
 ///
 /// `c_n = [sinh_f0, cosh_f0][n%2] / n!`
@@ -95,3 +106,20 @@ fn da_sinh(da: &DA) -> anyhow::Result<DA> {
     DA::horner_eval_with_rt(&da_prime, &xf, &rt)
 }
 
+/// Compute hyperbolic sine of a CD object using Horner's method.
+fn cd_sinh(cd: &CD) -> anyhow::Result<CD> {
+    let config = crate::rosy_lib::taylor::get_config()?;
+    let nocut = config.max_order as usize;
+
+    let f0 = cd.constant_part();
+    let cd_prime = cd.make_prime();
+
+    let mut xf = Vec::with_capacity(nocut + 1);
+    xf.push(f0.sinh());
+    if nocut >= 1 { xf.push(f0.cosh()); }
+    for i in 2..=nocut {
+        xf.push(xf[i - 2] / ((i * (i - 1)) as f64));
+    }
+
+    CD::horner_eval(&cd_prime, &xf)
+}
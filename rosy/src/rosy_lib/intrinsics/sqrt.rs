@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::rosy_lib::{IntrinsicTypeRule, RosyType};
-use crate::rosy_lib::{RE, CM, VE, DA};
+use crate::rosy_lib::{RE, CM, VE, DA, CD};
 
 /// Type registry for SQRT intrinsic function.
 ///
@@ -10,14 +10,17 @@ use crate::rosy_lib::{RE, CM, VE, DA};
 /// - CM -> CM
 /// - VE -> VE (elementwise)
 /// - DA -> DA (Taylor composition)
+/// - CD -> CD (complex Taylor composition)
 ///
 /// Note: DA test value uses EXP(DA(1)) to ensure a positive constant part,
-/// which is required for the binomial series expansion of sqrt.
+/// which is required for the binomial series expansion of sqrt. For CD any
+/// nonzero complex constant part works, taking the principal branch.
 pub const SQRT_REGISTRY: &[IntrinsicTypeRule] = &[
     IntrinsicTypeRule::new("RE", "RE", "4.0"),
     IntrinsicTypeRule::new("CM", "CM", "CM(3.0&4.0)"),
     IntrinsicTypeRule::new("VE", "VE", "1.0&4.0&9.0"),
     IntrinsicTypeRule::new("DA", "DA", "EXP(DA(1))"),
+    IntrinsicTypeRule::new("CD", "CD", "CM(3&4)+DA(1)"),
 ];
 
 /// Get the return type of SQRT for a given input type.
@@ -29,6 +32,7 @@ pub fn get_return_type(input: &RosyType) -> Option<RosyType> {
             (RosyType::CM(), RosyType::CM()),
             (RosyType::VE(), RosyType::VE()),
             (RosyType::DA(), RosyType::DA()),
+            (RosyType::CD(), RosyType::CD()),
         ];
         for (input_type, result_type) in all {
             m.insert(input_type, result_type);
@@ -77,6 +81,14 @@ impl RosySQRT for DA {
     }
 }
 
+/// SQRT for CD (complex Taylor composition via binomial series)
+impl RosySQRT for CD {
+    type Output = CD;
+    fn rosy_sqrt(&self) -> anyhow::Result<Self::Output> {
+        cd_sqrt(self)
+    }
+}
+
 /// Compute square root of a DA object using binomial series expansion.
 ///
 /// Uses: sqrt(f) = sqrt(f0) * sqrt(1 + u)  where u = (f - f0) / f0
@@ -111,3 +123,31 @@ fn da_sqrt(da: &DA) -> anyhow::Result<DA> {
     Ok(result)
 }
 
+/// Compute square root of a CD object using binomial series expansion.
+///
+/// Same expansion as `da_sqrt`, scaled by the principal square root of f₀.
+/// Only f₀ = 0 is rejected; unlike the real case a negative constant part is fine.
+fn cd_sqrt(cd: &CD) -> anyhow::Result<CD> {
+    use num_complex::Complex64;
+
+    let config = crate::rosy_lib::taylor::get_config()?;
+    let nocut = config.max_order as usize;
+
+    let f0 = cd.constant_part();
+    anyhow::ensure!(f0.norm() != 0.0, "SQRT: constant part of CD must be non-zero");
+
+    let cd_prime = cd.make_prime();
+    let cd_delta = (&cd_prime * CD::from_coeff(1.0 / f0))?;
+
+    // Binomial coefficients C(1/2, n) via recurrence
+    let mut xf = Vec::with_capacity(nocut + 1);
+    xf.push(Complex64::new(1.0, 0.0));
+    let mut binom_coeff = 0.5_f64;
+    for n in 1..=nocut {
+        xf.push(Complex64::new(binom_coeff, 0.0));
+        binom_coeff *= (0.5 - n as f64) / (n as f64 + 1.0);
+    }
+
+    let result = CD::horner_eval(&cd_delta, &xf)?;
+    &result * CD::from_coeff(f0.sqrt())
+}
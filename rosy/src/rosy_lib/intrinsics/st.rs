@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::rosy_lib::RosyType;
-use crate::rosy_lib::{RE, CM, VE, LO, ST, DA, CD};
+use crate::rosy_lib::{RE, CM, VE, LO, ST, DA, CD, GR};
 use crate::rosy_lib::core::display::RosyDisplay;
 
 pub fn get_return_type ( lhs: &RosyType ) -> Option<RosyType> {
@@ -15,6 +15,7 @@ pub fn get_return_type ( lhs: &RosyType ) -> Option<RosyType> {
             (RosyType::VE(), RosyType::ST()),
             (RosyType::DA(), RosyType::ST()),
             (RosyType::CD(), RosyType::ST()),
+            (RosyType::GR(), RosyType::ST()),
         );
         for (left, result) in all {
             m.insert(left, result);
@@ -77,4 +78,11 @@ impl RosyST for &CD {
     fn rosy_to_string(self) -> String {
         self.rosy_display()
     }
+}
+
+/// Convert graphics objects to their primitive listing
+impl RosyST for &GR {
+    fn rosy_to_string(self) -> String {
+        self.listing()
+    }
 }
\ No newline at end of file
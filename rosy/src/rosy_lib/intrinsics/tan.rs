@@ -1,20 +1,24 @@
 use std::collections::HashMap;
 
 use crate::rosy_lib::{IntrinsicTypeRule, RosyType};
-use crate::rosy_lib::{RE, VE, DA, CD};
+use crate::rosy_lib::{RE, CM, VE, DA, CD};
 
 /// Type registry for TAN intrinsic function.
 /// 
 /// According to COSY INFINITY manual, TAN supports:
 /// - RE -> RE
+/// - CM -> CM (complex tangent)
 /// - VE -> VE (elementwise)
 /// - DA -> DA (Taylor composition)
-/// 
-/// Note: CM is NOT supported for TAN in COSY.
+/// - CD -> CD (complex Taylor composition)
+///
+/// CM and CD go beyond COSY, which only defines TAN over real types.
 pub const TAN_REGISTRY: &[IntrinsicTypeRule] = &[
     IntrinsicTypeRule::new("RE", "RE", "1.5"),
+    IntrinsicTypeRule::new("CM", "CM", "CM(0.5&0.25)"),
     IntrinsicTypeRule::new("VE", "VE", "1.5&2.5&3.5"),
     IntrinsicTypeRule::new("DA", "DA", "DA(1)"),
+    IntrinsicTypeRule::new("CD", "CD", "CD(1)"),
 ];
 
 /// Get the return type of TAN for a given input type.
@@ -23,8 +27,10 @@ pub fn get_return_type(input: &RosyType) -> Option<RosyType> {
         let mut m = HashMap::new();
         let all = vec![
             (RosyType::RE(), RosyType::RE()),
+            (RosyType::CM(), RosyType::CM()),
             (RosyType::VE(), RosyType::VE()),
             (RosyType::DA(), RosyType::DA()),
+            (RosyType::CD(), RosyType::CD()),
         ];
         for (input_type, result_type) in all {
             m.insert(input_type, result_type);
@@ -49,6 +55,14 @@ impl RosyTAN for RE {
     }
 }
 
+/// TAN for complex numbers
+impl RosyTAN for CM {
+    type Output = CM;
+    fn rosy_tan(&self) -> anyhow::Result<Self::Output> {
+        Ok(self.tan())
+    }
+}
+
 /// TAN for vectors (elementwise)
 impl RosyTAN for VE {
     type Output = VE;
@@ -72,3 +86,18 @@ impl RosyTAN for DA {
     }
 }
 
+/// TAN for CD (complex Taylor composition)
+/// Uses: tan(f) = sin(f) / cos(f)
+impl RosyTAN for CD {
+    type Output = CD;
+    fn rosy_tan(&self) -> anyhow::Result<Self::Output> {
+        use crate::rosy_lib::intrinsics::sin::RosySIN;
+        use crate::rosy_lib::intrinsics::cos::RosyCOS;
+
+        let sin_f = self.rosy_sin()?;
+        let cos_f = self.rosy_cos()?;
+
+        &sin_f / &cos_f
+    }
+}
+
@@ -1,20 +1,24 @@
 use std::collections::HashMap;
 
 use crate::rosy_lib::{IntrinsicTypeRule, RosyType};
-use crate::rosy_lib::{RE, VE, DA};
+use crate::rosy_lib::{RE, CM, VE, DA, CD};
 
 /// Type registry for TANH intrinsic function.
 ///
 /// According to COSY INFINITY manual, TANH supports:
 /// - RE -> RE
+/// - CM -> CM (complex hyperbolic tangent)
 /// - VE -> VE (elementwise)
 /// - DA -> DA (Taylor composition)
+/// - CD -> CD (complex Taylor composition)
 ///
-/// Note: CM is NOT supported for TANH in COSY.
+/// CM and CD go beyond COSY, which only defines TANH over real types.
 pub const TANH_REGISTRY: &[IntrinsicTypeRule] = &[
     IntrinsicTypeRule::new("RE", "RE", "1.5"),
+    IntrinsicTypeRule::new("CM", "CM", "CM(0.5&0.25)"),
     IntrinsicTypeRule::new("VE", "VE", "1.5&2.5&3.5"),
     IntrinsicTypeRule::new("DA", "DA", "DA(1)"),
+    IntrinsicTypeRule::new("CD", "CD", "CD(1)"),
 ];
 
 /// Get the return type of TANH for a given input type.
@@ -23,8 +27,10 @@ pub fn get_return_type(input: &RosyType) -> Option<RosyType> {
         let mut m = HashMap::new();
         let all = vec![
             (RosyType::RE(), RosyType::RE()),
+            (RosyType::CM(), RosyType::CM()),
             (RosyType::VE(), RosyType::VE()),
             (RosyType::DA(), RosyType::DA()),
+            (RosyType::CD(), RosyType::CD()),
         ];
         for (input_type, result_type) in all {
             m.insert(input_type, result_type);
@@ -49,6 +55,14 @@ impl RosyTANH for RE {
     }
 }
 
+/// TANH for complex numbers
+impl RosyTANH for CM {
+    type Output = CM;
+    fn rosy_tanh(&self) -> anyhow::Result<Self::Output> {
+        Ok(self.tanh())
+    }
+}
+
 /// TANH for vectors (elementwise)
 impl RosyTANH for VE {
     type Output = VE;
@@ -72,3 +86,18 @@ impl RosyTANH for DA {
     }
 }
 
+/// TANH for CD (complex Taylor composition)
+/// Uses: tanh(f) = sinh(f) / cosh(f)
+impl RosyTANH for CD {
+    type Output = CD;
+    fn rosy_tanh(&self) -> anyhow::Result<Self::Output> {
+        use crate::rosy_lib::intrinsics::sinh::RosySINH;
+        use crate::rosy_lib::intrinsics::cosh::RosyCOSH;
+
+        let sinh_f = self.rosy_sinh()?;
+        let cosh_f = self.rosy_cosh()?;
+
+        &sinh_f / &cosh_f
+    }
+}
+
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::rosy_lib::{IntrinsicTypeRule, RosyType};
-use crate::rosy_lib::{RE, CM, ST, LO, VE, DA, CD};
+use crate::rosy_lib::{RE, CM, ST, LO, VE, DA, CD, GR};
 
 /// Type registry for TYPE intrinsic function.
 ///
@@ -15,6 +15,7 @@ pub const TYPE_REGISTRY: &[IntrinsicTypeRule] = &[
     IntrinsicTypeRule::new("VE", "RE", "1.5&2.5&3.5"),
     IntrinsicTypeRule::new("DA", "RE", "DA(1)"),
     IntrinsicTypeRule::new("CD", "RE", "CD(1)"),
+    IntrinsicTypeRule::new("GR", "RE", "GR(1)"),
 ];
 
 /// Get the return type of TYPE for a given input type.
@@ -30,6 +31,7 @@ pub fn get_return_type(input: &RosyType) -> Option<RosyType> {
             (RosyType::LO(), RosyType::RE()),
             (RosyType::VE(), RosyType::RE()),
             (RosyType::DA(), RosyType::RE()),
+            (RosyType::GR(), RosyType::RE()),
         ];
         for (input_type, result_type) in all {
             m.insert(input_type, result_type);
@@ -94,10 +96,17 @@ impl RosyTYPE for CD {
     }
 }
 
+/// TYPE for graphics - code 8
+impl RosyTYPE for GR {
+    fn rosy_type(&self) -> anyhow::Result<RE> {
+        Ok(8.0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::RosyTYPE;
-    use crate::rosy_lib::{CM, CD, DA, LO, RE, ST, VE};
+    use crate::rosy_lib::{CM, CD, DA, GR, LO, RE, ST, VE};
 
     #[test]
     fn type_codes_match_cosy_order_for_supported_types() -> anyhow::Result<()> {
@@ -119,6 +128,7 @@ mod tests {
         assert_eq!(ve.rosy_type()?, 5.0);
         assert_eq!(da.rosy_type()?, 6.0);
         assert_eq!(cd.rosy_type()?, 7.0);
+        assert_eq!(GR::default().rosy_type()?, 8.0);
 
         crate::rosy_lib::taylor::cleanup_taylor();
         Ok(())
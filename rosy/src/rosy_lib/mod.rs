@@ -15,16 +15,20 @@
 //! | `VE` | `Vec<f64>` | Vector of reals |
 //! | `DA` | [`taylor::DA`] | Differential Algebra (Taylor series) |
 //! | `CD` | [`taylor::CD`] | Complex Differential Algebra |
+//! | `GR` | [`graphics::GR`] | Graphics object (list of drawing primitives) |
 //!
 //! ## Sub-modules
 //!
 //! | Module | Contents |
 //! |--------|----------|
-//! | [`operators`] | Binary operator dispatch (add, sub, mult, div, etc.) |
+//! | [`operators`] | Binary operator dispatch (add, sub, mult, div, etc.) and numeric widening |
 //! | [`intrinsics`] | Built-in math functions (sin, sqr, exp, etc.) |
 //! | [`core`] | Core I/O, file management, concatenation |
 //! | [`taylor`] | DA/CD Taylor series implementation |
+//! | [`graphics`] | GR type and its SVG / EPS / gnuplot backends |
 //! | `mpi` | MPI parallel context |
+//! | [`ploop`] | PLOOP communication standards, shared by the parallel backends |
+//! | [`threads`] | Thread-pool PLOOP backend (`--threads`) |
 //! | [`optimizer`] | FIT loop optimization algorithms |
 
 pub mod operators;
@@ -32,7 +36,10 @@ pub mod intrinsics;
 pub mod core;
 #[cfg(feature = "mpi")]
 pub mod mpi;
+pub mod ploop;
+pub mod threads;
 pub mod taylor;
+pub mod graphics;
 pub mod optimizer;
 
 pub use operators::*;
@@ -40,8 +47,10 @@ pub use intrinsics::*;
 pub use core::*;
 #[cfg(feature = "mpi")]
 pub use mpi::*;
+pub use threads::RosyThreadContext;
 
 pub use taylor::{DA, CD};
+pub use graphics::GR;
 /// Immutable 1-based index. Returns `&T`.
 /// Rounds the float index to nearest integer (matching COSY INFINITY's NINT),
 /// then validates bounds with a 1-based error message.
@@ -95,6 +104,7 @@ pub enum RosyBaseType {
     VE,
     DA,
     CD,
+    GR,
 }
 impl std::fmt::Display for RosyBaseType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -106,6 +116,7 @@ impl std::fmt::Display for RosyBaseType {
             RosyBaseType::VE => write!(f, "VE"),
             RosyBaseType::DA => write!(f, "DA"),
             RosyBaseType::CD => write!(f, "CD"),
+            RosyBaseType::GR => write!(f, "GR"),
         }
     }
 }
@@ -175,6 +186,13 @@ impl RosyType {
             dimensions: 0
         }
     }
+    #[allow(non_snake_case)]
+    pub fn GR ( ) -> Self {
+        RosyType {
+            base_type: RosyBaseType::GR,
+            dimensions: 0
+        }
+    }
 
     /// Returns true if this type implements Copy in Rust (cheap to duplicate).
     /// RE (f64), LO (bool), CM (Complex64) are Copy at dimension 0.
@@ -195,6 +213,7 @@ impl RosyType {
             RosyBaseType::VE => "Vec<f64>",
             RosyBaseType::DA => "DA",
             RosyBaseType::CD => "CD",
+            RosyBaseType::GR => "GR",
         }.to_string();
 
         if self.dimensions == 0 {
@@ -219,6 +238,7 @@ impl TryFrom<&str> for RosyBaseType {
             "VE" => Ok(RosyBaseType::VE),
             "DA" => Ok(RosyBaseType::DA),
             "CD" => Ok(RosyBaseType::CD),
+            "GR" => Ok(RosyBaseType::GR),
             _ => Err(anyhow::anyhow!("Can't convert {} to a Rosy type", value)),
         }
     }
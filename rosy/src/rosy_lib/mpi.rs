@@ -1,8 +1,12 @@
 use mpi::{traits::*, topology::SimpleCommunicator, environment::Universe};
 use bincode::{Encode, Decode, config::Configuration};
-use anyhow::{Result, Context, ensure, bail};
+use bincode::{enc::Encoder, de::Decoder, error::{EncodeError, DecodeError}};
+use anyhow::{Result, Context, ensure};
+use rustc_hash::FxHashMap;
 
-use crate::rosy_lib::RE;
+use crate::rosy_lib::{RE, DA};
+use crate::rosy_lib::ploop::{self, Layout, Reducible, Standard};
+use crate::rosy_lib::taylor::{Monomial, MAX_VARS};
 
 
 pub struct RosyMPIContext {
@@ -26,16 +30,30 @@ impl RosyMPIContext {
         })
     }
     // Coordinates a value array between all different processes
-    //  according to the specified communication standard.
-    pub fn coordinate<T: Encode + Decode<()> + std::fmt::Debug + Default> (
+    //  according to the specified communication standard
+    //  (see `rosy_lib::ploop` for the standards).
+    pub fn coordinate<T: Encode + Decode<()> + std::fmt::Debug + Default + Clone + Reducible> (
         &self,
 
         value: &mut Vec<T>,
         communication_standard: u8,
         num_groups: &mut RE
     ) -> Result<()> {
-        match communication_standard {
-            1 => {
+        let standard = Standard::from_code(communication_standard)?;
+        let layout = Layout::new(self.size, self.rank, *num_groups)?;
+        let group_num = layout.group_num() as usize;
+        let num_groups = layout.num_groups as usize;
+
+        // The output array must have at least `num_groups` slots —
+        // one per group's contribution. The user's array may have
+        // been sized from a variable (e.g. `(RE NP) X`) before
+        // `PNPRO NP` ran, in which case it's still empty here.
+        if value.len() < num_groups {
+            value.resize_with(num_groups, T::default);
+        }
+
+        match standard {
+            Standard::AllToAll => {
                 // In this standard, each process sends to all other processes
                 //  in its group.
                 //
@@ -46,27 +64,11 @@ impl RosyMPIContext {
                 // - Process 3 sends/recieves from processes 1 and 5
                 // - Process 4 sends/recieves from processes 0 and 2
                 // - Process 5 sends/recieves from processes 1 and 3
-                let group_num = self.get_group_num(num_groups)
-                    .context("Failed to get group number")?;
-                let num_groups = *num_groups as i32;
-                let processes_per_group = self.size / num_groups;
-                let group_id = self.rank % processes_per_group;
-
-                // The output array must have at least `num_groups` slots —
-                // one per group's contribution. The user's array may have
-                // been sized from a variable (e.g. `(RE NP) X`) before
-                // `PNPRO NP` ran, in which case it's still empty here.
-                if value.len() < num_groups as usize {
-                    value.resize_with(num_groups as usize, T::default);
-                }
-
-                let other_nodes: Vec<i32> = (0..self.size)
-                    .filter(|r| (r % processes_per_group) == group_id && *r != self.rank)
-                    .collect();
+                let other_nodes = layout.others();
 
                 // Get the value we're going to be sending,
                 //  which is the group_num'th element of the array
-                let binary_value: Vec<u8> = bincode::encode_to_vec(&value[group_num as usize], self.bincode_config)
+                let binary_value: Vec<u8> = bincode::encode_to_vec(&value[group_num], self.bincode_config)
                     .context("Failed to serialize value for communication")?;
 
                 // Send this value to all other nodes in the group
@@ -78,31 +80,81 @@ impl RosyMPIContext {
                 // Now receive values from all other nodes in the group
                 for _ in other_nodes.iter() {
                     let (msg, status) = self.world.any_process().receive_vec::<u8>();
-                    let recieved_from = status.source_rank() as usize;
+                    let recieved_from = status.source_rank();
                     let (decoded_value, _): (T, _) = bincode::decode_from_slice(&msg, self.bincode_config)
                         .context("Failed to deserialize received value")?;
-                    
+
                     // Store the received value in the appropriate position
-                    let recieved_from_group = (recieved_from as i32) / processes_per_group;
-                    value[recieved_from_group as usize] = decoded_value;
+                    value[layout.group_of(recieved_from) as usize] = decoded_value;
+                }
+            },
+            Standard::Gather => {
+                // Only the root ends up with every group's slot
+                if layout.is_root() {
+                    for other in layout.others() {
+                        value[layout.group_of(other) as usize] = self.receive_from(other)?;
+                    }
+                } else {
+                    self.send_to(layout.root(), &value[group_num])?;
+                }
+            },
+            Standard::Reduce(reduction) => {
+                // Gather to the root, which reduces and sends the result back
+                let result = if layout.is_root() {
+                    for other in layout.others() {
+                        value[layout.group_of(other) as usize] = self.receive_from(other)?;
+                    }
+                    let result = ploop::reduce(&value[..num_groups], reduction)?;
+                    for other in layout.others() {
+                        self.send_to(other, &result)?;
+                    }
+                    result
+                } else {
+                    self.send_to(layout.root(), &value[group_num])?;
+                    self.receive_from(layout.root())?
+                };
+                value[..num_groups].fill(result);
+            },
+            Standard::Scatter => {
+                // The root hands every group its own slot
+                if layout.is_root() {
+                    for other in layout.others() {
+                        self.send_to(other, &value[layout.group_of(other) as usize])?;
+                    }
+                } else {
+                    value[group_num] = self.receive_from(layout.root())?;
                 }
-
-                Ok(())
             },
-            _ => bail!( "Unsupported communication standard: {}", communication_standard )
         }
+
+        Ok(())
+    }
+    fn send_to<T: Encode> (
+        &self,
+        rank: i32,
+        value: &T
+    ) -> Result<()> {
+        let binary_value: Vec<u8> = bincode::encode_to_vec(value, self.bincode_config)
+            .context("Failed to serialize value for communication")?;
+        self.world.process_at_rank(rank).send(&binary_value);
+        Ok(())
+    }
+    fn receive_from<T: Decode<()>> (
+        &self,
+        rank: i32
+    ) -> Result<T> {
+        let (msg, _status) = self.world.process_at_rank(rank).receive_vec::<u8>();
+        let (decoded_value, _): (T, _) = bincode::decode_from_slice(&msg, self.bincode_config)
+            .with_context(|| format!("Failed to deserialize value received from rank {}", rank))?;
+        Ok(decoded_value)
     }
     pub fn get_group_num ( 
         &self,
         num_groups: &mut RE
     ) -> Result<RE> {
-        let num_groups = *num_groups as i32;
-        let processes_per_group = self.size / num_groups;
-
-        ensure!(self.size % num_groups == 0, "Total number of processes ({}) must be divisible by the number of processes per group ({})!", self.size, num_groups);
-        let group_num = self.rank / processes_per_group;
+        let layout = Layout::new(self.size, self.rank, *num_groups)?;
 
-        Ok(group_num as RE)
+        Ok(layout.group_num() as RE)
     }
     fn _get_root_rank ( 
         &self,
@@ -116,4 +168,34 @@ impl RosyMPIContext {
 
         Ok(root_rank as RE)
     }
-}
\ No newline at end of file
+}
+
+// DA values travel as their nonzero terms, each the monomial's exponents
+//  and its coefficient, so they don't depend on the receiver's term order.
+impl Encode for DA {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let terms: Vec<(Vec<u8>, f64)> = self.coeffs_iter()
+            .into_iter()
+            .map(|(monomial, coeff)| (monomial.exponents.to_vec(), coeff))
+            .collect();
+        terms.encode(encoder)
+    }
+}
+impl<Context> Decode<Context> for DA {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let terms: Vec<(Vec<u8>, f64)> = Decode::decode(decoder)?;
+        let mut coeffs = FxHashMap::default();
+        for (exponents, coeff) in terms {
+            if exponents.iter().skip(MAX_VARS).any(|&e| e != 0) {
+                return Err(DecodeError::OtherString(format!(
+                    "Received a DA term in more than {} variables", MAX_VARS
+                )));
+            }
+            let mut padded = [0u8; MAX_VARS];
+            let shared = exponents.len().min(MAX_VARS);
+            padded[..shared].copy_from_slice(&exponents[..shared]);
+            coeffs.insert(Monomial::new(padded), coeff);
+        }
+        Ok(DA::from_coeffs(coeffs))
+    }
+}
@@ -15,7 +15,7 @@
 
 use anyhow::Result;
 use crate::rosy_lib::RosyType;
-use crate::rosy_lib::{RE, ST, VE, DA, CD};
+use crate::rosy_lib::{RE, ST, VE, DA, CD, GR};
 use crate::rosy_lib::operators::{TypeRule, build_type_registry};
 
 /// Type compatibility registry for concatenation operator.
@@ -29,7 +29,7 @@ use crate::rosy_lib::operators::{TypeRule, build_type_registry};
 /// 
 /// **Note:** This registry matches COSY INFINITY's & operator capabilities.
 /// See manual.md Section A.2 "& (Concatenation)" for the authoritative list.
-/// GR (Graphics) type is not yet implemented in Rosy.
+/// GR & GR appends the primitives of the right object to those of the left.
 pub const CONCAT_REGISTRY: &[TypeRule] = &[
     TypeRule::with_comment("RE", "RE", "VE", "1", "1", "Concatenate two Reals to a Vector"),
     TypeRule::with_comment("RE", "VE", "VE", "1", "1&2&3", "Prepend a Real to the left of a Vector"),
@@ -46,7 +46,8 @@ pub const CONCAT_REGISTRY: &[TypeRule] = &[
     TypeRule::with_comment("CD", "CD1", "CD1", "CD(1)", "CD(1)&CD(2)", "Prepend a CD to the left of a CD vector"),
     TypeRule::with_comment("CD1", "CD", "CD1", "CD(1)&CD(2)", "CD(3)", "Append a CD to the right of a CD vector"),
     TypeRule::with_comment("CD1", "CD1", "CD1", "CD(1)&CD(2)", "CD(3)&CD(4)", "Concatenate two CD vectors"),
-    // GR & GR => GR is in COSY but GR type not implemented in Rosy yet
+    // GR concatenation — assembles pictures from graphics primitives
+    TypeRule::with_comment("GR", "GR", "GR", "GR(1)", "GR(2)", "Append the primitives of the right GR to the left"),
 ];
 
 pub fn get_return_type(lhs: &RosyType, rhs: &RosyType) -> Option<RosyType> {
@@ -180,3 +181,13 @@ impl RosyConcat<&Vec<CD>> for &Vec<CD> {
         Ok(result)
     }
 }
+
+// GR & GR => GR
+impl RosyConcat<&GR> for &GR {
+    type Output = GR;
+    fn rosy_concat(self, other: &GR) -> Result<Self::Output> {
+        let mut result = self.clone();
+        result.elements.extend_from_slice(&other.elements);
+        Ok(result)
+    }
+}
@@ -15,7 +15,7 @@
 
 use anyhow::Result;
 use crate::rosy_lib::RosyType;
-use crate::rosy_lib::{RE, ST, LO};
+use crate::rosy_lib::{RE, ST, LO, CM, VE, DA, CD};
 use crate::rosy_lib::operators::{TypeRule, build_type_registry};
 
 /// Type compatibility registry for equality operator.
@@ -28,6 +28,20 @@ use crate::rosy_lib::operators::{TypeRule, build_type_registry};
 /// - Integration tests
 pub const EQ_REGISTRY: &[TypeRule] = &[
     TypeRule::with_comment("RE", "RE", "LO", "3.14159", "3.14159", "Equality with epsilon tolerance"),
+    TypeRule::with_comment("RE", "DA", "LO", "3", "DA(1)+3", "Compares the constant part"),
+    TypeRule::with_comment("DA", "RE", "LO", "DA(1)+3", "3", "Compares the constant part"),
+    TypeRule::with_comment("DA", "DA", "LO", "DA(1)+3", "DA(2)+3", "Compares the constant parts"),
+    TypeRule::with_comment("CD", "RE", "LO", "DA(1)+CM(3&0)", "3", "Compares the constant part"),
+    TypeRule::with_comment("RE", "CD", "LO", "3", "DA(1)+CM(3&0)", "Compares the constant part"),
+    TypeRule::with_comment("CD", "CD", "LO", "DA(1)+CM(1&2)", "DA(2)+CM(1&2)", "Compares the constant parts"),
+    TypeRule::with_comment("CD", "CM", "LO", "DA(1)+CM(1&2)", "CM(1&2)", "Compares the constant part"),
+    TypeRule::with_comment("CM", "CD", "LO", "CM(1&2)", "DA(1)+CM(1&2)", "Compares the constant part"),
+    TypeRule::with_comment("CM", "RE", "LO", "CM(3&0)", "3", "Complex equality with epsilon tolerance"),
+    TypeRule::with_comment("RE", "CM", "LO", "3", "CM(3&0)", "Complex equality with epsilon tolerance"),
+    TypeRule::with_comment("CM", "CM", "LO", "CM(1&2)", "CM(1&2)", "Complex equality with epsilon tolerance"),
+    TypeRule::with_comment("RE", "VE", "LO", "2", "2&2", "Every component"),
+    TypeRule::with_comment("VE", "RE", "LO", "2&2", "2", "Every component"),
+    TypeRule::with_comment("VE", "VE", "LO", "1&2", "1&2", "Same length and every component"),
     TypeRule::with_comment("ST", "ST", "LO", "'hello'", "'hello'", "String equality"),
     TypeRule::with_comment("LO", "LO", "LO", "TRUE", "TRUE", "Logical equality"),
 ];
@@ -65,3 +79,121 @@ impl RosyEq<&LO> for &LO {
         Ok(self == rhs)
     }
 }
+
+// RE = DA (constant part)
+impl RosyEq<&DA> for &RE {
+    type Output = LO;
+    fn rosy_eq(self, rhs: &DA) -> Result<Self::Output> {
+        Ok((*self - rhs.constant_part()).abs() < f64::EPSILON)
+    }
+}
+
+// DA = RE (constant part)
+impl RosyEq<&RE> for &DA {
+    type Output = LO;
+    fn rosy_eq(self, rhs: &RE) -> Result<Self::Output> {
+        Ok((self.constant_part() - *rhs).abs() < f64::EPSILON)
+    }
+}
+
+// DA = DA (constant parts)
+impl RosyEq<&DA> for &DA {
+    type Output = LO;
+    fn rosy_eq(self, rhs: &DA) -> Result<Self::Output> {
+        Ok((self.constant_part() - rhs.constant_part()).abs() < f64::EPSILON)
+    }
+}
+
+// CD = RE (constant part)
+impl RosyEq<&RE> for &CD {
+    type Output = LO;
+    fn rosy_eq(self, rhs: &RE) -> Result<Self::Output> {
+        Ok(within_epsilon(self.constant_part(), CM::new(*rhs, 0.0)))
+    }
+}
+
+// RE = CD (constant part)
+impl RosyEq<&CD> for &RE {
+    type Output = LO;
+    fn rosy_eq(self, rhs: &CD) -> Result<Self::Output> {
+        Ok(within_epsilon(CM::new(*self, 0.0), rhs.constant_part()))
+    }
+}
+
+// CD = CD (constant parts)
+impl RosyEq<&CD> for &CD {
+    type Output = LO;
+    fn rosy_eq(self, rhs: &CD) -> Result<Self::Output> {
+        Ok(within_epsilon(self.constant_part(), rhs.constant_part()))
+    }
+}
+
+// CD = CM (constant part)
+impl RosyEq<&CM> for &CD {
+    type Output = LO;
+    fn rosy_eq(self, rhs: &CM) -> Result<Self::Output> {
+        Ok(within_epsilon(self.constant_part(), *rhs))
+    }
+}
+
+// CM = CD (constant part)
+impl RosyEq<&CD> for &CM {
+    type Output = LO;
+    fn rosy_eq(self, rhs: &CD) -> Result<Self::Output> {
+        Ok(within_epsilon(*self, rhs.constant_part()))
+    }
+}
+
+// CM = RE (epsilon tolerance)
+impl RosyEq<&RE> for &CM {
+    type Output = LO;
+    fn rosy_eq(self, rhs: &RE) -> Result<Self::Output> {
+        Ok(within_epsilon(*self, CM::new(*rhs, 0.0)))
+    }
+}
+
+// RE = CM (epsilon tolerance)
+impl RosyEq<&CM> for &RE {
+    type Output = LO;
+    fn rosy_eq(self, rhs: &CM) -> Result<Self::Output> {
+        Ok(within_epsilon(CM::new(*self, 0.0), *rhs))
+    }
+}
+
+// CM = CM (epsilon tolerance)
+impl RosyEq<&CM> for &CM {
+    type Output = LO;
+    fn rosy_eq(self, rhs: &CM) -> Result<Self::Output> {
+        Ok(within_epsilon(*self, *rhs))
+    }
+}
+
+// RE = VE (every component equals the real)
+impl RosyEq<&VE> for &RE {
+    type Output = LO;
+    fn rosy_eq(self, rhs: &VE) -> Result<Self::Output> {
+        Ok(rhs.iter().all(|x| (self - x).abs() < f64::EPSILON))
+    }
+}
+
+// VE = RE (every component equals the real)
+impl RosyEq<&RE> for &VE {
+    type Output = LO;
+    fn rosy_eq(self, rhs: &RE) -> Result<Self::Output> {
+        Ok(self.iter().all(|x| (x - rhs).abs() < f64::EPSILON))
+    }
+}
+
+// VE = VE (same length, every component equal)
+impl RosyEq<&VE> for &VE {
+    type Output = LO;
+    fn rosy_eq(self, rhs: &VE) -> Result<Self::Output> {
+        Ok(self.len() == rhs.len() && self.iter().zip(rhs).all(|(a, b)| (a - b).abs() < f64::EPSILON))
+    }
+}
+
+/// Scalar equality used by every numeric form of `=`: the same absolute
+/// `f64::EPSILON` tolerance as `RE = RE`, applied to the modulus for complex values.
+fn within_epsilon(lhs: CM, rhs: CM) -> bool {
+    (lhs - rhs).norm() < f64::EPSILON
+}
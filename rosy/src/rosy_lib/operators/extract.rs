@@ -18,7 +18,7 @@ use anyhow::{Result, bail};
 use crate::rosy_lib::RosyType;
 use crate::rosy_lib::{RE, ST, VE, CM, DA, CD};
 use crate::rosy_lib::operators::{TypeRule, build_type_registry};
-use crate::rosy_lib::taylor::monomial::Monomial;
+use crate::rosy_lib::taylor::{get_weights, monomial::Monomial};
 
 /// Type compatibility registry for extraction operator.
 /// 
@@ -147,16 +147,16 @@ impl RosyExtract<&VE> for &VE {
 // DA | RE -> RE (extract 1D DA coefficient for supplied exponent)
 //
 // COSY semantics: the RE value is the exponent of the first variable.
+// Exponents are powers of the variables, also under weighted DA.
 // `DA(1) | 1` extracts the coefficient of x1^1 from the DA representing x1,
 // which is 1.0.
 impl RosyExtract<&RE> for &DA {
     type Output = RE;
 
     fn rosy_extract(self, index: &RE) -> Result<Self::Output> {
-        let exp = *index as u8;
-        let mut exponents = [0u8; crate::rosy_lib::taylor::MAX_VARS];
-        exponents[0] = exp;
-        let monomial = Monomial::new(exponents);
+        let mut powers = [0u8; crate::rosy_lib::taylor::MAX_VARS];
+        powers[0] = *index as u8;
+        let monomial = Monomial::from_powers(powers, &get_weights());
         Ok(self.get_coeff(&monomial))
     }
 }
@@ -174,11 +174,11 @@ impl RosyExtract<&VE> for &DA {
                 index.len(), config.num_vars
             );
         }
-        let mut exponents = [0u8; crate::rosy_lib::taylor::MAX_VARS];
+        let mut powers = [0u8; crate::rosy_lib::taylor::MAX_VARS];
         for (i, &val) in index.iter().enumerate() {
-            exponents[i] = val as u8;
+            powers[i] = val as u8;
         }
-        let monomial = Monomial::new(exponents);
+        let monomial = Monomial::from_powers(powers, &get_weights());
         Ok(self.get_coeff(&monomial))
     }
 }
@@ -192,10 +192,9 @@ impl RosyExtract<&RE> for &CD {
     type Output = CM;
 
     fn rosy_extract(self, index: &RE) -> Result<Self::Output> {
-        let exp = *index as u8;
-        let mut exponents = [0u8; crate::rosy_lib::taylor::MAX_VARS];
-        exponents[0] = exp;
-        let monomial = Monomial::new(exponents);
+        let mut powers = [0u8; crate::rosy_lib::taylor::MAX_VARS];
+        powers[0] = *index as u8;
+        let monomial = Monomial::from_powers(powers, &get_weights());
         Ok(self.get_coeff(&monomial))
     }
 }
@@ -213,11 +212,11 @@ impl RosyExtract<&VE> for &CD {
                 index.len(), config.num_vars
             );
         }
-        let mut exponents = [0u8; crate::rosy_lib::taylor::MAX_VARS];
+        let mut powers = [0u8; crate::rosy_lib::taylor::MAX_VARS];
         for (i, &val) in index.iter().enumerate() {
-            exponents[i] = val as u8;
+            powers[i] = val as u8;
         }
-        let monomial = Monomial::new(exponents);
+        let monomial = Monomial::from_powers(powers, &get_weights());
         Ok(self.get_coeff(&monomial))
     }
 }
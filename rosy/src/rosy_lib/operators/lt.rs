@@ -6,12 +6,18 @@
 
 use anyhow::Result;
 use crate::rosy_lib::RosyType;
-use crate::rosy_lib::{RE, ST, LO};
-use crate::rosy_lib::operators::{TypeRule, build_type_registry};
+use crate::rosy_lib::{RE, ST, LO, VE, DA};
+use crate::rosy_lib::operators::{TypeRule, build_type_registry, compare_componentwise};
 
 /// Type compatibility registry for less-than operator.
 pub const LT_REGISTRY: &[TypeRule] = &[
     TypeRule::with_comment("RE", "RE", "LO", "1.0", "2.0", "Numeric less-than"),
+    TypeRule::with_comment("RE", "DA", "LO", "1.0", "DA(1)+2.0", "Compares the constant part"),
+    TypeRule::with_comment("DA", "RE", "LO", "DA(1)+1.0", "2.0", "Compares the constant part"),
+    TypeRule::with_comment("DA", "DA", "LO", "DA(1)+1.0", "DA(2)+2.0", "Compares the constant parts"),
+    TypeRule::with_comment("RE", "VE", "LO", "1.0", "3&4", "Holds for every component"),
+    TypeRule::with_comment("VE", "RE", "LO", "1&2", "3.0", "Holds for every component"),
+    TypeRule::with_comment("VE", "VE", "LO", "1&2", "3&4", "Componentwise; lengths must match"),
     TypeRule::with_comment("ST", "ST", "LO", "'apple'", "'banana'", "Lexicographic ordering"),
 ];
 
@@ -33,6 +39,54 @@ impl RosyLt<&RE> for &RE {
     }
 }
 
+// RE < DA (constant part)
+impl RosyLt<&DA> for &RE {
+    type Output = LO;
+    fn rosy_lt(self, rhs: &DA) -> Result<Self::Output> {
+        Ok(*self < rhs.constant_part())
+    }
+}
+
+// DA < RE (constant part)
+impl RosyLt<&RE> for &DA {
+    type Output = LO;
+    fn rosy_lt(self, rhs: &RE) -> Result<Self::Output> {
+        Ok(self.constant_part() < *rhs)
+    }
+}
+
+// DA < DA (constant parts)
+impl RosyLt<&DA> for &DA {
+    type Output = LO;
+    fn rosy_lt(self, rhs: &DA) -> Result<Self::Output> {
+        Ok(self.constant_part() < rhs.constant_part())
+    }
+}
+
+// RE < VE (every component)
+impl RosyLt<&VE> for &RE {
+    type Output = LO;
+    fn rosy_lt(self, rhs: &VE) -> Result<Self::Output> {
+        Ok(rhs.iter().all(|x| *self < *x))
+    }
+}
+
+// VE < RE (every component)
+impl RosyLt<&RE> for &VE {
+    type Output = LO;
+    fn rosy_lt(self, rhs: &RE) -> Result<Self::Output> {
+        Ok(self.iter().all(|x| *x < *rhs))
+    }
+}
+
+// VE < VE (componentwise)
+impl RosyLt<&VE> for &VE {
+    type Output = LO;
+    fn rosy_lt(self, rhs: &VE) -> Result<Self::Output> {
+        compare_componentwise(self, rhs, "<", |a, b| a < b)
+    }
+}
+
 // ST < ST (lexicographic ordering)
 impl RosyLt<&ST> for &ST {
     type Output = LO;
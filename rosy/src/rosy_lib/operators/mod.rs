@@ -14,6 +14,7 @@ pub mod gte;
 pub mod not;
 pub mod and;
 pub mod or;
+pub mod widen;
 
 pub use add::RosyAdd;
 pub use sub::RosySub;
@@ -31,6 +32,7 @@ pub use gte::RosyGte;
 pub use not::RosyNot;
 pub use and::RosyAnd;
 pub use or::RosyOr;
+pub use widen::RosyWiden;
 
 use std::collections::HashMap;
 use crate::rosy_lib::{RosyType, RosyBaseType};
@@ -102,6 +104,7 @@ pub fn type_from_str(s: &str) -> RosyType {
         "VE" => RosyType::new(RosyBaseType::VE, 0),
         "DA" => RosyType::new(RosyBaseType::DA, 0),
         "CD" => RosyType::new(RosyBaseType::CD, 0),
+        "GR" => RosyType::new(RosyBaseType::GR, 0),
         _ => panic!("Unknown type: {}", s),
     }
 }
@@ -119,4 +122,25 @@ pub fn build_type_registry(rules: &[TypeRule]) -> HashMap<(RosyType, RosyType),
         );
     }
     m
-}
\ No newline at end of file
+}
+
+/// Compare two vectors component by component.
+///
+/// Used by the ordering operators: a VE comparison is TRUE only when `cmp`
+/// holds for every pair of components. Vectors of different lengths cannot
+/// be ordered against each other.
+pub fn compare_componentwise(
+    lhs: &[f64],
+    rhs: &[f64],
+    op: &str,
+    cmp: impl Fn(f64, f64) -> bool,
+) -> anyhow::Result<bool> {
+    anyhow::ensure!(
+        lhs.len() == rhs.len(),
+        "Cannot compare vectors of length {} and {} with '{}'",
+        lhs.len(),
+        rhs.len(),
+        op
+    );
+    Ok(lhs.iter().zip(rhs).all(|(a, b)| cmp(*a, *b)))
+}
@@ -15,8 +15,8 @@
 
 use anyhow::Result;
 use crate::rosy_lib::RosyType;
-use crate::rosy_lib::{RE, ST, LO};
-use crate::rosy_lib::operators::{TypeRule, build_type_registry};
+use crate::rosy_lib::{RE, ST, LO, CM, VE, DA, CD};
+use crate::rosy_lib::operators::{RosyEq, TypeRule, build_type_registry};
 
 /// Type compatibility registry for not-equals operator.
 /// 
@@ -28,6 +28,20 @@ use crate::rosy_lib::operators::{TypeRule, build_type_registry};
 /// - Integration tests
 pub const NEQ_REGISTRY: &[TypeRule] = &[
     TypeRule::with_comment("RE", "RE", "LO", "3.14159", "2.71828", "Not-equals with epsilon tolerance"),
+    TypeRule::with_comment("RE", "DA", "LO", "3", "DA(1)+4", "Compares the constant part"),
+    TypeRule::with_comment("DA", "RE", "LO", "DA(1)+3", "4", "Compares the constant part"),
+    TypeRule::with_comment("DA", "DA", "LO", "DA(1)+3", "DA(2)+4", "Compares the constant parts"),
+    TypeRule::with_comment("CD", "RE", "LO", "DA(1)+CM(3&1)", "3", "Compares the constant part"),
+    TypeRule::with_comment("RE", "CD", "LO", "3", "DA(1)+CM(3&1)", "Compares the constant part"),
+    TypeRule::with_comment("CD", "CD", "LO", "DA(1)+CM(1&2)", "DA(2)+CM(2&1)", "Compares the constant parts"),
+    TypeRule::with_comment("CD", "CM", "LO", "DA(1)+CM(1&2)", "CM(2&1)", "Compares the constant part"),
+    TypeRule::with_comment("CM", "CD", "LO", "CM(1&2)", "DA(1)+CM(2&1)", "Compares the constant part"),
+    TypeRule::with_comment("CM", "RE", "LO", "CM(3&1)", "3", "Complex not-equals with epsilon tolerance"),
+    TypeRule::with_comment("RE", "CM", "LO", "3", "CM(3&1)", "Complex not-equals with epsilon tolerance"),
+    TypeRule::with_comment("CM", "CM", "LO", "CM(1&2)", "CM(2&1)", "Complex not-equals with epsilon tolerance"),
+    TypeRule::with_comment("RE", "VE", "LO", "2", "2&3", "Any component differs"),
+    TypeRule::with_comment("VE", "RE", "LO", "2&3", "2", "Any component differs"),
+    TypeRule::with_comment("VE", "VE", "LO", "1&2", "1&3", "Lengths or any component differ"),
     TypeRule::with_comment("ST", "ST", "LO", "'hello'", "'world'", "String not-equals"),
     TypeRule::with_comment("LO", "LO", "LO", "TRUE", "FALSE", "Logical not-equals"),
 ];
@@ -65,3 +79,118 @@ impl RosyNeq<&LO> for &LO {
         Ok(self != rhs)
     }
 }
+
+// The numeric forms below (DA, CD, CM and VE operands) are the exact negation
+// of the matching `=` rule, so both operators always agree.
+
+// RE # DA
+impl RosyNeq<&DA> for &RE {
+    type Output = LO;
+    fn rosy_neq(self, rhs: &DA) -> Result<Self::Output> {
+        Ok(!self.rosy_eq(rhs)?)
+    }
+}
+
+// DA # RE
+impl RosyNeq<&RE> for &DA {
+    type Output = LO;
+    fn rosy_neq(self, rhs: &RE) -> Result<Self::Output> {
+        Ok(!self.rosy_eq(rhs)?)
+    }
+}
+
+// DA # DA
+impl RosyNeq<&DA> for &DA {
+    type Output = LO;
+    fn rosy_neq(self, rhs: &DA) -> Result<Self::Output> {
+        Ok(!self.rosy_eq(rhs)?)
+    }
+}
+
+// CD # RE
+impl RosyNeq<&RE> for &CD {
+    type Output = LO;
+    fn rosy_neq(self, rhs: &RE) -> Result<Self::Output> {
+        Ok(!self.rosy_eq(rhs)?)
+    }
+}
+
+// RE # CD
+impl RosyNeq<&CD> for &RE {
+    type Output = LO;
+    fn rosy_neq(self, rhs: &CD) -> Result<Self::Output> {
+        Ok(!self.rosy_eq(rhs)?)
+    }
+}
+
+// CD # CD
+impl RosyNeq<&CD> for &CD {
+    type Output = LO;
+    fn rosy_neq(self, rhs: &CD) -> Result<Self::Output> {
+        Ok(!self.rosy_eq(rhs)?)
+    }
+}
+
+// CD # CM
+impl RosyNeq<&CM> for &CD {
+    type Output = LO;
+    fn rosy_neq(self, rhs: &CM) -> Result<Self::Output> {
+        Ok(!self.rosy_eq(rhs)?)
+    }
+}
+
+// CM # CD
+impl RosyNeq<&CD> for &CM {
+    type Output = LO;
+    fn rosy_neq(self, rhs: &CD) -> Result<Self::Output> {
+        Ok(!self.rosy_eq(rhs)?)
+    }
+}
+
+// CM # RE
+impl RosyNeq<&RE> for &CM {
+    type Output = LO;
+    fn rosy_neq(self, rhs: &RE) -> Result<Self::Output> {
+        Ok(!self.rosy_eq(rhs)?)
+    }
+}
+
+// RE # CM
+impl RosyNeq<&CM> for &RE {
+    type Output = LO;
+    fn rosy_neq(self, rhs: &CM) -> Result<Self::Output> {
+        Ok(!self.rosy_eq(rhs)?)
+    }
+}
+
+// CM # CM
+impl RosyNeq<&CM> for &CM {
+    type Output = LO;
+    fn rosy_neq(self, rhs: &CM) -> Result<Self::Output> {
+        Ok(!self.rosy_eq(rhs)?)
+    }
+}
+
+// RE # VE
+impl RosyNeq<&VE> for &RE {
+    type Output = LO;
+    fn rosy_neq(self, rhs: &VE) -> Result<Self::Output> {
+        Ok(!self.rosy_eq(rhs)?)
+    }
+}
+
+// VE # RE
+impl RosyNeq<&RE> for &VE {
+    type Output = LO;
+    fn rosy_neq(self, rhs: &RE) -> Result<Self::Output> {
+        Ok(!self.rosy_eq(rhs)?)
+    }
+}
+
+// VE # VE
+impl RosyNeq<&VE> for &VE {
+    type Output = LO;
+    fn rosy_neq(self, rhs: &VE) -> Result<Self::Output> {
+        Ok(!self.rosy_eq(rhs)?)
+    }
+}
@@ -9,18 +9,30 @@
 //! According to COSY INFINITY manual:
 //! - RE ^ RE -> RE
 //! - VE ^ RE -> VE (component-wise)
+//! - DA ^ RE -> DA, CD ^ RE -> CD (Taylor composition)
+//! - CM ^ RE -> CM, CM ^ CM -> CM (principal branch)
+//!
+//! Integer exponents on DA/CD use binary exponentiation with
+//! `multiply_truncated`; other exponents go through the binomial series
+//! around the constant part, which must then be nonzero (positive for DA).
 
-use anyhow::Result;
+use anyhow::{Result, ensure};
+use num_complex::Complex64;
 use crate::rosy_lib::RosyType;
-use crate::rosy_lib::{RE, VE};
+use crate::rosy_lib::{RE, CM, VE, DA, CD};
 use crate::rosy_lib::operators::{TypeRule, build_type_registry};
+use crate::rosy_lib::taylor::{self, DACoefficient, da};
 
 /// Type compatibility registry for power/exponentiation operator.
 /// 
 /// This is the single source of truth for what type combinations are allowed.
 pub const POW_REGISTRY: &[TypeRule] = &[
     TypeRule::new("RE", "RE", "RE", "2", "3"),
+    TypeRule::new("CM", "RE", "CM", "CM(1&2)", "3"),
+    TypeRule::new("CM", "CM", "CM", "CM(1&2)", "CM(0.5&1)"),
     TypeRule::with_comment("VE", "RE", "VE", "1&2&3", "2", "Raise to Real power componentwise"),
+    TypeRule::with_comment("DA", "RE", "DA", "1+DA(1)", "3", "Constant part must be positive for non-integer powers"),
+    TypeRule::with_comment("CD", "RE", "CD", "1+DA(1)+CM(0&1)*DA(2)", "3", "Constant part must be nonzero for non-integer powers"),
 ];
 
 pub fn get_return_type(lhs: &RosyType, rhs: &RosyType) -> Option<RosyType> {
@@ -49,3 +61,119 @@ impl RosyPow<&RE> for &VE {
     }
 }
 
+
+// CM ^ RE
+impl RosyPow<&RE> for &CM {
+    type Output = CM;
+    fn rosy_pow(self, rhs: &RE) -> Result<Self::Output> {
+        if let Some(n) = integer_exponent(*rhs).and_then(|n| i32::try_from(n).ok()) {
+            return Ok(self.powi(n));
+        }
+        if *self == Complex64::new(0.0, 0.0) {
+            return Ok(*self);
+        }
+        Ok(self.powf(*rhs))
+    }
+}
+
+// CM ^ CM
+impl RosyPow<&CM> for &CM {
+    type Output = CM;
+    fn rosy_pow(self, rhs: &CM) -> Result<Self::Output> {
+        if rhs.im == 0.0 {
+            return self.rosy_pow(&rhs.re);
+        }
+        if *self == Complex64::new(0.0, 0.0) {
+            return Ok(*self);
+        }
+        Ok(self.powc(*rhs))
+    }
+}
+
+// DA ^ RE
+impl RosyPow<&RE> for &DA {
+    type Output = DA;
+    fn rosy_pow(self, rhs: &RE) -> Result<Self::Output> {
+        if let Some(n) = integer_exponent(*rhs) {
+            return da_powi(self, n);
+        }
+        let f0 = self.constant_part();
+        ensure!(
+            f0 > 0.0,
+            "DA ^ {rhs}: constant part of DA must be positive for a non-integer power, got {f0}"
+        );
+        let coeffs = binomial_coefficients(*rhs, taylor::get_config()?.max_order);
+        let delta = (&self.make_prime() / f0)?;
+        let series = DA::horner_eval(&delta, &coeffs)?;
+        &series * f0.powf(*rhs)
+    }
+}
+
+// CD ^ RE
+impl RosyPow<&RE> for &CD {
+    type Output = CD;
+    fn rosy_pow(self, rhs: &RE) -> Result<Self::Output> {
+        if let Some(n) = integer_exponent(*rhs) {
+            return da_powi(self, n);
+        }
+        let f0 = self.constant_part();
+        ensure!(
+            f0.norm() > 0.0,
+            "CD ^ {rhs}: constant part of CD must be nonzero for a non-integer power"
+        );
+        let coeffs: Vec<Complex64> = binomial_coefficients(*rhs, taylor::get_config()?.max_order)
+            .into_iter()
+            .map(|c| Complex64::new(c, 0.0))
+            .collect();
+        let delta = (&self.make_prime() / f0)?;
+        let series = CD::horner_eval(&delta, &coeffs)?;
+        &series * f0.powf(*rhs)
+    }
+}
+
+/// Returns `Some(n)` when `exponent` is an integer small enough for the
+/// repeated-squaring fast path.
+fn integer_exponent(exponent: RE) -> Option<i64> {
+    (exponent.fract() == 0.0 && exponent.abs() <= u32::MAX as f64).then_some(exponent as i64)
+}
+
+/// Raise a DA/CD to an integer power by binary exponentiation.
+///
+/// Every product goes through `multiply_truncated` at the current truncation
+/// order, so `X^n` costs O(log n) DA multiplies instead of n. Negative powers
+/// invert the positive power with DA division.
+fn da_powi<T: DACoefficient>(base: &da::DA<T>, n: i64) -> Result<da::DA<T>> {
+    let one = da::DA::from_coeff(T::one());
+    let positive = {
+        let rt = taylor::get_runtime()?;
+        let order = rt.config.max_order;
+        let mut remaining = n.unsigned_abs();
+        let mut result: Option<da::DA<T>> = None;
+        let mut square = base.clone();
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                result = Some(match result {
+                    Some(acc) => da::DA::multiply_truncated_with_rt(&acc, &square, order, &rt)?,
+                    None => square.clone(),
+                });
+            }
+            remaining >>= 1;
+            if remaining > 0 {
+                square = da::DA::multiply_truncated_with_rt(&square, &square, order, &rt)?;
+            }
+        }
+        result.unwrap_or_else(|| one.clone())
+    };
+    if n < 0 { &one / &positive } else { Ok(positive) }
+}
+
+/// Binomial series coefficients C(a, k) for k = 0..=order, so that
+/// (1 + u)^a = Σ C(a, k) u^k.
+fn binomial_coefficients(a: RE, order: u32) -> Vec<RE> {
+    let mut coeffs = Vec::with_capacity(order as usize + 1);
+    coeffs.push(1.0);
+    for k in 1..=order as usize {
+        coeffs.push(coeffs[k - 1] * (a - (k - 1) as f64) / k as f64);
+    }
+    coeffs
+}
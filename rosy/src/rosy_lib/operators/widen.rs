@@ -0,0 +1,147 @@
+//! Implicit widening between the numeric types.
+//!
+//! For type inference the numeric types form a small lattice:
+//!
+//! ```text
+//!        CD
+//!       /  \
+//!     CM    DA
+//!       \  /
+//!        RE
+//! ```
+//!
+//! A variable whose assignments disagree only along this lattice takes the
+//! least upper bound of their types (`X := 1; X := X*Y;` with `Y` a DA makes
+//! `X` a DA), and the narrower values are widened when they are stored.
+//! Arrays widen element-wise and only between equal dimensions.
+//!
+//! This module provides [`join`], [`is_widening`] and the `RosyWiden` trait
+//! the generated code uses for the conversion.
+
+use anyhow::Result;
+use num_complex::Complex64;
+use crate::rosy_lib::{RosyType, RosyBaseType, RE, CM, DA, CD};
+
+/// Position of a base type in the lattice, as (has complex part, has DA part).
+/// Types outside the lattice only relate to themselves.
+fn lattice_point(base: RosyBaseType) -> Option<(bool, bool)> {
+    match base {
+        RosyBaseType::RE => Some((false, false)),
+        RosyBaseType::CM => Some((true, false)),
+        RosyBaseType::DA => Some((false, true)),
+        RosyBaseType::CD => Some((true, true)),
+        _ => None,
+    }
+}
+
+fn from_lattice_point(point: (bool, bool)) -> RosyBaseType {
+    match point {
+        (false, false) => RosyBaseType::RE,
+        (true, false) => RosyBaseType::CM,
+        (false, true) => RosyBaseType::DA,
+        (true, true) => RosyBaseType::CD,
+    }
+}
+
+/// Least upper bound of two types, or `None` if they are incompatible.
+pub fn join(a: &RosyType, b: &RosyType) -> Option<RosyType> {
+    if a == b {
+        return Some(*a);
+    }
+    if a.dimensions != b.dimensions {
+        return None;
+    }
+    let (a_cm, a_da) = lattice_point(a.base_type)?;
+    let (b_cm, b_da) = lattice_point(b.base_type)?;
+    Some(RosyType::new(
+        from_lattice_point((a_cm || b_cm, a_da || b_da)),
+        a.dimensions,
+    ))
+}
+
+/// Whether a value of type `from` can be stored in a variable of the
+/// strictly wider type `to`.
+pub fn is_widening(from: &RosyType, to: &RosyType) -> bool {
+    from != to && join(from, to).as_ref() == Some(to)
+}
+
+pub trait RosyWiden<T> {
+    fn rosy_widen(self) -> Result<T>;
+}
+
+// RE -> CM
+impl RosyWiden<CM> for &RE {
+    fn rosy_widen(self) -> Result<CM> {
+        Ok(Complex64::new(*self, 0.0))
+    }
+}
+// RE -> DA
+impl RosyWiden<DA> for &RE {
+    fn rosy_widen(self) -> Result<DA> {
+        Ok(DA::constant(*self))
+    }
+}
+// RE -> CD
+impl RosyWiden<CD> for &RE {
+    fn rosy_widen(self) -> Result<CD> {
+        Ok(CD::constant(*self))
+    }
+}
+// CM -> CD
+impl RosyWiden<CD> for &CM {
+    fn rosy_widen(self) -> Result<CD> {
+        Ok(CD::complex_constant(*self))
+    }
+}
+// DA -> CD
+impl RosyWiden<CD> for &DA {
+    fn rosy_widen(self) -> Result<CD> {
+        Ok(CD::from_da(self))
+    }
+}
+// Arrays widen element-wise
+impl<'a, T, U> RosyWiden<Vec<U>> for &'a Vec<T>
+where
+    &'a T: RosyWiden<U>,
+{
+    fn rosy_widen(self) -> Result<Vec<U>> {
+        self.iter().map(|element| element.rosy_widen()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_follows_the_lattice() {
+        assert_eq!(join(&RosyType::RE(), &RosyType::DA()), Some(RosyType::DA()));
+        assert_eq!(join(&RosyType::DA(), &RosyType::RE()), Some(RosyType::DA()));
+        assert_eq!(join(&RosyType::RE(), &RosyType::CM()), Some(RosyType::CM()));
+        assert_eq!(join(&RosyType::CM(), &RosyType::DA()), Some(RosyType::CD()));
+        assert_eq!(join(&RosyType::CM(), &RosyType::CD()), Some(RosyType::CD()));
+        assert_eq!(join(&RosyType::ST(), &RosyType::ST()), Some(RosyType::ST()));
+        assert_eq!(join(&RosyType::RE(), &RosyType::VE()), None);
+        assert_eq!(join(&RosyType::RE(), &RosyType::new(RosyBaseType::DA, 1)), None);
+        assert_eq!(
+            join(&RosyType::new(RosyBaseType::RE, 2), &RosyType::new(RosyBaseType::CM, 2)),
+            Some(RosyType::new(RosyBaseType::CM, 2))
+        );
+    }
+
+    #[test]
+    fn widening_is_strict_and_directed() {
+        assert!(is_widening(&RosyType::RE(), &RosyType::CD()));
+        assert!(is_widening(&RosyType::DA(), &RosyType::CD()));
+        assert!(!is_widening(&RosyType::DA(), &RosyType::RE()));
+        assert!(!is_widening(&RosyType::CM(), &RosyType::DA()));
+        assert!(!is_widening(&RosyType::RE(), &RosyType::RE()));
+    }
+
+    #[test]
+    fn arrays_widen_element_wise() {
+        let values: Vec<RE> = vec![1.0, -2.5];
+        let widened: Vec<CM> = values.rosy_widen().unwrap();
+        assert_eq!(widened, vec![Complex64::new(1.0, 0.0), Complex64::new(-2.5, 0.0)]);
+    }
+}
@@ -0,0 +1,292 @@
+//! Bounded FIT variables and inequality constraints.
+//!
+//! Bounds are enforced by a change of variables, so every algorithm works
+//! on an unconstrained internal variable `u` while the FIT body only ever
+//! sees values inside the bounds (the transformations used by MINUIT):
+//!
+//! - `[lo, hi]`: `x = lo + (hi - lo) (sin u + 1) / 2`
+//! - `[lo, ]`:   `x = lo - 1 + sqrt(u² + 1)`
+//! - `[, hi]`:   `x = hi + 1 - sqrt(u² + 1)`
+//!
+//! Constraints are objectives that only have to stay inside their interval.
+//! Each one contributes `CONSTRAINT_PENALTY` times its distance outside the
+//! interval as an extra residual, which is zero while it is satisfied.
+
+use anyhow::{Result, bail};
+
+use crate::rosy_lib::core::display::RosyDisplay;
+
+/// Weight of a constraint violation relative to an ordinary objective.
+pub const CONSTRAINT_PENALTY: f64 = 1.0e6;
+
+/// Relative distance within which the report treats a value as sitting on
+/// a bound (or a constraint as met), since the optimizers only approach
+/// the boundary to within rounding.
+const REPORT_TOLERANCE: f64 = 1.0e-9;
+
+fn within_tolerance(distance: f64, reference: f64) -> bool {
+    distance <= REPORT_TOLERANCE * reference.abs().max(1.0)
+}
+
+/// Allowed interval of a fit variable or constraint. `None` on a side
+/// means unbounded.
+#[derive(Debug, Clone, Copy)]
+pub struct Bound {
+    pub name: &'static str,
+    pub lower: Option<f64>,
+    pub upper: Option<f64>,
+}
+
+impl Bound {
+    pub fn free(name: &'static str) -> Self {
+        Self { name, lower: None, upper: None }
+    }
+
+    pub fn is_free(&self) -> bool {
+        self.lower.is_none() && self.upper.is_none()
+    }
+
+    fn validate(&self, what: &str) -> Result<()> {
+        for value in [self.lower, self.upper].into_iter().flatten() {
+            if !value.is_finite() {
+                bail!("FIT: {} '{}' has a non-finite bound {}", what, self.name, value);
+            }
+        }
+        if let (Some(lo), Some(hi)) = (self.lower, self.upper)
+            && lo >= hi
+        {
+            bail!(
+                "FIT: {} '{}' has lower bound {} not below its upper bound {}",
+                what, self.name, lo, hi
+            );
+        }
+        Ok(())
+    }
+
+    fn clamp(&self, x: f64) -> f64 {
+        let x = self.lower.map_or(x, |lo| x.max(lo));
+        self.upper.map_or(x, |hi| x.min(hi))
+    }
+
+    /// Internal (unconstrained) value for an external value, which is first
+    /// clamped into the bounds.
+    fn to_internal(self, x: f64) -> f64 {
+        let x = self.clamp(x);
+        match (self.lower, self.upper) {
+            (Some(lo), Some(hi)) => (2.0 * (x - lo) / (hi - lo) - 1.0).clamp(-1.0, 1.0).asin(),
+            (Some(lo), None) => ((x - lo + 1.0).powi(2) - 1.0).max(0.0).sqrt(),
+            (None, Some(hi)) => ((hi - x + 1.0).powi(2) - 1.0).max(0.0).sqrt(),
+            (None, None) => x,
+        }
+    }
+
+    /// External value seen by the FIT body for an internal value.
+    fn to_external(self, u: f64) -> f64 {
+        let x = match (self.lower, self.upper) {
+            (Some(lo), Some(hi)) => lo + (hi - lo) * (u.sin() + 1.0) * 0.5,
+            (Some(lo), None) => lo - 1.0 + (u * u + 1.0).sqrt(),
+            (None, Some(hi)) => hi + 1.0 - (u * u + 1.0).sqrt(),
+            (None, None) => u,
+        };
+        // Guard against rounding just outside the interval
+        self.clamp(x)
+    }
+
+    /// Derivative of [`Self::to_external`] at `u`, for carrying exact
+    /// Jacobians over to the internal variables.
+    fn slope(&self, u: f64) -> f64 {
+        match (self.lower, self.upper) {
+            (Some(lo), Some(hi)) => (hi - lo) * u.cos() * 0.5,
+            (Some(_), None) => u / (u * u + 1.0).sqrt(),
+            (None, Some(_)) => -u / (u * u + 1.0).sqrt(),
+            (None, None) => 1.0,
+        }
+    }
+
+    /// Derivative of [`Self::violation`] with respect to `value`.
+    fn violation_slope(&self, value: f64) -> f64 {
+        if self.lower.is_some_and(|lo| value < lo) {
+            -1.0
+        } else if self.upper.is_some_and(|hi| value > hi) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Distance of `value` outside the interval (0 inside it).
+    fn violation(&self, value: f64) -> f64 {
+        let below = self.lower.map_or(0.0, |lo| (lo - value).max(0.0));
+        let above = self.upper.map_or(0.0, |hi| (value - hi).max(0.0));
+        below + above
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "[{}, {}]",
+            self.lower.map_or(String::new(), |lo| lo.to_string()),
+            self.upper.map_or(String::new(), |hi| hi.to_string()),
+        )
+    }
+}
+
+/// The variable transformation and penalty terms for one FIT.
+pub struct BoundedProblem<'a> {
+    pub variables: &'a [Bound],
+    pub constraints: &'a [Bound],
+}
+
+impl BoundedProblem<'_> {
+    pub fn validate(&self, num_variables: usize) -> Result<()> {
+        if self.variables.len() != num_variables {
+            bail!(
+                "FIT: {} bounds given for {} variables",
+                self.variables.len(), num_variables
+            );
+        }
+        for bound in self.variables {
+            bound.validate("variable")?;
+        }
+        for bound in self.constraints {
+            bound.validate("constraint")?;
+            if bound.is_free() {
+                bail!("FIT: constraint '{}' needs at least one bound", bound.name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether anything needs transforming or penalising.
+    pub fn is_trivial(&self) -> bool {
+        self.constraints.is_empty() && self.variables.iter().all(Bound::is_free)
+    }
+
+    pub fn to_internal(&self, external: &[f64]) -> Vec<f64> {
+        self.variables.iter().zip(external).map(|(b, &x)| b.to_internal(x)).collect()
+    }
+
+    pub fn to_external(&self, internal: &[f64], external: &mut [f64]) {
+        for ((b, &u), x) in self.variables.iter().zip(internal).zip(external.iter_mut()) {
+            *x = b.to_external(u);
+        }
+    }
+
+    /// Chain-rule factor `dx/du` of each variable at the internal point.
+    pub fn slopes(&self, internal: &[f64]) -> Vec<f64> {
+        self.variables.iter().zip(internal).map(|(b, &u)| b.slope(u)).collect()
+    }
+
+    /// Turn the Jacobian rows of the constraints into those of their penalty
+    /// residuals. `values` are the constraint values before [`Self::penalize`].
+    pub fn penalize_jacobian(&self, values: &[f64], jacobian: &mut [Vec<f64>], num_objectives: usize) {
+        let rows = jacobian[num_objectives..].iter_mut().zip(&values[num_objectives..]);
+        for (bound, (row, &value)) in self.constraints.iter().zip(rows) {
+            let factor = CONSTRAINT_PENALTY * bound.violation_slope(value);
+            row.iter_mut().for_each(|d| *d *= factor);
+        }
+    }
+
+    /// Replace the trailing constraint values returned by the body with
+    /// their penalty residuals.
+    pub fn penalize(&self, values: &mut [f64], num_objectives: usize) {
+        for (bound, value) in self.constraints.iter().zip(values[num_objectives..].iter_mut()) {
+            *value = CONSTRAINT_PENALTY * bound.violation(*value);
+        }
+    }
+
+    /// Summary of the bounded variables and constraints after the fit.
+    pub fn report(&self, variables: &[f64], values: &[f64], num_objectives: usize) -> String {
+        let mut out = String::new();
+        for (bound, &x) in self.variables.iter().zip(variables) {
+            if bound.is_free() {
+                continue;
+            }
+            let at = |limit: Option<f64>| limit.is_some_and(|b| within_tolerance((x - b).abs(), b));
+            let status = if at(bound.lower) {
+                "  at lower bound"
+            } else if at(bound.upper) {
+                "  at upper bound"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                " FIT variable   {} = {} in {}{}\n",
+                bound.name, (&x).rosy_display(), bound.describe(), status
+            ));
+        }
+        for (bound, &g) in self.constraints.iter().zip(&values[num_objectives..]) {
+            let violation = bound.violation(g);
+            let status = if !within_tolerance(violation, g) {
+                format!("  VIOLATED by {}", (&violation).rosy_display().trim())
+            } else {
+                String::new()
+            };
+            out.push_str(&format!(
+                " FIT constraint {} = {} in {}{}\n",
+                bound.name, (&g).rosy_display(), bound.describe(), status
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transformations_round_trip_inside_the_bounds() {
+        let bounds = [
+            Bound { name: "A", lower: Some(0.5), upper: Some(2.0) },
+            Bound { name: "B", lower: Some(-1.0), upper: None },
+            Bound { name: "C", lower: None, upper: Some(3.0) },
+            Bound::free("D"),
+        ];
+        for bound in &bounds {
+            for x in [-0.5, 0.75, 1.5, 2.5] {
+                let inside = bound.clamp(x);
+                let back = bound.to_external(bound.to_internal(x));
+                assert!((back - inside).abs() < 1e-12, "{} {} -> {}", bound.name, x, back);
+            }
+            for u in [-10.0, -1.0, 0.0, 0.3, 7.0] {
+                let x = bound.to_external(u);
+                assert_eq!(bound.clamp(x), x);
+            }
+        }
+    }
+
+    #[test]
+    fn slopes_match_finite_differences() {
+        let bounds = [
+            Bound { name: "A", lower: Some(0.5), upper: Some(2.0) },
+            Bound { name: "B", lower: Some(-1.0), upper: None },
+            Bound { name: "C", lower: None, upper: Some(3.0) },
+        ];
+        let h = 1e-6;
+        for bound in &bounds {
+            for u in [-2.0, -0.3, 0.4, 1.2] {
+                let fd = (bound.to_external(u + h) - bound.to_external(u - h)) / (2.0 * h);
+                assert!((bound.slope(u) - fd).abs() < 1e-6, "{} at {}", bound.name, u);
+            }
+        }
+    }
+
+    #[test]
+    fn constraints_penalize_only_violations() {
+        let constraints = [Bound { name: "G", lower: None, upper: Some(1.0) }];
+        let problem = BoundedProblem { variables: &[], constraints: &constraints };
+        let mut values = vec![4.0, 0.5];
+        problem.penalize(&mut values, 1);
+        assert_eq!(values, vec![4.0, 0.0]);
+        let mut values = vec![4.0, 1.5];
+        problem.penalize(&mut values, 1);
+        assert_eq!(values, vec![4.0, 0.5 * CONSTRAINT_PENALTY]);
+    }
+
+    #[test]
+    fn inverted_bounds_are_rejected() {
+        let variables = [Bound { name: "X", lower: Some(2.0), upper: Some(1.0) }];
+        let problem = BoundedProblem { variables: &variables, constraints: &[] };
+        assert!(problem.validate(1).is_err());
+    }
+}
@@ -2,30 +2,37 @@
 ///
 /// This is a Levenberg-Marquardt least-squares optimizer. It minimizes
 /// the sum of squares of the objective functions by using finite-difference
-/// approximations to the Jacobian matrix.
+/// approximations to the Jacobian matrix, or the exact Jacobian when the
+/// body supplies one.
 ///
 /// When there are fewer objectives than variables, it uses a damped
 /// gradient descent approach instead.
 
+use super::{Evaluation, FitBody};
+
+/// Jacobian of the objectives, `jacobian[objective][variable]`.
+pub type Jacobian = Vec<Vec<f64>>;
+
 /// Run LMDIF optimization.
 ///
-/// Minimizes sum of squares of objectives using Levenberg-Marquardt method
-/// with finite-difference Jacobian.
-pub fn lmdif<F>(
+/// Minimizes sum of squares of objectives using Levenberg-Marquardt method,
+/// with the Jacobian from `body` if it returns one and finite differences
+/// otherwise.
+pub fn lmdif<B>(
     variables: &mut [f64],
     eps: f64,
     max_iter: usize,
     num_objectives: usize,
-    body: &mut F,
+    body: &mut B,
 ) -> anyhow::Result<()>
 where
-    F: FnMut(&mut [f64]) -> anyhow::Result<Vec<f64>>,
+    B: FitBody<Evaluation>,
 {
     let nv = variables.len();
     let nf = num_objectives;
 
     // Initial evaluation
-    let mut residuals = body(variables)?;
+    let Evaluation { values: mut residuals, jacobian: mut exact_jacobian } = body.evaluate(variables)?;
     let mut cost = sum_of_squares(&residuals);
 
     // Levenberg-Marquardt parameter
@@ -45,23 +52,33 @@ where
             break;
         }
 
-        // Compute Jacobian via forward finite differences
-        let mut jacobian = vec![vec![0.0; nv]; nf];
-        for j in 0..nv {
-            let orig = variables[j];
-            let h = if orig.abs() > 1e-10 {
-                diff_step * orig.abs()
-            } else {
-                diff_step
-            };
-            variables[j] = orig + h;
-            let perturbed = body(variables)?;
-            variables[j] = orig;
-
-            for i in 0..nf {
-                jacobian[i][j] = (perturbed[i] - residuals[i]) / h;
+        let jacobian = match &exact_jacobian {
+            Some(jacobian) => jacobian.clone(),
+            None => {
+                // Compute Jacobian via forward finite differences; the
+                // perturbed points are independent, so evaluate them together
+                let steps: Vec<f64> = variables
+                    .iter()
+                    .map(|x| if x.abs() > 1e-10 { diff_step * x.abs() } else { diff_step })
+                    .collect();
+                let mut perturbed_points: Vec<Vec<f64>> = (0..nv)
+                    .map(|j| {
+                        let mut point = variables.to_vec();
+                        point[j] += steps[j];
+                        point
+                    })
+                    .collect();
+                let perturbed = body.evaluate_all(&mut perturbed_points)?;
+
+                let mut jacobian = vec![vec![0.0; nv]; nf];
+                for (j, evaluation) in perturbed.iter().enumerate() {
+                    for i in 0..nf {
+                        jacobian[i][j] = (evaluation.values[i] - residuals[i]) / steps[j];
+                    }
+                }
+                jacobian
             }
-        }
+        };
 
         // Compute J^T * J and J^T * r
         let mut jtj = vec![vec![0.0; nv]; nv];
@@ -105,13 +122,15 @@ where
             trial[j] += delta[j];
         }
 
-        let trial_residuals = body(trial.as_mut_slice())?;
+        let Evaluation { values: trial_residuals, jacobian: trial_jacobian } =
+            body.evaluate(trial.as_mut_slice())?;
         let trial_cost = sum_of_squares(&trial_residuals);
 
         if trial_cost < cost {
             // Accept step
             variables.copy_from_slice(&trial);
             residuals = trial_residuals;
+            exact_jacobian = trial_jacobian;
             cost = trial_cost;
             lambda = (lambda / lambda_down).max(1e-12);
 
@@ -139,7 +158,7 @@ where
     variables.copy_from_slice(&best_vars);
 
     // Run body one final time with best values so objectives are set
-    body(variables)?;
+    body.evaluate(variables)?;
 
     Ok(())
 }
@@ -1,6 +1,19 @@
 pub mod simplex;
 pub mod lmdif;
 pub mod sa;
+pub mod bounds;
+#[cfg(feature = "mpi")]
+pub mod parallel;
+
+pub use bounds::Bound;
+use bounds::BoundedProblem;
+pub use lmdif::Jacobian;
+#[cfg(feature = "mpi")]
+pub use parallel::Distributed;
+
+use std::cell::Cell;
+
+use crate::rosy_lib::taylor::{DA, get_config};
 
 /// Result of an optimization run
 #[derive(Debug)]
@@ -13,41 +26,409 @@ pub struct OptimizationResult {
     pub iterations: usize,
 }
 
+/// One run of the FIT body: its objective and constraint values, and, for
+/// DA fit variables, their exact first derivatives.
+#[derive(Debug, Clone, Default)]
+pub struct Evaluation {
+    pub values: Vec<f64>,
+    pub jacobian: Option<Jacobian>,
+}
+
+impl From<Vec<f64>> for Evaluation {
+    fn from(values: Vec<f64>) -> Self {
+        Self { values, jacobian: None }
+    }
+}
+
+impl Evaluation {
+    /// Read the values and first derivatives off DA results computed from
+    /// fit variables seeded by `parameters`.
+    pub fn from_da(results: &[DA], parameters: &DaParameters) -> anyhow::Result<Self> {
+        let mut values = Vec::with_capacity(results.len());
+        let mut jacobian = Vec::with_capacity(results.len());
+        for result in results {
+            values.push(result.constant_part());
+            let row = (0..parameters.count)
+                .map(|j| result.linear_coeff(parameters.variable(j)))
+                .collect::<anyhow::Result<Vec<f64>>>()?;
+            jacobian.push(row);
+        }
+        Ok(Self { values, jacobian: Some(jacobian) })
+    }
+}
+
+/// The body of a FIT loop as the optimizers see it: evaluates variable
+/// values to a `T`. Any `FnMut(&mut [f64]) -> Result<T>` closure is one.
+pub trait FitBody<T> {
+    fn evaluate(&mut self, variables: &mut [f64]) -> anyhow::Result<T>;
+
+    /// Evaluate independent points. Sequential unless the body can spread
+    /// them over several processes (see `parallel::Distributed`).
+    fn evaluate_all(&mut self, points: &mut [Vec<f64>]) -> anyhow::Result<Vec<T>> {
+        points.iter_mut().map(|point| self.evaluate(point)).collect()
+    }
+
+    /// How many points [`Self::evaluate_all`] evaluates at once.
+    fn concurrency(&self) -> usize {
+        1
+    }
+
+    /// Whether this process runs the optimizer; false on the worker ranks
+    /// of a parallel FIT.
+    fn drives(&self) -> bool {
+        true
+    }
+
+    /// On a worker rank: evaluate points for the driving process until it
+    /// is done, and return the final variables.
+    fn serve(&mut self) -> anyhow::Result<Vec<f64>> {
+        anyhow::bail!("FIT: only the worker ranks of a parallel FIT serve evaluations")
+    }
+
+    /// On the driving process: pass the final variables, or the failure,
+    /// on to any workers.
+    fn finish(&mut self, _outcome: Result<&[f64], &str>) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+impl<T, F> FitBody<T> for F
+where
+    F: FnMut(&mut [f64]) -> anyhow::Result<T>,
+{
+    fn evaluate(&mut self, variables: &mut [f64]) -> anyhow::Result<T> {
+        self(variables)
+    }
+}
+
+thread_local! {
+    /// The DA variables `first..end` (1-based) that the DA FIT bodies
+    /// running on this thread have their parameters on; empty outside one.
+    static SEEDED: Cell<(usize, usize)> = const { Cell::new((0, 0)) };
+}
+
+/// The DA variables one run of a DA FIT body has its fit variables on: the
+/// last ones DAINI set up, one per fit variable, below any an enclosing DA
+/// FIT uses. While this is alive the body cannot take them for itself with
+/// [`da_variable`]; dropping it frees them again.
+pub struct DaParameters {
+    first: usize,
+    count: usize,
+    enclosing: (usize, usize),
+}
+
+impl DaParameters {
+    /// Reserve DA variables for `count` fit variables.
+    pub fn seed(count: usize) -> anyhow::Result<Self> {
+        let num_vars = get_config()
+            .map_err(|e| e.context("FIT with DA variables needs DAINI to set up DA variables for them"))?
+            .num_vars;
+        let enclosing = SEEDED.get();
+        let end = if enclosing.0 < enclosing.1 { enclosing.0 } else { num_vars + 1 };
+        let first = end.saturating_sub(count);
+        if first < 1 && end > num_vars {
+            anyhow::bail!(
+                "FIT with {} DA variables needs DAINI to set up at least that many DA variables, found {}",
+                count, num_vars
+            );
+        }
+        if first < 1 {
+            anyhow::bail!(
+                "FIT with {} DA variables needs that many DA variables below the {} an enclosing FIT uses, but DAINI set up only {}",
+                count, num_vars + 1 - end, num_vars
+            );
+        }
+        SEEDED.set((first, end));
+        Ok(Self { first, count, enclosing })
+    }
+
+    /// The DA variable (1-based) that FIT variable `index` (0-based) is
+    /// seeded on.
+    pub fn variable(&self, index: usize) -> usize {
+        self.first + index
+    }
+
+    /// The value of FIT variable `index` as a DA parameter: `value` plus its
+    /// DA variable, so the body's results carry their first derivatives with
+    /// respect to each fit variable.
+    pub fn parameter(&self, index: usize, value: f64) -> anyhow::Result<DA> {
+        let mut parameter = DA::variable(self.variable(index))?;
+        parameter.add_constant_in_place(value);
+        Ok(parameter)
+    }
+}
+
+impl Drop for DaParameters {
+    fn drop(&mut self) {
+        SEEDED.set(self.enclosing);
+    }
+}
+
+/// `DA(var_index)` as a program writes it. Inside a DA FIT body the DA
+/// variables its fit variables are seeded on are not available.
+pub fn da_variable(var_index: usize) -> anyhow::Result<DA> {
+    let (first, end) = SEEDED.get();
+    if (first..end).contains(&var_index) {
+        anyhow::bail!(
+            "DA({}) is taken by the enclosing FIT, whose DA fit variables are seeded on DA({}) to DA({}); set up more DA variables with DAINI so the body's own stay below DA({})",
+            var_index, first, end - 1, first
+        );
+    }
+    DA::variable(var_index)
+}
+
 /// Run an optimization using the specified algorithm.
 ///
 /// # Arguments
 /// * `variables` - Initial values of the variables to optimize
+/// * `bounds` - Allowed interval of each variable (see [`bounds`])
+/// * `constraints` - Intervals for the constraint values the body returns
+///   after its `num_objectives` objectives
 /// * `eps` - Convergence tolerance
 /// * `max_iter` - Maximum number of iterations (0 = execute once, no optimization)
 /// * `algorithm` - Algorithm number (1 = Simplex, 3 = Simulated Annealing, 4 = LMDIF)
-/// * `body` - Closure that takes variable values and returns objective values
-///
-/// # Returns
-/// The optimized variable values and final objective values
-pub fn run_fit<F>(
+/// * `body` - Takes variable values and returns the objective values; when
+///   it also returns a Jacobian, LMDIF uses it instead of finite differences
+#[allow(clippy::too_many_arguments)]
+pub fn run_fit<B>(
     variables: &mut [f64],
+    bounds: &[Bound],
+    constraints: &[Bound],
     eps: f64,
     max_iter: usize,
     algorithm: usize,
     num_objectives: usize,
-    mut body: F,
+    mut body: B,
 ) -> anyhow::Result<()>
 where
-    F: FnMut(&mut [f64]) -> anyhow::Result<Vec<f64>>,
+    B: FitBody<Evaluation>,
+{
+    let problem = BoundedProblem { variables: bounds, constraints };
+    problem.validate(variables.len())?;
+
+    if !body.drives() {
+        let final_variables = body.serve()?;
+        variables.copy_from_slice(&final_variables);
+        // Leave this rank in the same state as the driving one
+        body.evaluate(variables)?;
+        return Ok(());
+    }
+
+    // The algorithms see the unconstrained internal variables; the body
+    // always sees values inside the bounds.
+    let mut internal = problem.to_internal(variables);
+    let mut transformed = Transformed {
+        problem: &problem,
+        body: &mut body,
+        num_objectives,
+        last_values: Vec::new(),
+    };
+    let num_residuals = num_objectives + constraints.len();
+    let result = run_algorithm(&mut internal, eps, max_iter, algorithm, num_residuals, &mut transformed);
+    let last_values = transformed.last_values;
+    if let Err(e) = result {
+        // Release any workers before reporting the failure
+        let _ = body.finish(Err(&format!("{:#}", e)));
+        return Err(e);
+    }
+
+    problem.to_external(&internal, variables);
+    body.finish(Ok(variables))?;
+    // Leave this rank in the same state as the workers, and report the
+    // values at the final variables rather than at the last trial point.
+    // With `max_iter` 0 the single run already was at the final variables.
+    let final_values = if max_iter == 0 {
+        last_values
+    } else {
+        body.evaluate(variables)?.values
+    };
+    if !problem.is_trivial() {
+        print!("{}", problem.report(variables, &final_values, num_objectives));
+    }
+    Ok(())
+}
+
+/// The FIT body seen through the variable transformation and constraint
+/// penalties of a [`BoundedProblem`].
+struct Transformed<'a, B> {
+    problem: &'a BoundedProblem<'a>,
+    body: &'a mut B,
+    num_objectives: usize,
+    /// Untransformed values of the latest evaluation, for the report of a
+    /// FIT whose body runs only once
+    last_values: Vec<f64>,
+}
+
+impl<B: FitBody<Evaluation>> Transformed<'_, B> {
+    fn external(&self, internal: &[f64]) -> Vec<f64> {
+        let mut external = vec![0.0; internal.len()];
+        self.problem.to_external(internal, &mut external);
+        external
+    }
+
+    fn penalize(&mut self, internal: &[f64], evaluation: Evaluation) -> anyhow::Result<Evaluation> {
+        let Evaluation { mut values, jacobian } = evaluation;
+        let expected = self.num_objectives + self.problem.constraints.len();
+        if values.len() != expected {
+            anyhow::bail!(
+                "FIT: body returned {} values, expected {} objectives and {} constraints",
+                values.len(), self.num_objectives, self.problem.constraints.len()
+            );
+        }
+
+        let jacobian = jacobian.map(|mut jacobian| {
+            let slopes = self.problem.slopes(internal);
+            for row in jacobian.iter_mut() {
+                row.iter_mut().zip(&slopes).for_each(|(d, slope)| *d *= slope);
+            }
+            self.problem.penalize_jacobian(&values, &mut jacobian, self.num_objectives);
+            jacobian
+        });
+        self.last_values.clone_from(&values);
+        self.problem.penalize(&mut values, self.num_objectives);
+        Ok(Evaluation { values, jacobian })
+    }
+}
+
+impl<B: FitBody<Evaluation>> FitBody<Evaluation> for Transformed<'_, B> {
+    fn evaluate(&mut self, internal: &mut [f64]) -> anyhow::Result<Evaluation> {
+        let evaluation = self.body.evaluate(&mut self.external(internal))?;
+        self.penalize(internal, evaluation)
+    }
+
+    fn evaluate_all(&mut self, points: &mut [Vec<f64>]) -> anyhow::Result<Vec<Evaluation>> {
+        let mut external: Vec<Vec<f64>> = points.iter().map(|u| self.external(u)).collect();
+        let evaluations = self.body.evaluate_all(&mut external)?;
+        points
+            .iter()
+            .zip(evaluations)
+            .map(|(u, evaluation)| self.penalize(u, evaluation))
+            .collect()
+    }
+
+    fn concurrency(&self) -> usize {
+        self.body.concurrency()
+    }
+}
+
+/// Objective values only, for the algorithms that do not use derivatives.
+impl<B: FitBody<Evaluation>> FitBody<Vec<f64>> for Transformed<'_, B> {
+    fn evaluate(&mut self, internal: &mut [f64]) -> anyhow::Result<Vec<f64>> {
+        FitBody::<Evaluation>::evaluate(self, internal).map(|evaluation| evaluation.values)
+    }
+
+    fn evaluate_all(&mut self, points: &mut [Vec<f64>]) -> anyhow::Result<Vec<Vec<f64>>> {
+        let evaluations = FitBody::<Evaluation>::evaluate_all(self, points)?;
+        Ok(evaluations.into_iter().map(|evaluation| evaluation.values).collect())
+    }
+
+    fn concurrency(&self) -> usize {
+        self.body.concurrency()
+    }
+}
+
+fn run_algorithm<B>(
+    variables: &mut [f64],
+    eps: f64,
+    max_iter: usize,
+    algorithm: usize,
+    num_objectives: usize,
+    body: &mut B,
+) -> anyhow::Result<()>
+where
+    B: FitBody<Evaluation> + FitBody<Vec<f64>>,
 {
     // If max_iter is 0, execute body once and return
     if max_iter == 0 {
-        body(variables)?;
+        FitBody::<Evaluation>::evaluate(body, variables)?;
         return Ok(());
     }
 
     match algorithm {
-        1 => simplex::nelder_mead(variables, eps, max_iter, num_objectives, &mut body),
+        1 => simplex::nelder_mead(variables, eps, max_iter, num_objectives, body),
         // Algorithm 2 is not available, rerouted to LMDIF per COSY manual
-        2 | 4 => lmdif::lmdif(variables, eps, max_iter, num_objectives, &mut body),
-        3 => sa::simulated_annealing(variables, eps, max_iter, num_objectives, &mut body),
+        2 | 4 => lmdif::lmdif(variables, eps, max_iter, num_objectives, body),
+        3 => sa::simulated_annealing(variables, eps, max_iter, num_objectives, body),
         other => {
             anyhow::bail!("Unknown optimization algorithm: {}. Supported: 1 (Simplex), 3 (SA), 4 (LMDIF)", other)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rosy_lib::taylor::init_taylor;
+    use serial_test::serial;
+    use std::{cell::Cell, rc::Rc};
+
+    /// A body that evaluates batches itself, counting the points it got at once.
+    struct Batched {
+        concurrency: usize,
+        batched_points: Rc<Cell<usize>>,
+    }
+
+    impl FitBody<Evaluation> for Batched {
+        fn evaluate(&mut self, x: &mut [f64]) -> anyhow::Result<Evaluation> {
+            Ok(vec![x[0] - 3.0, 2.0 * (x[1] + 1.0)].into())
+        }
+
+        fn evaluate_all(&mut self, points: &mut [Vec<f64>]) -> anyhow::Result<Vec<Evaluation>> {
+            self.batched_points.set(self.batched_points.get() + points.len());
+            points.iter_mut().map(|point| self.evaluate(point)).collect()
+        }
+
+        fn concurrency(&self) -> usize {
+            self.concurrency
+        }
+    }
+
+    #[test]
+    fn driver_ends_on_the_final_variables() {
+        for algorithm in [1, 3, 4] {
+            let mut variables = vec![0.5, 0.5];
+            let mut last_point = Vec::new();
+            let body = |x: &mut [f64]| -> anyhow::Result<Evaluation> {
+                last_point = x.to_vec();
+                Ok(vec![x[0] - 3.0, 2.0 * (x[1] + 1.0)].into())
+            };
+            let bounds = [Bound::free("X"), Bound::free("Y")];
+            run_fit(&mut variables, &bounds, &[], 1e-6, 20000, algorithm, 2, body).unwrap();
+            assert_eq!(last_point, variables, "algorithm {}", algorithm);
+        }
+    }
+
+    #[test]
+    fn batched_bodies_reach_the_same_minimum() {
+        for algorithm in [1, 3, 4] {
+            let mut variables = vec![0.5, 0.5];
+            let batched_points = Rc::new(Cell::new(0));
+            let body = Batched { concurrency: 4, batched_points: batched_points.clone() };
+            let bounds = [Bound::free("X"), Bound::free("Y")];
+            run_fit(&mut variables, &bounds, &[], 1e-6, 20000, algorithm, 2, body).unwrap();
+            assert!((variables[0] - 3.0).abs() < 1e-3, "algorithm {}: {:?}", algorithm, variables);
+            assert!((variables[1] + 1.0).abs() < 1e-3, "algorithm {}: {:?}", algorithm, variables);
+            assert!(batched_points.get() > 0, "algorithm {} never batched", algorithm);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn da_parameters_leave_the_first_variables_to_the_body() {
+        init_taylor(1, 3).unwrap();
+        let parameters = DaParameters::seed(2).unwrap();
+        assert_eq!((parameters.variable(0), parameters.variable(1)), (2, 3));
+        assert!(da_variable(1).is_ok());
+        assert!(da_variable(3).is_err());
+
+        // A nested DA FIT takes the variables below, and runs out after them
+        let inner = DaParameters::seed(1).unwrap();
+        assert_eq!(inner.variable(0), 1);
+        assert!(DaParameters::seed(1).is_err());
+        drop(inner);
+        drop(parameters);
+
+        assert!(da_variable(3).is_ok());
+        assert!(DaParameters::seed(4).is_err());
+    }
+}
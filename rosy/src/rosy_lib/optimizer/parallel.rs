@@ -0,0 +1,152 @@
+//! Parallel FIT body evaluation over MPI ranks (`PFIT`).
+//!
+//! Rank 0 runs the optimizer. Whenever it has independent points to
+//! evaluate (simplex vertices, finite-difference Jacobian columns, batches
+//! of annealing proposals) it sends every other rank a share of them and
+//! evaluates its own share meanwhile. The other ranks only evaluate what
+//! they are sent, until rank 0 tells them the final variables.
+//!
+//! Each batch is a single message per rank and a single reply, so a rank
+//! never has to receive while it still has a send outstanding.
+
+use anyhow::{Context, Result, anyhow, bail};
+use mpi::traits::*;
+
+use super::{Evaluation, FitBody, Jacobian};
+use crate::rosy_lib::mpi::RosyMPIContext;
+
+const EVALUATE: u8 = 0;
+const FINISHED: u8 = 1;
+const FAILED: u8 = 2;
+
+/// A message from rank 0: the command, the points to evaluate with their
+/// batch positions, the final variables, and the reason for a failure.
+type Command = (u8, Vec<(u64, Vec<f64>)>, Vec<f64>, String);
+
+/// A worker's reply: each point's batch position and its evaluation, or
+/// the error that stopped it.
+type Reply = Vec<(u64, Result<(Vec<f64>, Option<Jacobian>), String>)>;
+
+/// A FIT body whose batches are spread over all MPI ranks.
+pub struct Distributed<'a, B> {
+    context: &'a RosyMPIContext,
+    body: B,
+}
+
+impl<'a, B: FitBody<Evaluation>> Distributed<'a, B> {
+    pub fn new(context: &'a RosyMPIContext, body: B) -> Self {
+        Self { context, body }
+    }
+
+    fn send_command(&self, rank: i32, command: &Command) -> Result<()> {
+        let bytes = bincode::encode_to_vec(command, self.context.bincode_config)
+            .context("Failed to serialize PFIT command")?;
+        self.context.world.process_at_rank(rank).send(&bytes);
+        Ok(())
+    }
+
+    fn receive_reply(&self, rank: i32) -> Result<Reply> {
+        let (bytes, _status) = self.context.world.process_at_rank(rank).receive_vec::<u8>();
+        let (reply, _): (Reply, _) = bincode::decode_from_slice(&bytes, self.context.bincode_config)
+            .with_context(|| format!("Failed to deserialize PFIT results from rank {}", rank))?;
+        Ok(reply)
+    }
+}
+
+impl<B: FitBody<Evaluation>> FitBody<Evaluation> for Distributed<'_, B> {
+    fn evaluate(&mut self, variables: &mut [f64]) -> Result<Evaluation> {
+        self.body.evaluate(variables)
+    }
+
+    fn evaluate_all(&mut self, points: &mut [Vec<f64>]) -> Result<Vec<Evaluation>> {
+        let size = self.context.size as usize;
+        if size == 1 || points.len() < 2 {
+            return points.iter_mut().map(|point| self.body.evaluate(point)).collect();
+        }
+
+        // Point k is evaluated on rank k % size
+        let mut shares: Vec<Vec<(u64, Vec<f64>)>> = vec![Vec::new(); size];
+        for (k, point) in points.iter().enumerate() {
+            shares[k % size].push((k as u64, point.clone()));
+        }
+        let mut busy = Vec::new();
+        for (rank, share) in shares.into_iter().enumerate().skip(1) {
+            if !share.is_empty() {
+                self.send_command(rank as i32, &(EVALUATE, share, Vec::new(), String::new()))?;
+                busy.push(rank as i32);
+            }
+        }
+
+        // Keep going after a failure so every reply is still collected
+        let mut results: Vec<Option<Result<Evaluation>>> = (0..points.len()).map(|_| None).collect();
+        for k in (0..points.len()).step_by(size) {
+            results[k] = Some(self.body.evaluate(&mut points[k]));
+        }
+        for rank in busy {
+            for (k, outcome) in self.receive_reply(rank)? {
+                let slot = results
+                    .get_mut(k as usize)
+                    .with_context(|| format!("Rank {} returned an unknown PFIT point {}", rank, k))?;
+                *slot = Some(
+                    outcome
+                        .map(|(values, jacobian)| Evaluation { values, jacobian })
+                        .map_err(|e| anyhow!("{} (on rank {})", e, rank)),
+                );
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|result| result.context("A PFIT point was not evaluated by any rank")?)
+            .collect()
+    }
+
+    fn concurrency(&self) -> usize {
+        self.context.size as usize
+    }
+
+    fn drives(&self) -> bool {
+        self.context.rank == 0
+    }
+
+    fn serve(&mut self) -> Result<Vec<f64>> {
+        loop {
+            let (bytes, _status) = self.context.world.process_at_rank(0).receive_vec::<u8>();
+            let ((command, points, final_variables, reason), _): (Command, _) =
+                bincode::decode_from_slice(&bytes, self.context.bincode_config)
+                    .context("Failed to deserialize PFIT command from rank 0")?;
+            match command {
+                EVALUATE => {
+                    let reply: Reply = points
+                        .into_iter()
+                        .map(|(k, mut point)| {
+                            let outcome = self
+                                .body
+                                .evaluate(&mut point)
+                                .map(|evaluation| (evaluation.values, evaluation.jacobian))
+                                .map_err(|e| format!("{:#}", e));
+                            (k, outcome)
+                        })
+                        .collect();
+                    let bytes = bincode::encode_to_vec(&reply, self.context.bincode_config)
+                        .context("Failed to serialize PFIT results")?;
+                    self.context.world.process_at_rank(0).send(&bytes);
+                }
+                FINISHED => return Ok(final_variables),
+                FAILED => bail!("PFIT failed on rank 0: {}", reason),
+                other => bail!("Unknown PFIT command {} from rank 0", other),
+            }
+        }
+    }
+
+    fn finish(&mut self, outcome: Result<&[f64], &str>) -> Result<()> {
+        let command = match outcome {
+            Ok(variables) => (FINISHED, Vec::new(), variables.to_vec(), String::new()),
+            Err(reason) => (FAILED, Vec::new(), Vec::new(), reason.to_string()),
+        };
+        for rank in 1..self.context.size {
+            self.send_command(rank, &command)?;
+        }
+        Ok(())
+    }
+}
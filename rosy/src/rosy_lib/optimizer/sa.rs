@@ -11,6 +11,7 @@
 /// ensuring reproducible results when the seed is fixed (default: 0).
 
 use super::super::core::rng;
+use super::FitBody;
 
 /// Evaluate the merit function (sum of squares of objectives)
 fn merit(objectives: &[f64]) -> f64 {
@@ -26,22 +27,28 @@ fn merit(objectives: &[f64]) -> f64 {
 /// - `eps`: Convergence tolerance
 /// - `max_iter`: Maximum number of iterations
 /// - `_num_objectives`: Number of objectives (unused, kept for API consistency)
-/// - `body`: Closure that evaluates objectives given variable values
-pub fn simulated_annealing<F>(
+/// - `body`: Evaluates objectives given variable values
+///
+/// When `body` can evaluate several points at once, that many candidates
+/// are drawn around the current point and evaluated together, then
+/// considered in turn. Once one is accepted the rest were drawn around a
+/// stale point and are dropped, so every move is still a perturbation of
+/// the point it moves from.
+pub fn simulated_annealing<B>(
     variables: &mut [f64],
     eps: f64,
     max_iter: usize,
     _num_objectives: usize,
-    body: &mut F,
+    body: &mut B,
 ) -> anyhow::Result<()>
 where
-    F: FnMut(&mut [f64]) -> anyhow::Result<Vec<f64>>,
+    B: FitBody<Vec<f64>>,
 {
     let nv = variables.len();
 
     // Initial evaluation
     let mut current = variables.to_vec();
-    let objs = body(current.as_mut_slice())?;
+    let objs = body.evaluate(current.as_mut_slice())?;
     let mut current_cost = merit(&objs);
 
     // Track best solution found
@@ -76,75 +83,98 @@ where
     let mut total_count = 0u64;
     let adapt_interval = (20 * nv).max(50);
 
-    for iteration in 0..max_iter {
+    let mut iteration = 0;
+    'search: while iteration < max_iter {
         // Check convergence
         if best_cost < eps * eps {
             break;
         }
 
-        // Generate a random neighbor by perturbing one variable at a time
-        let mut candidate = current.clone();
-        for j in 0..nv {
-            candidate[j] += step_sizes[j] * rng::rng_f64_symmetric();
-        }
-
-        // Evaluate candidate
-        let candidate_objs = body(candidate.as_mut_slice())?;
-        let candidate_cost = merit(&candidate_objs);
-
-        // Metropolis acceptance criterion
-        let accept = if candidate_cost < current_cost {
-            true
-        } else {
-            let delta = candidate_cost - current_cost;
-            let acceptance_prob = (-delta / temperature).exp();
-            rng::rng_f64() < acceptance_prob
-        };
-
-        if accept {
-            current = candidate;
-            current_cost = candidate_cost;
-            accept_count += 1;
-
-            // Update best if this is the best we've seen
-            if current_cost < best_cost {
-                best = current.clone();
-                best_cost = current_cost;
+        // Generate random neighbors by perturbing every variable
+        let batch = body.concurrency().clamp(1, max_iter - iteration);
+        let mut candidates: Vec<Vec<f64>> = (0..batch)
+            .map(|_| {
+                let mut candidate = current.clone();
+                for j in 0..nv {
+                    candidate[j] += step_sizes[j] * rng::rng_f64_symmetric();
+                }
+                candidate
+            })
+            .collect();
+
+        // Evaluate candidates
+        let candidate_costs: Vec<f64> = body
+            .evaluate_all(&mut candidates)?
+            .iter()
+            .map(|objs| merit(objs))
+            .collect();
+
+        for (candidate, candidate_cost) in candidates.into_iter().zip(candidate_costs) {
+            if best_cost < eps * eps {
+                break 'search;
             }
-        }
-
-        total_count += 1;
 
-        // Adaptive step size adjustment
-        if total_count % adapt_interval as u64 == 0 {
-            let accept_ratio = accept_count as f64 / total_count as f64;
-
-            // Target acceptance ratio around 0.3-0.5
-            if accept_ratio > 0.5 {
-                // Too many acceptances — increase step sizes
-                for s in step_sizes.iter_mut() {
-                    *s *= 1.2;
+            // Metropolis acceptance criterion
+            let accept = if candidate_cost < current_cost {
+                true
+            } else {
+                let delta = candidate_cost - current_cost;
+                let acceptance_prob = (-delta / temperature).exp();
+                rng::rng_f64() < acceptance_prob
+            };
+            let mut moved = accept;
+
+            if accept {
+                current = candidate;
+                current_cost = candidate_cost;
+                accept_count += 1;
+
+                // Update best if this is the best we've seen
+                if current_cost < best_cost {
+                    best = current.clone();
+                    best_cost = current_cost;
                 }
-            } else if accept_ratio < 0.2 {
-                // Too few acceptances — decrease step sizes
-                for s in step_sizes.iter_mut() {
-                    *s *= 0.8;
+            }
+
+            total_count += 1;
+
+            // Adaptive step size adjustment
+            if total_count.is_multiple_of(adapt_interval as u64) {
+                let accept_ratio = accept_count as f64 / total_count as f64;
+
+                // Target acceptance ratio around 0.3-0.5
+                if accept_ratio > 0.5 {
+                    // Too many acceptances — increase step sizes
+                    for s in step_sizes.iter_mut() {
+                        *s *= 1.2;
+                    }
+                } else if accept_ratio < 0.2 {
+                    // Too few acceptances — decrease step sizes
+                    for s in step_sizes.iter_mut() {
+                        *s *= 0.8;
+                    }
                 }
+
+                accept_count = 0;
+                total_count = 0;
             }
 
-            accept_count = 0;
-            total_count = 0;
-        }
+            // Cool down
+            temperature *= cooling_rate;
 
-        // Cool down
-        temperature *= cooling_rate;
+            // Periodically restart from best (reheating)
+            if iteration > 0 && iteration % (max_iter / 4).max(1) == 0 {
+                current = best.clone();
+                current_cost = best_cost;
+                // Slight reheat to allow further exploration
+                temperature = temperature.max(t_initial * 0.01);
+                moved = true;
+            }
 
-        // Periodically restart from best (reheating)
-        if iteration > 0 && iteration % (max_iter / 4).max(1) == 0 {
-            current = best.clone();
-            current_cost = best_cost;
-            // Slight reheat to allow further exploration
-            temperature = temperature.max(t_initial * 0.01);
+            iteration += 1;
+            if moved {
+                break;
+            }
         }
     }
 
@@ -152,7 +182,7 @@ where
     variables.copy_from_slice(&best);
 
     // Run body one final time with best values so objectives are set
-    body(variables)?;
+    body.evaluate(variables)?;
 
     Ok(())
 }
@@ -8,6 +8,8 @@
 /// Reference: Nelder, J.A. and Mead, R. (1965), "A Simplex Method for Function
 /// Minimization", The Computer Journal, 7(4), 308-313.
 
+use super::FitBody;
+
 /// Evaluate the sum of squares of objectives (scalar merit function)
 fn merit(objectives: &[f64]) -> f64 {
     objectives.iter().map(|o| o * o).sum()
@@ -16,15 +18,15 @@ fn merit(objectives: &[f64]) -> f64 {
 /// Run Nelder-Mead simplex optimization.
 ///
 /// Minimizes the sum of squares of the objective values returned by `body`.
-pub fn nelder_mead<F>(
+pub fn nelder_mead<B>(
     variables: &mut [f64],
     eps: f64,
     max_iter: usize,
     _num_objectives: usize,
-    body: &mut F,
+    body: &mut B,
 ) -> anyhow::Result<()>
 where
-    F: FnMut(&mut [f64]) -> anyhow::Result<Vec<f64>>,
+    B: FitBody<Vec<f64>>,
 {
     let n = variables.len();
 
@@ -52,11 +54,7 @@ where
     }
 
     // Evaluate all vertices
-    let mut values: Vec<f64> = Vec::with_capacity(n + 1);
-    for vertex in &mut simplex {
-        let objs = body(vertex.as_mut_slice())?;
-        values.push(merit(&objs));
-    }
+    let mut values: Vec<f64> = body.evaluate_all(&mut simplex)?.iter().map(|objs| merit(objs)).collect();
 
     let mut iteration = 0;
 
@@ -106,7 +104,7 @@ where
         for j in 0..n {
             reflected[j] = centroid[j] + alpha * (centroid[j] - simplex[n][j]);
         }
-        let reflected_val = merit(&body(reflected.as_mut_slice())?);
+        let reflected_val = merit(&body.evaluate(reflected.as_mut_slice())?);
 
         if reflected_val < values[n - 1] && reflected_val >= values[0] {
             // Accept reflection
@@ -121,7 +119,7 @@ where
             for j in 0..n {
                 expanded[j] = centroid[j] + gamma * (reflected[j] - centroid[j]);
             }
-            let expanded_val = merit(&body(expanded.as_mut_slice())?);
+            let expanded_val = merit(&body.evaluate(expanded.as_mut_slice())?);
 
             if expanded_val < reflected_val {
                 simplex[n] = expanded;
@@ -138,7 +136,7 @@ where
         for j in 0..n {
             contracted[j] = centroid[j] + rho * (simplex[n][j] - centroid[j]);
         }
-        let contracted_val = merit(&body(contracted.as_mut_slice())?);
+        let contracted_val = merit(&body.evaluate(contracted.as_mut_slice())?);
 
         if contracted_val < values[n] {
             simplex[n] = contracted;
@@ -151,8 +149,10 @@ where
             for j in 0..n {
                 simplex[i][j] = simplex[0][j] + sigma * (simplex[i][j] - simplex[0][j]);
             }
-            let objs = body(simplex[i].as_mut_slice())?;
-            values[i] = merit(&objs);
+        }
+        let shrunk = body.evaluate_all(&mut simplex[1..])?;
+        for (value, objs) in values[1..].iter_mut().zip(&shrunk) {
+            *value = merit(objs);
         }
     }
 
@@ -160,7 +160,7 @@ where
     variables.copy_from_slice(&simplex[0]);
 
     // Run body one final time with best values so objectives are set
-    body(variables)?;
+    body.evaluate(variables)?;
 
     Ok(())
 }
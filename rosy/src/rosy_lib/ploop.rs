@@ -0,0 +1,228 @@
+//! # PLOOP communication standards
+//!
+//! How the output of a `PLOOP` is shared between the processes that ran it,
+//! independently of how the values actually travel between them.
+//!
+//! The processes are split into `num_groups` groups of equal size, and group
+//! `g` runs iteration `g + 1`. Process `r` belongs to group
+//! `r / processes_per_group`. The processes with the same
+//! `r % processes_per_group` form a *column* holding exactly one process of
+//! each group, and all communication happens within a column. The column's
+//! process in group 0 is its *root*.
+//!
+//! Slot `g` of the coordinated array belongs to group `g`:
+//!
+//! | Code | Standard | Afterwards |
+//! |------|----------|------------|
+//! | 1 | all-to-all | every process holds every slot |
+//! | 2 | gather | the root holds every slot, the others only their own |
+//! | 3 | sum | every slot on every process holds the sum of all slots |
+//! | 4 | minimum | as 3, with the element-wise minimum |
+//! | 5 | maximum | as 3, with the element-wise maximum |
+//! | 6 | scatter | every process holds the root's value of its own slot |
+//!
+//! Scatter distributes an input array, so it runs before the loop body
+//! rather than after it.
+
+use anyhow::{Result, bail, ensure};
+
+use crate::rosy_lib::{RE, DA};
+
+/// A PLOOP communication standard, decoded from the `ENDPLOOP` argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Standard {
+    AllToAll,
+    Gather,
+    Reduce(Reduction),
+    Scatter,
+}
+
+impl Standard {
+    pub fn from_code(code: u8) -> Result<Self> {
+        Ok(match code {
+            1 => Standard::AllToAll,
+            2 => Standard::Gather,
+            3 => Standard::Reduce(Reduction::Sum),
+            4 => Standard::Reduce(Reduction::Min),
+            5 => Standard::Reduce(Reduction::Max),
+            6 => Standard::Scatter,
+            other => bail!(
+                "Unsupported communication standard: {} (1 = all-to-all, 2 = gather, 3 = sum, 4 = minimum, 5 = maximum, 6 = scatter)",
+                other
+            ),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reduction {
+    Sum,
+    Min,
+    Max,
+}
+
+impl std::fmt::Display for Reduction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reduction::Sum => write!(f, "sum"),
+            Reduction::Min => write!(f, "minimum"),
+            Reduction::Max => write!(f, "maximum"),
+        }
+    }
+}
+
+/// A PLOOP output element that the reducing standards can combine.
+pub trait Reducible: Sized {
+    /// Fold `other` into `self`.
+    fn combine(&mut self, other: &Self, reduction: Reduction) -> Result<()>;
+}
+
+impl Reducible for RE {
+    fn combine(&mut self, other: &Self, reduction: Reduction) -> Result<()> {
+        *self = match reduction {
+            Reduction::Sum => *self + other,
+            Reduction::Min => self.min(*other),
+            Reduction::Max => self.max(*other),
+        };
+        Ok(())
+    }
+}
+
+impl Reducible for Vec<RE> {
+    fn combine(&mut self, other: &Self, reduction: Reduction) -> Result<()> {
+        ensure!(
+            self.len() == other.len(),
+            "Cannot take the {} of vectors of lengths {} and {}",
+            reduction, self.len(), other.len()
+        );
+        for (x, y) in self.iter_mut().zip(other) {
+            x.combine(y, reduction)?;
+        }
+        Ok(())
+    }
+}
+
+impl Reducible for DA {
+    fn combine(&mut self, other: &Self, reduction: Reduction) -> Result<()> {
+        match reduction {
+            Reduction::Sum => {
+                *self = (&*self + other)?;
+                Ok(())
+            }
+            _ => bail!("Cannot take the {} of DA values", reduction),
+        }
+    }
+}
+
+impl Reducible for String {
+    fn combine(&mut self, _other: &Self, reduction: Reduction) -> Result<()> {
+        bail!("Cannot take the {} of strings", reduction)
+    }
+}
+
+impl Reducible for bool {
+    fn combine(&mut self, _other: &Self, reduction: Reduction) -> Result<()> {
+        bail!("Cannot take the {} of logicals", reduction)
+    }
+}
+
+/// Reduce the slots of every group to a single value.
+pub fn reduce<T: Reducible + Clone>(slots: &[T], reduction: Reduction) -> Result<T> {
+    let (first, rest) = slots
+        .split_first()
+        .ok_or_else(|| anyhow::anyhow!("Cannot take the {} of no values", reduction))?;
+    let mut result = first.clone();
+    for slot in rest {
+        result.combine(slot, reduction)?;
+    }
+    Ok(result)
+}
+
+/// Where one process sits among the groups of a PLOOP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub size: i32,
+    pub rank: i32,
+    pub num_groups: i32,
+}
+
+impl Layout {
+    pub fn new(size: i32, rank: i32, num_groups: RE) -> Result<Self> {
+        let num_groups = num_groups as i32;
+        ensure!(num_groups >= 1, "A PLOOP needs at least one iteration, found {}", num_groups);
+        ensure!(
+            size % num_groups == 0,
+            "Total number of processes ({}) must be divisible by the number of PLOOP groups ({})!",
+            size, num_groups
+        );
+        Ok(Layout { size, rank, num_groups })
+    }
+
+    pub fn processes_per_group(&self) -> i32 {
+        self.size / self.num_groups
+    }
+
+    /// The group (0-based) that `rank` belongs to.
+    pub fn group_of(&self, rank: i32) -> i32 {
+        rank / self.processes_per_group()
+    }
+
+    pub fn group_num(&self) -> i32 {
+        self.group_of(self.rank)
+    }
+
+    /// The process of group `group` in this process's column.
+    pub fn rank_in_group(&self, group: i32) -> i32 {
+        group * self.processes_per_group() + self.rank % self.processes_per_group()
+    }
+
+    pub fn root(&self) -> i32 {
+        self.rank_in_group(0)
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.rank == self.root()
+    }
+
+    /// The other processes in this process's column, in group order.
+    pub fn others(&self) -> Vec<i32> {
+        (0..self.num_groups)
+            .map(|group| self.rank_in_group(group))
+            .filter(|&rank| rank != self.rank)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_hold_one_process_per_group() {
+        // 6 processes in 3 groups: columns {0, 2, 4} and {1, 3, 5}
+        let layout = Layout::new(6, 3, 3.0).unwrap();
+        assert_eq!(layout.group_num(), 1);
+        assert_eq!(layout.root(), 1);
+        assert_eq!(layout.others(), vec![1, 5]);
+        assert!(Layout::new(6, 4, 3.0).unwrap().others().iter().all(|r| r % 2 == 0));
+        assert!(Layout::new(6, 0, 4.0).is_err());
+    }
+
+    #[test]
+    fn reductions_combine_every_slot() {
+        assert_eq!(reduce(&[3.0, -1.0, 2.0], Reduction::Sum).unwrap(), 4.0);
+        assert_eq!(reduce(&[3.0, -1.0, 2.0], Reduction::Min).unwrap(), -1.0);
+        let vectors = vec![vec![1.0, 5.0], vec![4.0, 2.0]];
+        assert_eq!(reduce(&vectors, Reduction::Max).unwrap(), vec![4.0, 5.0]);
+        assert!(reduce(&[vec![1.0], vec![1.0, 2.0]], Reduction::Sum).is_err());
+        assert!(reduce(&["a".to_string()], Reduction::Sum).is_ok());
+        assert!(reduce(&["a".to_string(), "b".to_string()], Reduction::Sum).is_err());
+    }
+
+    #[test]
+    fn unknown_standards_are_rejected() {
+        assert_eq!(Standard::from_code(4).unwrap(), Standard::Reduce(Reduction::Min));
+        assert!(Standard::from_code(0).is_err());
+        assert!(Standard::from_code(7).is_err());
+    }
+}
@@ -4,8 +4,18 @@
 //! the N×N multiplication index table. All tables are immutable after init; only
 //! `epsilon` and `max_order` can be changed at runtime via `set_epsilon()` /
 //! `set_truncation_order()`.
-
-use std::sync::RwLock;
+//!
+//! The setup is process-wide, so a `DAINI` made on one thread (such as by
+//! `State::new()` of a generated library) holds on every other. A thread can
+//! instead be given a setup of its own ([`own_taylor_state`]): the thread
+//! `PLOOP` backend gives each of its threads a copy of the spawning thread's
+//! setup, sharing the tables, so a `DAINI`, `DANOT` or `DAFSET` in a loop
+//! body only changes the setup of the thread running it, as it would only
+//! change that of one MPI rank.
+
+use std::cell::RefCell;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
 use anyhow::{Result, Context, bail};
 use rustc_hash::FxHashMap;
 
@@ -26,7 +36,7 @@ impl TaylorConfig {
     pub fn new(max_order: u32, num_vars: usize, epsilon: f64) -> Result<Self> {
         if num_vars > MAX_VARS {
             bail!(
-                "Number of variables ({}) exceeds maximum ({})",
+                "Number of variables ({}) exceeds the compiled-in maximum ({})",
                 num_vars, MAX_VARS
             );
         }
@@ -48,6 +58,7 @@ pub const DERIV_INVALID: u32 = u32::MAX;
 ///
 /// Built once at `init_taylor()` time. The multiplication table enables
 /// O(1) product-index lookup instead of runtime exponent addition + hash.
+#[derive(Clone)]
 pub struct TaylorRuntime {
     /// Mutable configuration (epsilon and max_order can change via DAEPS/DANOT)
     pub config: TaylorConfig,
@@ -63,41 +74,91 @@ pub struct TaylorRuntime {
     pub monomial_orders: Vec<u8>,
     /// Flat-index of each variable's monomial: `variable_indices[v]` = index of x\_{v+1}
     pub variable_indices: [u32; MAX_VARS],
+    /// Order weight of each variable (all 1 unless set by DANOTW or DAINI).
+    /// Monomial exponents are stored as `weight × exponent`, so `total_order`
+    /// and `monomial_orders` are weighted orders.
+    pub weights: [u8; MAX_VARS],
     /// Multiplication index table: `mult_table[i * num_monomials + j]` = index of
     /// monomial_i × monomial_j, or `MULT_INVALID` if the product exceeds init_order.
     /// `None` when num_monomials is too large for a table (falls back to on-the-fly).
     pub mult_table: Option<Vec<u32>>,
     /// Precomputed derivative target indices: `deriv_target[v * num_monomials + k]` =
-    /// flat index of the monomial obtained by decrementing exponent `v` of monomial `k`,
-    /// or `DERIV_INVALID` if exponent `v` is 0.
+    /// flat index of the monomial obtained by lowering the power of variable `v` in
+    /// monomial `k` by one, or `DERIV_INVALID` if variable `v` does not appear.
     pub deriv_target: Vec<u32>,
     /// Precomputed derivative exponents: `deriv_exponent[v * num_monomials + k]` =
-    /// the exponent of variable `v` in monomial `k` (the multiplier for differentiation).
+    /// the (unweighted) exponent of variable `v` in monomial `k`, i.e. the
+    /// multiplier for differentiation.
     pub deriv_exponent: Vec<u8>,
     /// Precomputed integral target indices: `integ_target[v * num_monomials + k]` =
-    /// flat index of the monomial obtained by incrementing exponent `v` of monomial `k`,
-    /// or `DERIV_INVALID` if the result would exceed init_order.
+    /// flat index of the monomial obtained by raising the power of variable `v` in
+    /// monomial `k` by one, or `DERIV_INVALID` if the result would exceed init_order.
     pub integ_target: Vec<u32>,
 }
 
-/// Read guard wrapper that dereferences directly to `TaylorRuntime`.
-pub struct RuntimeRef(std::sync::RwLockReadGuard<'static, Option<TaylorRuntime>>);
+/// Shared handle to the runtime this thread uses that dereferences directly
+/// to `TaylorRuntime`.
+pub struct RuntimeRef(Arc<TaylorRuntime>);
 
 impl std::ops::Deref for RuntimeRef {
     type Target = TaylorRuntime;
     #[inline]
     fn deref(&self) -> &TaylorRuntime {
-        // SAFETY: get_runtime() checks is_some() before constructing RuntimeRef
-        unsafe { self.0.as_ref().unwrap_unchecked() }
+        &self.0
     }
 }
 
-static TAYLOR_RUNTIME: RwLock<Option<TaylorRuntime>> = RwLock::new(None);
+/// Bumped by every `init_taylor()`, so anything derived from the monomial
+/// layout (such as prepared POLVAL trees) can tell it was built for another.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// The current Taylor runtime generation (see [`GENERATION`]).
+pub fn taylor_generation() -> u64 {
+    GENERATION.load(Ordering::Acquire)
+}
+
+/// A whole Taylor setup, for handing to another thread. The runtime tables
+/// are shared rather than copied.
+#[derive(Clone)]
+pub struct TaylorState {
+    runtime: Option<Arc<TaylorRuntime>>,
+    /// Weight vector set by DANOTW. Each element is the "cost" of one power of the
+    /// corresponding variable. `None` means unweighted (all weights = 1).
+    /// Consumed and cleared by the next `init_taylor()` call.
+    pending_weights: Option<Vec<u32>>,
+    /// Filter template set by DAFSET. Each element corresponds to one component of the DA array.
+    /// A monomial is kept by DAFILT only if the corresponding coefficient in this template is nonzero.
+    /// `None` means filtering is disabled.
+    filter: Option<Vec<super::da::DA<f64>>>,
+}
+
+/// The setup of every thread without one of its own.
+static SHARED: RwLock<TaylorState> = RwLock::new(TaylorState {
+    runtime: None,
+    pending_weights: None,
+    filter: None,
+});
 
-/// Weight vector set by DANOTW. Each element is the "cost" of one power of the
-/// corresponding variable. `None` means unweighted (all weights = 1).
-/// Consumed and cleared by the next `init_taylor()` call.
-static WEIGHT_VECTOR: RwLock<Option<Vec<u32>>> = RwLock::new(None);
+thread_local! {
+    /// This thread's own setup, if [`own_taylor_state`] gave it one.
+    static LOCAL: RefCell<Option<TaylorState>> = const { RefCell::new(None) };
+}
+
+/// Read the setup this thread uses. `f` must not reach for the setup again.
+fn with_state<R>(f: impl FnOnce(&TaylorState) -> R) -> R {
+    LOCAL.with_borrow(|local| match local {
+        Some(state) => f(state),
+        None => f(&SHARED.read().unwrap_or_else(PoisonError::into_inner)),
+    })
+}
+
+/// Change the setup this thread uses. `f` must not reach for the setup again.
+fn with_state_mut<R>(f: impl FnOnce(&mut TaylorState) -> R) -> R {
+    LOCAL.with_borrow_mut(|local| match local {
+        Some(state) => f(state),
+        None => f(&mut SHARED.write().unwrap_or_else(PoisonError::into_inner)),
+    })
+}
 
 /// Set the per-variable weight vector (called by DANOTW transpiled code).
 /// Weights must all be ≥ 1.
@@ -107,37 +168,25 @@ pub fn set_weight_vector(weights: Vec<u32>) -> Result<()> {
             anyhow::bail!("DANOTW: weight for variable {} is {} — weights must be positive integers ≥ 1", i + 1, w);
         }
     }
-    let mut guard = WEIGHT_VECTOR.write()
-        .map_err(|e| anyhow::anyhow!("Failed to acquire weight vector lock: {}", e))?;
-    *guard = Some(weights);
+    with_state_mut(|state| state.pending_weights = Some(weights));
     Ok(())
 }
 
 /// Take and clear the weight vector (consumed by init_taylor).
 fn take_weight_vector() -> Result<Option<Vec<u32>>> {
-    let mut guard = WEIGHT_VECTOR.write()
-        .map_err(|e| anyhow::anyhow!("Failed to acquire weight vector lock: {}", e))?;
-    Ok(guard.take())
+    Ok(with_state_mut(|state| state.pending_weights.take()))
 }
 
-/// Filter template set by DAFSET. Each element corresponds to one component of the DA array.
-/// A monomial is kept by DAFILT only if the corresponding coefficient in this template is nonzero.
-/// `None` means filtering is disabled.
-pub static FILTER_DA: RwLock<Option<Vec<super::da::DA<f64>>>> = RwLock::new(None);
-
 /// Set the DAFILT template (DAFSET). Pass `None` to disable filtering.
 pub fn set_filter_da(template: Option<Vec<super::da::DA<f64>>>) -> Result<()> {
-    let mut guard = FILTER_DA.write()
-        .map_err(|e| anyhow::anyhow!("Failed to acquire filter lock: {}", e))?;
-    *guard = template;
+    // The old template is dropped once the setup is let go of
+    let _old = with_state_mut(|state| std::mem::replace(&mut state.filter, template));
     Ok(())
 }
 
 /// Get a snapshot of the current filter template (cloned).
 pub fn get_filter_da() -> Result<Option<Vec<super::da::DA<f64>>>> {
-    let guard = FILTER_DA.read()
-        .map_err(|e| anyhow::anyhow!("Failed to acquire filter lock: {}", e))?;
-    Ok(guard.clone())
+    Ok(with_state(|state| state.filter.clone()))
 }
 
 /// Initialize the Taylor series system and precompute runtime tables.
@@ -151,22 +200,30 @@ pub fn get_filter_da() -> Result<Option<Vec<super::da::DA<f64>>>> {
 /// * `max_order` - Maximum order of Taylor expansions
 /// * `num_vars` - Number of variables (≤ MAX_VARS)
 pub fn init_taylor(max_order: u32, num_vars: usize) -> Result<usize> {
-    let mut guard = TAYLOR_RUNTIME.write()
-        .map_err(|e| anyhow::anyhow!("Failed to acquire runtime lock: {}", e))?;
-
-    if guard.is_some() {
+    if is_initialized() {
         bail!("Taylor system already initialized. Call cleanup_taylor() first.");
     }
 
     let config = TaylorConfig::new(max_order, num_vars, DEFAULT_EPSILON)?;
 
-    // Consume the weight vector (set by DANOTW, if any)
-    let weights = take_weight_vector()?;
-    // Trim to num_vars (extra weights ignored per spec)
-    let weights: Option<Vec<u32>> = weights.map(|w| w.into_iter().take(num_vars).collect());
+    // Consume the weight vector (set by DANOTW or DAINI, if any). Extra
+    // weights are ignored and missing ones default to 1.
+    let mut weights = [1u8; MAX_VARS];
+    if let Some(requested) = take_weight_vector()? {
+        for (v, &w) in requested.iter().take(num_vars).enumerate() {
+            if w > max_order {
+                bail!(
+                    "Weight {} of variable {} exceeds the DA order {}; the variable could not appear in any monomial",
+                    w, v + 1, max_order
+                );
+            }
+            weights[v] = w as u8;
+        }
+    }
+    let weight_slice: Vec<u32> = weights[..num_vars].iter().map(|&w| w as u32).collect();
 
     // Build monomial list in graded lexicographic order
-    let monomial_list = enumerate_monomials(max_order, num_vars as u32, weights.as_deref());
+    let monomial_list = enumerate_monomials(max_order, num_vars as u32, Some(&weight_slice));
     let num_monomials = monomial_list.len();
 
     // Build reverse index: Monomial → flat index
@@ -182,14 +239,13 @@ pub fn init_taylor(max_order: u32, num_vars: usize) -> Result<usize> {
     // With weights, variable v has internal exponent w_v (not 1)
     let mut variable_indices = [0u32; MAX_VARS];
     for v in 0..num_vars {
-        let w = weights.as_ref().map_or(1u8, |ws| ws[v] as u8);
         let mut exponents = [0u8; MAX_VARS];
-        exponents[v] = w;
+        exponents[v] = weights[v];
         let mono = Monomial::new(exponents);
         variable_indices[v] = *monomial_index.get(&mono)
             .unwrap_or_else(|| panic!(
                 "BUG: variable monomial x{} (weight={}) missing from enumeration (order={}, nvars={})",
-                v + 1, w, max_order, num_vars
+                v + 1, weights[v], max_order, num_vars
             ));
     }
 
@@ -215,10 +271,10 @@ pub fn init_taylor(max_order: u32, num_vars: usize) -> Result<usize> {
     };
 
     // Build precomputed derivative/integral index tables (#19 + #21).
-    // For each variable v and monomial index k, precompute:
-    //   deriv_target[v*N+k]  = index of monomial with exponents[v] decremented by 1
-    //   deriv_exponent[v*N+k] = exponents[v] (the derivative multiplier)
-    //   integ_target[v*N+k]  = index of monomial with exponents[v] incremented by 1
+    // For each variable v (weight w) and monomial index k, precompute:
+    //   deriv_target[v*N+k]  = index of monomial with exponents[v] decremented by w
+    //   deriv_exponent[v*N+k] = exponents[v] / w (the derivative multiplier)
+    //   integ_target[v*N+k]  = index of monomial with exponents[v] incremented by w
     let table_size = num_vars * num_monomials;
     let mut deriv_target = vec![DERIV_INVALID; table_size];
     let mut deriv_exponent = vec![0u8; table_size];
@@ -226,26 +282,27 @@ pub fn init_taylor(max_order: u32, num_vars: usize) -> Result<usize> {
 
     for v in 0..num_vars {
         let base = v * num_monomials;
+        let w = weights[v];
         for k in 0..num_monomials {
             let mono = &monomial_list[k];
             let exp_v = mono.exponents[v];
-            deriv_exponent[base + k] = exp_v;
+            deriv_exponent[base + k] = exp_v / w;
 
-            // Derivative target: decrement exponent v
+            // Derivative target: lower the power of variable v
             if exp_v > 0 {
                 let mut new_exp = mono.exponents;
-                new_exp[v] -= 1;
+                new_exp[v] -= w;
                 let new_mono = Monomial::new(new_exp);
                 if let Some(&idx) = monomial_index.get(&new_mono) {
                     deriv_target[base + k] = idx;
                 }
             }
 
-            // Integral target: increment exponent v
-            let mut new_exp = mono.exponents;
-            new_exp[v] += 1;
-            let new_mono = Monomial::new(new_exp);
-            if new_mono.within_order(max_order) {
+            // Integral target: raise the power of variable v
+            if (mono.total_order as u32) + (w as u32) <= max_order {
+                let mut new_exp = mono.exponents;
+                new_exp[v] += w;
+                let new_mono = Monomial::new(new_exp);
                 if let Some(&idx) = monomial_index.get(&new_mono) {
                     integ_target[base + k] = idx;
                 }
@@ -253,7 +310,7 @@ pub fn init_taylor(max_order: u32, num_vars: usize) -> Result<usize> {
         }
     }
 
-    *guard = Some(TaylorRuntime {
+    let runtime = TaylorRuntime {
         config,
         init_order: max_order,
         num_monomials,
@@ -261,11 +318,14 @@ pub fn init_taylor(max_order: u32, num_vars: usize) -> Result<usize> {
         monomial_index,
         monomial_orders,
         variable_indices,
+        weights,
         mult_table,
         deriv_target,
         deriv_exponent,
         integ_target,
-    });
+    };
+    with_state_mut(|state| state.runtime = Some(Arc::new(runtime)));
+    GENERATION.fetch_add(1, Ordering::Release);
 
     Ok(num_monomials)
 }
@@ -288,18 +348,25 @@ pub fn dump_addressing_arrays() -> Result<()> {
     Ok(())
 }
 
-/// Get a read-locked reference to the runtime tables.
+/// Get a reference to the runtime tables this thread uses.
 ///
-/// The returned `RuntimeRef` holds a read lock for its lifetime.
-/// In single-threaded Rosy programs, this has no contention.
+/// The returned `RuntimeRef` keeps the tables alive for its lifetime, even
+/// across a `DAINI` that replaces them.
 #[inline]
 pub fn get_runtime() -> Result<RuntimeRef> {
-    let guard = TAYLOR_RUNTIME.read()
-        .map_err(|e| anyhow::anyhow!("Failed to acquire runtime lock: {}", e))?;
-    if guard.is_none() {
-        bail!("Taylor system not initialized. Call init_taylor() first.");
-    }
-    Ok(RuntimeRef(guard))
+    with_state(|state| state.runtime.clone())
+        .map(RuntimeRef)
+        .context("Taylor system not initialized. Call init_taylor() first.")
+}
+
+/// Change the runtime configuration this thread uses. The tables are only
+/// copied if another thread (or a live `RuntimeRef`) still shares them.
+fn update_config<R>(f: impl FnOnce(&mut TaylorRuntime) -> Result<R>) -> Result<R> {
+    with_state_mut(|state| {
+        let rt = state.runtime.as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Taylor system not initialized"))?;
+        f(Arc::make_mut(rt))
+    })
 }
 
 /// Get the current Taylor configuration (convenience wrapper).
@@ -308,18 +375,23 @@ pub fn get_config() -> Result<TaylorConfig> {
     Ok(rt.config)
 }
 
+/// Order weight of each variable, all 1 when DA is unweighted or not
+/// initialized. Dividing a monomial's exponent by its weight gives the
+/// actual power of that variable.
+pub fn get_weights() -> [u8; MAX_VARS] {
+    get_runtime().map_or([1; MAX_VARS], |rt| rt.weights)
+}
+
 /// Set the epsilon value for coefficient truncation.
 ///
 /// # Returns
 /// The previous epsilon value
 pub fn set_epsilon(epsilon: f64) -> Result<f64> {
-    let mut guard = TAYLOR_RUNTIME.write()
-        .map_err(|e| anyhow::anyhow!("Failed to acquire runtime lock: {}", e))?;
-    let rt = guard.as_mut()
-        .ok_or_else(|| anyhow::anyhow!("Taylor system not initialized"))?;
-    let old = rt.config.epsilon;
-    rt.config.epsilon = epsilon;
-    Ok(old)
+    update_config(|rt| {
+        let old = rt.config.epsilon;
+        rt.config.epsilon = epsilon;
+        Ok(old)
+    })
 }
 
 /// Set the momentary truncation order for DA/CD computations (DANOT).
@@ -329,33 +401,79 @@ pub fn set_epsilon(epsilon: f64) -> Result<f64> {
 /// # Returns
 /// The previous truncation order
 pub fn set_truncation_order(order: u32) -> Result<u32> {
-    let mut guard = TAYLOR_RUNTIME.write()
-        .map_err(|e| anyhow::anyhow!("Failed to acquire runtime lock: {}", e))?;
-    let rt = guard.as_mut()
-        .ok_or_else(|| anyhow::anyhow!("Taylor system not initialized"))?;
-    if order > rt.init_order {
-        bail!(
-            "Cannot set truncation order ({}) above init order ({})",
-            order, rt.init_order
-        );
-    }
-    let old = rt.config.max_order;
-    rt.config.max_order = order;
-    Ok(old)
+    update_config(|rt| {
+        if order > rt.init_order {
+            bail!(
+                "Cannot set truncation order ({}) above init order ({})",
+                order, rt.init_order
+            );
+        }
+        let old = rt.config.max_order;
+        rt.config.max_order = order;
+        Ok(old)
+    })
 }
 
 /// Check if Taylor system is initialized.
 pub fn is_initialized() -> bool {
-    TAYLOR_RUNTIME.read()
-        .map(|g| g.is_some())
-        .unwrap_or(false)
+    with_state(|state| state.runtime.is_some())
 }
 
 /// Clean up the Taylor system (for re-initialization).
 pub fn cleanup_taylor() {
-    if let Ok(mut guard) = TAYLOR_RUNTIME.write() {
-        *guard = None;
-    }
+    let _old = with_state_mut(|state| state.runtime.take());
+}
+
+/// Capture the Taylor setup this thread uses.
+pub fn taylor_state() -> TaylorState {
+    with_state(Clone::clone)
+}
+
+/// Continue with the Taylor setup `state`: on this thread alone if it has a
+/// setup of its own, on every thread without one otherwise.
+pub fn set_taylor_state(state: TaylorState) {
+    let _old = with_state_mut(|current| std::mem::replace(current, state));
+}
+
+/// Give this thread a Taylor setup of its own, starting from `state`, so
+/// nothing it changes reaches other threads.
+pub fn own_taylor_state(state: TaylorState) {
+    LOCAL.set(Some(state));
+}
+
+/// Everything a program has configured about the Taylor system: what DAINI
+/// built, what DANOT/DAEPS changed since, the DAFSET template and any DANOTW
+/// weights still waiting for the next DAINI. Saved by `CHECKPOINT`.
+#[derive(Debug, Clone)]
+pub struct TaylorSnapshot {
+    pub init_order: u32,
+    pub config: TaylorConfig,
+    pub weights: Vec<u32>,
+    pub pending_weights: Option<Vec<u32>>,
+    pub filter: Option<Vec<super::da::DA<f64>>>,
+}
+
+/// Capture the current Taylor configuration.
+pub fn taylor_snapshot() -> Result<TaylorSnapshot> {
+    let (init_order, config, weights) = {
+        let rt = get_runtime()?;
+        let weights = rt.weights[..rt.config.num_vars].iter().map(|&w| w as u32).collect();
+        (rt.init_order, rt.config, weights)
+    };
+    let pending_weights = with_state(|state| state.pending_weights.clone());
+    Ok(TaylorSnapshot { init_order, config, weights, pending_weights, filter: get_filter_da()? })
+}
+
+/// Rebuild the Taylor system exactly as it was when `snapshot` was taken.
+pub fn restore_taylor(snapshot: &TaylorSnapshot) -> Result<()> {
+    cleanup_taylor();
+    set_weight_vector(snapshot.weights.clone())?;
+    init_taylor(snapshot.init_order, snapshot.config.num_vars)?;
+    set_epsilon(snapshot.config.epsilon)?;
+    set_truncation_order(snapshot.config.max_order)?;
+    set_filter_da(snapshot.filter.clone())?;
+    with_state_mut(|state| state.pending_weights = snapshot.pending_weights.clone());
+    Ok(())
 }
 
 #[cfg(test)]
@@ -459,4 +577,77 @@ mod tests {
 
         cleanup_taylor();
     }
+
+    #[test]
+    #[serial]
+    fn test_weighted_derivative_tables() {
+        cleanup_taylor();
+        set_weight_vector(vec![1, 2]).unwrap();
+        init_taylor(4, 2).unwrap();
+
+        let rt = get_runtime().unwrap();
+        let n = rt.num_monomials;
+        assert_eq!(&rt.weights[..2], &[1, 2]);
+
+        // x2^2 is stored with internal exponent 4 and has weighted order 4
+        let mut exponents = [0u8; MAX_VARS];
+        exponents[1] = 4;
+        let x2_sq = rt.monomial_index[&Monomial::new(exponents)] as usize;
+        assert_eq!(rt.monomial_orders[x2_sq], 4);
+
+        // d/dx2 (x2^2) = 2 x2
+        assert_eq!(rt.deriv_exponent[n + x2_sq], 2);
+        assert_eq!(rt.deriv_target[n + x2_sq], rt.variable_indices[1]);
+
+        // Integrating x2 over x2 gives x2^2; integrating x2^2 would exceed order 4
+        assert_eq!(rt.integ_target[n + rt.variable_indices[1] as usize], x2_sq as u32);
+        assert_eq!(rt.integ_target[n + x2_sq], DERIV_INVALID);
+
+        drop(rt);
+        cleanup_taylor();
+    }
+
+    #[test]
+    #[serial]
+    fn test_weight_above_order_is_rejected() {
+        cleanup_taylor();
+        set_weight_vector(vec![1, 5]).unwrap();
+        assert!(init_taylor(4, 2).is_err());
+        assert!(!is_initialized());
+
+        // Short weight vectors leave the remaining variables at weight 1
+        set_weight_vector(vec![2]).unwrap();
+        init_taylor(4, 3).unwrap();
+        assert_eq!(&get_runtime().unwrap().weights[..3], &[2, 1, 1]);
+        cleanup_taylor();
+    }
+
+    #[test]
+    #[serial]
+    fn test_other_threads_share_the_setup_unless_they_own_one() {
+        cleanup_taylor();
+        init_taylor(4, 2).unwrap();
+
+        // A thread that never ran DAINI uses the one made here
+        let order = std::thread::spawn(|| {
+            let x = super::super::DA::variable(1).unwrap();
+            assert!(!x.is_zero());
+            get_config().unwrap().max_order
+        })
+        .join()
+        .unwrap();
+        assert_eq!(order, 4);
+
+        // One given its own setup changes only that
+        let state = taylor_state();
+        std::thread::spawn(move || {
+            own_taylor_state(state);
+            set_truncation_order(2).unwrap();
+            assert_eq!(get_config().unwrap().max_order, 2);
+        })
+        .join()
+        .unwrap();
+        assert_eq!(get_config().unwrap().max_order, 4);
+        cleanup_taylor();
+    }
 }
@@ -179,6 +179,8 @@ impl<T: DACoefficient> Clone for DA<T> {
 impl<T: DACoefficient> Drop for DA<T> {
     fn drop(&mut self) {
         if self.coeffs.is_empty() { return; }
+        // The buffer is about to be reused; forget any POLVAL tree built on it
+        crate::rosy_lib::core::polval::release_buffer(self.coeffs.as_ptr() as usize);
         // Restore invariant: zero out entries we used
         for &i in &self.nonzero {
             self.coeffs[i as usize] = T::zero();
@@ -262,6 +264,19 @@ impl<T: DACoefficient> DA<T> {
         self.coeffs[0]
     }
 
+    /// Coefficient of the variable monomial `DA::variable(var_index)`, i.e.
+    /// the first derivative with respect to that (1-based) variable.
+    pub fn linear_coeff(&self, var_index: usize) -> Result<T> {
+        let rt = get_runtime()?;
+        if var_index == 0 || var_index > rt.config.num_vars {
+            anyhow::bail!(
+                "Variable index {} out of range [1, {}]",
+                var_index, rt.config.num_vars
+            );
+        }
+        Ok(self.coeffs[rt.variable_indices[var_index - 1] as usize])
+    }
+
     pub fn get_coeff(&self, monomial: &Monomial) -> T {
         let rt = get_runtime().expect("Taylor system not initialized");
         if let Some(&idx) = rt.monomial_index.get(monomial) {
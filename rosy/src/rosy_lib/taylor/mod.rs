@@ -9,7 +9,7 @@ pub mod da;
 pub mod horner;
 
 pub use monomial::{Monomial, enumerate_monomials};
-pub use config::{TaylorConfig, TaylorRuntime, init_taylor, cleanup_taylor, get_config, get_runtime, set_epsilon, set_truncation_order, set_filter_da, get_filter_da, set_weight_vector, dump_addressing_arrays};
+pub use config::{TaylorConfig, TaylorRuntime, init_taylor, cleanup_taylor, get_config, get_runtime, get_weights, taylor_generation, set_epsilon, set_truncation_order, set_filter_da, get_filter_da, set_weight_vector, dump_addressing_arrays, TaylorSnapshot, taylor_snapshot, restore_taylor, TaylorState, taylor_state, set_taylor_state, own_taylor_state};
 pub use da::DACoefficient;
 pub use horner::FixedMultiplier;
 
@@ -23,11 +23,16 @@ pub type DA = da::DA<f64>;
 pub type CD = da::DA<Complex64>;
 
 /// Maximum number of variables supported.
-/// 
-/// Set to 16 to handle typical beam physics cases:
+///
+/// This is the width of [`Monomial::exponents`]. The default of 16 covers
+/// typical beam physics cases:
 /// - 6D phase space (x, px, y, py, z, pz)
-/// - Additional coupling/parameter variables
-pub const MAX_VARS: usize = 6;
+/// - Additional coupling/parameter variables (energy spread, magnet errors)
+///
+/// Generated projects get a narrower (or wider) width: the transpiler rewrites
+/// this constant from the program's `DAINI` arguments when it vendors
+/// `rosy_lib`, so a 6-variable program keeps the compact 6-byte monomial.
+pub const MAX_VARS: usize = 16;
 
 /// Default epsilon for coefficient truncation.
 pub const DEFAULT_EPSILON: f64 = 1e-15;
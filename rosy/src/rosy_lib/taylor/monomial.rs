@@ -57,6 +57,27 @@ impl Monomial {
         }
     }
 
+    /// Create the monomial with the given power of each variable under the
+    /// order weights `weights` (see [`super::get_weights`]), which scale the
+    /// stored exponents.
+    pub fn from_powers(powers: [u8; MAX_VARS], weights: &[u8; MAX_VARS]) -> Self {
+        let mut exponents = [0; MAX_VARS];
+        for (exponent, (&power, &weight)) in exponents.iter_mut().zip(powers.iter().zip(weights)) {
+            *exponent = power.saturating_mul(weight);
+        }
+        Self::new(exponents)
+    }
+
+    /// The power of each variable in this monomial, undoing the weighting
+    /// of [`Self::from_powers`].
+    pub fn powers(&self, weights: &[u8; MAX_VARS]) -> [u8; MAX_VARS] {
+        let mut powers = self.exponents;
+        for (power, &weight) in powers.iter_mut().zip(weights) {
+            *power /= weight;
+        }
+        powers
+    }
+
     /// Multiply two monomials by adding their exponents.
     ///
     /// # Arguments
@@ -224,4 +245,19 @@ mod tests {
         assert!(m1 < m2);
         assert!(m2 < m3);
     }
+
+    #[test]
+    fn test_monomial_powers_under_weights() {
+        let mut weights = [1; MAX_VARS];
+        weights[2] = 2;
+        let mut powers = [0; MAX_VARS];
+        powers[0] = 1;
+        powers[2] = 1;
+
+        // x1 * x3 with x3 weighted 2 is stored as exponents (1, 0, 2)
+        let m = Monomial::from_powers(powers, &weights);
+        assert_eq!(&m.exponents[..3], &[1, 0, 2]);
+        assert_eq!(m.total_order, 3);
+        assert_eq!(m.powers(&weights), powers);
+    }
 }
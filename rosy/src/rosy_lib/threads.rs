@@ -0,0 +1,413 @@
+//! # Thread PLOOP Backend
+//!
+//! Runs each `PLOOP` on a pool of scoped threads instead of MPI ranks, for
+//! programs built with `--threads`. Every thread plays one rank: it gets its
+//! own copy of the program's variables, runs the loop body for its group
+//! and coordinates the output with the other threads exactly as
+//! `RosyMPIContext::coordinate` does between processes, using the same
+//! [`Layout`] and the standards of [`ploop`].
+//!
+//! Outside a `PLOOP` the program runs once, as rank 0, and carries on with
+//! rank 0's copies of the variables afterwards. The number of threads is
+//! read from the `ROSY_THREADS` environment variable and defaults to the
+//! number of available cores.
+//!
+//! Like the variables, the random number generator and the Taylor setup
+//! (`DAINI`, `DANOT`, `DAEPS`, `DAFSET`, ...) are copied for every thread:
+//! each starts from the spawning thread's, just as every MPI rank has reached
+//! the same state when a `PLOOP` begins, and the program carries on with
+//! rank 0's. A run therefore draws the same random numbers however the threads
+//! are scheduled.
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+use anyhow::{Context, Result, anyhow, bail, ensure};
+
+use crate::rosy_lib::RE;
+use crate::rosy_lib::core::{rng, trace};
+use crate::rosy_lib::taylor;
+use crate::rosy_lib::ploop::{self, Layout, Reducible, Standard};
+
+/// Environment variable that sets the number of threads.
+pub const THREADS_ENV: &str = "ROSY_THREADS";
+
+type Message = (i32, Box<dyn Any + Send>);
+
+/// Where a thread's random numbers and Taylor setup were when its body
+/// finished; `None` if it panicked.
+type ThreadState = Option<(rng::RngState, taylor::TaylorState)>;
+
+/// Messages in flight between the threads of one `PLOOP`.
+struct Mailboxes {
+    state: Mutex<MailboxState>,
+    delivered: Condvar,
+}
+
+struct MailboxState {
+    /// Pending messages for each rank, with their senders, oldest first
+    queues: Vec<VecDeque<Message>>,
+    /// The first rank whose body failed, which stops every other rank
+    failed_rank: Option<i32>,
+}
+
+impl Mailboxes {
+    fn lock(&self) -> MutexGuard<'_, MailboxState> {
+        // A panicking rank never holds the lock, so poisoning is harmless
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn fail(&self, rank: i32) {
+        let mut state = self.lock();
+        state.failed_rank.get_or_insert(rank);
+        self.delivered.notify_all();
+    }
+}
+
+pub struct RosyThreadContext {
+    pub size: i32,
+    pub rank: i32,
+    /// Set on the threads of a running `PLOOP`
+    mailboxes: Option<Arc<Mailboxes>>,
+}
+
+impl RosyThreadContext {
+    pub fn new() -> Result<Self> {
+        let size = match std::env::var(THREADS_ENV) {
+            Ok(threads) => threads
+                .trim()
+                .parse::<i32>()
+                .ok()
+                .filter(|&n| n >= 1)
+                .with_context(|| format!("{} must be a positive integer, found '{}'", THREADS_ENV, threads))?,
+            Err(_) => std::thread::available_parallelism()
+                .map(|n| n.get() as i32)
+                .unwrap_or(1),
+        };
+        Ok(RosyThreadContext { size, rank: 0, mailboxes: None })
+    }
+
+    /// Runs `body` once per rank, each on its own thread, and returns rank
+    /// 0's result. `body` gets the context of its rank, through which it
+    /// finds its group and coordinates the output.
+    pub fn ploop<R, F>(&mut self, num_groups: RE, body: F) -> Result<R>
+    where
+        R: Send,
+        F: Fn(&mut RosyThreadContext) -> Result<R> + Sync,
+    {
+        ensure!(
+            self.mailboxes.is_none(),
+            "Nested PLOOPs are not supported by the thread backend"
+        );
+        // Fail here rather than on every thread
+        Layout::new(self.size, self.rank, num_groups)?;
+
+        let mailboxes = Arc::new(Mailboxes {
+            state: Mutex::new(MailboxState {
+                queues: (0..self.size).map(|_| VecDeque::new()).collect(),
+                failed_rank: None,
+            }),
+            delivered: Condvar::new(),
+        });
+        // Threads report errors with the calls that led to the PLOOP, and
+        // start from this thread's random numbers and Taylor setup
+        let caller = trace::capture();
+        let rng_state = rng::rng_state();
+        let taylor_state = taylor::taylor_state();
+        let mut outcomes: Vec<(Result<R>, ThreadState)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..self.size)
+                .map(|rank| {
+                    let mut context = RosyThreadContext {
+                        size: self.size,
+                        rank,
+                        mailboxes: Some(mailboxes.clone()),
+                    };
+                    let body = &body;
+                    let mailboxes = &mailboxes;
+                    let caller = caller.clone();
+                    let taylor_state = taylor_state.clone();
+                    scope.spawn(move || {
+                        trace::restore(caller);
+                        rng::own_rng_state(rng_state);
+                        taylor::own_taylor_state(taylor_state);
+                        let outcome = catch_unwind(AssertUnwindSafe(|| body(&mut context)))
+                            .unwrap_or_else(|_| Err(anyhow!("PLOOP thread {} panicked", rank)));
+                        if outcome.is_err() {
+                            mailboxes.fail(rank);
+                        }
+                        let outcome = outcome.map_err(|e| {
+                            if e.downcast_ref::<trace::Trace>().is_some() {
+                                e
+                            } else {
+                                e.context(trace::capture())
+                            }
+                        });
+                        (outcome, Some((rng::rng_state(), taylor::taylor_state())))
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|_| (Err(anyhow!("PLOOP thread panicked")), None))
+                })
+                .collect()
+        });
+
+        // Report the failure that stopped the others, not their reaction to it
+        if let Some(rank) = mailboxes.lock().failed_rank {
+            return match outcomes.swap_remove(rank as usize).0 {
+                Err(e) => Err(e.context(format!("...on PLOOP thread {} of {}", rank, self.size))),
+                Ok(_) => Err(anyhow!("PLOOP thread {} failed", rank)),
+            };
+        }
+        let (outcome, state) = outcomes.swap_remove(0);
+        if let Some((rng_state, taylor_state)) = state {
+            rng::set_rng_state(rng_state);
+            taylor::set_taylor_state(taylor_state);
+        }
+        outcome
+    }
+
+    // Coordinates a value array between all threads according to the
+    //  specified communication standard, exactly like
+    //  `RosyMPIContext::coordinate` does between MPI processes.
+    pub fn coordinate<T: Send + Default + Clone + Reducible + 'static>(
+        &self,
+        value: &mut Vec<T>,
+        communication_standard: u8,
+        num_groups: &mut RE,
+    ) -> Result<()> {
+        let standard = Standard::from_code(communication_standard)?;
+        let layout = Layout::new(self.size, self.rank, *num_groups)?;
+        let group_num = layout.group_num() as usize;
+        let num_groups = layout.num_groups as usize;
+
+        // One slot per group, as in the MPI backend
+        if value.len() < num_groups {
+            value.resize_with(num_groups, T::default);
+        }
+
+        match standard {
+            Standard::AllToAll => {
+                let others = layout.others();
+                for &other in &others {
+                    self.send_to(other, value[group_num].clone())?;
+                }
+                for &other in &others {
+                    value[layout.group_of(other) as usize] = self.receive_from(other)?;
+                }
+            }
+            Standard::Gather => {
+                if layout.is_root() {
+                    for other in layout.others() {
+                        value[layout.group_of(other) as usize] = self.receive_from(other)?;
+                    }
+                } else {
+                    self.send_to(layout.root(), value[group_num].clone())?;
+                }
+            }
+            Standard::Reduce(reduction) => {
+                let result = if layout.is_root() {
+                    for other in layout.others() {
+                        value[layout.group_of(other) as usize] = self.receive_from(other)?;
+                    }
+                    let result = ploop::reduce(&value[..num_groups], reduction)?;
+                    for other in layout.others() {
+                        self.send_to(other, result.clone())?;
+                    }
+                    result
+                } else {
+                    self.send_to(layout.root(), value[group_num].clone())?;
+                    self.receive_from(layout.root())?
+                };
+                value[..num_groups].fill(result);
+            }
+            Standard::Scatter => {
+                if layout.is_root() {
+                    for other in layout.others() {
+                        self.send_to(other, value[layout.group_of(other) as usize].clone())?;
+                    }
+                } else {
+                    value[group_num] = self.receive_from(layout.root())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get_group_num(&self, num_groups: &mut RE) -> Result<RE> {
+        let layout = Layout::new(self.size, self.rank, *num_groups)?;
+
+        Ok(layout.group_num() as RE)
+    }
+
+    fn mailboxes(&self) -> Result<&Mailboxes> {
+        self.mailboxes
+            .as_deref()
+            .context("PLOOP outputs can only be coordinated inside a PLOOP")
+    }
+
+    fn send_to<T: Send + 'static>(&self, rank: i32, value: T) -> Result<()> {
+        let mailboxes = self.mailboxes()?;
+        let mut state = mailboxes.lock();
+        state.queues[rank as usize].push_back((self.rank, Box::new(value)));
+        mailboxes.delivered.notify_all();
+        Ok(())
+    }
+
+    /// The oldest message from `rank`, waiting for it if needed.
+    fn receive_from<T: 'static>(&self, rank: i32) -> Result<T> {
+        let mailboxes = self.mailboxes()?;
+        let mut state = mailboxes.lock();
+        loop {
+            if let Some(failed) = state.failed_rank {
+                bail!("Stopped because PLOOP thread {} failed", failed);
+            }
+            let queue = &mut state.queues[self.rank as usize];
+            if let Some(position) = queue.iter().position(|(sender, _)| *sender == rank) {
+                let (_, message) = queue.remove(position).expect("position is in the queue");
+                return message
+                    .downcast::<T>()
+                    .map(|value| *value)
+                    .map_err(|_| anyhow!("Received a value of the wrong type from PLOOP thread {}", rank));
+            }
+            state = mailboxes
+                .delivered
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    fn context(size: i32) -> RosyThreadContext {
+        RosyThreadContext { size, rank: 0, mailboxes: None }
+    }
+
+    #[test]
+    fn every_standard_matches_its_description() {
+        // Each group writes its iteration number into its slot
+        let run = |size: i32, num_groups: RE, standard: u8| {
+            context(size)
+                .ploop(num_groups, |ctx| {
+                    let mut end = num_groups;
+                    let i = ctx.get_group_num(&mut end)? + 1.0;
+                    let mut value = vec![0.0; num_groups as usize];
+                    value[i as usize - 1] = i;
+                    ctx.coordinate(&mut value, standard, &mut end)?;
+                    Ok(value)
+                })
+                .unwrap()
+        };
+        assert_eq!(run(4, 4.0, 1), vec![1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(run(4, 4.0, 2), vec![1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(run(6, 3.0, 3), vec![6.0, 6.0, 6.0]);
+        assert_eq!(run(4, 2.0, 4), vec![1.0, 1.0]);
+        assert_eq!(run(4, 4.0, 5), vec![4.0; 4]);
+    }
+
+    #[test]
+    fn non_root_ranks_see_only_what_they_are_sent() {
+        let slots = context(3)
+            .ploop(3.0, |ctx| {
+                let mut end = 3.0;
+                let i = ctx.get_group_num(&mut end)? + 1.0;
+                let mut gathered = vec![String::new(); 3];
+                gathered[i as usize - 1] = format!("rank {}", ctx.rank);
+                ctx.coordinate(&mut gathered, 2, &mut end)?;
+                let mut scattered = if ctx.rank == 0 { vec![10.0, 20.0, 30.0] } else { vec![0.0; 3] };
+                ctx.coordinate(&mut scattered, 6, &mut end)?;
+                Ok((ctx.rank, gathered, scattered))
+            })
+            .unwrap();
+        assert_eq!(slots.1, vec!["rank 0", "rank 1", "rank 2"]);
+        assert_eq!(slots.2, vec![10.0, 20.0, 30.0]);
+
+        let own = context(3)
+            .ploop(3.0, |ctx| {
+                let mut end = 3.0;
+                let mut scattered = if ctx.rank == 0 { vec![10.0, 20.0, 30.0] } else { vec![0.0; 3] };
+                ctx.coordinate(&mut scattered, 6, &mut end)?;
+                ensure!(scattered[ctx.rank as usize] == 10.0 * (ctx.rank + 1) as f64);
+                Ok(())
+            });
+        assert!(own.is_ok());
+    }
+
+    #[test]
+    #[serial]
+    fn random_numbers_do_not_depend_on_scheduling() {
+        let sums = || {
+            rng::set_rng_seed(0.0);
+            context(4)
+                .ploop(4.0, |ctx| {
+                    let mut end = 4.0;
+                    let i = ctx.get_group_num(&mut end)?;
+                    let mut sums = vec![0.0; 4];
+                    sums[i as usize] = (0..20000).map(|_| rng::rosy_reran()).sum();
+                    ctx.coordinate(&mut sums, 1, &mut end)?;
+                    Ok(sums)
+                })
+                .unwrap()
+        };
+        let first = sums();
+        for _ in 0..3 {
+            assert_eq!(sums(), first);
+        }
+        // Every thread starts where the spawning thread was
+        assert!(first.iter().all(|&sum| sum == first[0]));
+    }
+
+    #[test]
+    #[serial]
+    fn taylor_setup_changes_stay_on_their_thread() {
+        taylor::cleanup_taylor();
+        taylor::init_taylor(4, 2).unwrap();
+        let orders = context(3)
+            .ploop(3.0, |ctx| {
+                let mut end = 3.0;
+                if ctx.rank == 1 {
+                    taylor::set_truncation_order(2)?;
+                } else if ctx.rank == 0 {
+                    taylor::set_truncation_order(3)?;
+                }
+                // Let every rank make its change before any looks
+                let mut orders = vec![0.0; 3];
+                orders[ctx.rank as usize] = taylor::get_config()?.max_order as f64;
+                ctx.coordinate(&mut orders, 1, &mut end)?;
+                Ok(orders)
+            })
+            .unwrap();
+        assert_eq!(orders, vec![3.0, 2.0, 4.0]);
+        // The program carries on with rank 0's setup
+        assert_eq!(taylor::get_config().unwrap().max_order, 3);
+        taylor::cleanup_taylor();
+    }
+
+    #[test]
+    fn a_failing_rank_stops_the_others() {
+        let error = context(4)
+            .ploop(4.0, |ctx| {
+                let mut end = 4.0;
+                if ctx.rank == 2 {
+                    bail!("boom");
+                }
+                let mut value = vec![0.0; 4];
+                ctx.coordinate(&mut value, 1, &mut end)?;
+                Ok(value)
+            })
+            .unwrap_err();
+        assert_eq!(error.root_cause().to_string(), "boom");
+        assert!(context(4).ploop(3.0, |_| Ok(())).is_err());
+    }
+}
@@ -10,6 +10,9 @@
 //! | [`TranspileableStatement`] | Represents a statement node that can be transpiled |
 //! | [`TranspileableExpr`] | Represents an expression node that can be transpiled |
 //!
+//! Statement and expression nodes are also run by the [`interpret`](crate::interpret)
+//! module, so both traits require its `Interpret` or `Evaluate`.
+//!
 //! ## Context
 //!
 //! [`TranspilationInputContext`] tracks variable scope, function/procedure
@@ -22,6 +25,7 @@
 //! breadcrumbs for error diagnostics.
 
 use crate::{
+    interpret::{Evaluate, Interpret},
     program::statements::SourceLocation,
     resolve::{ExprRecipe, ScopeContext, TypeResolver, TypeSlot},
     rosy_lib::RosyType,
@@ -49,7 +53,7 @@ pub enum ExprFunctionCallResult {
     NoFunctionCalls,
 }
 
-pub trait TranspileableStatement: Transpile + Send + Sync {
+pub trait TranspileableStatement: Transpile + Interpret + Send + Sync {
     fn register_typeslot_declaration(
         &self,
         _resolver: &mut TypeResolver,
@@ -67,8 +71,22 @@ pub trait TranspileableStatement: Transpile + Send + Sync {
         _resolver: &TypeResolver,
         _current_scope: &[String],
     ) -> TypeHydrationResult;
+    /// Whether this statement only declares a variable, procedure or function.
+    /// Declarations still run when a checkpointed program resumes; everything
+    /// else before the checkpointed loop is skipped.
+    fn is_declaration(&self) -> bool {
+        false
+    }
+    /// Whether this is a `CHECKPOINT` statement.
+    fn is_checkpoint(&self) -> bool {
+        false
+    }
+    /// Whether this is a `LOOP` whose body ends with `CHECKPOINT`.
+    fn ends_with_checkpoint(&self) -> bool {
+        false
+    }
 }
-pub trait TranspileableExpr: Transpile + Send + Sync {
+pub trait TranspileableExpr: Transpile + Evaluate + Send + Sync {
     fn type_of(&self, context: &TranspilationInputContext) -> Result<RosyType>;
     fn discover_expr_function_calls(
         &self,
@@ -131,12 +149,23 @@ pub struct TranspilationInputProcedureContext {
     pub args: Vec<VariableData>,
     pub requested_variables: BTreeSet<String>,
 }
+/// The variables a `CHECKPOINT` saves: everything declared before the
+/// program's checkpointed `LOOP`, in name order.
+#[derive(Debug, Clone)]
+pub struct CheckpointTarget {
+    pub variables: Vec<VariableData>,
+}
 #[derive(Default, Clone)]
 pub struct TranspilationInputContext {
     pub variables: HashMap<String, ScopedVariableData>,
     pub functions: HashMap<String, TranspilationInputFunctionContext>,
     pub procedures: HashMap<String, TranspilationInputProcedureContext>,
     pub in_loop: bool,
+    /// Set only while transpiling the checkpointed loop and its final `CHECKPOINT`
+    pub checkpoint: Option<CheckpointTarget>,
+    /// How runtime errors name the program's own file; included files are
+    /// named by their path
+    pub source_file: Option<String>,
 }
 
 impl TranspilationInputContext {